@@ -49,10 +49,17 @@ async fn main() -> std::io::Result<()> {
         ping_interval: std::time::Duration::from_secs(12),
         transport_options: Default::default(),
         enable_json_response: false,
+        json_response_fallback: false,
+        sse_stream_permits: None,
         event_store: None,
+        event_store_error_policy: Default::default(),
         task_store: None,
         client_task_store: None,
         message_observer: None,
+        audit_sink: None,
+        handler_execution: Default::default(),
+        require_initialized: false,
+        session_grace_period: None,
     });
     let http_handler = Arc::new(McpHttpHandler::new(None, vec![], None));
 
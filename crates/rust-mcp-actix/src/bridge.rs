@@ -62,7 +62,7 @@ pub(crate) async fn to_actix_response(res: http::Response<GenericBody>) -> HttpR
 
 /// Converts an actix `HttpRequest` into an `http::Request<&str>`.
 ///
-/// Extracts method, URI, headers, and optional body for use with `McpHttpHandler`.
+/// Extracts method, URI, headers, peer address, and optional body for use with `McpHttpHandler`.
 pub(crate) fn from_actix_request<'a>(
     req: &'a actix_web::HttpRequest,
     body: Option<&'a str>,
@@ -86,7 +86,13 @@ pub(crate) fn from_actix_request<'a>(
         }
     }
 
-    rust_mcp_sdk::mcp_http::McpHttpHandler::create_request(method, uri, headers, body)
+    let mut request =
+        rust_mcp_sdk::mcp_http::McpHttpHandler::create_request(method, uri, headers, body);
+    // the peer address is looked up by middlewares such as `IpFilterMiddleware`
+    if let Some(peer_addr) = req.peer_addr() {
+        request.extensions_mut().insert(peer_addr);
+    }
+    request
 }
 
 /// Converts an `McpHttpError` into an Actix `HttpResponse` with a JSON error body.
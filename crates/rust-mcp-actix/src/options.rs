@@ -1,13 +1,17 @@
+use rust_mcp_sdk::audit::AuditSink;
 use rust_mcp_sdk::auth::AuthProvider;
-use rust_mcp_sdk::event_store::EventStore;
+use rust_mcp_sdk::event_store::{EventStore, EventStoreErrorPolicy};
 use rust_mcp_sdk::id_generator::IdGenerator;
+use rust_mcp_sdk::mcp_http::middleware::{BodyLoggingConfig, SecurityHeadersConfig};
 use rust_mcp_sdk::mcp_http::DnsRebindingOptions;
 use rust_mcp_sdk::mcp_http::HealthHandler;
 use rust_mcp_sdk::mcp_http::McpMountOptions;
+use rust_mcp_sdk::mcp_http::{resolve_ip_filter_middleware, IpFilterOptions};
 use rust_mcp_sdk::mcp_http::{
     DEFAULT_MAX_REQUEST_BODY_SIZE, DEFAULT_MESSAGES_ENDPOINT, DEFAULT_SSE_ENDPOINT,
     DEFAULT_STREAMABLE_HTTP_ENDPOINT,
 };
+use rust_mcp_sdk::mcp_server::HandlerExecution;
 use rust_mcp_sdk::schema::schema_utils::{ClientMessage, ServerMessage};
 use rust_mcp_sdk::session_store::SessionStore;
 use rust_mcp_sdk::task_store::{ClientTaskStore, ServerTaskStore};
@@ -30,19 +34,30 @@ pub struct ActixServerOptions {
     /// TCP port (default: `8080`)
     pub port: u16,
     /// Optional session ID generator
+    ///
+    /// Use an `AffinityIdGenerator` to prefix session IDs with a per-node routing key for
+    /// load balancers with sticky routing; the key is also sent in the `mcp-routing-key` header.
     pub session_id_generator: Option<Arc<dyn IdGenerator<SessionId>>>,
-    /// Custom Streamable HTTP endpoint path (default: `/mcp`)
+    /// Custom Streamable HTTP endpoint path (default: `/mcp`). Must start with `/`, have no query
+    /// or fragment and not be the path of another route; `/` only serves requests to the root itself.
     pub custom_streamable_http_endpoint: Option<String>,
     /// Shared transport configuration
     pub transport_options: Arc<TransportOptions>,
     /// Event store for resumability support
     pub event_store: Option<Arc<dyn EventStore>>,
+    /// What a stream does when the event store fails: log and drop resumability (default) or fail the send
+    pub event_store_error_policy: EventStoreErrorPolicy,
     /// Task store for server-side tasks
     pub task_store: Option<Arc<ServerTaskStore>>,
     /// Task store for client-side tasks
     pub client_task_store: Option<Arc<ClientTaskStore>>,
     /// If true, return JSON instead of SSE stream
     pub enable_json_response: Option<bool>,
+    /// If true, answer clients that only accept `application/json` with JSON instead of `406`,
+    /// and clients that accept both with JSON when they prefer it by `q` value
+    pub json_response_fallback: Option<bool>,
+    /// Maximum number of SSE streams held open at once, new ones get `503` (default: unlimited)
+    pub max_sse_streams: Option<usize>,
     /// Interval between keep-alive pings
     pub ping_interval: Duration,
     /// Enable SSE transport support (default: true)
@@ -59,6 +74,13 @@ pub struct ActixServerOptions {
     pub health_handler: Option<Arc<dyn HealthHandler>>,
     /// Optional message observer for telemetry
     pub message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    /// Optional sink receiving an audit record for every tool invocation
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Strategy used to execute request handlers (default: `HandlerExecution::Async`)
+    pub handler_execution: HandlerExecution,
+    /// Reject requests other than `initialize` and `ping` until the client sends
+    /// `notifications/initialized` (default: false)
+    pub require_initialized: bool,
     /// Maximum request body size in bytes. Defaults to 4 MiB when None.
     pub max_request_body_size: Option<usize>,
     /// DNS rebinding protection configuration (enabled by default).
@@ -67,11 +89,26 @@ pub struct ActixServerOptions {
     /// `allowed_origins` are configured, `allowed_hosts` is auto-derived from
     /// `host:port` unless the bind address is a wildcard.
     pub dns_rebinding: DnsRebindingOptions,
+    /// IP allow/deny lists (CIDR networks or single addresses) checked against the client address
+    /// before MCP processing. Rejected requests receive `403 Forbidden`.
+    /// With `trusted_proxies`, the `X-Forwarded-For` entry that many positions from the right is
+    /// checked instead of the peer address. Disabled by default (no lists).
+    pub ip_filter: IpFilterOptions,
+    /// Optional security headers (`X-Content-Type-Options`, `Referrer-Policy`, `Content-Security-Policy`)
+    /// attached to HTTP responses, except Server-Sent Events streams. Disabled by default (`None`).
+    pub security_headers: Option<SecurityHeadersConfig>,
+    /// Optional logging of request and response bodies at `debug` level, to help diagnosing protocol
+    /// issues. Logged bodies are truncated and JSON values of sensitive keys are redacted, see
+    /// [`BodyLoggingConfig`]. Disabled by default (`None`).
+    pub debug_body_logging: Option<BodyLoggingConfig>,
     /// Optional session store implementation. Defaults to a bounded
     /// `InMemorySessionStore` (10k max sessions, no idle TTL) when `None`.
     /// Pass your own [`SessionStore`] implementation to use Redis, custom
     /// limits, or any other session backend.
     pub session_store: Option<Arc<dyn SessionStore>>,
+    /// How long a session whose standalone SSE stream disconnected is retained, so a client
+    /// reconnecting with the same `mcp-session-id` resumes it (default: None, retained until deleted)
+    pub session_grace_period: Option<Duration>,
     /// Enable TLS/SSL (requires `ssl` feature, default: false)
     pub enable_ssl: bool,
     /// Path to TLS certificate PEM file
@@ -92,6 +129,14 @@ impl ActixServerOptions {
                     .into(),
             );
         }
+        resolve_ip_filter_middleware(&self.ip_filter)?;
+        let auth_endpoints: Vec<&str> = self
+            .auth
+            .as_ref()
+            .and_then(|auth| auth.auth_endpoints())
+            .map(|endpoints| endpoints.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        self.resolve_mount_options().validate(&auth_endpoints)?;
         Ok(())
     }
 
@@ -181,9 +226,12 @@ impl Default for ActixServerOptions {
             custom_streamable_http_endpoint: None,
             transport_options: Default::default(),
             event_store: None,
+            event_store_error_policy: EventStoreErrorPolicy::default(),
             task_store: None,
             client_task_store: None,
             enable_json_response: None,
+            json_response_fallback: None,
+            max_sse_streams: None,
             ping_interval: DEFAULT_CLIENT_PING_INTERVAL,
             sse_support: true,
             custom_sse_endpoint: None,
@@ -192,9 +240,16 @@ impl Default for ActixServerOptions {
             health_endpoint: None,
             health_handler: None,
             message_observer: None,
+            audit_sink: None,
+            handler_execution: HandlerExecution::default(),
+            require_initialized: false,
             max_request_body_size: None,
             dns_rebinding: DnsRebindingOptions::default(),
+            ip_filter: IpFilterOptions::default(),
+            security_headers: None,
+            debug_body_logging: None,
             session_store: None,
+            session_grace_period: None,
             enable_ssl: false,
             ssl_cert_path: None,
             ssl_key_path: None,
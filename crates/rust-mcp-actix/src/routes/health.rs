@@ -1,12 +1,17 @@
 use actix_web::{web, HttpRequest, HttpResponse};
-use rust_mcp_sdk::mcp_http::McpHttpHandler;
+use rust_mcp_sdk::mcp_http::{McpAppState, McpHttpHandler};
+use std::sync::Arc;
 
 pub async fn handle_health_check(
     req: HttpRequest,
+    state: web::Data<Arc<McpAppState>>,
     handler: web::Data<McpHttpHandler>,
 ) -> HttpResponse {
     let request = super::super::bridge::from_actix_request(&req, None);
-    match handler.handle_health(request).await {
+    match handler
+        .handle_health(request, state.get_ref().clone())
+        .await
+    {
         Ok(res) => super::super::bridge::to_actix_response(res).await,
         Err(err) => super::super::bridge::to_actix_error(err),
     }
@@ -21,7 +21,7 @@ use rust_mcp_sdk::{
         ElicitCompleteParams, GetTaskPayloadParams, ProgressNotificationParams, RpcError,
         TaskStatusNotificationParams,
     },
-    SessionId,
+    SessionId, StreamId,
 };
 use std::io;
 use std::sync::Arc;
@@ -152,6 +152,42 @@ impl ActixRuntime {
             })
     }
 
+    /// Informs the client of every active session that the list of tools the server offers has changed,
+    /// see [`McpHttpServer::notify_all_sessions`].
+    pub async fn notify_all_tool_list_changed(&self, params: Option<NotificationParams>) -> usize {
+        McpHttpServer::notify_all_sessions(
+            self,
+            NotificationFromServer::ToolListChangedNotification(params),
+        )
+        .await
+    }
+
+    /// Informs the client of every active session that the list of resources it can read from has changed,
+    /// see [`McpHttpServer::notify_all_sessions`].
+    pub async fn notify_all_resource_list_changed(
+        &self,
+        params: Option<NotificationParams>,
+    ) -> usize {
+        McpHttpServer::notify_all_sessions(
+            self,
+            NotificationFromServer::ResourceListChangedNotification(params),
+        )
+        .await
+    }
+
+    /// Informs the client of every active session that the list of prompts the server offers has changed,
+    /// see [`McpHttpServer::notify_all_sessions`].
+    pub async fn notify_all_prompt_list_changed(
+        &self,
+        params: Option<NotificationParams>,
+    ) -> usize {
+        McpHttpServer::notify_all_sessions(
+            self,
+            NotificationFromServer::PromptListChangedNotification(params),
+        )
+        .await
+    }
+
     /// Returns all active session IDs.
     pub async fn sessions(&self) -> Vec<String> {
         self.state.session_store.keys().await
@@ -200,6 +236,18 @@ impl ActixRuntime {
         Ok(runtime.client_info())
     }
 
+    /// Returns the ids of the streams currently open for the given session.
+    pub async fn stream_ids(&self, session_id: &SessionId) -> SdkResult<Vec<StreamId>> {
+        let runtime = self.runtime_by_session(session_id).await?;
+        Ok(runtime.stream_ids())
+    }
+
+    /// Returns the number of streams currently open for the given session.
+    pub async fn stream_count(&self, session_id: &SessionId) -> SdkResult<usize> {
+        let runtime = self.runtime_by_session(session_id).await?;
+        Ok(runtime.stream_count())
+    }
+
     pub async fn request_elicitation(
         &self,
         session_id: &SessionId,
@@ -379,6 +427,20 @@ impl ActixRuntime {
             .await
     }
 
+    /// Pushes a vendor-specific notification to the session's client,
+    /// see [`McpServer::send_custom_notification`].
+    pub async fn send_custom_notification(
+        &self,
+        session_id: &SessionId,
+        method: &str,
+        params: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> SdkResult<()> {
+        self.runtime_by_session(session_id)
+            .await?
+            .send_custom_notification(method, params)
+            .await
+    }
+
     // --- Additional request methods (parity with AxumRuntime) ---
 
     pub async fn request_elicitation_task(
@@ -550,6 +612,10 @@ impl McpHttpServer for ActixRuntime {
     async fn runtime_by_session(&self, id: &SessionId) -> SdkResult<Arc<ServerRuntime>> {
         ActixRuntime::runtime_by_session(self, id).await
     }
+
+    async fn close_all_sessions(&self, reason: Option<String>) -> usize {
+        self.state.close_all_sessions(reason).await
+    }
 }
 
 #[cfg(feature = "ssl")]
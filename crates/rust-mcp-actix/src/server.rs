@@ -1,7 +1,9 @@
 use crate::options::ActixServerOptions;
 use crate::ActixRuntime;
-use rust_mcp_sdk::mcp_http::middleware::AuthMiddleware;
-use rust_mcp_sdk::mcp_http::{resolve_dns_middleware, Middleware};
+use rust_mcp_sdk::mcp_http::middleware::{
+    AuthMiddleware, BodyLoggingMiddleware, SecurityHeadersMiddleware,
+};
+use rust_mcp_sdk::mcp_http::{resolve_dns_middleware, resolve_ip_filter_middleware, Middleware};
 use rust_mcp_sdk::{
     error::SdkResult,
     id_generator::{FastIdGenerator, UuidGenerator},
@@ -12,6 +14,7 @@ use rust_mcp_sdk::{
     session_store::InMemorySessionStore,
 };
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 
 /// Turnkey Actix MCP server.
 ///
@@ -45,14 +48,39 @@ impl ActixServer {
             ping_interval: server_options.ping_interval,
             transport_options: Arc::clone(&server_options.transport_options),
             enable_json_response: server_options.enable_json_response.unwrap_or(false),
+            json_response_fallback: server_options.json_response_fallback.unwrap_or(false),
+            sse_stream_permits: server_options
+                .max_sse_streams
+                .map(|max| Arc::new(Semaphore::new(max))),
             event_store: server_options.event_store.as_ref().map(Arc::clone),
+            event_store_error_policy: server_options.event_store_error_policy,
             task_store: server_options.task_store.take(),
             client_task_store: server_options.client_task_store.take(),
             message_observer: server_options.message_observer.take(),
+            audit_sink: server_options.audit_sink.take(),
+            handler_execution: server_options.handler_execution.clone(),
+            require_initialized: server_options.require_initialized,
+            session_grace_period: server_options.session_grace_period,
         });
 
         let mut middlewares: Vec<Arc<dyn Middleware>> = vec![];
 
+        // registered first so the headers are also attached to responses rejected by other middlewares
+        if let Some(security_headers) = server_options.security_headers.clone() {
+            middlewares.push(Arc::new(SecurityHeadersMiddleware::new(security_headers)));
+        }
+
+        if let Some(body_logging) = server_options.debug_body_logging.clone() {
+            middlewares.push(Arc::new(BodyLoggingMiddleware::new(body_logging)));
+        }
+
+        // invalid lists are reported by `validate()` when the server starts
+        match resolve_ip_filter_middleware(&server_options.ip_filter) {
+            Ok(Some(ip_filter)) => middlewares.push(Arc::new(ip_filter)),
+            Ok(None) => {}
+            Err(error) => tracing::error!("Invalid IP filter options: {error}"),
+        }
+
         if let Some(dns) = resolve_dns_middleware(
             &mut server_options.dns_rebinding,
             &server_options.host,
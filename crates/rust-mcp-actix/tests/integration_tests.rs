@@ -39,10 +39,17 @@ fn make_state() -> (Arc<McpAppState>, Arc<McpHttpHandler>) {
         ping_interval: std::time::Duration::from_secs(12),
         transport_options: Default::default(),
         enable_json_response: false,
+        json_response_fallback: false,
+        sse_stream_permits: None,
         event_store: None,
+        event_store_error_policy: Default::default(),
         task_store: None,
         client_task_store: None,
         message_observer: None,
+        audit_sink: None,
+        handler_execution: Default::default(),
+        require_initialized: false,
+        session_grace_period: None,
     });
     let handler = Arc::new(McpHttpHandler::new(None, vec![], None));
     (state, handler)
@@ -52,6 +59,7 @@ fn make_state_json_mode() -> (Arc<McpAppState>, Arc<McpHttpHandler>) {
     let (state, handler) = make_state();
     let state = McpAppState {
         enable_json_response: true,
+        json_response_fallback: false,
         ..Arc::unwrap_or_clone(state)
     };
     (Arc::new(state), handler)
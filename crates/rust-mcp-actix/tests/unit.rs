@@ -66,6 +66,24 @@ fn test_actix_server_options_default_valid() {
     assert!(options.validate().is_ok());
 }
 
+#[test]
+fn test_actix_server_options_invalid_streamable_http_endpoint() {
+    let options = rust_mcp_actix::ActixServerOptions {
+        custom_streamable_http_endpoint: Some("/messages".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        options.validate().unwrap_err(),
+        "Invalid streamable HTTP endpoint '/messages': it collides with the messages endpoint '/messages'."
+    );
+
+    let options = rust_mcp_actix::ActixServerOptions {
+        custom_streamable_http_endpoint: Some("/".into()),
+        ..Default::default()
+    };
+    assert!(options.validate().is_ok());
+}
+
 #[test]
 fn test_actix_server_options_resolve_address() {
     let options = rust_mcp_actix::ActixServerOptions::default();
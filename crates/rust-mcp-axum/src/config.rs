@@ -0,0 +1,194 @@
+use crate::AxumServerOptions;
+use rust_mcp_sdk::{
+    event_store::EventStoreErrorPolicy,
+    mcp_http::{
+        middleware::{BodyLoggingConfig, SecurityHeadersConfig},
+        DnsRebindingOptions, IpFilterOptions,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Serializable subset of [`AxumServerOptions`], to load the server configuration from a file.
+///
+/// It covers the plain-data fields of [`AxumServerOptions`], durations are expressed in milliseconds.
+/// Missing fields take the [`AxumServerOptions::default`] values, so a file only needs to list what it
+/// overrides.
+///
+/// The following fields hold trait objects, runtime state or HTTP types and can't be serialized, they keep
+/// their default values when converting into [`AxumServerOptions`] and must be wired in code:
+/// `session_id_generator`, `event_store`, `task_store`, `client_task_store`, `session_store`, `auth`,
+/// `health_handler`, `message_observer`, `audit_sink`, `handler_execution` and `cors`.
+/// Of `transport_options`, only the request timeout is covered.
+///
+/// # Example
+/// ```
+/// use rust_mcp_axum::{AxumServerConfig, AxumServerOptions};
+///
+/// let config = AxumServerConfig::from_json(r#"{ "host": "0.0.0.0", "port": 3000 }"#).unwrap();
+/// let options = AxumServerOptions {
+///     // callbacks, stores and generators are wired here
+///     ..config.into()
+/// };
+/// assert_eq!(options.port, 3000);
+/// assert!(options.sse_support);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxumServerConfig {
+    /// See [`AxumServerOptions::host`].
+    pub host: String,
+    /// See [`AxumServerOptions::port`].
+    pub port: u16,
+    /// See [`AxumServerOptions::custom_streamable_http_endpoint`].
+    pub custom_streamable_http_endpoint: Option<String>,
+    /// See [`AxumServerOptions::custom_sse_endpoint`].
+    pub custom_sse_endpoint: Option<String>,
+    /// See [`AxumServerOptions::custom_messages_endpoint`].
+    pub custom_messages_endpoint: Option<String>,
+    /// See [`AxumServerOptions::health_endpoint`].
+    pub health_endpoint: Option<String>,
+    /// See [`AxumServerOptions::sse_support`].
+    pub sse_support: bool,
+    /// See [`AxumServerOptions::enable_json_response`].
+    pub enable_json_response: Option<bool>,
+    /// See [`AxumServerOptions::json_response_fallback`].
+    pub json_response_fallback: Option<bool>,
+    /// See [`AxumServerOptions::max_sse_streams`].
+    pub max_sse_streams: Option<usize>,
+    /// See [`AxumServerOptions::max_request_body_size`].
+    pub max_request_body_size: Option<usize>,
+    /// [`AxumServerOptions::ping_interval`], in milliseconds.
+    pub ping_interval_ms: u64,
+    /// [`AxumServerOptions::session_grace_period`], in milliseconds.
+    pub session_grace_period_ms: Option<u64>,
+    /// The request timeout of [`AxumServerOptions::transport_options`], in milliseconds.
+    pub request_timeout_ms: u64,
+    /// See [`AxumServerOptions::event_store_error_policy`].
+    pub event_store_error_policy: EventStoreErrorPolicy,
+    /// See [`AxumServerOptions::enable_ssl`].
+    pub enable_ssl: bool,
+    /// See [`AxumServerOptions::ssl_cert_path`].
+    pub ssl_cert_path: Option<String>,
+    /// See [`AxumServerOptions::ssl_key_path`].
+    pub ssl_key_path: Option<String>,
+    /// See [`DnsRebindingOptions::dns_rebinding_protection`].
+    pub dns_rebinding_protection: bool,
+    /// See [`DnsRebindingOptions::allowed_hosts`].
+    pub allowed_hosts: Option<Vec<String>>,
+    /// See [`DnsRebindingOptions::allowed_origins`].
+    pub allowed_origins: Option<Vec<String>>,
+    /// See [`IpFilterOptions::allowed_ips`].
+    pub allowed_ips: Option<Vec<String>>,
+    /// See [`IpFilterOptions::denied_ips`].
+    pub denied_ips: Option<Vec<String>>,
+    /// See [`IpFilterOptions::trusted_proxies`].
+    pub trusted_proxies: usize,
+    /// See [`AxumServerOptions::security_headers`].
+    pub security_headers: Option<SecurityHeadersConfig>,
+    /// See [`AxumServerOptions::debug_body_logging`].
+    pub debug_body_logging: Option<BodyLoggingConfig>,
+    /// See [`AxumServerOptions::require_initialized`].
+    pub require_initialized: bool,
+    /// See [`AxumServerOptions::validate_capabilities`].
+    pub validate_capabilities: bool,
+}
+
+impl AxumServerConfig {
+    /// Parses a configuration from JSON, missing fields take their default values.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Serializes the configuration as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl Default for AxumServerConfig {
+    fn default() -> Self {
+        (&AxumServerOptions::default()).into()
+    }
+}
+
+impl From<&AxumServerOptions> for AxumServerConfig {
+    fn from(options: &AxumServerOptions) -> Self {
+        Self {
+            host: options.host.clone(),
+            port: options.port,
+            custom_streamable_http_endpoint: options.custom_streamable_http_endpoint.clone(),
+            custom_sse_endpoint: options.custom_sse_endpoint.clone(),
+            custom_messages_endpoint: options.custom_messages_endpoint.clone(),
+            health_endpoint: options.health_endpoint.clone(),
+            sse_support: options.sse_support,
+            enable_json_response: options.enable_json_response,
+            json_response_fallback: options.json_response_fallback,
+            max_sse_streams: options.max_sse_streams,
+            max_request_body_size: options.max_request_body_size,
+            ping_interval_ms: options.ping_interval.as_millis() as u64,
+            session_grace_period_ms: options
+                .session_grace_period
+                .map(|grace_period| grace_period.as_millis() as u64),
+            request_timeout_ms: options.transport_options.timeout.as_millis() as u64,
+            event_store_error_policy: options.event_store_error_policy,
+            enable_ssl: options.enable_ssl,
+            ssl_cert_path: options.ssl_cert_path.clone(),
+            ssl_key_path: options.ssl_key_path.clone(),
+            dns_rebinding_protection: options.dns_rebinding.dns_rebinding_protection,
+            allowed_hosts: options.dns_rebinding.allowed_hosts.clone(),
+            allowed_origins: options.dns_rebinding.allowed_origins.clone(),
+            allowed_ips: options.ip_filter.allowed_ips.clone(),
+            denied_ips: options.ip_filter.denied_ips.clone(),
+            trusted_proxies: options.ip_filter.trusted_proxies,
+            security_headers: options.security_headers.clone(),
+            debug_body_logging: options.debug_body_logging.clone(),
+            require_initialized: options.require_initialized,
+            validate_capabilities: options.validate_capabilities,
+        }
+    }
+}
+
+impl From<AxumServerConfig> for AxumServerOptions {
+    fn from(config: AxumServerConfig) -> Self {
+        let defaults = AxumServerOptions::default();
+        let mut transport_options = (*defaults.transport_options).clone();
+        transport_options.timeout = Duration::from_millis(config.request_timeout_ms);
+
+        Self {
+            host: config.host,
+            port: config.port,
+            custom_streamable_http_endpoint: config.custom_streamable_http_endpoint,
+            custom_sse_endpoint: config.custom_sse_endpoint,
+            custom_messages_endpoint: config.custom_messages_endpoint,
+            health_endpoint: config.health_endpoint,
+            sse_support: config.sse_support,
+            enable_json_response: config.enable_json_response,
+            json_response_fallback: config.json_response_fallback,
+            max_sse_streams: config.max_sse_streams,
+            max_request_body_size: config.max_request_body_size,
+            ping_interval: Duration::from_millis(config.ping_interval_ms),
+            session_grace_period: config.session_grace_period_ms.map(Duration::from_millis),
+            transport_options: transport_options.into(),
+            event_store_error_policy: config.event_store_error_policy,
+            enable_ssl: config.enable_ssl,
+            ssl_cert_path: config.ssl_cert_path,
+            ssl_key_path: config.ssl_key_path,
+            dns_rebinding: DnsRebindingOptions {
+                dns_rebinding_protection: config.dns_rebinding_protection,
+                allowed_hosts: config.allowed_hosts,
+                allowed_origins: config.allowed_origins,
+            },
+            ip_filter: IpFilterOptions {
+                allowed_ips: config.allowed_ips,
+                denied_ips: config.denied_ips,
+                trusted_proxies: config.trusted_proxies,
+            },
+            security_headers: config.security_headers,
+            debug_body_logging: config.debug_body_logging,
+            require_initialized: config.require_initialized,
+            validate_capabilities: config.validate_capabilities,
+            ..defaults
+        }
+    }
+}
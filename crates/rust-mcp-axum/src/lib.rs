@@ -1,3 +1,4 @@
+mod config;
 mod error;
 mod factory;
 pub mod routes;
@@ -5,9 +6,10 @@ mod runtime;
 mod server;
 mod utils;
 
+pub use config::*;
 pub use error::*;
 pub use factory::*;
-pub use routes::mcp_routes;
+pub use routes::{mcp_router, mcp_routes};
 pub use runtime::*;
 pub use server::*;
 
@@ -11,6 +11,39 @@ use rust_mcp_sdk::mcp_http::McpHttpHandler;
 use rust_mcp_sdk::mcp_http::McpMountOptions;
 use std::sync::Arc;
 
+/// Constructs an Axum router serving only the MCP routes, at their default endpoints
+/// (streamable HTTP at `/mcp`, SSE at `/sse` and messages at `/messages`).
+///
+/// Meant for embedding MCP in an existing Axum app that runs its own server:
+///
+/// ```ignore
+/// let app = axum::Router::new()
+///     .route("/api/custom", get(my_handler))
+///     .fallback(my_not_found)
+///     .merge(rust_mcp_axum::mcp_router(state));
+///
+/// // or under a prefix, serving streamable HTTP at `/tools/mcp`
+/// let app = axum::Router::new().nest("/tools", rust_mcp_axum::mcp_router(state));
+/// ```
+///
+/// Unlike [`mcp_routes`], no fallback route is added, so the router merges into one that has
+/// its own fallback. Use [`mcp_routes`] for custom endpoints, auth, middlewares or a health check.
+///
+/// # Security
+///
+/// The routes have **no DNS rebinding protection and no authentication**: the allowed hosts of
+/// the default `DnsRebindingOptions` are derived from the server's bind address, which the router
+/// does not know. Unless the app is only reachable by trusted clients, apply your own host/origin
+/// checks and auth to the router, or build it with [`mcp_routes`] and an [`McpHttpHandler`] holding
+/// the middleware returned by `resolve_dns_middleware()` and an auth provider.
+pub fn mcp_router(state: Arc<McpAppState>) -> Router {
+    build_routes(
+        state,
+        &McpMountOptions::default(),
+        McpHttpHandler::new(None, vec![], None),
+    )
+}
+
 /// Constructs the Axum router with all MCP application routes.
 ///
 /// This is the **BYO-server** mount function — use it to mount MCP endpoints
@@ -37,37 +70,41 @@ pub fn mcp_routes(
     mount_options: &McpMountOptions,
     http_handler: McpHttpHandler,
 ) -> Router {
-    let http_handler = Arc::new(http_handler);
+    build_routes(state, mount_options, http_handler).fallback(fallback_routes::not_found)
+}
 
-    let router = {
-        let mut router = Router::new();
+/// Builds the MCP routes with their state and layers, without a fallback route.
+fn build_routes(
+    state: Arc<McpAppState>,
+    mount_options: &McpMountOptions,
+    http_handler: McpHttpHandler,
+) -> Router {
+    let http_handler = Arc::new(http_handler);
 
-        router = router.merge(auth_routes::routes(http_handler.clone()));
+    let mut router = Router::new();
 
-        router = router.merge(streamable_http_routes::routes(
-            &mount_options.streamable_http_endpoint,
-        ));
+    router = router.merge(auth_routes::routes(http_handler.clone()));
 
-        // mount health check if enabled
-        if let Some(health_check_endpoint) = mount_options.health_endpoint.as_ref() {
-            router = router.merge(health_check_route::routes(health_check_endpoint));
-        }
+    router = router.merge(streamable_http_routes::routes(
+        &mount_options.streamable_http_endpoint,
+    ));
 
-        router = router
-            .merge(sse_routes::routes(
-                &mount_options.sse_endpoint,
-                &mount_options.sse_messages_endpoint,
-            ))
-            .merge(messages_routes::routes(
-                &mount_options.sse_messages_endpoint,
-            ));
+    // mount health check if enabled
+    if let Some(health_check_endpoint) = mount_options.health_endpoint.as_ref() {
+        router = router.merge(health_check_route::routes(health_check_endpoint));
+    }
 
-        router = router.merge(fallback_routes::routes());
-        router
-            .with_state(state)
-            .layer(Extension(http_handler))
-            .layer(DefaultBodyLimit::max(mount_options.max_request_body_size))
-    };
+    router = router
+        .merge(sse_routes::routes(
+            &mount_options.sse_endpoint,
+            &mount_options.sse_messages_endpoint,
+        ))
+        .merge(messages_routes::routes(
+            &mount_options.sse_messages_endpoint,
+        ));
 
     router
+        .with_state(state)
+        .layer(Extension(http_handler))
+        .layer(DefaultBodyLimit::max(mount_options.max_request_body_size))
 }
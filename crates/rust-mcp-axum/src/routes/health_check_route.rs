@@ -1,5 +1,6 @@
 use crate::error::TransportServerResult;
 use crate::utils::remove_query_and_hash;
+use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::Extension;
@@ -28,10 +29,11 @@ pub fn routes(health_check_endpoint: &str) -> Router<Arc<McpAppState>> {
 pub async fn handle_health_check(
     headers: HeaderMap,
     uri: Uri,
+    State(state): State<Arc<McpAppState>>,
     Extension(http_handler): Extension<Arc<McpHttpHandler>>,
 ) -> TransportServerResult<impl IntoResponse> {
     let request = McpHttpHandler::create_request(Method::GET, uri, headers, None);
-    let generic_res = http_handler.handle_health(request).await?;
+    let generic_res = http_handler.handle_health(request, state).await?;
     let (parts, body) = generic_res.into_parts();
     let resp = axum::response::Response::from_parts(parts, axum::body::Body::new(body));
     Ok(resp)
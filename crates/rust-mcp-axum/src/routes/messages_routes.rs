@@ -1,9 +1,14 @@
 use crate::error::TransportServerResult;
-use crate::utils::remove_query_and_hash;
-use axum::{extract::State, response::IntoResponse, routing::post, Extension, Router};
+use crate::utils::{remove_query_and_hash, with_remote_addr};
+use axum::{
+    extract::{ConnectInfo, State},
+    response::IntoResponse,
+    routing::post,
+    Extension, Router,
+};
 use http::{HeaderMap, Method, Uri};
 use rust_mcp_sdk::mcp_http::{McpAppState, McpHttpHandler};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 pub fn routes(sse_message_endpoint: &str) -> Router<Arc<McpAppState>> {
     Router::new().route(
@@ -17,9 +22,13 @@ pub async fn handle_messages(
     headers: HeaderMap,
     State(state): State<Arc<McpAppState>>,
     Extension(http_handler): Extension<Arc<McpHttpHandler>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
     message: String,
 ) -> TransportServerResult<impl IntoResponse> {
-    let request = McpHttpHandler::create_request(Method::POST, uri, headers, Some(&message));
+    let request = with_remote_addr(
+        McpHttpHandler::create_request(Method::POST, uri, headers, Some(&message)),
+        connect_info,
+    );
     let generic_response = http_handler
         .handle_sse_message(request, state.clone())
         .await?;
@@ -1,8 +1,14 @@
 use crate::error::TransportServerResult;
-use axum::{extract::State, response::IntoResponse, routing::get, Extension, Router};
+use crate::utils::with_remote_addr;
+use axum::{
+    extract::{ConnectInfo, State},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
 use http::{HeaderMap, Method, Uri};
 use rust_mcp_sdk::mcp_http::{McpAppState, McpHttpHandler};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 #[derive(Clone)]
 pub struct SseMessageEndpoint(pub String);
@@ -40,10 +46,14 @@ pub async fn handle_sse(
     uri: Uri,
     Extension(sse_message_endpoint): Extension<SseMessageEndpoint>,
     Extension(http_handler): Extension<Arc<McpHttpHandler>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
     State(state): State<Arc<McpAppState>>,
 ) -> TransportServerResult<impl IntoResponse> {
     let SseMessageEndpoint(sse_message_endpoint) = sse_message_endpoint;
-    let request = McpHttpHandler::create_request(Method::GET, uri, headers, None);
+    let request = with_remote_addr(
+        McpHttpHandler::create_request(Method::GET, uri, headers, None),
+        connect_info,
+    );
     let generic_response = http_handler
         .handle_sse_connection(request, state.clone(), Some(&sse_message_endpoint))
         .await?;
@@ -1,16 +1,17 @@
 use crate::error::TransportServerResult;
+use crate::utils::with_remote_addr;
 use axum::body::Bytes;
 use axum::routing::get;
 use axum::Extension;
 use axum::{
-    extract::{Query, State},
+    extract::{ConnectInfo, Query, State},
     response::IntoResponse,
-    routing::{delete, post},
+    routing::{delete, options, post},
     Router,
 };
 use http::{HeaderMap, Method, StatusCode, Uri};
 use rust_mcp_sdk::mcp_http::{McpAppState, McpHttpHandler};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
 
 pub fn routes(streamable_http_endpoint: &str) -> Router<Arc<McpAppState>> {
     Router::new()
@@ -20,6 +21,10 @@ pub fn routes(streamable_http_endpoint: &str) -> Router<Arc<McpAppState>> {
             streamable_http_endpoint,
             delete(handle_streamable_http_delete),
         )
+        .route(
+            streamable_http_endpoint,
+            options(handle_streamable_http_options),
+        )
 }
 
 pub async fn handle_streamable_http_get(
@@ -27,8 +32,29 @@ pub async fn handle_streamable_http_get(
     uri: Uri,
     State(state): State<Arc<McpAppState>>,
     Extension(http_handler): Extension<Arc<McpHttpHandler>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+) -> TransportServerResult<impl IntoResponse> {
+    let request = with_remote_addr(
+        McpHttpHandler::create_request(Method::GET, uri, headers, None),
+        connect_info,
+    );
+    let generic_res = http_handler.handle_streamable_http(request, state).await?;
+    let (parts, body) = generic_res.into_parts();
+    let resp = axum::response::Response::from_parts(parts, axum::body::Body::new(body));
+    Ok(resp)
+}
+
+pub async fn handle_streamable_http_options(
+    headers: HeaderMap,
+    uri: Uri,
+    State(state): State<Arc<McpAppState>>,
+    Extension(http_handler): Extension<Arc<McpHttpHandler>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
 ) -> TransportServerResult<impl IntoResponse> {
-    let request = McpHttpHandler::create_request(Method::GET, uri, headers, None);
+    let request = with_remote_addr(
+        McpHttpHandler::create_request(Method::OPTIONS, uri, headers, None),
+        connect_info,
+    );
     let generic_res = http_handler.handle_streamable_http(request, state).await?;
     let (parts, body) = generic_res.into_parts();
     let resp = axum::response::Response::from_parts(parts, axum::body::Body::new(body));
@@ -40,6 +66,7 @@ pub async fn handle_streamable_http_post(
     uri: Uri,
     State(state): State<Arc<McpAppState>>,
     Extension(http_handler): Extension<Arc<McpHttpHandler>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
     Query(_params): Query<HashMap<String, String>>,
     payload: Bytes,
 ) -> TransportServerResult<impl IntoResponse> {
@@ -56,7 +83,10 @@ pub async fn handle_streamable_http_post(
         }
     };
 
-    let request = McpHttpHandler::create_request(Method::POST, uri, headers, Some(payload));
+    let request = with_remote_addr(
+        McpHttpHandler::create_request(Method::POST, uri, headers, Some(payload)),
+        connect_info,
+    );
     let generic_res = http_handler.handle_streamable_http(request, state).await?;
     let (parts, body) = generic_res.into_parts();
     let resp = axum::response::Response::from_parts(parts, axum::body::Body::new(body));
@@ -68,8 +98,12 @@ pub async fn handle_streamable_http_delete(
     uri: Uri,
     State(state): State<Arc<McpAppState>>,
     Extension(http_handler): Extension<Arc<McpHttpHandler>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
 ) -> TransportServerResult<impl IntoResponse> {
-    let request = McpHttpHandler::create_request(Method::DELETE, uri, headers, None);
+    let request = with_remote_addr(
+        McpHttpHandler::create_request(Method::DELETE, uri, headers, None),
+        connect_info,
+    );
     let generic_res = http_handler.handle_streamable_http(request, state).await?;
     let (parts, body) = generic_res.into_parts();
     let resp = axum::response::Response::from_parts(parts, axum::body::Body::new(body));
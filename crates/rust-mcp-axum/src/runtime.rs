@@ -5,7 +5,7 @@ use futures::StreamExt;
 use rust_mcp_sdk::McpHttpServer;
 use rust_mcp_sdk::{
     error::SdkResult,
-    mcp_server::ServerRuntime,
+    mcp_server::{validate_capabilities, CapabilityIssueSeverity, ServerRuntime},
     session_store::SessionStore,
     task_store::{ClientTaskStore, ServerTaskStore, TaskStatusPoller},
 };
@@ -27,7 +27,7 @@ use rust_mcp_sdk::{
         GetTaskPayloadParams, GetTaskResult, ProgressNotificationParams, RpcError,
         TaskStatusNotificationParams,
     },
-    SessionId,
+    SessionId, StreamId,
 };
 use std::net::SocketAddr;
 use std::{sync::Arc, time::Duration};
@@ -71,11 +71,27 @@ impl AxumRuntime {
         callback
     }
     pub async fn create(server: AxumServer) -> SdkResult<Self> {
+        if let Some(error) = server.mount_error.clone() {
+            return Err(TransportServerError::InvalidServerOptions(error).into());
+        }
         let addr = server.options.resolve_server_address().await?;
         let state = server.state();
 
         let server_handle = server.server_handle();
 
+        if server.options.validate_capabilities {
+            let issues = validate_capabilities(
+                Arc::clone(&state.server_details),
+                Arc::clone(&state.handler),
+            );
+            for issue in issues {
+                match issue.severity {
+                    CapabilityIssueSeverity::Warning => tracing::warn!("{issue}"),
+                    CapabilityIssueSeverity::Error => tracing::error!("{issue}"),
+                }
+            }
+        }
+
         let server_task = tokio::spawn(async move {
             #[cfg(feature = "ssl")]
             if server.options.enable_ssl {
@@ -133,6 +149,42 @@ impl AxumRuntime {
         result.map_err(|err| err.into())
     }
 
+    /// Informs the client of every active session that the list of tools the server offers has changed,
+    /// see [`McpHttpServer::notify_all_sessions`].
+    pub async fn notify_all_tool_list_changed(&self, params: Option<NotificationParams>) -> usize {
+        McpHttpServer::notify_all_sessions(
+            self,
+            NotificationFromServer::ToolListChangedNotification(params),
+        )
+        .await
+    }
+
+    /// Informs the client of every active session that the list of resources it can read from has changed,
+    /// see [`McpHttpServer::notify_all_sessions`].
+    pub async fn notify_all_resource_list_changed(
+        &self,
+        params: Option<NotificationParams>,
+    ) -> usize {
+        McpHttpServer::notify_all_sessions(
+            self,
+            NotificationFromServer::ResourceListChangedNotification(params),
+        )
+        .await
+    }
+
+    /// Informs the client of every active session that the list of prompts the server offers has changed,
+    /// see [`McpHttpServer::notify_all_sessions`].
+    pub async fn notify_all_prompt_list_changed(
+        &self,
+        params: Option<NotificationParams>,
+    ) -> usize {
+        McpHttpServer::notify_all_sessions(
+            self,
+            NotificationFromServer::PromptListChangedNotification(params),
+        )
+        .await
+    }
+
     /// Returns a list of active session IDs from the session store.
     pub async fn sessions(&self) -> Vec<String> {
         self.state.session_store.keys().await
@@ -180,6 +232,18 @@ impl AxumRuntime {
         Ok(runtime.client_info())
     }
 
+    /// Returns the ids of the streams currently open for the given session.
+    pub async fn stream_ids(&self, session_id: &SessionId) -> SdkResult<Vec<StreamId>> {
+        let runtime = self.runtime_by_session(session_id).await?;
+        Ok(runtime.stream_ids())
+    }
+
+    /// Returns the number of streams currently open for the given session.
+    pub async fn stream_count(&self, session_id: &SessionId) -> SdkResult<usize> {
+        let runtime = self.runtime_by_session(session_id).await?;
+        Ok(runtime.stream_count())
+    }
+
     /*******************
           Requests
     *******************/
@@ -412,6 +476,18 @@ impl AxumRuntime {
         runtime.notify_custom(params).await
     }
 
+    /// Pushes a vendor-specific notification to the session's client,
+    /// see [`McpServer::send_custom_notification`].
+    pub async fn send_custom_notification(
+        &self,
+        session_id: &SessionId,
+        method: &str,
+        params: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> SdkResult<()> {
+        let runtime = self.runtime_by_session(session_id).await?;
+        runtime.send_custom_notification(method, params).await
+    }
+
     #[deprecated(since = "0.8.0", note = "Use `request_root_list()` instead.")]
     pub async fn list_roots(
         &self,
@@ -521,4 +597,8 @@ impl McpHttpServer for AxumRuntime {
             .await
             .map_err(Into::into)
     }
+
+    async fn close_all_sessions(&self, reason: Option<String>) -> usize {
+        self.state.close_all_sessions(reason).await
+    }
 }
@@ -7,26 +7,35 @@ use axum::Router;
 #[cfg(feature = "ssl")]
 use axum_server::tls_rustls::RustlsConfig;
 use axum_server::Handle;
+use rust_mcp_sdk::audit::AuditSink;
 use rust_mcp_sdk::auth::AuthProvider;
-use rust_mcp_sdk::mcp_http::middleware::AuthMiddleware;
+use rust_mcp_sdk::mcp_http::middleware::{
+    AuthMiddleware, BodyLoggingConfig, BodyLoggingMiddleware, CorsConfig, CorsMiddleware,
+    SecurityHeadersConfig, SecurityHeadersMiddleware,
+};
 use rust_mcp_sdk::schema::schema_utils::{ClientMessage, ServerMessage};
 use rust_mcp_sdk::{
     error::SdkResult,
     id_generator::{FastIdGenerator, UuidGenerator},
     mcp_http::{
-        resolve_dns_middleware, DnsRebindingOptions, HealthHandler, McpAppState, McpHttpHandler,
+        resolve_dns_middleware, resolve_ip_filter_middleware, DnsRebindingOptions, HealthHandler,
+        IpFilterOptions, McpAppState, McpHttpHandler,
     },
+    mcp_server::HandlerExecution,
     session_store::{InMemorySessionStore, SessionStore},
     task_store::{ClientTaskStore, ServerTaskStore},
     IdGenerator, McpObserver, McpServerHandler,
 };
-use rust_mcp_sdk::{event_store::EventStore, SessionId, TransportOptions};
+use rust_mcp_sdk::{
+    event_store::{EventStore, EventStoreErrorPolicy},
+    SessionId, TransportOptions,
+};
 use rust_mcp_sdk::{
     mcp_http::{
-        Middleware, DEFAULT_MESSAGES_ENDPOINT, DEFAULT_SSE_ENDPOINT,
+        Middleware, DEFAULT_HEALTH_ENDPOINT, DEFAULT_MESSAGES_ENDPOINT, DEFAULT_SSE_ENDPOINT,
         DEFAULT_STREAMABLE_HTTP_ENDPOINT,
     },
-    schema::InitializeResult,
+    schema::{InitializeResult, ProtocolVersion},
 };
 use std::{
     net::{SocketAddr, ToSocketAddrs},
@@ -34,7 +43,7 @@ use std::{
     sync::Arc,
     time::Duration,
 };
-use tokio::signal;
+use tokio::{signal, sync::Semaphore};
 
 // Default client ping interval (12 seconds)
 const DEFAULT_CLIENT_PING_INTERVAL: Duration = Duration::from_secs(12);
@@ -75,9 +84,16 @@ pub struct AxumServerOptions {
     pub port: u16,
 
     /// Optional thread-safe session id generator to generate unique session IDs.
+    ///
+    /// Use an `AffinityIdGenerator` to prefix session IDs with a per-node routing key for
+    /// load balancers with sticky routing; the key is also sent in the `mcp-routing-key` header.
     pub session_id_generator: Option<Arc<dyn IdGenerator<SessionId>>>,
 
     /// Optional custom path for the Streamable HTTP endpoint (default: `/mcp`)
+    ///
+    /// It must start with `/`, have no query or fragment, and differ from the SSE, messages,
+    /// health and OAuth endpoints, otherwise the server fails to start. It may be the root `/`,
+    /// which only serves requests to `/` itself and leaves the other routes reachable.
     pub custom_streamable_http_endpoint: Option<String>,
 
     /// Shared transport configuration used by the server
@@ -87,6 +103,12 @@ pub struct AxumServerOptions {
     /// If provided, resumability will be enabled, allowing clients to reconnect and resume messages
     pub event_store: Option<Arc<dyn EventStore>>,
 
+    /// What a resumable stream does when the event store fails to store one of its messages.
+    /// - `EventStoreErrorPolicy::ContinueWithoutResumability` (default) → the error is logged and the
+    ///   stream keeps delivering messages, without event ids, so it can no longer be resumed
+    /// - `EventStoreErrorPolicy::FailSend` → sending the message fails and the error is returned to the sender
+    pub event_store_error_policy: EventStoreErrorPolicy,
+
     /// Task store for handling incoming task-augmented requests from the client.
     /// In other words, for tasks executed on this server.
     ///
@@ -118,6 +140,22 @@ pub struct AxumServerOptions {
     /// Default is false (SSE streams are preferred).
     pub enable_json_response: Option<bool>,
 
+    /// This setting only applies to streamable HTTP.
+    /// If true, POST requests from clients that accept `application/json` but not
+    /// `text/event-stream` are answered with a buffered JSON response instead of
+    /// being rejected with `406 Not Acceptable`. Clients accepting both also get a
+    /// buffered JSON response when their `Accept` header gives JSON a higher `q` value.
+    /// Default is false (clients must accept both, as required by the specification).
+    pub json_response_fallback: Option<bool>,
+
+    /// Maximum number of SSE streams held open at once, across all sessions: standalone GET streams
+    /// of Streamable HTTP, and connections of the HTTP+SSE transport. Streams answering a POST request
+    /// close with their response and are not limited.
+    /// New streams are rejected with `503 Service Unavailable` once the limit is reached,
+    /// and a slot is freed whenever a stream is closed.
+    /// Default is `None` (unlimited).
+    pub max_sse_streams: Option<usize>,
+
     /// Interval between automatic ping messages sent to clients to detect disconnects
     pub ping_interval: Duration,
 
@@ -131,6 +169,12 @@ pub struct AxumServerOptions {
     /// limits, or any other session backend.
     pub session_store: Option<Arc<dyn SessionStore>>,
 
+    /// Optional grace period during which a session whose standalone SSE stream disconnected
+    /// is retained, so a client reconnecting with the same `mcp-session-id` (and `last-event-id`)
+    /// resumes it without initializing again. Sessions not resumed in time are closed and removed.
+    /// Default is `None`, sessions are retained until deleted or evicted by the session store.
+    pub session_grace_period: Option<Duration>,
+
     /// Enables SSL/TLS if set to `true`
     pub enable_ssl: bool,
 
@@ -149,6 +193,26 @@ pub struct AxumServerOptions {
     /// `host:port` unless the bind address is a wildcard.
     pub dns_rebinding: DnsRebindingOptions,
 
+    /// IP allow/deny lists (CIDR networks or single addresses) checked against the client address
+    /// before MCP processing. Rejected requests receive `403 Forbidden`.
+    /// With `trusted_proxies`, the `X-Forwarded-For` entry that many positions from the right is
+    /// checked instead of the remote address. Disabled by default (no lists).
+    pub ip_filter: IpFilterOptions,
+
+    /// Optional CORS policy for browser-based clients, answering preflight (`OPTIONS`) requests and
+    /// attaching CORS headers to responses. The `mcp-session-id` and `mcp-protocol-version` headers
+    /// are allowed and exposed in addition to the configured ones. Disabled by default (`None`).
+    pub cors: Option<CorsConfig>,
+
+    /// Optional security headers (`X-Content-Type-Options`, `Referrer-Policy`, `Content-Security-Policy`)
+    /// attached to HTTP responses, except Server-Sent Events streams. Disabled by default (`None`).
+    pub security_headers: Option<SecurityHeadersConfig>,
+
+    /// Optional logging of request and response bodies at `debug` level, to help diagnosing protocol
+    /// issues. Logged bodies are truncated and JSON values of sensitive keys are redacted, see
+    /// [`BodyLoggingConfig`]. Disabled by default (`None`).
+    pub debug_body_logging: Option<BodyLoggingConfig>,
+
     /// If set to true, the SSE transport will also be supported for backward compatibility (default: true)
     pub sse_support: bool,
 
@@ -163,25 +227,49 @@ pub struct AxumServerOptions {
     /// Optional authentication provider for protecting MCP server.
     pub auth: Option<Arc<dyn AuthProvider>>,
 
-    /// Path for the optional health-check endpoint.
-    /// Set to `None` to **disable** the health check endpoint completely
+    /// Path for the health-check endpoint (default: "/health").
+    /// Set to `None` to **disable** the health check endpoint completely.
+    /// The endpoint requires no authentication and skips DNS rebinding protection, so load balancer
+    /// probes succeed whatever host they target.
     pub health_endpoint: Option<String>,
 
     /// Custom handler for the health endpoint.
     /// Only used when `health_endpoint` is `Some(_)`.
-    /// - `None` → fast static `200 OK` response with minimal json payload `{"status":"ok", "sdk":"rust-mcp-sdk/x.x.x"}`
+    /// - `None` → `200 OK` response with a json payload reporting the number of sessions in the session store,
+    ///   e.g. `{"status":"ok", "sessions": 2, "server":"rust-mcp-sdk", "version":"x.x.x"}`
     /// - `Some(...)` → user-provided handler
     pub health_handler: Option<Arc<dyn HealthHandler>>,
 
     /// Optional observer for incoming/outgoing messages.
     /// Implementations should be fast and preferably non-blocking.
     pub message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+
+    /// Optional sink receiving an audit record (caller, tool, arguments and outcome) for every
+    /// tool invocation. Wrap it in a `RedactingAuditSink` to mask sensitive arguments.
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+
+    /// Strategy used to execute request handlers.
+    /// Use `HandlerExecution::Blocking` or `HandlerExecution::bounded(n)` when tools perform
+    /// blocking or CPU-heavy work, so they don't starve async IO.
+    /// Default is `HandlerExecution::Async`.
+    pub handler_execution: HandlerExecution,
+
+    /// If true, requests other than `initialize` and `ping` are rejected with an `invalid request`
+    /// error until the client sends the `notifications/initialized` notification. Default is false.
+    pub require_initialized: bool,
+
+    /// If true, the declared server capabilities are checked against the handler when the server
+    /// starts, and mismatches (e.g. `tools` declared without any tools) are logged.
+    /// See [`validate_capabilities`](rust_mcp_sdk::mcp_server::validate_capabilities). Default is false.
+    pub validate_capabilities: bool,
 }
 
 impl AxumServerOptions {
     /// Validates the server configuration options
     ///
-    /// Ensures that SSL-related paths are provided and valid when SSL is enabled.
+    /// Ensures that SSL-related paths are provided and valid when SSL is enabled,
+    /// that the IP allow/deny lists only contain valid networks, and that the streamable HTTP
+    /// endpoint is a valid path not used by another route, see [`McpMountOptions::validate`].
     ///
     /// # Returns
     /// * `TransportServerResult<()>` - Ok if validation passes, Err with TransportServerError if invalid
@@ -207,9 +295,26 @@ impl AxumServerOptions {
             }
         }
 
+        resolve_ip_filter_middleware(&self.ip_filter)
+            .map_err(TransportServerError::InvalidServerOptions)?;
+
+        self.validate_mount_options()
+            .map_err(TransportServerError::InvalidServerOptions)?;
+
         Ok(())
     }
 
+    /// Validates the endpoint paths against each other and the OAuth endpoints of `auth`.
+    fn validate_mount_options(&self) -> Result<(), String> {
+        let auth_endpoints: Vec<&str> = self
+            .auth
+            .as_ref()
+            .and_then(|auth| auth.auth_endpoints())
+            .map(|endpoints| endpoints.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        self.resolve_mount_options().validate(&auth_endpoints)
+    }
+
     /// Resolves the server address from host and port
     ///
     /// Validates the configuration and converts the host/port into a SocketAddr.
@@ -319,21 +424,33 @@ impl Default for AxumServerOptions {
             ping_interval: DEFAULT_CLIENT_PING_INTERVAL,
             max_request_body_size: None,
             session_store: None,
+            session_grace_period: None,
             transport_options: Default::default(),
             enable_ssl: false,
             ssl_cert_path: None,
             ssl_key_path: None,
             session_id_generator: None,
             enable_json_response: None,
+            json_response_fallback: None,
+            max_sse_streams: None,
             sse_support: true,
             dns_rebinding: DnsRebindingOptions::default(),
+            ip_filter: IpFilterOptions::default(),
+            cors: None,
+            security_headers: None,
+            debug_body_logging: None,
             event_store: None,
+            event_store_error_policy: EventStoreErrorPolicy::default(),
             auth: None,
             task_store: None,
             client_task_store: None,
-            health_endpoint: None,
+            health_endpoint: Some(DEFAULT_HEALTH_ENDPOINT.to_string()),
             health_handler: None,
             message_observer: None,
+            audit_sink: None,
+            handler_execution: HandlerExecution::default(),
+            require_initialized: false,
+            validate_capabilities: false,
         }
     }
 }
@@ -344,6 +461,10 @@ pub struct AxumServer {
     state: Arc<McpAppState>,
     pub(crate) options: AxumServerOptions,
     handle: Handle<SocketAddr>,
+    // resolved at construction, as the corresponding options are consumed when building middlewares
+    auth_enabled: bool,
+    dns_rebinding_protection: bool,
+    pub(crate) mount_error: Option<String>,
 }
 
 impl AxumServer {
@@ -378,22 +499,58 @@ impl AxumServer {
             ping_interval: server_options.ping_interval,
             transport_options: Arc::clone(&server_options.transport_options),
             enable_json_response: server_options.enable_json_response.unwrap_or(false),
+            json_response_fallback: server_options.json_response_fallback.unwrap_or(false),
+            sse_stream_permits: server_options
+                .max_sse_streams
+                .map(|max| Arc::new(Semaphore::new(max))),
             event_store: server_options.event_store.as_ref().map(Arc::clone),
+            event_store_error_policy: server_options.event_store_error_policy,
             task_store: server_options.task_store.take(),
             client_task_store: server_options.client_task_store.take(),
             message_observer: server_options.message_observer.take(),
+            audit_sink: server_options.audit_sink.take(),
+            handler_execution: server_options.handler_execution.clone(),
+            require_initialized: server_options.require_initialized,
+            session_grace_period: server_options.session_grace_period,
         });
 
         // populate middlewares
         let mut middlewares: Vec<Arc<dyn Middleware>> = vec![];
 
-        if let Some(dns) = resolve_dns_middleware(
+        // registered first so preflight requests are answered before any other check and
+        // the headers are also attached to responses rejected by other middlewares
+        if let Some(cors) = server_options.cors.clone() {
+            middlewares.push(Arc::new(CorsMiddleware::new(cors.with_mcp_headers())));
+        }
+
+        // registered early so the headers are also attached to responses rejected by other middlewares
+        if let Some(security_headers) = server_options.security_headers.clone() {
+            middlewares.push(Arc::new(SecurityHeadersMiddleware::new(security_headers)));
+        }
+
+        if let Some(body_logging) = server_options.debug_body_logging.clone() {
+            middlewares.push(Arc::new(BodyLoggingMiddleware::new(body_logging)));
+        }
+
+        // invalid lists are reported by `validate()` when the server starts
+        match resolve_ip_filter_middleware(&server_options.ip_filter) {
+            Ok(Some(ip_filter)) => middlewares.push(Arc::new(ip_filter)),
+            Ok(None) => {}
+            Err(error) => tracing::error!("Invalid IP filter options: {error}"),
+        }
+
+        let dns_middleware = resolve_dns_middleware(
             &mut server_options.dns_rebinding,
             &server_options.host,
             server_options.port,
-        ) {
+        );
+        let dns_rebinding_protection = dns_middleware.is_some();
+        if let Some(dns) = dns_middleware {
             middlewares.push(Arc::new(dns));
         }
+        let auth_enabled = server_options.auth.is_some();
+        // checked before the auth provider is consumed, as its endpoints are mounted as routes too
+        let mount_error = server_options.validate_mount_options().err();
 
         let http_handler = {
             let auth_provider = server_options.auth.take();
@@ -408,14 +565,22 @@ impl AxumServer {
             )
         };
 
-        let mount_options = server_options.resolve_mount_options();
-        let app = mcp_routes(Arc::clone(&state), &mount_options, http_handler);
+        // invalid endpoints would make the router panic, they are reported when the server starts
+        let app = if mount_error.is_none() {
+            let mount_options = server_options.resolve_mount_options();
+            mcp_routes(Arc::clone(&state), &mount_options, http_handler)
+        } else {
+            Router::new()
+        };
 
         Self {
             app,
             state,
             options: server_options,
             handle: Handle::new(),
+            auth_enabled,
+            dns_rebinding_protection,
+            mount_error,
         }
     }
 
@@ -488,6 +653,76 @@ impl AxumServer {
         &self.options
     }
 
+    /// Collects the effective configuration the server is started with
+    pub(crate) fn startup_summary(&self, addr: SocketAddr) -> StartupSummary {
+        let server_details = &self.state.server_details;
+        let protocol_version = server_details.protocol_version.clone();
+        let capabilities = &server_details.capabilities;
+
+        let mut enabled_capabilities = vec![];
+        if capabilities.tools.is_some() {
+            enabled_capabilities.push("tools");
+        }
+        if let Some(resources) = capabilities.resources.as_ref() {
+            enabled_capabilities.push("resources");
+            if resources.subscribe.unwrap_or(false) {
+                enabled_capabilities.push("resources.subscribe");
+            }
+        }
+        if capabilities.prompts.is_some() {
+            enabled_capabilities.push("prompts");
+        }
+        if capabilities.logging.is_some() {
+            enabled_capabilities.push("logging");
+        }
+        if capabilities.completions.is_some() {
+            enabled_capabilities.push("completions");
+        }
+        if capabilities.tasks.is_some() {
+            enabled_capabilities.push("tasks");
+        }
+        if capabilities.experimental.is_some() {
+            enabled_capabilities.push("experimental");
+        }
+
+        StartupSummary {
+            server_name: server_details.server_info.name.clone(),
+            server_version: server_details.server_info.version.clone(),
+            // clients may negotiate down to any supported version up to the server's own
+            supported_protocol_versions: ProtocolVersion::supported_versions(false)
+                .into_iter()
+                .map(|version| version.to_string())
+                .filter(|version| *version <= protocol_version)
+                .collect(),
+            protocol_version,
+            capabilities: enabled_capabilities,
+            address: addr,
+            ssl: self.options.enable_ssl,
+            streamable_http_endpoint: self.options.streamable_http_endpoint().to_string(),
+            sse_endpoint: self
+                .options
+                .sse_support
+                .then(|| self.options.sse_endpoint().to_string()),
+            sse_messages_endpoint: self
+                .options
+                .sse_support
+                .then(|| self.options.sse_messages_endpoint().to_string()),
+            health_endpoint: self.options.health_endpoint.clone(),
+            auth: self.auth_enabled,
+            resumability: self.state.event_store.is_some(),
+            json_response: self.state.enable_json_response,
+            dns_rebinding_protection: self.dns_rebinding_protection,
+            task_store: self.state.task_store.is_some(),
+            client_task_store: self.state.client_task_store.is_some(),
+            handler_execution: match self.state.handler_execution {
+                HandlerExecution::Async => "async",
+                HandlerExecution::Blocking => "blocking",
+                HandlerExecution::Bounded(_) => "bounded",
+            },
+            ping_interval: self.state.ping_interval,
+        }
+    }
+
     // pub fn with_layer<L>(mut self, layer: L) -> Self
     // where
     //     // L: Layer<axum::body::Body> + Clone + Send + Sync + 'static,
@@ -516,6 +751,7 @@ impl AxumServer {
         .map_err(|err| TransportServerError::SslCertError(err.to_string()))?;
 
         tracing::info!("{}", self.server_info(Some(addr)).await?);
+        self.startup_summary(addr).log();
 
         // Spawn a task to trigger shutdown on signal
         let handle_clone = self.handle.clone();
@@ -527,7 +763,7 @@ impl AxumServer {
         let handle_clone = self.handle.clone();
         axum_server::bind_rustls(addr, config)
             .handle(handle_clone)
-            .serve(self.app.into_make_service())
+            .serve(self.app.into_make_service_with_connect_info::<SocketAddr>())
             .await
             .map_err(|err| TransportServerError::ServerStartError(err.to_string()))
     }
@@ -546,6 +782,7 @@ impl AxumServer {
     /// * `TransportServerResult<()>` - Ok if the server starts successfully, Err otherwise
     pub(crate) async fn start_http(self, addr: SocketAddr) -> TransportServerResult<()> {
         tracing::info!("{}", self.server_info(Some(addr)).await?);
+        self.startup_summary(addr).log();
 
         // Spawn a task to trigger shutdown on signal
         let handle_clone = self.handle.clone();
@@ -556,7 +793,7 @@ impl AxumServer {
         let handle_clone = self.handle.clone();
         axum_server::bind(addr)
             .handle(handle_clone)
-            .serve(self.app.into_make_service())
+            .serve(self.app.into_make_service_with_connect_info::<SocketAddr>())
             .await
             .map_err(|err| TransportServerError::ServerStartError(err.to_string()))
     }
@@ -584,6 +821,60 @@ impl AxumServer {
     }
 }
 
+/// Effective configuration of a started server.
+///
+/// Logged once at startup so it can be pasted into bug reports. It only records whether
+/// authentication or SSL are enabled, never credentials, tokens or certificate paths.
+#[derive(Debug, Clone)]
+pub(crate) struct StartupSummary {
+    server_name: String,
+    server_version: String,
+    protocol_version: String,
+    supported_protocol_versions: Vec<String>,
+    capabilities: Vec<&'static str>,
+    address: SocketAddr,
+    ssl: bool,
+    streamable_http_endpoint: String,
+    sse_endpoint: Option<String>,
+    sse_messages_endpoint: Option<String>,
+    health_endpoint: Option<String>,
+    auth: bool,
+    resumability: bool,
+    json_response: bool,
+    dns_rebinding_protection: bool,
+    task_store: bool,
+    client_task_store: bool,
+    handler_execution: &'static str,
+    ping_interval: Duration,
+}
+
+impl StartupSummary {
+    fn log(&self) {
+        tracing::info!(
+            server_name = %self.server_name,
+            server_version = %self.server_version,
+            protocol_version = %self.protocol_version,
+            supported_protocol_versions = ?self.supported_protocol_versions,
+            capabilities = ?self.capabilities,
+            address = %self.address,
+            ssl = self.ssl,
+            streamable_http_endpoint = %self.streamable_http_endpoint,
+            sse_endpoint = ?self.sse_endpoint,
+            sse_messages_endpoint = ?self.sse_messages_endpoint,
+            health_endpoint = ?self.health_endpoint,
+            auth = self.auth,
+            resumability = self.resumability,
+            json_response = self.json_response,
+            dns_rebinding_protection = self.dns_rebinding_protection,
+            task_store = self.task_store,
+            client_task_store = self.client_task_store,
+            handler_execution = self.handler_execution,
+            ping_interval = ?self.ping_interval,
+            "MCP server started"
+        );
+    }
+}
+
 // Shutdown signal handler
 async fn shutdown_signal(handle: Handle<SocketAddr>, state: Arc<McpAppState>) {
     // Wait for a Ctrl+C or SIGTERM signal
@@ -619,8 +910,45 @@ async fn shutdown_signal(handle: Handle<SocketAddr>, state: Arc<McpAppState>) {
 mod tests {
     use super::*;
 
+    use rust_mcp_sdk::mcp_server::ToMcpServerHandler;
+    use rust_mcp_sdk::testing::{echo_server_details, EchoServerHandler};
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_startup_summary_reflects_configuration() {
+        let server = AxumServer::new(
+            echo_server_details(),
+            EchoServerHandler.to_mcp_server_handler(),
+            AxumServerOptions {
+                sse_support: false,
+                enable_json_response: Some(true),
+                health_endpoint: Some("/health".to_string()),
+                handler_execution: HandlerExecution::bounded(4),
+                ..Default::default()
+            },
+        );
+        let summary = server.startup_summary("127.0.0.1:8080".parse().unwrap());
+
+        assert_eq!(summary.server_name, "rust-mcp-sdk-echo-server");
+        assert_eq!(summary.protocol_version, "2025-11-25");
+        assert!(summary
+            .supported_protocol_versions
+            .contains(&"2024-11-05".to_string()));
+        assert!(summary
+            .supported_protocol_versions
+            .iter()
+            .all(|version| *version <= summary.protocol_version));
+        assert_eq!(summary.capabilities, vec!["tools"]);
+        assert_eq!(summary.streamable_http_endpoint, "/mcp");
+        assert_eq!(summary.sse_endpoint, None);
+        assert_eq!(summary.health_endpoint.as_deref(), Some("/health"));
+        assert!(!summary.auth);
+        assert!(!summary.resumability);
+        assert!(summary.json_response);
+        assert!(summary.dns_rebinding_protection);
+        assert_eq!(summary.handler_execution, "bounded");
+    }
+
     #[test]
     fn test_server_options_base_url_custom() {
         let options = AxumServerOptions {
@@ -674,6 +1002,48 @@ mod tests {
         assert_eq!(options.sse_messages_endpoint(), "/abcd/messages");
     }
 
+    #[test]
+    fn test_server_options_validate_streamable_http_endpoint() {
+        let options = AxumServerOptions {
+            custom_streamable_http_endpoint: Some("/".into()),
+            health_endpoint: Some("/health".into()),
+            ..Default::default()
+        };
+        assert!(options.validate().is_ok());
+
+        for endpoint in ["mcp", "/mcp?debug=1", "/health", "/sse"] {
+            let options = AxumServerOptions {
+                custom_streamable_http_endpoint: Some(endpoint.into()),
+                health_endpoint: Some("/health".into()),
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    options.validate(),
+                    Err(TransportServerError::InvalidServerOptions(_))
+                ),
+                "{endpoint}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_streamable_http_endpoint_is_reported_at_startup() {
+        // the router panics on a path without a leading `/`, so it is not mounted
+        let server = AxumServer::new(
+            echo_server_details(),
+            EchoServerHandler.to_mcp_server_handler(),
+            AxumServerOptions {
+                custom_streamable_http_endpoint: Some("mcp".into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            server.mount_error.as_deref(),
+            Some("Invalid streamable HTTP endpoint 'mcp': it must start with '/'.")
+        );
+    }
+
     #[test]
     fn test_server_options_validate() {
         let options = AxumServerOptions::default();
@@ -1,3 +1,6 @@
+use axum::{extract::ConnectInfo, Extension};
+use std::net::SocketAddr;
+
 pub(crate) fn remove_query_and_hash(endpoint: &str) -> String {
     let without_fragment = endpoint.split_once('#').map_or(endpoint, |(path, _)| path);
     let without_query = without_fragment
@@ -9,3 +12,16 @@ pub(crate) fn remove_query_and_hash(endpoint: &str) -> String {
         without_query.to_string()
     }
 }
+
+/// Stores the remote address of the connection in the request extensions, where middlewares
+/// such as `IpFilterMiddleware` look it up.
+/// It is only known when the router is served with `into_make_service_with_connect_info`.
+pub(crate) fn with_remote_addr(
+    mut request: http::Request<&str>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+) -> http::Request<&str> {
+    if let Some(Extension(ConnectInfo(addr))) = connect_info {
+        request.extensions_mut().insert(addr);
+    }
+    request
+}
@@ -1,15 +1,20 @@
 use axum::body::Body;
+use axum::extract::ConnectInfo;
 use axum::http::{Method, StatusCode};
+use axum::routing::get;
 use axum::Router;
 use http_body_util::BodyExt;
-use rust_mcp_axum::{mcp_routes, AxumServerOptions, McpMountOptions};
+use rust_mcp_axum::{mcp_router, mcp_routes, AxumServerConfig, AxumServerOptions, McpMountOptions};
 use rust_mcp_sdk::id_generator::{FastIdGenerator, UuidGenerator};
+use rust_mcp_sdk::mcp_http::middleware::{SecurityHeadersConfig, SecurityHeadersMiddleware};
 use rust_mcp_sdk::mcp_http::McpAppState;
 use rust_mcp_sdk::mcp_http::McpHttpHandler;
+use rust_mcp_sdk::mcp_http::{resolve_ip_filter_middleware, IpFilterOptions};
 use rust_mcp_sdk::mcp_server::ServerHandler;
 use rust_mcp_sdk::schema::{Implementation, InitializeResult, ProtocolVersion, ServerCapabilities};
 use rust_mcp_sdk::session_store::InMemorySessionStore;
 use rust_mcp_sdk::ToMcpServerHandler;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tower::ServiceExt;
 
@@ -34,8 +39,8 @@ fn test_server_details() -> InitializeResult {
 struct DummyHandler;
 impl ServerHandler for DummyHandler {}
 
-fn make_app(http_handler: McpHttpHandler, mount: &McpMountOptions) -> Router {
-    let state = Arc::new(McpAppState {
+fn make_state() -> Arc<McpAppState> {
+    Arc::new(McpAppState {
         session_store: Arc::new(InMemorySessionStore::new()),
         id_generator: Arc::new(UuidGenerator {}),
         stream_id_gen: Arc::new(FastIdGenerator::new(Some("s_"))),
@@ -44,12 +49,22 @@ fn make_app(http_handler: McpHttpHandler, mount: &McpMountOptions) -> Router {
         ping_interval: std::time::Duration::from_secs(12),
         transport_options: Default::default(),
         enable_json_response: false,
+        json_response_fallback: false,
+        sse_stream_permits: None,
         event_store: None,
+        event_store_error_policy: Default::default(),
         task_store: None,
         client_task_store: None,
         message_observer: None,
-    });
-    mcp_routes(state, mount, http_handler)
+        audit_sink: None,
+        handler_execution: Default::default(),
+        require_initialized: false,
+        session_grace_period: None,
+    })
+}
+
+fn make_app(http_handler: McpHttpHandler, mount: &McpMountOptions) -> Router {
+    mcp_routes(make_state(), mount, http_handler)
 }
 
 fn default_mount() -> McpMountOptions {
@@ -102,7 +117,7 @@ fn test_resolve_mount_options_default() {
     assert_eq!(mount.streamable_http_endpoint, "/mcp");
     assert_eq!(mount.sse_endpoint, "/sse");
     assert_eq!(mount.sse_messages_endpoint, "/messages");
-    assert!(mount.health_endpoint.is_none());
+    assert_eq!(mount.health_endpoint.as_deref(), Some("/health"));
     assert_eq!(mount.max_request_body_size, 4 * 1024 * 1024);
 }
 
@@ -124,6 +139,64 @@ fn test_resolve_mount_options_custom() {
     assert_eq!(mount.max_request_body_size, 1024);
 }
 
+// =====================================================================
+// AxumServerConfig <-> AxumServerOptions
+// =====================================================================
+
+#[test]
+fn test_server_config_fills_missing_fields_with_defaults() {
+    let config = AxumServerConfig::from_json(
+        r#"{
+            "host": "0.0.0.0",
+            "port": 3000,
+            "ping_interval_ms": 5000,
+            "event_store_error_policy": "fail_send",
+            "allowed_hosts": ["example.com"]
+        }"#,
+    )
+    .unwrap();
+
+    let options: AxumServerOptions = config.into();
+    assert_eq!(options.host, "0.0.0.0");
+    assert_eq!(options.port, 3000);
+    assert_eq!(options.ping_interval.as_millis(), 5000);
+    assert_eq!(
+        options.event_store_error_policy,
+        rust_mcp_sdk::event_store::EventStoreErrorPolicy::FailSend
+    );
+    assert_eq!(
+        options.dns_rebinding.allowed_hosts,
+        Some(vec!["example.com".to_string()])
+    );
+    assert!(options.dns_rebinding.dns_rebinding_protection);
+    assert!(options.sse_support);
+    assert_eq!(
+        options.transport_options.timeout,
+        AxumServerOptions::default().transport_options.timeout
+    );
+}
+
+#[test]
+fn test_server_config_round_trips_through_json() {
+    let options = AxumServerOptions {
+        custom_streamable_http_endpoint: Some("/custom/mcp".into()),
+        max_sse_streams: Some(10),
+        require_initialized: true,
+        ..AxumServerOptions::default()
+    };
+    let config = AxumServerConfig::from(&options);
+    let json = config.to_json().unwrap();
+    assert_eq!(AxumServerConfig::from_json(&json).unwrap(), config);
+
+    let restored: AxumServerOptions = config.into();
+    assert_eq!(
+        restored.custom_streamable_http_endpoint,
+        Some("/custom/mcp".into())
+    );
+    assert_eq!(restored.max_sse_streams, Some(10));
+    assert!(restored.require_initialized);
+}
+
 // =====================================================================
 // Health check route tests
 // =====================================================================
@@ -202,6 +275,67 @@ async fn test_streamable_http_get_without_session_id() {
     assert!(!response.status().is_success());
 }
 
+#[tokio::test]
+async fn test_security_headers_attached_to_responses() {
+    let security_headers = SecurityHeadersMiddleware::new(SecurityHeadersConfig {
+        content_security_policy: Some("default-src 'none'".into()),
+        ..Default::default()
+    });
+    let handler = McpHttpHandler::new(None, vec![Arc::new(security_headers)], None);
+    let mount = default_mount();
+    let app = make_app(handler, &mount);
+
+    let response = app
+        .oneshot(
+            axum::http::Request::builder()
+                .method(Method::GET)
+                .uri("/mcp")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let headers = response.headers();
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    assert_eq!(headers["referrer-policy"], "no-referrer");
+    assert_eq!(headers["content-security-policy"], "default-src 'none'");
+}
+
+#[tokio::test]
+async fn test_ip_filter_rejects_denied_remote_addresses() {
+    let ip_filter = resolve_ip_filter_middleware(&IpFilterOptions {
+        allowed_ips: Some(vec!["127.0.0.0/8".into()]),
+        denied_ips: Some(vec!["127.0.0.2".into()]),
+        trusted_proxies: 0,
+    })
+    .unwrap()
+    .unwrap();
+    let handler = McpHttpHandler::new(None, vec![Arc::new(ip_filter)], None);
+    let mount = default_mount();
+    let app = make_app(handler, &mount);
+
+    let request = |remote: &str| {
+        let mut request = axum::http::Request::builder()
+            .method(Method::GET)
+            .uri("/mcp")
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(remote.parse::<SocketAddr>().unwrap()));
+        request
+    };
+
+    for remote in ["127.0.0.2:4000", "10.0.0.1:4000"] {
+        let response = app.clone().oneshot(request(remote)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN, "{remote}");
+    }
+
+    let response = app.oneshot(request("127.0.0.1:4000")).await.unwrap();
+    assert_ne!(response.status(), StatusCode::FORBIDDEN);
+}
+
 #[tokio::test]
 async fn test_streamable_http_post_invalid_body() {
     let handler = McpHttpHandler::new(None, vec![], None);
@@ -320,6 +454,62 @@ async fn test_fallback_unknown_path_returns_404() {
     assert!(body_str.contains("does not exist"));
 }
 
+// =====================================================================
+// mcp_router tests
+// =====================================================================
+
+async fn get_status(app: Router, uri: &str) -> StatusCode {
+    app.oneshot(
+        axum::http::Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+#[tokio::test]
+async fn test_mcp_router_merges_into_router_with_fallback() {
+    let app = Router::new()
+        .route("/api/custom", get(|| async { "custom" }))
+        .fallback(|| async { StatusCode::IM_A_TEAPOT })
+        .merge(mcp_router(make_state()));
+
+    assert_eq!(get_status(app.clone(), "/api/custom").await, StatusCode::OK);
+    assert_eq!(
+        get_status(app.clone(), "/non-existent-path").await,
+        StatusCode::IM_A_TEAPOT
+    );
+    // served by the MCP route, which requires a session id
+    let status = get_status(app, "/mcp").await;
+    assert!(!status.is_success());
+    assert_ne!(status, StatusCode::IM_A_TEAPOT);
+}
+
+#[tokio::test]
+async fn test_mcp_router_nests_under_prefix() {
+    let app = Router::new().nest("/tools", mcp_router(make_state()));
+
+    let response = app
+        .oneshot(
+            axum::http::Request::builder()
+                .method(Method::POST)
+                .uri("/tools/mcp")
+                .header("content-type", "application/json")
+                .header("accept", "application/json, text/event-stream")
+                .body(Body::from("not json"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // answered by the streamable HTTP handler rather than the router
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 // =====================================================================
 // SSE endpoint presence test
 // =====================================================================
@@ -0,0 +1,134 @@
+//! [`AuditSink`] implementations for recording tool invocations.
+use async_trait::async_trait;
+use rust_mcp_sdk::audit::{AuditSink, ToolAuditRecord};
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// An [`AuditSink`] that appends each record as a line of JSON to a file.
+///
+/// Records are written in the format of [`ToolAuditRecord::to_json`], one per line, so the file can
+/// be tailed or shipped to a log pipeline as is. Write failures are logged and do not affect the
+/// tool call.
+///
+/// # Example
+/// ```no_run
+/// use rust_mcp_extra::audit::JsonlAuditSink;
+/// use rust_mcp_sdk::audit::{AuditRedaction, RedactingAuditSink};
+/// use std::sync::Arc;
+///
+/// let sink = RedactingAuditSink::new(
+///     Arc::new(JsonlAuditSink::new("audit.jsonl").unwrap()),
+///     AuditRedaction::new(["password"]),
+/// );
+/// ```
+pub struct JsonlAuditSink {
+    file: Mutex<File>,
+}
+
+impl JsonlAuditSink {
+    /// Opens `path` for appending, creating it if it does not exist.
+    pub fn new(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self {
+            file: Mutex::new(File::from_std(file)),
+        })
+    }
+}
+
+#[async_trait]
+impl AuditSink for JsonlAuditSink {
+    async fn record_tool_call(&self, record: ToolAuditRecord) {
+        let mut line = record.to_json().to_string();
+        line.push('\n');
+
+        let mut file = self.file.lock().await;
+        if let Err(error) = async {
+            file.write_all(line.as_bytes()).await?;
+            file.flush().await
+        }
+        .await
+        {
+            tracing::error!("Failed to write audit record: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_mcp_sdk::audit::ToolAuditOutcome;
+    use rust_mcp_sdk::schema::RequestId;
+    use serde_json::{json, Value};
+    use std::time::{Duration, SystemTime};
+
+    fn record(request_id: i64, outcome: ToolAuditOutcome) -> ToolAuditRecord {
+        ToolAuditRecord {
+            timestamp: SystemTime::now(),
+            duration: Duration::from_millis(12),
+            session_id: Some("session-1".to_string()),
+            request_id: RequestId::Integer(request_id),
+            subject: Some("alice".to_string()),
+            client_id: None,
+            client_name: Some("test-client".to_string()),
+            tool_name: "greet".to_string(),
+            arguments: json!({ "name": "bob" }).as_object().cloned(),
+            outcome,
+        }
+    }
+
+    #[tokio::test]
+    async fn appends_one_json_line_per_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+
+        let sink = JsonlAuditSink::new(&path).unwrap();
+        sink.record_tool_call(record(1, ToolAuditOutcome::Success))
+            .await;
+        sink.record_tool_call(record(
+            2,
+            ToolAuditOutcome::Failed {
+                code: -32602,
+                message: "invalid arguments".to_string(),
+            },
+        ))
+        .await;
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["request_id"], 1);
+        assert_eq!(lines[0]["tool"], "greet");
+        assert_eq!(lines[0]["arguments"], json!({ "name": "bob" }));
+        assert_eq!(lines[0]["outcome"], "success");
+        assert_eq!(lines[1]["request_id"], 2);
+        assert_eq!(lines[1]["outcome"], "failed");
+        assert_eq!(lines[1]["error"]["code"], -32602);
+    }
+
+    #[tokio::test]
+    async fn appends_to_an_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+
+        JsonlAuditSink::new(&path)
+            .unwrap()
+            .record_tool_call(record(1, ToolAuditOutcome::Success))
+            .await;
+        JsonlAuditSink::new(&path)
+            .unwrap()
+            .record_tool_call(record(2, ToolAuditOutcome::ToolError))
+            .await;
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+    }
+}
@@ -23,6 +23,9 @@ pub(crate) struct GenericMcpMacroAttributes {
     pub open_world_hint: Option<bool>,
     pub read_only_hint: Option<bool>,
     pub execution: Option<ExecutionSupportDsl>,
+    pub blocking: Option<bool>,
+    pub timeout_ms: Option<u64>,
+    pub output: Option<syn::Path>,
 }
 
 impl Parse for GenericMcpMacroAttributes {
@@ -43,6 +46,9 @@ impl Parse for GenericMcpMacroAttributes {
             open_world_hint: None,
             read_only_hint: None,
             execution: None,
+            blocking: None,
+            timeout_ms: None,
+            output: None,
         };
 
         let meta_list: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated(attributes)?;
@@ -141,6 +147,38 @@ impl Parse for GenericMcpMacroAttributes {
                             };
                             instance.size = Some(value);
                         }
+
+                        // u64
+                        "timeout_ms" => {
+                            let value = match &meta_name_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Int(lit_int),
+                                    ..
+                                }) => lit_int.base10_parse::<u64>()?,
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        &meta_name_value.value,
+                                        "Expected a integer literal",
+                                    ));
+                                }
+                            };
+                            instance.timeout_ms = Some(value);
+                        }
+                        // type path
+                        "output" => {
+                            let value = match &meta_name_value.value {
+                                Expr::Path(expr_path) if expr_path.qself.is_none() => {
+                                    expr_path.path.clone()
+                                }
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        &meta_name_value.value,
+                                        "Expected a type deriving JsonSchema, e.g. `output = MyResult`",
+                                    ));
+                                }
+                            };
+                            instance.output = Some(value);
+                        }
                         "meta" => {
                             let value = match &meta_name_value.value {
                                 Expr::Lit(ExprLit {
@@ -232,7 +270,7 @@ impl Parse for GenericMcpMacroAttributes {
 
                         // for tools annotations
                         "destructive_hint" | "idempotent_hint" | "open_world_hint"
-                        | "read_only_hint" => {
+                        | "read_only_hint" | "blocking" => {
                             let value = match &meta_name_value.value {
                                 Expr::Lit(ExprLit {
                                     lit: Lit::Bool(lit_bool),
@@ -251,6 +289,7 @@ impl Parse for GenericMcpMacroAttributes {
                                 "idempotent_hint" => instance.idempotent_hint = Some(value),
                                 "open_world_hint" => instance.open_world_hint = Some(value),
                                 "read_only_hint" => instance.read_only_hint = Some(value),
+                                "blocking" => instance.blocking = Some(value),
                                 _ => {}
                             }
                         }
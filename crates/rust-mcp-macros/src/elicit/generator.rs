@@ -1,10 +1,12 @@
 use crate::is_option;
+use crate::is_skipped;
 use crate::is_vec_string;
-use quote::quote;
 use quote::ToTokens;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{
-    punctuated::Punctuated, token::Comma, Expr, ExprLit, Ident, Lit, Meta, PathArguments, Token,
-    Type,
+    punctuated::Punctuated, token::Comma, Expr, ExprLit, Ident, Lit, LitInt, Meta, PathArguments,
+    Token, Type,
 };
 
 fn json_field_name(field: &syn::Field) -> String {
@@ -61,11 +63,148 @@ fn json_field_name(field: &syn::Field) -> String {
         .unwrap_or_else(|| field.ident.as_ref().unwrap().to_string())
 }
 
+/// The `#[json_schema(...)]` items of a field that affect parsing its elicited value.
+#[derive(Default)]
+struct FieldSchemaAttrs {
+    default: Option<Lit>,
+    min_length: Option<u64>,
+    max_length: Option<u64>,
+    minimum: Option<Bound>,
+    maximum: Option<Bound>,
+}
+
+/// A `minimum` or `maximum`, which may be an integer or a float.
+enum Bound {
+    Int(i64),
+    Float(f64),
+}
+
+impl Bound {
+    fn parse(lit: Lit) -> syn::Result<Self> {
+        match lit {
+            Lit::Int(lit) => Ok(Self::Int(lit.base10_parse()?)),
+            Lit::Float(lit) => Ok(Self::Float(lit.base10_parse()?)),
+            lit => Err(syn::Error::new(lit.span(), "expected a number")),
+        }
+    }
+
+    /// The integer `value` compared with `op` against this bound.
+    fn compare(&self, op: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Int(bound) => quote! { i64::from(*value) #op #bound },
+            Self::Float(bound) => quote! { (*value as f64) #op #bound },
+        }
+    }
+}
+
+impl ToTokens for Bound {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Int(bound) => bound.to_tokens(tokens),
+            Self::Float(bound) => bound.to_tokens(tokens),
+        }
+    }
+}
+
+fn field_schema_attrs(field: &syn::Field) -> syn::Result<FieldSchemaAttrs> {
+    let mut attrs = FieldSchemaAttrs::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("json_schema"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                attrs.default = Some(meta.value()?.parse::<Lit>()?);
+            } else if meta.path.is_ident("min_length") {
+                attrs.min_length = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("max_length") {
+                attrs.max_length = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            } else if meta.path.is_ident("minimum") {
+                attrs.minimum = Some(Bound::parse(meta.value()?.parse::<Lit>()?)?);
+            } else if meta.path.is_ident("maximum") {
+                attrs.maximum = Some(Bound::parse(meta.value()?.parse::<Lit>()?)?);
+            } else if meta.input.peek(Token![=]) {
+                // skip the value of any other `key = value` item
+                meta.value()?.parse::<Lit>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// Checks a parsed `value: &T` against the length and range constraints declared on its field,
+/// so input violating the form schema is rejected even if the client did not validate it.
+fn constraint_checks(
+    ty: &Type,
+    key: &str,
+    attrs: &FieldSchemaAttrs,
+) -> Option<proc_macro2::TokenStream> {
+    let is_ident = |name: &str| matches!(ty, Type::Path(p) if p.path.is_ident(name));
+    let mut checks = Vec::new();
+
+    let length = if is_ident("String") {
+        Some((quote! { value.chars().count() }, "characters"))
+    } else if is_vec_string(ty) {
+        Some((quote! { value.len() }, "items"))
+    } else {
+        None
+    };
+    if let Some((len, unit)) = length {
+        if let Some(min) = attrs.min_length {
+            let min = min as usize;
+            checks.push(quote! {
+                if #len < #min {
+                    return Err(RpcError::parse_error().with_message(format!(
+                        "Field '{}' must have at least {} {}", #key, #min, #unit
+                    )));
+                }
+            });
+        }
+        if let Some(max) = attrs.max_length {
+            let max = max as usize;
+            checks.push(quote! {
+                if #len > #max {
+                    return Err(RpcError::parse_error().with_message(format!(
+                        "Field '{}' must have at most {} {}", #key, #max, #unit
+                    )));
+                }
+            });
+        }
+    }
+
+    if is_ident("i32") || is_ident("i64") {
+        if let Some(min) = &attrs.minimum {
+            let below = min.compare(quote! { < });
+            checks.push(quote! {
+                if #below {
+                    return Err(RpcError::parse_error().with_message(format!(
+                        "Field '{}' must be at least {}", #key, #min
+                    )));
+                }
+            });
+        }
+        if let Some(max) = &attrs.maximum {
+            let above = max.compare(quote! { > });
+            checks.push(quote! {
+                if #above {
+                    return Err(RpcError::parse_error().with_message(format!(
+                        "Field '{}' must be at most {}", #key, #max
+                    )));
+                }
+            });
+        }
+    }
+
+    (!checks.is_empty()).then(|| quote! { #(#checks)* })
+}
+
 //  Form implementation generation
 pub fn generate_from_impl(
     fields: &Punctuated<syn::Field, Comma>,
     base: &proc_macro2::TokenStream,
-) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+) -> syn::Result<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
     let mut assigns = Vec::new();
     let mut idents = Vec::new();
 
@@ -76,9 +215,24 @@ pub fn generate_from_impl(
 
         idents.push(ident);
 
+        // a skipped field is not part of the form schema, so it is never elicited
+        if is_skipped(&field.attrs) {
+            assigns.push(quote! { let #ident = ::std::default::Default::default(); });
+            continue;
+        }
+
+        let attrs = field_schema_attrs(field)?;
+
         let block = if is_option(ty) {
             let inner = get_option_inner(ty);
-            let (expected, pat, conv) = match_type(inner, &key, base);
+            let (expected, pat, conv) = match_type(inner, &key, base)?;
+            let checks = constraint_checks(inner, &key, &attrs).map(|checks| {
+                quote! {
+                    if let Some(value) = &#ident {
+                        #checks
+                    }
+                }
+            });
             quote! {
                 let #ident = match map.remove(#key) {
                     Some(#pat) => Some(#conv),
@@ -88,9 +242,26 @@ pub fn generate_from_impl(
                     ))),
                     None => None,
                 };
+                #checks
             }
         } else {
-            let (expected, pat, conv) = match_type(ty, &key, base);
+            let (expected, pat, conv) = match_type(ty, &key, base)?;
+            let checks = constraint_checks(ty, &key, &attrs).map(|checks| {
+                quote! {
+                    {
+                        let value = &#ident;
+                        #checks
+                    }
+                }
+            });
+            // a defaulted field falls back to its `#[json_schema(default = ...)]` value when missing
+            let missing = match attrs.default {
+                Some(Lit::Str(lit)) => quote! { ::std::string::String::from(#lit) },
+                Some(lit) => quote! { #lit },
+                None => quote! {
+                    return Err(RpcError::parse_error().with_message(format!("Missing required field '{}'", #key)))
+                },
+            };
             quote! {
                 let #ident = match map.remove(#key) {
                     Some(#pat) => #conv,
@@ -98,15 +269,16 @@ pub fn generate_from_impl(
                         "Type mismatch for required field '{}': expected {}, got {:?}",
                         #key, #expected, other
                     ))),
-                    None => return Err(RpcError::parse_error().with_message(format!("Missing required field '{}'", #key))),
+                    None => #missing,
                 };
+                #checks
             }
         };
 
         assigns.push(block);
     }
 
-    (quote! { #(#assigns)* }, quote! { Self { #(#idents),* } })
+    Ok((quote! { #(#assigns)* }, quote! { Self { #(#idents),* } }))
 }
 
 pub fn get_option_inner(ty: &Type) -> &Type {
@@ -128,40 +300,85 @@ pub fn match_type(
     ty: &Type,
     key: &str,
     base: &proc_macro2::TokenStream,
-) -> (String, proc_macro2::TokenStream, proc_macro2::TokenStream) {
+) -> syn::Result<(String, proc_macro2::TokenStream, proc_macro2::TokenStream)> {
     if is_vec_string(ty) {
-        return (
+        return Ok((
             "string array".into(), // expected
             quote! { V::StringArray(v) },
             quote! { v },
-        );
+        ));
     };
 
     match ty {
-        Type::Path(p) if p.path.is_ident("String") => (
+        Type::Path(p) if p.path.is_ident("String") => Ok((
             "string".into(),
             quote! { V::Primitive(#base::ElicitResultContentPrimitive::String(v)) },
             quote! { v.clone() },
-        ),
-        Type::Path(p) if p.path.is_ident("bool") => (
+        )),
+        Type::Path(p) if p.path.is_ident("bool") => Ok((
             "bool".into(),
             quote! { V::Primitive(#base::ElicitResultContentPrimitive::Boolean(v)) },
             quote! { v },
-        ),
-        Type::Path(p) if p.path.is_ident("i32") => (
+        )),
+        Type::Path(p) if p.path.is_ident("i32") => Ok((
             "i32".into(),
             quote! { V::Primitive(#base::ElicitResultContentPrimitive::Integer(v)) },
             quote! { (v).try_into().map_err(|_| RpcError::parse_error().with_message(format!("i32 overflow in field '{}'", #key)))? },
-        ),
-        Type::Path(p) if p.path.is_ident("i64") => (
+        )),
+        Type::Path(p) if p.path.is_ident("i64") => Ok((
             "i64".into(),
             quote! { V::Primitive(#base::ElicitResultContentPrimitive::Integer(v)) },
             quote! { v },
-        ),
-        _ => panic!("Unsupported type in mcp_elicit: {}", ty.to_token_stream()),
+        )),
+        // any other plain path is taken as a unit-only enum deriving `JsonSchema`, offered as a single-select choice
+        Type::Path(p) if might_be_enum(ty) => {
+            let path = &p.path;
+            // spanned at the field type, so a type without `from_enum_value` is reported there
+            let parse = quote_spanned! {ty.span()=> #path::from_enum_value(&v) };
+            Ok((
+                "string".into(),
+                quote! { V::Primitive(#base::ElicitResultContentPrimitive::String(v)) },
+                quote! {
+                    #parse.ok_or_else(|| {
+                        RpcError::parse_error().with_message(format!(
+                            "Invalid selection '{}' for field '{}': not one of the {} options",
+                            v, #key, stringify!(#path)
+                        ))
+                    })?
+                },
+            ))
+        }
+        _ => Err(syn::Error::new_spanned(
+            ty,
+            format!(
+                "mcp_elicit does not support fields of type `{}`; use String, bool, i32, i64, \
+                 Vec<String>, a unit-only enum deriving JsonSchema, or an Option of one of these",
+                ty.to_token_stream()
+            ),
+        )),
     }
 }
 
+/// Whether a type could name a unit-only enum: a path without generic arguments that is not
+/// one of the primitive or standard types `match_type` rejects.
+fn might_be_enum(ty: &Type) -> bool {
+    let Type::Path(p) = ty else {
+        return false;
+    };
+    if p.qself.is_some() || p.path.segments.iter().any(|s| !s.arguments.is_empty()) {
+        return false;
+    }
+    let Some(last) = p.path.segments.last() else {
+        return false;
+    };
+    let std_types = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64", "bool", "char", "str", "String", "Vec", "Option", "HashMap", "BTreeMap",
+        "HashSet", "BTreeSet", "Box", "Value",
+    ];
+    !std_types.contains(&last.ident.to_string().as_str())
+}
+
 pub fn generate_form_schema(
     struct_name: &Ident,
     base: &proc_macro2::TokenStream,
@@ -208,25 +425,153 @@ pub fn generate_form_schema(
                 out
             }
 
+            // A field typed as a unit-only enum derives `{"oneOf": [{"enum": ["A"]}, ...]}`,
+            // wrapped as `{"anyOf": [.., {"type": "null"}]}` when optional. Neither is an elicit
+            // single-select schema, so project it to `{"type": "string", "enum": [..]}`, or to
+            // `{"type": "string", "oneOf": [{"const": .., "title": ..}]}` when every variant is
+            // titled. Any other schema is returned unchanged.
+            fn __mcp_single_select_enum(
+                obj: serde_json::Map<String, serde_json::Value>,
+            ) -> serde_json::Map<String, serde_json::Value> {
+                let mut out = obj.clone();
+                if let Some(serde_json::Value::Array(any_of)) = obj.get("anyOf") {
+                    let null_schema = serde_json::json!({ "type": "null" });
+                    let inner: Vec<_> = any_of.iter().filter(|v| **v != null_schema).collect();
+                    match inner.as_slice() {
+                        [serde_json::Value::Object(inner)] if any_of.len() == 2 && inner.contains_key("oneOf") => {
+                            out.remove("anyOf");
+                            for (k, v) in inner {
+                                out.entry(k.clone()).or_insert_with(|| v.clone());
+                            }
+                        }
+                        _ => return obj,
+                    }
+                }
+
+                let Some(serde_json::Value::Array(one_of)) = out.get("oneOf") else {
+                    return obj;
+                };
+                let mut values = Vec::with_capacity(one_of.len());
+                let mut titled = Vec::with_capacity(one_of.len());
+                for variant in one_of {
+                    let value = variant
+                        .get("enum")
+                        .and_then(|e| e.as_array())
+                        .filter(|e| e.len() == 1)
+                        .and_then(|e| e[0].as_str());
+                    let Some(value) = value else {
+                        return obj;
+                    };
+                    values.push(serde_json::Value::String(value.to_string()));
+                    if let Some(title) = variant.get("title").and_then(|t| t.as_str()) {
+                        titled.push(serde_json::json!({ "const": value, "title": title }));
+                    }
+                }
+
+                out.remove("oneOf");
+                out.insert("type".to_string(), serde_json::Value::String("string".to_string()));
+                if titled.len() == values.len() {
+                    out.insert("oneOf".to_string(), serde_json::Value::Array(titled));
+                } else {
+                    out.insert("enum".to_string(), serde_json::Value::Array(values));
+                }
+                out
+            }
+
             let json = #struct_name::json_schema();
             let properties = json.get("properties")
                 .and_then(|v| v.as_object())
                 .into_iter()
                 .flatten()
                 .filter_map(|(k, v)| {
-                    let normalized = __mcp_strip_null_from_type(v.as_object()?);
+                    let normalized = __mcp_single_select_enum(__mcp_strip_null_from_type(v.as_object()?));
                     #base::PrimitiveSchemaDefinition::try_from(&normalized).ok().map(|def| (k.clone(), def))
                 })
                 .collect();
 
+            // A field carrying a default can be left empty by the user, so it is not
+            // required in the form even when its Rust type is not an `Option<T>`.
             let required = json.get("required")
                 .and_then(|v| v.as_array())
                 .into_iter()
                 .flatten()
-                .filter_map(|v| v.as_str().map(String::from))
+                .filter_map(|v| v.as_str())
+                .filter(|k| {
+                    json.get("properties")
+                        .and_then(|p| p.get(*k))
+                        .and_then(|p| p.get("default"))
+                        .is_none()
+                })
+                .map(String::from)
                 .collect();
 
             #base::ElicitFormSchema::new(properties, required, None)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_of(item: syn::ItemStruct) -> syn::Field {
+        item.fields.into_iter().next().unwrap()
+    }
+
+    fn match_type_of(ty: &str) -> syn::Result<String> {
+        let ty: Type = syn::parse_str(ty).unwrap();
+        match_type(&ty, "field", &quote! { base }).map(|(expected, _, _)| expected)
+    }
+
+    #[test]
+    fn parses_float_bounds_without_dropping_other_items() {
+        let field = field_of(syn::parse_quote! {
+            struct Review {
+                #[json_schema(minimum = 0.5, maximum = 10, default = 3)]
+                rating: i64,
+            }
+        });
+        let attrs = field_schema_attrs(&field).unwrap();
+        assert!(matches!(attrs.minimum, Some(Bound::Float(min)) if min == 0.5));
+        assert!(matches!(attrs.maximum, Some(Bound::Int(10))));
+        assert!(matches!(attrs.default, Some(Lit::Int(_))));
+    }
+
+    #[test]
+    fn reports_invalid_schema_attributes() {
+        let field = field_of(syn::parse_quote! {
+            struct Review {
+                #[json_schema(minimum = "low", default = 3)]
+                rating: i64,
+            }
+        });
+        let error = field_schema_attrs(&field).err().unwrap();
+        assert_eq!(error.to_string(), "expected a number");
+    }
+
+    #[test]
+    fn takes_plain_paths_as_enums() {
+        assert_eq!(match_type_of("Color").unwrap(), "string");
+        assert_eq!(match_type_of("crate::colors::Color").unwrap(), "string");
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        for ty in [
+            "f64",
+            "u32",
+            "std::string::String",
+            "HashMap<String, String>",
+            "Vec<i32>",
+            "Wrapper<Color>",
+            "(String, bool)",
+            "&'static str",
+        ] {
+            let error = match_type_of(ty).unwrap_err().to_string();
+            assert!(
+                error.starts_with("mcp_elicit does not support fields of type"),
+                "{ty}: {error}"
+            );
+        }
+    }
+}
@@ -13,17 +13,31 @@ use crate::resource::generator::{
     ResourceTokens,
 };
 use crate::resource::parser::{McpResourceMacroAttributes, McpResourceTemplateMacroAttributes};
-use crate::tool::generator::{generate_tool_tokens, ToolTokens};
+use crate::tool::generator::{
+    generate_from_request_params, generate_sample_arguments, generate_sensitive_fields,
+    generate_tool_tokens, FromRequestParamsTokens, ToolTokens,
+};
 use crate::tool::parser::McpToolMacroAttributes;
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, Data, DeriveInput, Fields};
-use utils::{base_crate, is_option, is_vec_string, renamed_field, type_to_json_schema};
+use utils::{
+    base_crate, enum_tagging, inline_schema_defs_fn, is_option, is_skipped, is_vec_string,
+    renamed_field, schema_description, skip_rename_conflict, type_to_json_schema_with_defs,
+    EnumTagging, SchemaDefs,
+};
 
 /// A procedural macro attribute to generate rust_mcp_schema::Tool related utility methods for a struct.
 ///
 /// The `mcp_tool` macro generates an implementation for the annotated struct that includes:
 /// - A `tool_name()` method returning the tool's name as a string.
+/// - An `is_blocking()` method indicating whether the tool was marked with `blocking = true`.
+/// - A `timeout()` method returning the timeout set with `timeout_ms`, if any.
+/// - A `from_request_params()` method deserializing the arguments of a `CallToolRequestParams` into
+///   the struct, with errors naming the missing or invalid argument.
+/// - A `sample_arguments()` method returning example arguments built from the input schema
+///   with `rust_mcp_sdk::macros::sample_arguments`.
+/// - A `sensitive_fields()` method returning the paths of fields marked with `#[json_schema(sensitive)]`.
 /// - A `tool()` method returning a `rust_mcp_schema::Tool` instance with the tool's name,
 ///   description, input schema, meta, and title derived from the struct's fields and attributes.
 ///
@@ -32,6 +46,15 @@ use utils::{base_crate, is_option, is_vec_string, renamed_field, type_to_json_sc
 /// * `description` - A description of the tool (required, non-empty string).
 /// * `meta` - Optional JSON object as a string literal for metadata.
 /// * `title` - Optional string for the tool's title.
+/// * `blocking` - Optional boolean (default `false`). Marks a tool that calls synchronous libraries
+///   or performs CPU-heavy work; it is exposed via the generated `is_blocking()` method so the server
+///   can execute the tool on `tokio::task::spawn_blocking`.
+/// * `timeout_ms` - Optional integer. How long, in milliseconds, the server lets the tool's handler run
+///   before cancelling it and responding with an error; it is exposed via the generated `timeout()` method.
+///   Combined with `blocking = true` the error is sent on time, but the blocking work keeps running until it returns.
+/// * `output` - Optional type deriving `JsonSchema`, e.g. `output = WeatherReport`. Its schema becomes the
+///   `output_schema` of the tool, so clients can validate the tool's structured results. A type without a
+///   `json_schema()` method is a compile error.
 ///
 /// # Panics
 /// Panics if the macro is applied to anything other than a struct.
@@ -124,7 +147,16 @@ pub fn mcp_tool(attributes: TokenStream, input: TokenStream) -> TokenStream {
         annotations,
         execution,
         icons,
+        blocking,
+        timeout,
     } = generate_tool_tokens(macro_attributes);
+    let FromRequestParamsTokens {
+        bounds: from_request_params_bounds,
+        body: from_request_params,
+    } = generate_from_request_params(&original_item, &tool_name);
+    let sample_arguments = generate_sample_arguments();
+    let sensitive_fields = generate_sensitive_fields();
+    let inline_schema_defs = inline_schema_defs_fn();
 
     // TODO: add support for schema version to ToolInputSchema :
     // it defaults to JSON Schema 2020-12 when no explicit $schema is provided.
@@ -149,6 +181,18 @@ pub fn mcp_tool(attributes: TokenStream, input: TokenStream) -> TokenStream {
                 #tool_name.to_string()
             }
 
+            /// Returns `true` if the tool is marked as blocking with `#[mcp_tool(blocking = true)]`,
+            /// meaning its handler should run on the blocking thread pool.
+            pub fn is_blocking() -> bool {
+                #blocking
+            }
+
+            /// Returns the timeout set with `#[mcp_tool(timeout_ms = ...)]`, after which the server
+            /// cancels the tool's handler, or `None` if the tool has no timeout of its own.
+            pub fn timeout() -> Option<std::time::Duration> {
+                #timeout
+            }
+
             /// Returns a `CallToolRequestParams` initialized with the current tool's name.
             ///
             /// You can further customize the request by adding arguments or other attributes
@@ -170,43 +214,82 @@ pub fn mcp_tool(attributes: TokenStream, input: TokenStream) -> TokenStream {
                #base_crate::CallToolRequestParams::new(#tool_name.to_string())
             }
 
+            /// Builds the tool from the `arguments` of a `tools/call` request.
+            ///
+            /// Fails with an `invalid_params` error naming the offending argument, in both the
+            /// message and the `field` of the error data, when a required argument is missing or
+            /// an argument has a value of the wrong type.
+            ///
+            /// ```ignore
+            /// let tool = MyTool::from_request_params(params)?;
+            /// ```
+            pub fn from_request_params(
+                params: #base_crate::CallToolRequestParams,
+            ) -> std::result::Result<Self, #base_crate::RpcError>
+            where
+                #from_request_params_bounds
+            {
+                #from_request_params
+            }
+
+            #sample_arguments
+
+            /// Returns the dot-separated paths of the arguments marked with `#[json_schema(sensitive)]`,
+            /// including those of nested structs, e.g. `api_key` or `credentials.token`.
+            /// The server redacts their values from audit records and error messages.
+            pub fn sensitive_fields() -> Vec<String> {
+                #sensitive_fields
+            }
+
             /// Constructs and returns a `rust_mcp_schema::Tool` instance.
             ///
-            /// The tool includes the name, description, input schema, meta, and title derived from
-            /// the struct's attributes.
+            /// The tool includes the name, description, input schema, output schema, meta, and title
+            /// derived from the struct's attributes.
             pub fn tool() -> #base_crate::Tool {
-                let json_schema = &#input_ident::json_schema();
-
-                let required: Vec<_> = match json_schema.get("required").and_then(|r| r.as_array()) {
-                    Some(arr) => arr
-                        .iter()
-                        .filter_map(|item| item.as_str().map(String::from))
-                        .collect(),
-                    None => Vec::new(),
-                };
-
-                let properties: Option<
-                    std::collections::BTreeMap<String, serde_json::Map<String, serde_json::Value>>,
-                > = json_schema
-                    .get("properties")
-                    .and_then(|v| v.as_object()) // Safely extract "properties" as an object.
-                    .map(|properties| {
-                        properties
+                // a tool input or output schema has no `$defs`, so shared definitions are inlined
+                #inline_schema_defs
+
+                fn schema_parts(
+                    json_schema: &serde_json::Map<String, serde_json::Value>,
+                ) -> (
+                    Vec<String>,
+                    Option<std::collections::BTreeMap<String, serde_json::Map<String, serde_json::Value>>>,
+                ) {
+                    let required: Vec<_> = match json_schema.get("required").and_then(|r| r.as_array()) {
+                        Some(arr) => arr
                             .iter()
-                            .filter_map(|(key, value)| {
-                                serde_json::to_value(value)
-                                    .ok() // If serialization fails, return None.
-                                    .and_then(|v| {
-                                        if let serde_json::Value::Object(obj) = v {
-                                            Some(obj)
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .map(|obj| (key.to_string(), obj)) // Return the (key, value) tuple
-                            })
-                            .collect()
-                    });
+                            .filter_map(|item| item.as_str().map(String::from))
+                            .collect(),
+                        None => Vec::new(),
+                    };
+
+                    let properties: Option<
+                        std::collections::BTreeMap<String, serde_json::Map<String, serde_json::Value>>,
+                    > = json_schema
+                        .get("properties")
+                        .and_then(|v| v.as_object()) // Safely extract "properties" as an object.
+                        .map(|properties| {
+                            properties
+                                .iter()
+                                .filter_map(|(key, value)| {
+                                    serde_json::to_value(value)
+                                        .ok() // If serialization fails, return None.
+                                        .and_then(|v| {
+                                            if let serde_json::Value::Object(obj) = v {
+                                                Some(obj)
+                                            } else {
+                                                None
+                                            }
+                                        })
+                                        .map(|obj| (key.to_string(), obj)) // Return the (key, value) tuple
+                                })
+                                .collect()
+                        });
+                    (required, properties)
+                }
+
+                let (required, properties) =
+                    schema_parts(&inline_schema_defs(#input_ident::json_schema()));
 
                 #tool_token
             }
@@ -239,7 +322,10 @@ pub fn mcp_elicit(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let impl_block = match elicit_args.mode {
         ElicitMode::Form => {
-            let (from_content, init) = generate_from_impl(fields, &base_crate);
+            let (from_content, init) = match generate_from_impl(fields, &base_crate) {
+                Ok(generated) => generated,
+                Err(error) => return error.to_compile_error().into(),
+            };
             let schema = generate_form_schema(struct_name, &base_crate);
 
             quote! {
@@ -282,7 +368,10 @@ pub fn mcp_elicit(args: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
         ElicitMode::Url { url } => {
-            let (from_content, init) = generate_from_impl(fields, &base_crate);
+            let (from_content, init) = match generate_from_impl(fields, &base_crate) {
+                Ok(generated) => generated,
+                Err(error) => return error.to_compile_error().into(),
+            };
 
             quote! {
                 impl #struct_name {
@@ -354,9 +443,34 @@ pub fn mcp_elicit(args: TokenStream, input: TokenStream) -> TokenStream {
 ///   carries no meaning in JSON Schema, so it never actually permitted `null` in the first
 ///   place.
 /// - **`Vec<T>`:** Generates an `"array"` schema with an `"items"` field describing the inner type.
+/// - **`[T; N]`:** Generates an `"array"` schema like `Vec<T>`, with `"minItems"` and `"maxItems"` set to `N`
+///   when the length is an integer literal. Other const lengths (e.g. `[u8; LEN]`) leave the bounds out.
+/// - **`HashMap<String, T>` / `BTreeMap<String, T>`:** Generates an `"object"` schema whose `"additionalProperties"`
+///   describes the value type. Maps with non-`String` keys are rejected at compile time.
+/// - **`Box<T>`, `Arc<T>`, `Rc<T>`:** Transparent, the schema is the one of `T`, so `Option<Box<String>>`
+///   is a nullable string.
 /// - **Nested Structs:** Recursively includes the schema of nested structs (assumed to derive `JsonSchema`),
-///   embedding their `"properties"` and `"required"` fields.
+///   embedding their `"properties"` and `"required"` fields. A nested type used more than once is emitted
+///   once under a top-level `"$defs"` map and each use is a `{"$ref": "#/$defs/TypeName"}`. The input
+///   schema of an `mcp_tool` has no `"$defs"`, so `tool()` inlines these definitions again.
+/// - **Enums:** Generates a `"oneOf"` schema with one entry per variant. A unit-only enum also gets a
+///   `from_enum_value(&str) -> Option<Self>` method returning the variant for a schema value.
+///   The entries follow serde's enum representation: with `#[serde(tag = "type")]` each entry is an
+///   object whose `"type"` property is the variant name, with `#[serde(tag = "t", content = "c")]` an
+///   object holding the variant name in `"t"` and its content in `"c"`, and with `#[serde(untagged)]`
+///   the variant's content alone (`null` for a unit variant). Internally tagged enums cannot have
+///   tuple variants.
+/// - **Descriptions:** Doc comments on the struct (or enum) and on its fields become the `"description"`
+///   of the schema and of each property. An explicit `#[json_schema(description = "...")]` takes precedence.
 /// - **Required Fields:** Adds a top-level `"required"` array listing field names not wrapped in `Option`.
+/// - **Validation Keywords:** `#[json_schema(...)]` accepts `min_length`/`max_length`, `format` and `pattern`
+///   (strings; `pattern` must be a string literal and is not compiled by the macro), `minimum`,
+///   `maximum` and `multiple_of` (numbers) and `min_items`/`max_items` (`Vec<T>`, `[T; N]`), emitted as the matching
+///   JSON Schema keywords. A zero `min_items` or `multiple_of` asserts nothing and is omitted.
+/// - **Skipped Fields:** `#[json_schema(skip)]` leaves a field out of `"properties"` and `"required"`, e.g. for
+///   internal state of a tool. It cannot be combined with `#[serde(rename = "...")]`.
+/// - **Sensitive Fields:** `#[json_schema(sensitive)]` marks a field `"writeOnly": true`, so servers
+///   redact its value (e.g. an API key) from audit records and error messages.
 ///
 /// # Notes
 /// It’s designed as a straightforward solution to meet the basic needs of this package, supporting
@@ -367,6 +481,9 @@ pub fn mcp_elicit(args: TokenStream, input: TokenStream) -> TokenStream {
 /// # Limitations
 /// - Supports only structs with named fields (e.g., `struct S { field: Type }`).
 /// - Nested structs must also derive `JsonSchema`, or compilation will fail.
+/// - Recursive types (e.g. a `Box<Self>` field) are not supported.
+/// - `"$defs"` entries are named after the last segment of the type path, so two different types with
+///   the same name share one definition.
 /// - Unknown types are mapped to `{"type": "unknown"}`.
 /// - Type paths must be in scope (e.g., fully qualified paths like `my_mod::InnerStruct` work if imported).
 ///
@@ -384,14 +501,24 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
     let schema_body = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => {
-                let field_entries = fields.named.iter().map(|field| {
+                if let Some(error) = fields.named.iter().find_map(skip_rename_conflict) {
+                    return error.to_compile_error().into();
+                }
+
+                let schema_fields = fields
+                    .named
+                    .iter()
+                    .filter(|field| !is_skipped(&field.attrs));
+                let defs = SchemaDefs::from_types(schema_fields.clone().map(|field| &field.ty));
+
+                let field_entries = schema_fields.clone().map(|field| {
                     let field_attrs = &field.attrs;
                     let renamed_field = renamed_field(field_attrs);
                     let field_name =
                         renamed_field.unwrap_or(field.ident.as_ref().unwrap().to_string());
                     let field_type = &field.ty;
 
-                    let schema = type_to_json_schema(field_type, field_attrs);
+                    let schema = type_to_json_schema_with_defs(field_type, field_attrs, &defs);
                     quote! {
                         properties.insert(
                             #field_name.to_string(),
@@ -400,7 +527,7 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                     }
                 });
 
-                let required_fields = fields.named.iter().filter_map(|field| {
+                let required_fields = schema_fields.filter_map(|field| {
                     let renamed_field = renamed_field(&field.attrs);
                     let field_name =
                         renamed_field.unwrap_or(field.ident.as_ref().unwrap().to_string());
@@ -414,6 +541,7 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                         None
                     }
                 });
+                let definitions = defs.definitions();
 
                 quote! {
                     let mut schema = serde_json::Map::new();
@@ -432,12 +560,35 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                         ));
                     }
 
+                    #definitions
+
                     schema
                 }
             }
             _ => panic!("JsonSchema derive macro only supports named fields for structs"),
         },
         Data::Enum(data) => {
+            let defs = SchemaDefs::from_types(
+                data.variants
+                    .iter()
+                    .flat_map(|variant| variant.fields.iter())
+                    .map(|field| &field.ty),
+            );
+            let tagging = enum_tagging(&input.attrs);
+            if let EnumTagging::Internal { .. } = tagging {
+                let tuple_variant = data.variants.iter().find(
+                    |variant| matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() > 1),
+                );
+                if let Some(variant) = tuple_variant {
+                    return syn::Error::new_spanned(
+                        variant,
+                        "internally tagged enums (`#[serde(tag = \"...\")]`) cannot have tuple variants",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            }
+
             let variant_schemas = data.variants.iter().map(|variant| {
                 let variant_attrs = &variant.attrs;
                 let variant_name = variant.ident.to_string();
@@ -466,7 +617,7 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                     quote! { map.insert("description".to_string(), serde_json::Value::String(#desc.to_string())); }
                 });
 
-                match &variant.fields {
+                let variant_schema = match &variant.fields {
                     Fields::Unit => {
                         // Unit variant: use "enum" with the variant name
                         quote! {
@@ -475,9 +626,7 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                                 map.insert("enum".to_string(), serde_json::Value::Array(vec![
                                     serde_json::Value::String(#renamed_variant.to_string())
                                 ]));
-                                #title_quote
-                                #description_quote
-                                serde_json::Value::Object(map)
+                                map
                             }
                         }
                     }
@@ -488,21 +637,13 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                             let field = &fields.unnamed[0];
                             let field_type = &field.ty;
                             let field_attrs = &field.attrs;
-                            let schema = type_to_json_schema(field_type, field_attrs);
-                            quote! {
-                                {
-                                    let mut map = #schema;
-                                    #title_quote
-                                    #description_quote
-                                    serde_json::Value::Object(map)
-                                }
-                            }
+                            type_to_json_schema_with_defs(field_type, field_attrs, &defs)
                         } else {
                             // Tuple variant: array with items
                             let field_schemas = fields.unnamed.iter().map(|field| {
                                 let field_type = &field.ty;
                                 let field_attrs = &field.attrs;
-                                let schema = type_to_json_schema(field_type, field_attrs);
+                                let schema = type_to_json_schema_with_defs(field_type, field_attrs, &defs);
                                 quote! { serde_json::Value::Object(#schema) }
                             });
                             quote! {
@@ -511,9 +652,7 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                                     map.insert("type".to_string(), serde_json::Value::String("array".to_string()));
                                     map.insert("items".to_string(), serde_json::Value::Array(vec![#(#field_schemas),*]));
                                     map.insert("additionalItems".to_string(), serde_json::Value::Bool(false));
-                                    #title_quote
-                                    #description_quote
-                                    serde_json::Value::Object(map)
+                                    map
                                 }
                             }
                         }
@@ -526,7 +665,7 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                             let field_name = renamed_field.unwrap_or(field.ident.as_ref().unwrap().to_string());
                             let field_type = &field.ty;
 
-                            let schema = type_to_json_schema(field_type, field_attrs);
+                            let schema = type_to_json_schema_with_defs(field_type, field_attrs, &defs);
                             quote! {
                                 properties.insert(
                                     #field_name.to_string(),
@@ -566,31 +705,160 @@ pub fn derive_json_schema(input: TokenStream) -> TokenStream {
                                         required.into_iter().map(serde_json::Value::String).collect()
                                     ));
                                 }
-                                #title_quote
-                                #description_quote
-                                serde_json::Value::Object(map)
+                                map
                             }
                         }
                     }
+                };
+
+                // an object holding the variant name in its `tag` property
+                let tag_object = |tag: &str| {
+                    quote! {
+                        {
+                            let mut properties = serde_json::Map::new();
+                            properties.insert(#tag.to_string(), serde_json::json!({
+                                "type": "string",
+                                "const": #renamed_variant
+                            }));
+                            let mut map = serde_json::Map::new();
+                            map.insert("type".to_string(), serde_json::Value::String("object".to_string()));
+                            map.insert("properties".to_string(), serde_json::Value::Object(properties));
+                            map.insert("required".to_string(), serde_json::json!([#tag]));
+                            map
+                        }
+                    }
+                };
+
+                let is_unit = matches!(variant.fields, Fields::Unit);
+                let variant_schema = match &tagging {
+                    EnumTagging::External => variant_schema,
+                    // serde writes a unit variant of an untagged enum as `null`
+                    EnumTagging::Untagged if is_unit => quote! {
+                        {
+                            let mut map = serde_json::Map::new();
+                            map.insert("type".to_string(), serde_json::Value::String("null".to_string()));
+                            map
+                        }
+                    },
+                    EnumTagging::Untagged => variant_schema,
+                    EnumTagging::Internal { tag } if is_unit => tag_object(tag),
+                    EnumTagging::Internal { tag } => {
+                        let tag_object = tag_object(tag);
+                        if matches!(variant.fields, Fields::Named(_)) {
+                            // the tag is one more property of the variant's object
+                            quote! {
+                                {
+                                    let mut map = #variant_schema;
+                                    let tag_object = #tag_object;
+                                    if let Some(serde_json::Value::Object(properties)) = map.get_mut("properties") {
+                                        properties.insert(#tag.to_string(), tag_object["properties"][#tag].clone());
+                                    }
+                                    let mut required = vec![serde_json::Value::String(#tag.to_string())];
+                                    if let Some(serde_json::Value::Array(fields)) = map.remove("required") {
+                                        required.extend(fields);
+                                    }
+                                    map.insert("required".to_string(), serde_json::Value::Array(required));
+                                    map
+                                }
+                            }
+                        } else {
+                            // a newtype variant's content is flattened next to the tag
+                            quote! {
+                                {
+                                    let mut map = serde_json::Map::new();
+                                    map.insert("allOf".to_string(), serde_json::Value::Array(vec![
+                                        serde_json::Value::Object(#tag_object),
+                                        serde_json::Value::Object(#variant_schema),
+                                    ]));
+                                    map
+                                }
+                            }
+                        }
+                    }
+                    EnumTagging::Adjacent { tag, .. } if is_unit => tag_object(tag),
+                    EnumTagging::Adjacent { tag, content } => {
+                        let tag_object = tag_object(tag);
+                        quote! {
+                            {
+                                let mut map = #tag_object;
+                                if let Some(serde_json::Value::Object(properties)) = map.get_mut("properties") {
+                                    properties.insert(#content.to_string(), serde_json::Value::Object(#variant_schema));
+                                }
+                                map.insert("required".to_string(), serde_json::json!([#tag, #content]));
+                                map
+                            }
+                        }
+                    }
+                };
+
+                quote! {
+                    {
+                        let mut map = #variant_schema;
+                        #title_quote
+                        #description_quote
+                        serde_json::Value::Object(map)
+                    }
                 }
             });
 
+            let definitions = defs.definitions();
             quote! {
                 let mut schema = serde_json::Map::new();
                 schema.insert("oneOf".to_string(), serde_json::Value::Array(vec![
                     #(#variant_schemas),*
                 ]));
+                #definitions
                 schema
             }
         }
         _ => panic!("JsonSchema derive macro only supports structs and enums"),
     };
 
+    // A unit-only enum maps to a set of string choices, so it can also be parsed back from
+    // the value selected for it, e.g. in an elicitation form.
+    let enum_value_fn = match &input.data {
+        Data::Enum(data)
+            if enum_tagging(&input.attrs) == EnumTagging::External
+                && !data.variants.is_empty()
+                && data
+                    .variants
+                    .iter()
+                    .all(|v| matches!(v.fields, Fields::Unit)) =>
+        {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let value = renamed_field(&variant.attrs).unwrap_or(ident.to_string());
+                quote! { #value => Some(Self::#ident), }
+            });
+            Some(quote! {
+                /// Returns the variant whose schema value is `value`, or `None` if there is none.
+                pub fn from_enum_value(value: &str) -> Option<Self> {
+                    match value {
+                        #(#arms)*
+                        _ => None,
+                    }
+                }
+            })
+        }
+        _ => None,
+    };
+
+    let schema_body = match schema_description(&input.attrs) {
+        Some(desc) => quote! {
+            let mut schema = { #schema_body };
+            schema.insert("description".to_string(), serde_json::Value::String(#desc.to_string()));
+            schema
+        },
+        None => schema_body,
+    };
+
     let expanded = quote! {
         impl #name {
             pub fn json_schema() -> serde_json::Map<String, serde_json::Value> {
                 #schema_body
             }
+
+            #enum_value_fn
         }
     };
     TokenStream::from(expanded)
@@ -723,6 +991,7 @@ pub fn mcp_resource(attributes: TokenStream, input: TokenStream) -> TokenStream
 /// Generated methods:
 /// - `resource_name()` → returns the resource name as `&'static str`
 /// - `resource_uri_template()` → returns the resource template URI as `&'static str`
+/// - `expand_uri(&HashMap<String, String>)` → returns the URI with its `{param}` placeholders substituted
 /// - `resource()` → constructs and returns a complete `rust_mcp_schema::Resource` value
 ///
 /// # Attributes
@@ -736,7 +1005,7 @@ pub fn mcp_resource(attributes: TokenStream, input: TokenStream) -> TokenStream
 /// | `title`       | string literal or `concat!(...)`     | No       | Display title for the resource. |
 /// | `meta`        | JSON object as string literal        | No       | Arbitrary metadata as a valid JSON object. Must parse as a JSON object (not array, null, etc.). |
 /// | `mime_type`   | string literal                       | No       | MIME type of the resource (e.g., `"image/png"`, `"application/pdf"`). |
-/// | `uri_template`         | string literal                       | No       | URI template where the resource can be accessed. |
+/// | `uri_template`         | string literal                       | Yes      | URI template where the resource can be accessed, with at least one `{param}` placeholder. |
 /// | `audience`    | array of string literals             | No       | List of intended audiences (e.g., `["user", "system"]`). |
 /// | `icons`       | array of icon objects                | No       | List of icons in the same format as web app manifests (supports `src`, `sizes`, `type`). |
 ///
@@ -747,6 +1016,7 @@ pub fn mcp_resource(attributes: TokenStream, input: TokenStream) -> TokenStream
 /// The macro will cause a compile-time error (not a runtime panic) if:
 /// - Applied to anything other than a struct.
 /// - Required attributes (`name` or `uri_template`) are missing or empty.
+/// - `uri_template` has no `{...}` placeholder.
 /// - `meta` is provided but is not a valid JSON object.
 /// - Invalid types are used for any attribute (e.g., non-integer for `size`).
 ///
@@ -776,6 +1046,12 @@ pub fn mcp_resource(attributes: TokenStream, input: TokenStream) -> TokenStream
 ///     "https://example.com/assets/{file_path}"
 /// );
 ///
+/// let params = std::collections::HashMap::from([("file_path".to_string(), "logo.png".to_string())]);
+/// assert_eq!(
+///     CompanyLogo::expand_uri(&params),
+///     "https://example.com/assets/logo.png"
+/// );
+///
 /// let resource_template = CompanyLogo::resource_template();
 /// assert_eq!(resource_template.name, "company-logos");
 /// assert_eq!(resource_template.mime_type.unwrap(), "image/png");
@@ -796,6 +1072,7 @@ pub fn mcp_resource_template(attributes: TokenStream, input: TokenStream) -> Tok
         annotations,
         mime_type,
         uri_template,
+        expand_uri,
     } = generate_resource_template_tokens(macro_attributes);
 
     quote! {
@@ -811,6 +1088,12 @@ pub fn mcp_resource_template(attributes: TokenStream, input: TokenStream) -> Tok
                 #name
             }
 
+            /// Builds a resource URI from the template, replacing each `{param}` placeholder with
+            /// the value of that parameter. Placeholders without a value in `params` are kept as-is.
+            pub fn expand_uri(params: &std::collections::HashMap<String, String>) -> String {
+                #expand_uri
+            }
+
             /// Constructs and returns a `rust_mcp_schema::Resource` instance.
             pub fn resource_template()->#base_crate::ResourceTemplate{
                 #base_crate::ResourceTemplate{
@@ -1,5 +1,8 @@
 use crate::common::generate_icons;
-use crate::resource::parser::{McpResourceMacroAttributes, McpResourceTemplateMacroAttributes};
+use crate::resource::parser::{
+    uri_template_segments, McpResourceMacroAttributes, McpResourceTemplateMacroAttributes,
+    UriTemplateSegment,
+};
 use crate::utils::base_crate;
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -27,6 +30,7 @@ pub struct ResourceTemplateTokens {
     pub annotations: TokenStream,
     pub mime_type: TokenStream,
     pub uri_template: TokenStream,
+    pub expand_uri: TokenStream,
 }
 
 pub fn generate_resource_tokens(macro_attributes: McpResourceMacroAttributes) -> ResourceTokens {
@@ -102,6 +106,9 @@ pub fn generate_resource_template_tokens(
         .map(|v| quote! {#v.into() })
         .expect("'uri_template' is a required attribute!");
 
+    let expand_uri =
+        generate_expand_uri(macro_attributes.uri_template.as_deref().unwrap_or_default());
+
     let mime_type = macro_attributes
         .mime_type
         .as_ref()
@@ -134,6 +141,28 @@ pub fn generate_resource_template_tokens(
         description,
         mime_type,
         uri_template,
+        expand_uri,
+    }
+}
+
+/// Generates the body of `expand_uri()`, which builds a URI from the template by replacing each
+/// `{param}` placeholder with the value of that parameter. Placeholders without a value are kept.
+fn generate_expand_uri(uri_template: &str) -> TokenStream {
+    let segments = uri_template_segments(uri_template)
+        .into_iter()
+        .map(|segment| match segment {
+            UriTemplateSegment::Literal(text) => quote! { uri.push_str(#text); },
+            UriTemplateSegment::Param(name) => {
+                let placeholder = format!("{{{name}}}");
+                quote! {
+                    uri.push_str(params.get(#name).map_or(#placeholder, |value| value.as_str()));
+                }
+            }
+        });
+    quote! {
+        let mut uri = String::new();
+        #(#segments)*
+        uri
     }
 }
 
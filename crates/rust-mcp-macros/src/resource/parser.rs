@@ -3,6 +3,44 @@ use syn::{parse::Parse, Error};
 
 pub(crate) const VALID_ROLES: [&str; 2] = ["assistant", "user"];
 
+/// A part of a resource URI template: literal text, or a `{param}` placeholder.
+#[derive(Debug, PartialEq)]
+pub(crate) enum UriTemplateSegment {
+    Literal(String),
+    Param(String),
+}
+
+/// Splits a URI template such as `file:///{path}` into its literal parts and `{param}` placeholders.
+/// Braces that don't enclose a name are kept as literal text.
+pub(crate) fn uri_template_segments(template: &str) -> Vec<UriTemplateSegment> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        literal.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find(['{', '}']) {
+            Some(end) if after.as_bytes()[end] == b'}' && !after[..end].trim().is_empty() => {
+                if !literal.is_empty() {
+                    segments.push(UriTemplateSegment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(UriTemplateSegment::Param(after[..end].trim().to_string()));
+                rest = &after[end + 1..];
+            }
+            _ => {
+                literal.push('{');
+                rest = after;
+            }
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(UriTemplateSegment::Literal(literal));
+    }
+    segments
+}
+
 #[derive(Debug)]
 pub(crate) struct McpResourceMacroAttributes {
     pub name: Option<String>,
@@ -34,6 +72,9 @@ impl Parse for McpResourceMacroAttributes {
             open_world_hint: _,
             read_only_hint: _,
             execution: _,
+            blocking: _,
+            timeout_ms: _,
+            output: _,
         } = GenericMcpMacroAttributes::parse(attributes)?;
 
         let instance = Self {
@@ -120,6 +161,9 @@ impl Parse for McpResourceTemplateMacroAttributes {
             open_world_hint: _,
             read_only_hint: _,
             execution: _,
+            blocking: _,
+            timeout_ms: _,
+            output: _,
         } = GenericMcpMacroAttributes::parse(attributes)?;
 
         let instance = Self {
@@ -158,6 +202,16 @@ impl Parse for McpResourceTemplateMacroAttributes {
             ));
         }
 
+        if !uri_template_segments(instance.uri_template.as_deref().unwrap_or_default())
+            .iter()
+            .any(|segment| matches!(segment, UriTemplateSegment::Param(_)))
+        {
+            return Err(Error::new(
+                attributes.span(),
+                "The 'uri_template' attribute must contain at least one `{...}` placeholder, e.g. \"file:///{path}\".",
+            ));
+        }
+
         if instance
             .audience
             .as_ref()
@@ -351,6 +405,47 @@ mod tests {
             .contains("Is there any duplication in the audience values?"),);
     }
 
+    #[test]
+    fn test_uri_template_segments() {
+        assert_eq!(
+            uri_template_segments("file:///{dir}/{ name }.txt"),
+            vec![
+                UriTemplateSegment::Literal("file:///".into()),
+                UriTemplateSegment::Param("dir".into()),
+                UriTemplateSegment::Literal("/".into()),
+                UriTemplateSegment::Param("name".into()),
+                UriTemplateSegment::Literal(".txt".into()),
+            ]
+        );
+        assert_eq!(
+            uri_template_segments("a{}b{c"),
+            vec![UriTemplateSegment::Literal("a{}b{c".into())]
+        );
+        assert_eq!(
+            uri_template_segments("{{id}}"),
+            vec![
+                UriTemplateSegment::Literal("{".into()),
+                UriTemplateSegment::Param("id".into()),
+                UriTemplateSegment::Literal("}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uri_template_without_placeholder() {
+        let result: syn::Result<McpResourceTemplateMacroAttributes> = parse_str(
+            r#"name = "files", description = "Files", uri_template = "file:///{}/static""#,
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The 'uri_template' attribute must contain at least one `{...}` placeholder, e.g. \"file:///{path}\"."
+        );
+
+        let result: syn::Result<McpResourceTemplateMacroAttributes> =
+            parse_str(r#"name = "files", description = "Files", uri_template = "file:///{path}""#);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_empty_name() {
         let err = parse_attributes(
@@ -1,8 +1,9 @@
 use crate::common::{ExecutionSupportDsl, IconThemeDsl};
-use crate::utils::base_crate;
+use crate::utils::{base_crate, has_serde_attr, inline_schema_defs_fn, is_option, renamed_field};
 use crate::McpToolMacroAttributes;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 
 pub struct ToolTokens {
     pub base_crate: TokenStream,
@@ -14,6 +15,8 @@ pub struct ToolTokens {
     pub annotations: TokenStream,
     pub execution: TokenStream,
     pub icons: TokenStream,
+    pub blocking: bool,
+    pub timeout: TokenStream,
 }
 
 pub fn generate_tool_tokens(macro_attributes: McpToolMacroAttributes) -> ToolTokens {
@@ -34,12 +37,31 @@ pub fn generate_tool_tokens(macro_attributes: McpToolMacroAttributes) -> ToolTok
             quote! { meta: Some(serde_json::from_str(#m).expect("Failed to parse meta JSON")), }
         });
 
-    //TODO: add support for output_schema
-    let output_schema = quote! { output_schema: None,};
+    // `schema_parts` and `inline_schema_defs` are defined in the generated `tool()`;
+    // the call is spanned to the attribute, so a type without `json_schema()` is reported there
+    let output_schema =
+        macro_attributes
+            .output
+            .as_ref()
+            .map_or(quote! { output_schema: None, }, |output| {
+                let output_json_schema = quote_spanned! {output.span()=> #output::json_schema() };
+                quote! {
+                    output_schema: Some({
+                        let (required, properties) =
+                            schema_parts(&inline_schema_defs(#output_json_schema));
+                        #base_crate::ToolOutputSchema::new(required, properties, None)
+                    }),
+                }
+            });
 
     let annotations = generate_annotations(&base_crate, &macro_attributes);
     let execution = generate_executions(&base_crate, &macro_attributes);
     let icons = generate_icons(&base_crate, &macro_attributes);
+    let blocking = macro_attributes.blocking.unwrap_or(false);
+    let timeout = macro_attributes.timeout_ms.map_or(
+        quote! { None },
+        |ms| quote! { Some(std::time::Duration::from_millis(#ms)) },
+    );
 
     ToolTokens {
         base_crate,
@@ -51,6 +73,8 @@ pub fn generate_tool_tokens(macro_attributes: McpToolMacroAttributes) -> ToolTok
         annotations,
         execution,
         icons,
+        blocking,
+        timeout,
     }
 }
 
@@ -173,3 +197,167 @@ fn generate_annotations(
 
     quote! { annotations: #annotations, }
 }
+
+/// Generates the `sample_arguments()` method, which builds example arguments from the tool's
+/// input schema with `rust_mcp_sdk::macros::sample_arguments`.
+///
+/// The method is left out when expanded in the `rust-mcp-macros` tests, which have no access to
+/// `rust-mcp-sdk` (see [`base_crate`](crate::utils::base_crate)).
+pub fn generate_sample_arguments() -> TokenStream {
+    let pkg_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    if pkg_name == "rust-mcp-macros" {
+        return quote! {};
+    }
+    let inline_schema_defs = inline_schema_defs_fn();
+    quote! {
+        /// Returns example arguments for the tool, built from its input schema with
+        /// type-appropriate placeholder values (defaults and the first enum variant are used
+        /// when available). Optional fields are omitted and nested structs are filled in,
+        /// so the result can be shown in docs or used as test input.
+        pub fn sample_arguments() -> serde_json::Value {
+            #inline_schema_defs
+            rust_mcp_sdk::macros::sample_arguments(&inline_schema_defs(Self::json_schema()))
+        }
+    }
+}
+
+pub fn generate_sensitive_fields() -> TokenStream {
+    let inline_schema_defs = inline_schema_defs_fn();
+    quote! {
+        fn collect(
+            schema: &serde_json::Map<String, serde_json::Value>,
+            prefix: &str,
+            fields: &mut Vec<String>,
+        ) {
+            if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+                for (key, value) in properties {
+                    let Some(value) = value.as_object() else {
+                        continue;
+                    };
+                    let path = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    if value.get("writeOnly").and_then(|w| w.as_bool()) == Some(true) {
+                        fields.push(path);
+                    } else {
+                        collect(value, &path, fields);
+                    }
+                }
+            }
+            // array items and nullable or derived enum variants share the path of their parent
+            if let Some(items) = schema.get("items").and_then(|i| i.as_object()) {
+                collect(items, prefix, fields);
+            }
+            for keyword in ["oneOf", "anyOf"] {
+                if let Some(variants) = schema.get(keyword).and_then(|v| v.as_array()) {
+                    for variant in variants.iter().filter_map(|v| v.as_object()) {
+                        collect(variant, prefix, fields);
+                    }
+                }
+            }
+        }
+
+        let mut fields = Vec::new();
+        #inline_schema_defs
+        collect(&inline_schema_defs(Self::json_schema()), "", &mut fields);
+        fields.dedup();
+        fields
+    }
+}
+
+pub struct FromRequestParamsTokens {
+    pub bounds: TokenStream,
+    pub body: TokenStream,
+}
+
+/// Generates the `from_request_params()` method parts for the tool struct `item`: the
+/// `Deserialize` bounds it depends on, and a body deserializing the call arguments into the
+/// struct, which reports a missing or invalid argument by its name.
+pub fn generate_from_request_params(item: &syn::Item, tool_name: &str) -> FromRequestParamsTokens {
+    let base_crate = base_crate();
+
+    // field checks are skipped when the argument names can't be derived from the fields alone
+    let fields: Vec<&syn::Field> = match item {
+        syn::Item::Struct(syn::ItemStruct {
+            attrs,
+            fields: syn::Fields::Named(fields),
+            ..
+        }) if !has_serde_attr(attrs, &["rename_all", "default"]) => fields.named.iter().collect(),
+        _ => Vec::new(),
+    };
+    let fields: Vec<(String, &syn::Field)> = fields
+        .into_iter()
+        .filter(|field| {
+            !has_serde_attr(
+                &field.attrs,
+                &[
+                    "skip",
+                    "skip_deserializing",
+                    "flatten",
+                    "with",
+                    "deserialize_with",
+                ],
+            )
+        })
+        .map(|field| {
+            let name = renamed_field(&field.attrs)
+                .unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
+            (name, field)
+        })
+        .collect();
+
+    let required_names = fields
+        .iter()
+        .filter(|(_, field)| !is_option(&field.ty) && !has_serde_attr(&field.attrs, &["default"]))
+        .map(|(name, _)| name);
+    let field_names = fields.iter().map(|(name, _)| name);
+    let field_types: Vec<_> = fields.iter().map(|(_, field)| &field.ty).collect();
+
+    let bounds = quote! {
+        for<'de> Self: ::serde::Deserialize<'de>,
+        #(for<'de> #field_types: ::serde::Deserialize<'de>,)*
+    };
+
+    let body = quote! {
+        let arguments = params.arguments.unwrap_or_default();
+        let invalid_argument = |field: &str, message: String| {
+            let mut data = serde_json::Map::new();
+            data.insert("field".to_string(), serde_json::Value::String(field.to_string()));
+            #base_crate::RpcError::invalid_params()
+                .with_message(format!("Invalid arguments for tool '{}': {}", #tool_name, message))
+                .with_data(Some(serde_json::Value::Object(data)))
+        };
+
+        match serde_json::from_value::<Self>(serde_json::Value::Object(arguments.clone())) {
+            Ok(tool) => Ok(tool),
+            Err(error) => {
+                #(
+                    if !arguments.contains_key(#required_names) {
+                        return Err(invalid_argument(
+                            #required_names,
+                            format!("missing required argument `{}`", #required_names),
+                        ));
+                    }
+                )*
+                #(
+                    if let Some(value) = arguments.get(#field_names) {
+                        if let Err(error) = serde_json::from_value::<#field_types>(value.clone()) {
+                            return Err(invalid_argument(
+                                #field_names,
+                                format!("invalid value for `{}`: {}", #field_names, error),
+                            ));
+                        }
+                    }
+                )*
+                Err(#base_crate::RpcError::invalid_params().with_message(format!(
+                    "Invalid arguments for tool '{}': {}",
+                    #tool_name, error
+                )))
+            }
+        }
+    };
+
+    FromRequestParamsTokens { bounds, body }
+}
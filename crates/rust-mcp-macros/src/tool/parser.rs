@@ -17,6 +17,9 @@ use crate::common::{ExecutionSupportDsl, GenericMcpMacroAttributes, IconDsl};
 ///   * `idempotent_hint` - Optional boolean for `ToolAnnotations::idempotent_hint`.
 ///   * `open_world_hint` - Optional boolean for `ToolAnnotations::open_world_hint`.
 ///   * `read_only_hint` - Optional boolean for `ToolAnnotations::read_only_hint`.
+/// * `blocking` - Optional boolean, marks the tool as blocking so the runtime executes it on `spawn_blocking`.
+/// * `timeout_ms` - Optional integer, the time in milliseconds the runtime lets the tool's handler run.
+/// * `output` - Optional type deriving `JsonSchema`, whose schema becomes the tool's output schema.
 ///
 pub(crate) struct McpToolMacroAttributes {
     pub name: Option<String>,
@@ -29,6 +32,9 @@ pub(crate) struct McpToolMacroAttributes {
     pub read_only_hint: Option<bool>,
     pub execution: Option<ExecutionSupportDsl>,
     pub icons: Option<Vec<IconDsl>>,
+    pub blocking: Option<bool>,
+    pub timeout_ms: Option<u64>,
+    pub output: Option<syn::Path>,
 }
 
 impl Parse for McpToolMacroAttributes {
@@ -61,6 +67,9 @@ impl Parse for McpToolMacroAttributes {
             open_world_hint,
             read_only_hint,
             execution,
+            blocking,
+            timeout_ms,
+            output,
         } = GenericMcpMacroAttributes::parse(attributes)?;
 
         let instance = Self {
@@ -74,6 +83,9 @@ impl Parse for McpToolMacroAttributes {
             read_only_hint,
             execution,
             icons,
+            blocking,
+            timeout_ms,
+            output,
         };
 
         // Validate presence and non-emptiness
@@ -120,6 +132,49 @@ mod tests {
         assert_eq!(parsed.title.unwrap(), "Test Tool");
     }
 
+    #[test]
+    fn test_timeout_ms_attribute() {
+        let input = r#"name = "test_tool", description = "A test tool.", timeout_ms = 1500"#;
+        let parsed: McpToolMacroAttributes = parse_str(input).unwrap();
+        assert_eq!(parsed.timeout_ms, Some(1500));
+
+        let input = r#"name = "test_tool", description = "A test tool.", timeout_ms = "1s""#;
+        let result: Result<McpToolMacroAttributes, Error> = parse_str(input);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Expected a integer literal"
+        );
+    }
+
+    #[test]
+    fn test_blocking_attribute() {
+        let input = r#"name = "test_tool", description = "A test tool.", blocking = true"#;
+        let parsed: McpToolMacroAttributes = parse_str(input).unwrap();
+        assert_eq!(parsed.blocking, Some(true));
+
+        let input = r#"name = "test_tool", description = "A test tool.", blocking = "yes""#;
+        let result: Result<McpToolMacroAttributes, Error> = parse_str(input);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Expected a boolean literal"
+        );
+    }
+
+    #[test]
+    fn test_output_attribute() {
+        let input = r#"name = "test_tool", description = "A test tool.", output = results::Report"#;
+        let parsed: McpToolMacroAttributes = parse_str(input).unwrap();
+        let output = parsed.output.unwrap();
+        assert_eq!(quote::quote!(#output).to_string(), "results :: Report");
+
+        let input = r#"name = "test_tool", description = "A test tool.", output = "Report""#;
+        let result: Result<McpToolMacroAttributes, Error> = parse_str(input);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Expected a type deriving JsonSchema, e.g. `output = MyResult`"
+        );
+    }
+
     #[test]
     fn test_missing_name() {
         let input = r#"description = "Only description""#;
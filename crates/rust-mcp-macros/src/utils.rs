@@ -76,6 +76,24 @@ pub fn inner_type(ty: &Type) -> Option<&Type> {
     None
 }
 
+/// Returns the `#[json_schema(description = "...")]` of an item, falling back to its doc comment.
+pub fn schema_description(attrs: &[Attribute]) -> Option<String> {
+    let mut description = None;
+    for attr in attrs {
+        if attr.path().is_ident("json_schema") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("description") {
+                    description = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+        }
+    }
+    description.or_else(|| doc_comment(attrs))
+}
+
 pub fn doc_comment(attrs: &[Attribute]) -> Option<String> {
     let mut docs = Vec::new();
     for attr in attrs {
@@ -167,7 +185,254 @@ pub fn generate_enum_parse(
     }
 }
 
+/// Returns the inner type of a `Box<T>`, `Arc<T>` or `Rc<T>`, which serialize exactly like `T`.
+fn smart_pointer_inner_type(type_path: &TypePath) -> Option<&Type> {
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Box" && segment.ident != "Arc" && segment.ident != "Rc" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner_ty)) if args.args.len() == 1 => Some(inner_ty),
+        _ => None,
+    }
+}
+
+/// Returns the key and value types of a `HashMap<K, V>` or `BTreeMap<K, V>`.
+fn map_key_value_types(type_path: &TypePath) -> Option<(&Type, &Type)> {
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "HashMap" && segment.ident != "BTreeMap" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    Some((types.next()?, types.next()?))
+}
+
+fn is_string_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(p) if p.qself.is_none()
+        && p.path.segments.last().is_some_and(|s| s.ident == "String" && s.arguments.is_empty()))
+}
+
+/// Nested schema types used more than once within a single `JsonSchema` expansion.
+///
+/// Their schemas are emitted once under the top-level `"$defs"` and each use is a
+/// `{"$ref": "#/$defs/TypeName"}`, instead of inlining the full schema every time.
+#[derive(Default)]
+pub struct SchemaDefs {
+    nested: bool,
+    repeated: Vec<(String, Path)>,
+}
+
+impl SchemaDefs {
+    /// Finds the nested schema types of `types` and the ones used more than once.
+    pub fn from_types<'a>(types: impl IntoIterator<Item = &'a Type>) -> Self {
+        let mut paths = Vec::new();
+        for ty in types {
+            nested_schema_paths(ty, &mut paths);
+        }
+        let mut repeated: Vec<(String, Path)> = Vec::new();
+        for (index, path) in paths.iter().enumerate() {
+            let key = quote!(#path).to_string();
+            let is_repeated = paths[index + 1..]
+                .iter()
+                .any(|other| quote!(#other).to_string() == key);
+            if is_repeated && !repeated.iter().any(|(name, _)| name == &key) {
+                repeated.push((key, path.clone()));
+            }
+        }
+        Self {
+            nested: !paths.is_empty(),
+            repeated,
+        }
+    }
+
+    /// Returns the `$defs` name of `path`, if its schema is emitted under `$defs`.
+    fn def_name(&self, path: &Path) -> Option<String> {
+        let key = quote!(#path).to_string();
+        self.repeated
+            .iter()
+            .any(|(name, _)| name == &key)
+            .then(|| path.segments.last().map(|s| s.ident.to_string()))
+            .flatten()
+    }
+
+    /// Generates the code adding the `"$defs"` map to `schema`.
+    ///
+    /// Definitions found in nested schemas are moved to the top-level map as well, since
+    /// `$ref` pointers are resolved from the root of the schema.
+    pub fn definitions(&self) -> TokenStream {
+        if !self.nested {
+            return quote! {};
+        }
+        let definitions = self.repeated.iter().map(|(_, path)| {
+            let name = path
+                .segments
+                .last()
+                .map(|s| s.ident.to_string())
+                .unwrap_or_default();
+            quote! {
+                let mut definition = #path::json_schema();
+                hoist_defs(&mut definition, &mut defs);
+                defs.insert(#name.to_string(), serde_json::Value::Object(definition));
+            }
+        });
+        quote! {
+            fn hoist_defs(
+                schema: &mut serde_json::Map<String, serde_json::Value>,
+                defs: &mut serde_json::Map<String, serde_json::Value>,
+            ) {
+                if let Some(serde_json::Value::Object(nested)) = schema.remove("$defs") {
+                    for (name, definition) in nested {
+                        defs.entry(name).or_insert(definition);
+                    }
+                }
+                for (key, value) in schema.iter_mut() {
+                    match value {
+                        // the keys of `properties` are field names, not keywords
+                        serde_json::Value::Object(properties) if key == "properties" => {
+                            for property in properties.values_mut() {
+                                if let serde_json::Value::Object(property) = property {
+                                    hoist_defs(property, defs);
+                                }
+                            }
+                        }
+                        serde_json::Value::Object(child) => hoist_defs(child, defs),
+                        serde_json::Value::Array(items) => {
+                            for item in items.iter_mut() {
+                                if let serde_json::Value::Object(item) = item {
+                                    hoist_defs(item, defs);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            let mut defs = serde_json::Map::new();
+            #(#definitions)*
+            hoist_defs(&mut schema, &mut defs);
+            if !defs.is_empty() {
+                schema.insert("$defs".to_string(), serde_json::Value::Object(defs));
+            }
+        }
+    }
+}
+
+/// Collects the paths of the nested types of `ty` whose schema comes from their own `json_schema()`.
+fn nested_schema_paths(ty: &Type, paths: &mut Vec<Path>) {
+    match ty {
+        Type::Array(type_array) => nested_schema_paths(&type_array.elem, paths),
+        Type::Path(type_path) => {
+            if let Some(inner_ty) = smart_pointer_inner_type(type_path) {
+                return nested_schema_paths(inner_ty, paths);
+            }
+            if let Some((_, value_ty)) = map_key_value_types(type_path) {
+                return nested_schema_paths(value_ty, paths);
+            }
+            if type_path.path.segments.len() != 1 {
+                return;
+            }
+            let segment = &type_path.path.segments[0];
+            if segment.ident == "Option" || segment.ident == "Vec" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
+                        nested_schema_paths(inner_ty, paths);
+                    }
+                }
+            } else if segment.ident != "Number" && might_be_struct(ty) {
+                paths.push(type_path.path.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Generates a `fn inline_schema_defs(schema) -> schema` replacing every `$ref` of a schema
+/// generated by the `JsonSchema` derive with the definition it points to, and removing `"$defs"`.
+///
+/// Used where the schema is consumed property by property, e.g. a tool input schema which
+/// has no room for `"$defs"`.
+pub fn inline_schema_defs_fn() -> TokenStream {
+    quote! {
+        fn inline_schema_defs(
+            mut schema: serde_json::Map<String, serde_json::Value>,
+        ) -> serde_json::Map<String, serde_json::Value> {
+            fn inline_refs(
+                schema: &mut serde_json::Map<String, serde_json::Value>,
+                defs: &serde_json::Map<String, serde_json::Value>,
+            ) {
+                let definition = schema
+                    .get("$ref")
+                    .and_then(|r| r.as_str())
+                    .and_then(|r| r.strip_prefix("#/$defs/"))
+                    .and_then(|name| defs.get(name))
+                    .and_then(|definition| definition.as_object())
+                    .cloned();
+                if let Some(definition) = definition {
+                    schema.remove("$ref");
+                    // keywords set on the field, e.g. its description, take precedence
+                    for (key, value) in definition {
+                        schema.entry(key).or_insert(value);
+                    }
+                }
+                for value in schema.values_mut() {
+                    match value {
+                        serde_json::Value::Object(child) => inline_refs(child, defs),
+                        serde_json::Value::Array(items) => {
+                            for item in items.iter_mut() {
+                                if let serde_json::Value::Object(item) = item {
+                                    inline_refs(item, defs);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            if let Some(serde_json::Value::Object(defs)) = schema.remove("$defs") {
+                inline_refs(&mut schema, &defs);
+            }
+            schema
+        }
+    }
+}
+
+/// Returns an expression building the `serde_json::Number` of an integer or float literal.
+fn json_number(lit: &Lit) -> syn::Result<proc_macro2::TokenStream> {
+    match lit {
+        Lit::Int(lit_int) => {
+            let value = lit_int.base10_parse::<i64>()?;
+            Ok(quote! { serde_json::Number::from(#value) })
+        }
+        Lit::Float(lit_float) => {
+            let value = lit_float.base10_parse::<f64>()?;
+            Ok(quote! { serde_json::Number::from_f64(#value).expect("Invalid float") })
+        }
+        _ => Err(syn::Error::new(lit.span(), "expected a number")),
+    }
+}
+
+#[allow(unused)]
 pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    type_to_json_schema_with_defs(ty, attrs, &SchemaDefs::default())
+}
+
+/// Like [`type_to_json_schema`], nested types listed in `defs` are referenced with `$ref`.
+pub fn type_to_json_schema_with_defs(
+    ty: &Type,
+    attrs: &[Attribute],
+    defs: &SchemaDefs,
+) -> proc_macro2::TokenStream {
     let integer_types = [
         "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128",
     ];
@@ -176,12 +441,18 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
     // Parse custom json_schema attributes
     let mut title: Option<String> = None;
     let mut format: Option<String> = None;
+    let mut pattern: Option<String> = None;
+    let mut pattern_error: Option<syn::Error> = None;
     let mut min_length: Option<u64> = None;
     let mut max_length: Option<u64> = None;
-    let mut minimum: Option<i64> = None;
-    let mut maximum: Option<i64> = None;
+    let mut minimum: Option<proc_macro2::TokenStream> = None;
+    let mut maximum: Option<proc_macro2::TokenStream> = None;
+    let mut min_items: Option<u64> = None;
+    let mut max_items: Option<u64> = None;
+    let mut multiple_of: Option<proc_macro2::TokenStream> = None;
     let mut default: Option<proc_macro2::TokenStream> = None;
     let mut attr_description: Option<String> = None;
+    let mut sensitive = false;
 
     for attr in attrs {
         if attr.path().is_ident("json_schema") {
@@ -192,14 +463,48 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                     attr_description = Some(meta.value()?.parse::<LitStr>()?.value());
                 } else if meta.path.is_ident("format") {
                     format = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("pattern") {
+                    // the regex is passed through as is; it is only checked to be a string literal
+                    match meta.value()?.parse::<LitStr>() {
+                        Ok(lit) => pattern = Some(lit.value()),
+                        Err(err) => {
+                            let err = syn::Error::new(
+                                err.span(),
+                                "`pattern` must be a string literal, e.g. `pattern = \"^[a-z]+$\"`",
+                            );
+                            pattern_error = Some(err.clone());
+                            return Err(err);
+                        }
+                    }
                 } else if meta.path.is_ident("min_length") {
                     min_length = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u64>()?);
                 } else if meta.path.is_ident("max_length") {
                     max_length = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u64>()?);
                 } else if meta.path.is_ident("minimum") {
-                    minimum = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<i64>()?);
+                    minimum = Some(json_number(&meta.value()?.parse::<Lit>()?)?);
                 } else if meta.path.is_ident("maximum") {
-                    maximum = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<i64>()?);
+                    maximum = Some(json_number(&meta.value()?.parse::<Lit>()?)?);
+                } else if meta.path.is_ident("min_items") {
+                    // `minItems: 0` asserts nothing, so it is omitted
+                    min_items = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u64>()?)
+                        .filter(|min| *min > 0);
+                } else if meta.path.is_ident("max_items") {
+                    max_items = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u64>()?);
+                } else if meta.path.is_ident("multiple_of") {
+                    // JSON Schema requires `multipleOf` to be strictly positive, so zero is omitted
+                    multiple_of = match meta.value()?.parse::<Lit>()? {
+                        Lit::Int(lit_int) => {
+                            let value = lit_int.base10_parse::<u64>()?;
+                            (value > 0).then(|| quote! { serde_json::Number::from(#value) })
+                        }
+                        Lit::Float(lit_float) => {
+                            let value = lit_float.base10_parse::<f64>()?;
+                            (value > 0.0).then(|| quote! { serde_json::Number::from_f64(#value).expect("Invalid float") })
+                        }
+                        _ => return Err(meta.error("multiple_of must be a positive number")),
+                    };
+                } else if meta.path.is_ident("sensitive") {
+                    sensitive = true;
                 } else if meta.path.is_ident("default") {
                     let lit = meta.value()?.parse::<Lit>()?;
                     default = Some(match lit {
@@ -231,6 +536,10 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
         }
     }
 
+    if let Some(err) = pattern_error {
+        return err.to_compile_error();
+    }
+
     let description = attr_description.or(doc_comment(attrs));
     let description_quote = description.as_ref().map(|desc| {
         quote! {
@@ -244,14 +553,56 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
         }
     });
 
+    let multiple_of_quote = multiple_of.as_ref().map(|m| {
+        quote! {
+            map.insert("multipleOf".to_string(), serde_json::Value::Number(#m));
+        }
+    });
+
     let default_quote = default.as_ref().map(|d| {
         quote! {
             map.insert("default".to_string(), #d);
         }
     });
 
+    // sensitive values are marked `writeOnly`, so they can be redacted from logs and error messages
+    let write_only_quote = sensitive.then(|| {
+        quote! {
+            map.insert("writeOnly".to_string(), serde_json::Value::Bool(true));
+        }
+    });
+
     match ty {
         Type::Path(type_path) => {
+            // Box<T>, Arc<T> and Rc<T> are transparent, the field attributes apply to T
+            if let Some(inner_ty) = smart_pointer_inner_type(type_path) {
+                return type_to_json_schema_with_defs(inner_ty, attrs, defs);
+            }
+
+            // Handle HashMap<String, T> and BTreeMap<String, T>, also when written as a full path
+            if let Some((key_ty, value_ty)) = map_key_value_types(type_path) {
+                if !is_string_type(key_ty) {
+                    return syn::Error::new_spanned(
+                        key_ty,
+                        "JsonSchema only supports maps with `String` keys: JSON object keys are always strings, so other key types are not representable in JSON Schema",
+                    )
+                    .to_compile_error();
+                }
+                let value_schema = type_to_json_schema_with_defs(value_ty, &[], defs);
+                return quote! {
+                    {
+                        let mut map = serde_json::Map::new();
+                        map.insert("type".to_string(), serde_json::Value::String("object".to_string()));
+                        map.insert("additionalProperties".to_string(), serde_json::Value::Object(#value_schema));
+                        #description_quote
+                        #title_quote
+                        #default_quote
+                        #write_only_quote
+                        map
+                    }
+                };
+            }
+
             if type_path.path.segments.len() == 1 {
                 let segment = &type_path.path.segments[0];
                 let ident = &segment.ident;
@@ -261,7 +612,8 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                     if let PathArguments::AngleBracketed(args) = &segment.arguments {
                         if args.args.len() == 1 {
                             if let syn::GenericArgument::Type(inner_ty) = &args.args[0] {
-                                let inner_schema = type_to_json_schema(inner_ty, attrs);
+                                let inner_schema =
+                                    type_to_json_schema_with_defs(inner_ty, attrs, defs);
                                 let format_quote = format.as_ref().map(|f| {
                                     quote! {
                                         map.insert("format".to_string(), serde_json::Value::String(#f.to_string()));
@@ -279,12 +631,12 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                                 });
                                 let min_num_quote = minimum.as_ref().map(|min| {
                                     quote! {
-                                        map.insert("minimum".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
+                                        map.insert("minimum".to_string(), serde_json::Value::Number(#min));
                                     }
                                 });
                                 let max_num_quote = maximum.as_ref().map(|max| {
                                     quote! {
-                                        map.insert("maximum".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
+                                        map.insert("maximum".to_string(), serde_json::Value::Number(#max));
                                     }
                                 });
                                 return quote! {
@@ -346,6 +698,7 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                                         #min_num_quote
                                         #max_num_quote
                                         #default_quote
+                                        #write_only_quote
                                         map
                                     }
                                 };
@@ -358,13 +711,14 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                     if let PathArguments::AngleBracketed(args) = &segment.arguments {
                         if args.args.len() == 1 {
                             if let syn::GenericArgument::Type(inner_ty) = &args.args[0] {
-                                let inner_schema = type_to_json_schema(inner_ty, &[]);
-                                let min_quote = min_length.as_ref().map(|min| {
+                                let inner_schema =
+                                    type_to_json_schema_with_defs(inner_ty, &[], defs);
+                                let min_quote = min_items.or(min_length).map(|min| {
                                     quote! {
                                         map.insert("minItems".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
                                     }
                                 });
-                                let max_quote = max_length.as_ref().map(|max| {
+                                let max_quote = max_items.or(max_length).map(|max| {
                                     quote! {
                                         map.insert("maxItems".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
                                     }
@@ -379,6 +733,7 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                                         #min_quote
                                         #max_quote
                                         #default_quote
+                                        #write_only_quote
                                         map
                                     }
                                 };
@@ -389,15 +744,15 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                 //  Handle imported serde_json::Number (single-segment case, common when `use serde_json::Number;`)
                 else if ident == "Number" {
                     let min_num_quote = minimum.as_ref().map(|min| {
-                                        quote! {
-                                            map.insert("minimum".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
-                                        }
-                                    });
+                        quote! {
+                            map.insert("minimum".to_string(), serde_json::Value::Number(#min));
+                        }
+                    });
                     let max_num_quote = maximum.as_ref().map(|max| {
-                                        quote! {
-                                            map.insert("maximum".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
-                                        }
-                                    });
+                        quote! {
+                            map.insert("maximum".to_string(), serde_json::Value::Number(#max));
+                        }
+                    });
                     return quote! {
                         {
                             let mut map = serde_json::Map::new();
@@ -406,7 +761,9 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             #title_quote
                             #min_num_quote
                             #max_num_quote
+                            #multiple_of_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -414,12 +771,27 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                 // Handle nested structs
                 else if might_be_struct(ty) {
                     let path = &type_path.path;
+                    if let Some(def_name) = defs.def_name(path) {
+                        let reference = format!("#/$defs/{def_name}");
+                        return quote! {
+                            {
+                                let mut map = serde_json::Map::new();
+                                map.insert("$ref".to_string(), serde_json::Value::String(#reference.to_string()));
+                                #description_quote
+                                #title_quote
+                                #default_quote
+                                #write_only_quote
+                                map
+                            }
+                        };
+                    }
                     return quote! {
                         {
                             let mut map = #path::json_schema();
                             #description_quote
                             #title_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -441,6 +813,11 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             map.insert("maxLength".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
                         }
                     });
+                    let pattern_quote = pattern.as_ref().map(|p| {
+                        quote! {
+                            map.insert("pattern".to_string(), serde_json::Value::String(#p.to_string()));
+                        }
+                    });
                     return quote! {
                         {
                             let mut map = serde_json::Map::new();
@@ -448,9 +825,11 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             #description_quote
                             #title_quote
                             #format_quote
+                            #pattern_quote
                             #min_quote
                             #max_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -459,12 +838,12 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                 else if integer_types.iter().any(|t| ident == t) {
                     let min_quote = minimum.as_ref().map(|min| {
                         quote! {
-                            map.insert("minimum".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
+                            map.insert("minimum".to_string(), serde_json::Value::Number(#min));
                         }
                     });
                     let max_quote = maximum.as_ref().map(|max| {
                         quote! {
-                            map.insert("maximum".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
+                            map.insert("maximum".to_string(), serde_json::Value::Number(#max));
                         }
                     });
                     return quote! {
@@ -475,7 +854,9 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             #title_quote
                             #min_quote
                             #max_quote
+                            #multiple_of_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -484,12 +865,12 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                 else if float_types.iter().any(|t| ident == t) {
                     let min_quote = minimum.as_ref().map(|min| {
                         quote! {
-                            map.insert("minimum".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
+                            map.insert("minimum".to_string(), serde_json::Value::Number(#min));
                         }
                     });
                     let max_quote = maximum.as_ref().map(|max| {
                         quote! {
-                            map.insert("maximum".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
+                            map.insert("maximum".to_string(), serde_json::Value::Number(#max));
                         }
                     });
                     return quote! {
@@ -500,7 +881,9 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             #title_quote
                             #min_quote
                             #max_quote
+                            #multiple_of_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -514,6 +897,7 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             #description_quote
                             #title_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -528,15 +912,15 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                     && seg1.arguments.is_empty()
                 {
                     let min_num_quote = minimum.as_ref().map(|min| {
-                                    quote! {
-                                        map.insert("minimum".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
-                                    }
-                                });
+                        quote! {
+                            map.insert("minimum".to_string(), serde_json::Value::Number(#min));
+                        }
+                    });
                     let max_num_quote = maximum.as_ref().map(|max| {
-                                    quote! {
-                                        map.insert("maximum".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
-                                    }
-                                });
+                        quote! {
+                            map.insert("maximum".to_string(), serde_json::Value::Number(#max));
+                        }
+                    });
                     return quote! {
                         {
                             let mut map = serde_json::Map::new();
@@ -545,7 +929,9 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                             #title_quote
                             #min_num_quote
                             #max_num_quote
+                            #multiple_of_quote
                             #default_quote
+                            #write_only_quote
                             map
                         }
                     };
@@ -559,6 +945,36 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                     #description_quote
                     #title_quote
                     #default_quote
+                    #write_only_quote
+                    map
+                }
+            }
+        }
+        // Handle [T; N], bounded to exactly N items when N is an integer literal
+        Type::Array(type_array) => {
+            let inner_schema = type_to_json_schema_with_defs(&type_array.elem, &[], defs);
+            let length = array_length(&type_array.len);
+            let min_quote = length.or(min_items).or(min_length).map(|min| {
+                quote! {
+                    map.insert("minItems".to_string(), serde_json::Value::Number(serde_json::Number::from(#min)));
+                }
+            });
+            let max_quote = length.or(max_items).or(max_length).map(|max| {
+                quote! {
+                    map.insert("maxItems".to_string(), serde_json::Value::Number(serde_json::Number::from(#max)));
+                }
+            });
+            quote! {
+                {
+                    let mut map = serde_json::Map::new();
+                    map.insert("type".to_string(), serde_json::Value::String("array".to_string()));
+                    map.insert("items".to_string(), serde_json::Value::Object(#inner_schema));
+                    #description_quote
+                    #title_quote
+                    #min_quote
+                    #max_quote
+                    #default_quote
+                    #write_only_quote
                     map
                 }
             }
@@ -570,12 +986,26 @@ pub fn type_to_json_schema(ty: &Type, attrs: &[Attribute]) -> proc_macro2::Token
                 #description_quote
                 #title_quote
                 #default_quote
+                #write_only_quote
                 map
             }
         },
     }
 }
 
+/// Returns the length of an array type `[T; N]` when `N` is an integer literal,
+/// other const expressions are not evaluated.
+fn array_length(len: &syn::Expr) -> Option<u64> {
+    match len {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(lit_int),
+            ..
+        }) => lit_int.base10_parse::<u64>().ok(),
+        syn::Expr::Group(group) => array_length(&group.expr),
+        _ => None,
+    }
+}
+
 #[allow(unused)]
 pub fn has_derive(attrs: &[Attribute], trait_name: &str) -> bool {
     attrs.iter().any(|attr| {
@@ -622,6 +1052,106 @@ pub fn is_vec_string(ty: &Type) -> bool {
     }
 }
 
+/// Returns `true` if the field is marked `#[json_schema(skip)]`.
+pub fn is_skipped(attrs: &[Attribute]) -> bool {
+    let mut skipped = false;
+    for attr in attrs {
+        if attr.path().is_ident("json_schema") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skipped = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            });
+        }
+    }
+    skipped
+}
+
+/// Returns `true` if a `#[serde(...)]` attribute contains any of `names`,
+/// e.g. `default`, `flatten` or `deserialize_with = "..."`.
+pub fn has_serde_attr(attrs: &[Attribute], names: &[&str]) -> bool {
+    let mut found = false;
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if names.iter().any(|name| meta.path.is_ident(name)) {
+                    found = true;
+                }
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::TokenTree>()?;
+                }
+                Ok(())
+            });
+        }
+    }
+    found
+}
+
+/// How an enum is represented in JSON, as set with serde's container attributes.
+#[derive(Debug, PartialEq)]
+pub enum EnumTagging {
+    /// No attribute: each variant has its own schema, a unit variant is its name.
+    External,
+    /// `#[serde(tag = "...")]`: the variant name is a property of the variant's object.
+    Internal { tag: String },
+    /// `#[serde(tag = "...", content = "...")]`: an object with the variant name and its content.
+    Adjacent { tag: String, content: String },
+    /// `#[serde(untagged)]`: the variant's content without its name.
+    Untagged,
+}
+
+/// Returns the representation of an enum from its `#[serde(tag, content, untagged)]` attributes.
+pub fn enum_tagging(attrs: &[Attribute]) -> EnumTagging {
+    let mut tag = None;
+    let mut content = None;
+    let mut untagged = false;
+
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("untagged") {
+                    untagged = true;
+                } else if meta.path.is_ident("tag") || meta.path.is_ident("content") {
+                    let value = meta.value()?.parse::<syn::LitStr>()?.value();
+                    if meta.path.is_ident("tag") {
+                        tag = Some(value);
+                    } else {
+                        content = Some(value);
+                    }
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::TokenTree>()?;
+                }
+                Ok(())
+            });
+        }
+    }
+
+    match (untagged, tag, content) {
+        (true, _, _) => EnumTagging::Untagged,
+        (false, Some(tag), Some(content)) => EnumTagging::Adjacent { tag, content },
+        (false, Some(tag), None) => EnumTagging::Internal { tag },
+        (false, None, _) => EnumTagging::External,
+    }
+}
+
+/// Rejects a field marked `#[json_schema(skip)]` that is also renamed: it is left out of the
+/// schema, so the rename would silently have no effect there.
+pub fn skip_rename_conflict(field: &syn::Field) -> Option<syn::Error> {
+    (is_skipped(&field.attrs) && renamed_field(&field.attrs).is_some()).then(|| {
+        syn::Error::new_spanned(
+            field,
+            "`#[json_schema(skip)]` cannot be combined with `rename` on the same field",
+        )
+    })
+}
+
 pub fn renamed_field(attrs: &[Attribute]) -> Option<String> {
     let mut renamed = None;
 
@@ -746,6 +1276,42 @@ mod tests {
         assert_eq!(renamed_field(&[attr]), None);
     }
 
+    #[test]
+    fn test_has_serde_attr() {
+        let attr: Attribute = parse_quote!(
+            #[serde(rename(deserialize = "n"), with = "my_mod", default)]
+        );
+        assert!(has_serde_attr(std::slice::from_ref(&attr), &["default"]));
+        assert!(has_serde_attr(std::slice::from_ref(&attr), &["with"]));
+        assert!(!has_serde_attr(&[attr], &["flatten"]));
+
+        let attr: Attribute = parse_quote!(#[json_schema(default = 1)]);
+        assert!(!has_serde_attr(&[attr], &["default"]));
+    }
+
+    #[test]
+    fn test_enum_tagging() {
+        assert_eq!(enum_tagging(&[]), EnumTagging::External);
+
+        let attr: Attribute = parse_quote!(#[serde(rename_all = "snake_case", tag = "kind")]);
+        assert_eq!(
+            enum_tagging(&[attr]),
+            EnumTagging::Internal { tag: "kind".into() }
+        );
+
+        let attr: Attribute = parse_quote!(#[serde(tag = "t", content = "c")]);
+        assert_eq!(
+            enum_tagging(&[attr]),
+            EnumTagging::Adjacent {
+                tag: "t".into(),
+                content: "c".into()
+            }
+        );
+
+        let attr: Attribute = parse_quote!(#[serde(untagged)]);
+        assert_eq!(enum_tagging(&[attr]), EnumTagging::Untagged);
+    }
+
     #[test]
     fn test_get_doc_comment_single_line() {
         let attrs: Vec<Attribute> = vec![parse_quote!(#[doc = "This is a test comment."])];
@@ -974,6 +1540,110 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_json_schema_sensitive() {
+        let ty: syn::Type = parse_quote!(Option<String>);
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[json_schema(sensitive)])];
+        let tokens = type_to_json_schema(&ty, &attrs);
+        let output = render(tokens);
+        assert!(output.contains("\"writeOnly\".to_string(),serde_json::Value::Bool(true)"));
+    }
+
+    #[test]
+    fn test_json_schema_pattern_must_be_string_literal() {
+        let ty: syn::Type = parse_quote!(String);
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[json_schema(pattern = 42)])];
+        let output = render(type_to_json_schema(&ty, &attrs));
+        assert!(output.starts_with("::core::compile_error!"));
+        assert!(output.contains("`pattern`mustbeastringliteral"));
+    }
+
+    #[test]
+    fn test_json_schema_map() {
+        let ty: syn::Type = parse_quote!(std::collections::HashMap<String, Vec<i32>>);
+        let tokens = type_to_json_schema(&ty, &[]);
+        let output = render(tokens);
+        assert!(output
+            .contains("\"type\".to_string(),serde_json::Value::String(\"object\".to_string())"));
+        assert!(output.contains("\"additionalProperties\".to_string()"));
+        assert!(output
+            .contains("\"type\".to_string(),serde_json::Value::String(\"array\".to_string())"));
+    }
+
+    #[test]
+    fn test_is_skipped() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[json_schema(title = "Cache", skip)])];
+        assert!(is_skipped(&attrs));
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[json_schema(sensitive)])];
+        assert!(!is_skipped(&attrs));
+    }
+
+    #[test]
+    fn test_skip_rename_conflict() {
+        let field: syn::Field = parse_quote!(
+            #[json_schema(skip)]
+            #[serde(rename = "cacheKey")]
+            pub cache_key: String
+        );
+        let error = skip_rename_conflict(&field).unwrap();
+        assert!(error
+            .to_string()
+            .contains("cannot be combined with `rename`"));
+
+        let field: syn::Field = parse_quote!(
+            #[json_schema(skip)]
+            pub cache_key: String
+        );
+        assert!(skip_rename_conflict(&field).is_none());
+    }
+
+    #[test]
+    fn test_json_schema_smart_pointers() {
+        let ty: syn::Type = parse_quote!(Option<std::sync::Arc<Box<String>>>);
+        let tokens = type_to_json_schema(&ty, &[]);
+        let output = render(tokens);
+        assert!(output
+            .contains("\"type\".to_string(),serde_json::Value::String(\"string\".to_string())"));
+        assert!(!output.contains("unknown"));
+    }
+
+    #[test]
+    fn test_json_schema_fixed_size_array() {
+        let ty: syn::Type = parse_quote!([f64; 3]);
+        let tokens = type_to_json_schema(&ty, &[]);
+        let output = render(tokens);
+        assert!(output
+            .contains("\"type\".to_string(),serde_json::Value::String(\"array\".to_string())"));
+        assert!(output
+            .contains("\"type\".to_string(),serde_json::Value::String(\"number\".to_string())"));
+        assert!(output.contains(
+            "\"minItems\".to_string(),serde_json::Value::Number(serde_json::Number::from(3u64))"
+        ));
+        assert!(output.contains(
+            "\"maxItems\".to_string(),serde_json::Value::Number(serde_json::Number::from(3u64))"
+        ));
+    }
+
+    #[test]
+    fn test_json_schema_fixed_size_array_with_const_length() {
+        let ty: syn::Type = parse_quote!([u8; LENGTH]);
+        let tokens = type_to_json_schema(&ty, &[]);
+        let output = render(tokens);
+        assert!(output
+            .contains("\"type\".to_string(),serde_json::Value::String(\"array\".to_string())"));
+        assert!(!output.contains("minItems"));
+        assert!(!output.contains("maxItems"));
+    }
+
+    #[test]
+    fn test_json_schema_map_with_non_string_key() {
+        let ty: syn::Type = parse_quote!(BTreeMap<i32, String>);
+        let tokens = type_to_json_schema(&ty, &[]);
+        let output = render(tokens);
+        assert!(output.starts_with("::core::compile_error!"));
+        assert!(output.contains("mapswith`String`keys"));
+    }
+
     #[test]
     fn test_json_schema_fallback_unknown() {
         let ty: syn::Type = parse_quote!((i32, i32));
@@ -3,7 +3,7 @@ use rust_mcp_schema::{
     ElicitRequestFormParams, ElicitRequestParams, ElicitRequestUrlParams, ElicitResultContent,
     PrimitiveSchemaDefinition, RpcError,
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn test_form_basic_conversion() {
@@ -478,3 +478,321 @@ fn test_optional_fields_survive_form_schema_conversion() {
         _ => panic!("Expected FormParams"),
     }
 }
+
+#[test]
+fn test_optional_and_defaulted_fields() {
+    #[derive(Debug, Clone, JsonSchema)]
+    #[mcp_elicit(message = "Mixed fields", mode = form)]
+    pub struct MixedFields {
+        #[json_schema(title = "Name", min_length = 1)]
+        pub name: String,
+        pub nickname: Option<String>,
+        #[json_schema(title = "Language", default = "en")]
+        pub language: String,
+        #[json_schema(default = 10)]
+        pub limit: i32,
+        #[json_schema(default = true)]
+        pub notify: bool,
+        #[json_schema(default = 5)]
+        pub retries: Option<i64>,
+    }
+
+    match MixedFields::elicit_request_params() {
+        ElicitRequestParams::FormParams(form) => {
+            let schema = &form.requested_schema;
+            // optional and defaulted fields are left out of `required`
+            assert_eq!(schema.required, vec!["name"]);
+            match schema.properties.get("language") {
+                Some(PrimitiveSchemaDefinition::StringSchema(s)) => {
+                    assert_eq!(s.default.as_deref(), Some("en"))
+                }
+                other => panic!("Expected a string schema, got {other:?}"),
+            }
+            match schema.properties.get("notify") {
+                Some(PrimitiveSchemaDefinition::BooleanSchema(s)) => {
+                    assert_eq!(s.default, Some(true))
+                }
+                other => panic!("Expected a boolean schema, got {other:?}"),
+            }
+        }
+        _ => panic!("Expected FormParams"),
+    }
+
+    // only the required field is sent: optional fields are `None`, defaulted ones use their default
+    let mut content = BTreeMap::new();
+    content.insert("name".to_string(), "Dana".into());
+    let value = MixedFields::from_elicit_result_content(Some(content)).unwrap();
+    assert_eq!(value.name, "Dana");
+    assert_eq!(value.nickname, None);
+    assert_eq!(value.language, "en");
+    assert_eq!(value.limit, 10);
+    assert!(value.notify);
+    assert_eq!(value.retries, None);
+
+    // values sent by the client take precedence over defaults
+    let mut content = BTreeMap::new();
+    content.insert("name".to_string(), "Dana".into());
+    content.insert("nickname".to_string(), "D".into());
+    content.insert("language".to_string(), "fr".into());
+    content.insert("limit".to_string(), 3.into());
+    content.insert(
+        "notify".to_string(),
+        ElicitResultContent::Primitive(rust_mcp_schema::ElicitResultContentPrimitive::Boolean(
+            false,
+        )),
+    );
+    content.insert("retries".to_string(), 1.into());
+    let value = MixedFields::from_elicit_result_content(Some(content)).unwrap();
+    assert_eq!(value.nickname.as_deref(), Some("D"));
+    assert_eq!(value.language, "fr");
+    assert_eq!(value.limit, 3);
+    assert!(!value.notify);
+    assert_eq!(value.retries, Some(1));
+
+    // a missing required field is still an error
+    assert!(MixedFields::from_elicit_result_content(Some(BTreeMap::new())).is_err());
+}
+
+#[test]
+fn test_single_select_enum_fields() {
+    #[derive(Debug, Clone, PartialEq, JsonSchema)]
+    pub enum Plan {
+        #[json_schema(title = "Free plan")]
+        Free,
+        #[json_schema(title = "Pro plan")]
+        Pro,
+    }
+
+    #[derive(Debug, Clone, PartialEq, JsonSchema)]
+    pub enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Debug, Clone, JsonSchema)]
+    #[mcp_elicit(message = "Pick your options", mode = form)]
+    pub struct Preferences {
+        #[json_schema(title = "Plan")]
+        pub plan: Plan,
+        #[json_schema(title = "Color", default = "Green")]
+        pub color: Option<Color>,
+    }
+
+    match Preferences::elicit_request_params() {
+        ElicitRequestParams::FormParams(form) => {
+            let schema = &form.requested_schema;
+            assert_eq!(schema.required, vec!["plan"]);
+            match schema.properties.get("plan") {
+                Some(PrimitiveSchemaDefinition::TitledSingleSelectEnumSchema(s)) => {
+                    let options: Vec<_> = s
+                        .one_of
+                        .iter()
+                        .map(|o| (o.const_.as_str(), o.title.as_str()))
+                        .collect();
+                    assert_eq!(options, vec![("Free", "Free plan"), ("Pro", "Pro plan")]);
+                    assert_eq!(s.title.as_deref(), Some("Plan"));
+                }
+                other => panic!("Expected a titled single-select schema, got {other:?}"),
+            }
+            match schema.properties.get("color") {
+                Some(PrimitiveSchemaDefinition::UntitledSingleSelectEnumSchema(s)) => {
+                    assert_eq!(s.enum_, vec!["Red", "Green"]);
+                    assert_eq!(s.default.as_deref(), Some("Green"));
+                }
+                other => panic!("Expected an untitled single-select schema, got {other:?}"),
+            }
+        }
+        _ => panic!("Expected FormParams"),
+    }
+
+    let mut content = BTreeMap::new();
+    content.insert("plan".to_string(), "Pro".into());
+    content.insert("color".to_string(), "Red".into());
+    let value = Preferences::from_elicit_result_content(Some(content)).unwrap();
+    assert_eq!(value.plan, Plan::Pro);
+    assert_eq!(value.color, Some(Color::Red));
+
+    let mut content = BTreeMap::new();
+    content.insert("plan".to_string(), "Free".into());
+    let value = Preferences::from_elicit_result_content(Some(content)).unwrap();
+    assert_eq!(value.plan, Plan::Free);
+    assert_eq!(value.color, None);
+
+    // a selection outside the enum's options is rejected
+    let mut content = BTreeMap::new();
+    content.insert("plan".to_string(), "Enterprise".into());
+    let err = Preferences::from_elicit_result_content(Some(content)).unwrap_err();
+    assert!(err
+        .message
+        .contains("Invalid selection 'Enterprise' for field 'plan'"));
+}
+
+#[test]
+fn test_form_field_constraints() {
+    #[derive(Debug, Clone, JsonSchema)]
+    #[mcp_elicit(message = "Sign up", mode = form)]
+    pub struct SignUp {
+        #[json_schema(title = "Username", min_length = 3, max_length = 8)]
+        pub username: String,
+        #[json_schema(format = "email")]
+        pub email: String,
+        #[json_schema(minimum = 18, maximum = 120)]
+        pub age: i32,
+        #[json_schema(minimum = 1, maximum = 10)]
+        pub seats: Option<i64>,
+        #[json_schema(max_length = 4)]
+        pub bio: Option<String>,
+    }
+
+    match SignUp::elicit_request_params() {
+        ElicitRequestParams::FormParams(form) => {
+            let properties = &form.requested_schema.properties;
+            match properties.get("username") {
+                Some(PrimitiveSchemaDefinition::StringSchema(s)) => {
+                    assert_eq!(s.min_length, Some(3));
+                    assert_eq!(s.max_length, Some(8));
+                }
+                other => panic!("Expected a string schema, got {other:?}"),
+            }
+            match properties.get("email") {
+                Some(PrimitiveSchemaDefinition::StringSchema(s)) => {
+                    assert_eq!(s.format, Some(rust_mcp_schema::StringSchemaFormat::Email));
+                }
+                other => panic!("Expected a string schema, got {other:?}"),
+            }
+            match properties.get("age") {
+                Some(PrimitiveSchemaDefinition::NumberSchema(s)) => {
+                    assert_eq!(s.minimum, Some(18.0));
+                    assert_eq!(s.maximum, Some(120.0));
+                }
+                other => panic!("Expected a number schema, got {other:?}"),
+            }
+            match properties.get("seats") {
+                Some(PrimitiveSchemaDefinition::NumberSchema(s)) => {
+                    assert_eq!(s.minimum, Some(1.0));
+                    assert_eq!(s.maximum, Some(10.0));
+                }
+                other => panic!("Expected a number schema, got {other:?}"),
+            }
+        }
+        _ => panic!("Expected FormParams"),
+    }
+
+    let content = |username: &str, age: i64, seats: Option<i64>, bio: Option<&str>| {
+        let mut content: BTreeMap<String, ElicitResultContent> = BTreeMap::new();
+        content.insert("username".to_string(), username.into());
+        content.insert("email".to_string(), "user@example.com".into());
+        content.insert("age".to_string(), age.into());
+        if let Some(seats) = seats {
+            content.insert("seats".to_string(), seats.into());
+        }
+        if let Some(bio) = bio {
+            content.insert("bio".to_string(), bio.into());
+        }
+        Some(content)
+    };
+
+    let value = SignUp::from_elicit_result_content(content("ali", 18, Some(10), None)).unwrap();
+    assert_eq!(value.username, "ali");
+    assert_eq!(value.age, 18);
+    assert_eq!(value.seats, Some(10));
+    assert_eq!(value.bio, None);
+
+    // input violating the declared constraints is rejected, even if the client let it through
+    let message = |content| {
+        SignUp::from_elicit_result_content(content)
+            .unwrap_err()
+            .message
+    };
+    assert_eq!(
+        message(content("al", 30, None, None)),
+        "Field 'username' must have at least 3 characters"
+    );
+    assert_eq!(
+        message(content("alexander", 30, None, None)),
+        "Field 'username' must have at most 8 characters"
+    );
+    assert_eq!(
+        message(content("ali", 17, None, None)),
+        "Field 'age' must be at least 18"
+    );
+    assert_eq!(
+        message(content("ali", 121, None, None)),
+        "Field 'age' must be at most 120"
+    );
+    assert_eq!(
+        message(content("ali", 30, Some(0), None)),
+        "Field 'seats' must be at least 1"
+    );
+    assert_eq!(
+        message(content("ali", 30, None, Some("hello"))),
+        "Field 'bio' must have at most 4 characters"
+    );
+    // length is counted in characters, not bytes
+    assert!(SignUp::from_elicit_result_content(content("ali", 30, None, Some("héé!"))).is_ok());
+}
+
+#[test]
+fn test_float_bounds_keep_the_field_default() {
+    #[derive(Debug, Clone, JsonSchema)]
+    #[mcp_elicit(message = "Rate it", mode = form)]
+    pub struct Rating {
+        #[json_schema(minimum = 0.5, maximum = 5, default = 3)]
+        pub stars: i64,
+    }
+
+    match Rating::elicit_request_params() {
+        ElicitRequestParams::FormParams(form) => {
+            match form.requested_schema.properties.get("stars") {
+                Some(PrimitiveSchemaDefinition::NumberSchema(s)) => {
+                    assert_eq!(s.minimum, Some(0.5));
+                    assert_eq!(s.maximum, Some(5.0));
+                    assert_eq!(s.default, Some(3.0));
+                }
+                other => panic!("Expected a number schema, got {other:?}"),
+            }
+        }
+        _ => panic!("Expected FormParams"),
+    }
+
+    // a missing value falls back to the default, and the float bound is enforced
+    let value = Rating::from_elicit_result_content(None).unwrap();
+    assert_eq!(value.stars, 3);
+    let mut content: BTreeMap<String, ElicitResultContent> = BTreeMap::new();
+    content.insert("stars".to_string(), 0.into());
+    assert_eq!(
+        Rating::from_elicit_result_content(Some(content))
+            .unwrap_err()
+            .message,
+        "Field 'stars' must be at least 0.5"
+    );
+}
+
+#[test]
+fn test_skipped_fields_are_not_elicited() {
+    #[derive(Debug, Clone, JsonSchema)]
+    #[mcp_elicit(message = "Your name", mode = form)]
+    pub struct Profile {
+        pub name: String,
+        #[json_schema(skip)]
+        pub scores: HashMap<String, f64>,
+    }
+
+    match Profile::elicit_request_params() {
+        ElicitRequestParams::FormParams(form) => {
+            let schema = &form.requested_schema;
+            assert_eq!(schema.properties.len(), 1);
+            assert!(schema.properties.contains_key("name"));
+            assert_eq!(schema.required, vec!["name".to_string()]);
+        }
+        _ => panic!("Expected FormParams"),
+    }
+
+    // a value sent for a skipped field is ignored, the field gets its default
+    let mut content: BTreeMap<String, ElicitResultContent> = BTreeMap::new();
+    content.insert("name".to_string(), "Ali".into());
+    content.insert("scores".to_string(), "ignored".into());
+    let profile = Profile::from_elicit_result_content(Some(content)).unwrap();
+    assert_eq!(profile.name, "Ali");
+    assert!(profile.scores.is_empty());
+}
@@ -1,5 +1,6 @@
 use rust_mcp_macros::JsonSchema;
 use serde_json::Number;
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn test_schema_number() {
@@ -148,3 +149,613 @@ fn test_option_widens_array_type_union() {
         "array-type union append arm did not widen type to include null"
     );
 }
+
+#[test]
+fn test_schema_string_keyed_maps() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Inner {
+        pub a: String,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct WithMaps {
+        /// Arbitrary labels.
+        pub labels: HashMap<String, String>,
+        pub scores: BTreeMap<String, Vec<i32>>,
+        pub nested: std::collections::HashMap<String, HashMap<String, bool>>,
+        pub items: HashMap<String, Inner>,
+        pub extra: Option<BTreeMap<String, i64>>,
+    }
+
+    let schema = serde_json::Value::Object(WithMaps::json_schema());
+
+    assert_eq!(
+        schema,
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "labels": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" },
+                    "description": "Arbitrary labels."
+                },
+                "scores": {
+                    "type": "object",
+                    "additionalProperties": { "type": "array", "items": { "type": "integer" } }
+                },
+                "nested": {
+                    "type": "object",
+                    "additionalProperties": {
+                        "type": "object",
+                        "additionalProperties": { "type": "boolean" }
+                    }
+                },
+                "items": {
+                    "type": "object",
+                    "additionalProperties": {
+                        "type": "object",
+                        "properties": { "a": { "type": "string" } },
+                        "required": ["a"]
+                    }
+                },
+                "extra": {
+                    "type": ["object", "null"],
+                    "additionalProperties": { "type": "integer" }
+                }
+            },
+            "required": ["labels", "scores", "nested", "items"]
+        })
+    );
+}
+
+#[test]
+fn test_schema_validation_keywords() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Constrained {
+        #[json_schema(minimum = 0, maximum = 100)]
+        pub percent: u8,
+        #[json_schema(multiple_of = 0.5)]
+        pub step: f64,
+        #[json_schema(multiple_of = 3)]
+        pub count: Option<i64>,
+        #[json_schema(min_items = 1, max_items = 3)]
+        pub tags: Vec<String>,
+        #[json_schema(min_items = 2)]
+        pub pairs: Option<Vec<i32>>,
+        // zero values assert nothing and are omitted
+        #[json_schema(min_items = 0, multiple_of = 0)]
+        pub unconstrained: Vec<u32>,
+    }
+
+    assert_eq!(
+        serde_json::Value::Object(Constrained::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "percent": { "type": "integer", "minimum": 0, "maximum": 100 },
+                "step": { "type": "number", "multipleOf": 0.5 },
+                "count": { "type": ["integer", "null"], "multipleOf": 3 },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "minItems": 1,
+                    "maxItems": 3
+                },
+                "pairs": {
+                    "type": ["array", "null"],
+                    "items": { "type": "integer" },
+                    "minItems": 2
+                },
+                "unconstrained": { "type": "array", "items": { "type": "integer" } }
+            },
+            "required": ["percent", "step", "tags", "unconstrained"]
+        })
+    );
+}
+
+#[test]
+fn test_schema_string_pattern_and_format() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Contact {
+        #[json_schema(pattern = "^[a-z]+$")]
+        pub handle: String,
+        #[json_schema(format = "email", pattern = "@example\\.com$")]
+        pub email: String,
+        #[json_schema(format = "uri")]
+        pub website: Option<String>,
+        #[json_schema(format = "date", pattern = r"^\d{4}-\d{2}-\d{2}$")]
+        pub birthday: Option<String>,
+    }
+
+    assert_eq!(
+        serde_json::Value::Object(Contact::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "handle": { "type": "string", "pattern": "^[a-z]+$" },
+                "email": { "type": "string", "format": "email", "pattern": "@example\\.com$" },
+                "website": { "type": ["string", "null"], "format": "uri" },
+                "birthday": {
+                    "type": ["string", "null"],
+                    "format": "date",
+                    "pattern": "^\\d{4}-\\d{2}-\\d{2}$"
+                }
+            },
+            "required": ["handle", "email"]
+        })
+    );
+}
+
+#[test]
+fn test_schema_descriptions_from_doc_comments() {
+    /// A postal address.
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Address {
+        /// Street and house number.
+        pub street: String,
+    }
+
+    /// Contact details of a customer.
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[json_schema(description = "A customer contact")]
+    struct Customer {
+        /// Full name of the customer.
+        pub name: String,
+        /// Shown in the doc comment only.
+        #[json_schema(description = "Primary email address")]
+        pub email: String,
+        pub billing: Address,
+        /// Where orders are shipped to.
+        pub shipping: Address,
+    }
+
+    assert_eq!(
+        serde_json::Value::Object(Address::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "description": "A postal address.",
+            "properties": {
+                "street": { "type": "string", "description": "Street and house number." }
+            },
+            "required": ["street"]
+        })
+    );
+
+    let schema = Customer::json_schema();
+    assert_eq!(schema["description"], "A customer contact");
+    assert_eq!(
+        schema["properties"]["name"]["description"],
+        "Full name of the customer."
+    );
+    assert_eq!(
+        schema["properties"]["email"]["description"],
+        "Primary email address"
+    );
+    // `Address` is used twice, so its description is on the shared definition
+    assert_eq!(schema["properties"]["billing"]["$ref"], "#/$defs/Address");
+    assert_eq!(
+        schema["$defs"]["Address"]["description"],
+        "A postal address."
+    );
+    assert_eq!(
+        schema["properties"]["shipping"]["description"],
+        "Where orders are shipped to."
+    );
+}
+
+#[test]
+fn test_schema_smart_pointers() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Payload {
+        pub size: i64,
+    }
+
+    #[allow(unused, clippy::box_collection)]
+    #[derive(JsonSchema)]
+    struct Message {
+        #[json_schema(min_length = 1)]
+        pub title: Box<String>,
+        pub subtitle: Option<Box<String>>,
+        pub tags: Vec<Arc<String>>,
+        pub payload: Box<Payload>,
+        pub attachment: Option<Rc<Payload>>,
+        pub scores: Arc<Vec<Option<Box<i32>>>>,
+    }
+
+    assert_eq!(
+        serde_json::Value::Object(Message::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string", "minLength": 1 },
+                "subtitle": { "type": ["string", "null"] },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "payload": { "$ref": "#/$defs/Payload" },
+                "attachment": {
+                    "anyOf": [{ "$ref": "#/$defs/Payload" }, { "type": "null" }]
+                },
+                "scores": { "type": "array", "items": { "type": ["integer", "null"] } }
+            },
+            "required": ["title", "tags", "payload", "scores"],
+            "$defs": {
+                "Payload": {
+                    "type": "object",
+                    "properties": { "size": { "type": "integer" } },
+                    "required": ["size"]
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_schema_skipped_fields() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct SearchTool {
+        pub query: String,
+        #[json_schema(skip)]
+        pub cached_client: std::sync::Arc<String>,
+        #[json_schema(skip, description = "internal")]
+        pub request_count: u64,
+        #[json_schema(skip)]
+        pub last_error: Option<String>,
+    }
+
+    assert_eq!(
+        serde_json::Value::Object(SearchTool::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string" }
+            },
+            "required": ["query"]
+        })
+    );
+}
+
+#[test]
+fn test_schema_fixed_size_arrays() {
+    const CHANNELS: usize = 4;
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Placement {
+        /// x, y and z coordinates
+        pub position: [f64; 3],
+        pub corners: Option<[[i32; 2]; 4]>,
+        pub color: [u8; CHANNELS],
+    }
+
+    assert_eq!(
+        serde_json::Value::Object(Placement::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "position": {
+                    "type": "array",
+                    "items": { "type": "number" },
+                    "minItems": 3,
+                    "maxItems": 3,
+                    "description": "x, y and z coordinates"
+                },
+                "corners": {
+                    "type": ["array", "null"],
+                    "items": {
+                        "type": "array",
+                        "items": { "type": "integer" },
+                        "minItems": 2,
+                        "maxItems": 2
+                    },
+                    "minItems": 4,
+                    "maxItems": 4
+                },
+                "color": { "type": "array", "items": { "type": "integer" } }
+            },
+            "required": ["position", "color"]
+        })
+    );
+}
+
+#[test]
+fn test_schema_defs_for_repeated_nested_types() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Point {
+        pub x: i32,
+        pub y: i32,
+        pub label: Option<String>,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Size {
+        pub width: u32,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Line {
+        pub start: Point,
+        /// End of the line
+        pub end: Point,
+        pub waypoints: Vec<Point>,
+        pub size: Size,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Drawing {
+        pub line: Line,
+        pub origin: Option<Point>,
+    }
+
+    let point_schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "x": { "type": "integer" },
+            "y": { "type": "integer" },
+            "label": { "type": ["string", "null"] }
+        },
+        "required": ["x", "y"]
+    });
+
+    // a nested type used once is inlined, as before
+    assert_eq!(
+        serde_json::Value::Object(Line::json_schema()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "start": { "$ref": "#/$defs/Point" },
+                "end": { "$ref": "#/$defs/Point", "description": "End of the line" },
+                "waypoints": { "type": "array", "items": { "$ref": "#/$defs/Point" } },
+                "size": {
+                    "type": "object",
+                    "properties": { "width": { "type": "integer" } },
+                    "required": ["width"]
+                }
+            },
+            "required": ["start", "end", "waypoints", "size"],
+            "$defs": { "Point": point_schema.clone() }
+        })
+    );
+
+    // definitions of nested schemas are moved to the root, where `$ref` pointers resolve
+    let drawing = serde_json::Value::Object(Drawing::json_schema());
+    assert_eq!(drawing["$defs"]["Point"], point_schema);
+    assert!(drawing["properties"]["line"].get("$defs").is_none());
+    assert_eq!(
+        drawing["properties"]["line"]["properties"]["start"]["$ref"],
+        "#/$defs/Point"
+    );
+    assert_eq!(
+        drawing["properties"]["origin"]["type"],
+        serde_json::json!(["object", "null"])
+    );
+}
+
+/// Checks `value` against the subset of JSON Schema the derive emits for enums.
+fn matches_schema(schema: &serde_json::Value, value: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    let schema = schema.as_object().unwrap();
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        let actual = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_i64() || n.is_u64() => {
+                if expected == "number" {
+                    "number"
+                } else {
+                    "integer"
+                }
+            }
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        if actual != expected {
+            return false;
+        }
+    }
+    if schema.get("const").is_some_and(|c| c != value) {
+        return false;
+    }
+    if let Some(choices) = schema.get("enum").and_then(Value::as_array) {
+        if !choices.contains(value) {
+            return false;
+        }
+    }
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        if !required
+            .iter()
+            .all(|key| value.get(key.as_str().unwrap()).is_some())
+        {
+            return false;
+        }
+    }
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            if value
+                .get(key)
+                .is_some_and(|property_value| !matches_schema(property, property_value))
+            {
+                return false;
+            }
+        }
+    }
+    if let Some(items) = schema.get("items").and_then(Value::as_array) {
+        let values = value.as_array().unwrap();
+        if values.len() != items.len()
+            || !items.iter().zip(values).all(|(s, v)| matches_schema(s, v))
+        {
+            return false;
+        }
+    }
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        if !all_of.iter().all(|s| matches_schema(s, value)) {
+            return false;
+        }
+    }
+    if let Some(one_of) = schema.get("oneOf").and_then(Value::as_array) {
+        if one_of.iter().filter(|s| matches_schema(s, value)).count() != 1 {
+            return false;
+        }
+    }
+    true
+}
+
+#[test]
+fn test_schema_internally_tagged_enum() {
+    #[allow(unused)]
+    #[derive(JsonSchema, ::serde::Deserialize)]
+    struct Side {
+        length: f64,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema, ::serde::Deserialize)]
+    #[serde(tag = "kind")]
+    enum Shape {
+        Circle {
+            radius: f64,
+        },
+        Square(Side),
+        #[serde(rename = "nothing")]
+        Empty,
+    }
+
+    let schema = serde_json::Value::Object(Shape::json_schema());
+    assert_eq!(
+        schema["oneOf"][0],
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "kind": { "type": "string", "const": "Circle" },
+                "radius": { "type": "number" }
+            },
+            "required": ["kind", "radius"]
+        })
+    );
+    assert_eq!(
+        schema["oneOf"][2],
+        serde_json::json!({
+            "type": "object",
+            "properties": { "kind": { "type": "string", "const": "nothing" } },
+            "required": ["kind"]
+        })
+    );
+
+    for sample in [
+        serde_json::json!({ "kind": "Circle", "radius": 1.5 }),
+        serde_json::json!({ "kind": "Square", "length": 2.0 }),
+        serde_json::json!({ "kind": "nothing" }),
+    ] {
+        assert!(
+            serde_json::from_value::<Shape>(sample.clone()).is_ok(),
+            "{sample}"
+        );
+        assert!(matches_schema(&schema, &sample), "{sample}");
+    }
+    assert!(!matches_schema(
+        &schema,
+        &serde_json::json!({ "kind": "Triangle", "radius": 1.5 })
+    ));
+    assert!(!matches_schema(
+        &schema,
+        &serde_json::json!({ "radius": 1.5 })
+    ));
+}
+
+#[test]
+fn test_schema_adjacently_tagged_enum() {
+    #[allow(unused)]
+    #[derive(JsonSchema, ::serde::Deserialize)]
+    #[serde(tag = "t", content = "c")]
+    enum Message {
+        Text(String),
+        Move { x: i32, y: i32 },
+        Pair(i32, String),
+        Quit,
+    }
+
+    let schema = serde_json::Value::Object(Message::json_schema());
+    assert_eq!(
+        schema["oneOf"][0],
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "t": { "type": "string", "const": "Text" },
+                "c": { "type": "string" }
+            },
+            "required": ["t", "c"]
+        })
+    );
+
+    for sample in [
+        serde_json::json!({ "t": "Text", "c": "hello" }),
+        serde_json::json!({ "t": "Move", "c": { "x": 1, "y": 2 } }),
+        serde_json::json!({ "t": "Pair", "c": [1, "one"] }),
+        serde_json::json!({ "t": "Quit" }),
+    ] {
+        assert!(
+            serde_json::from_value::<Message>(sample.clone()).is_ok(),
+            "{sample}"
+        );
+        assert!(matches_schema(&schema, &sample), "{sample}");
+    }
+    assert!(!matches_schema(
+        &schema,
+        &serde_json::json!({ "t": "Text", "c": 5 })
+    ));
+}
+
+#[test]
+fn test_schema_untagged_enum() {
+    #[allow(unused)]
+    #[derive(JsonSchema, ::serde::Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Flag(bool),
+        Point { x: i32, y: i32 },
+        Missing,
+    }
+
+    let schema = serde_json::Value::Object(Value::json_schema());
+    assert_eq!(
+        schema["oneOf"],
+        serde_json::json!([
+            { "type": "boolean" },
+            {
+                "type": "object",
+                "properties": { "x": { "type": "integer" }, "y": { "type": "integer" } },
+                "required": ["x", "y"]
+            },
+            { "type": "null" }
+        ])
+    );
+
+    for sample in [
+        serde_json::json!(true),
+        serde_json::json!({ "x": 1, "y": 2 }),
+        serde_json::Value::Null,
+    ] {
+        assert!(
+            serde_json::from_value::<Value>(sample.clone()).is_ok(),
+            "{sample}"
+        );
+        assert!(matches_schema(&schema, &sample), "{sample}");
+    }
+    assert!(!matches_schema(&schema, &serde_json::json!("Flag")));
+}
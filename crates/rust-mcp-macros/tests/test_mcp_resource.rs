@@ -1,5 +1,6 @@
 use rust_mcp_macros::{mcp_elicit, mcp_resource, mcp_resource_template, JsonSchema};
 use rust_mcp_schema::{Resource, ResourceTemplate, Role};
+use std::collections::HashMap;
 
 #[test]
 fn full_annotated_resource() {
@@ -75,6 +76,29 @@ fn full_annotated_resource_template() {
     assert_eq!(icon.sizes, vec!["48x48"]);
 }
 
+#[test]
+fn resource_template_expand_uri() {
+    #[mcp_resource_template(
+        name = "project-files",
+        description = "Files of a project",
+        uri_template = "file:///{project}/{path}?rev={rev}"
+    )]
+    struct ProjectFile {}
+
+    let params = HashMap::from([
+        ("project".to_string(), "sdk".to_string()),
+        ("path".to_string(), "src/lib.rs".to_string()),
+    ]);
+    assert_eq!(
+        ProjectFile::expand_uri(&params),
+        "file:///sdk/src/lib.rs?rev={rev}"
+    );
+    assert_eq!(
+        ProjectFile::expand_uri(&HashMap::new()),
+        ProjectFile::resource_template_uri()
+    );
+}
+
 #[test]
 fn adhoc() {
     use rust_mcp_macros::mcp_resource;
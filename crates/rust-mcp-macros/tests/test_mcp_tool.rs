@@ -16,8 +16,12 @@ pub mod common;
 fn test_rename() {
     let schema = EditOperation::json_schema();
 
-    assert_eq!(schema.len(), 3);
+    assert_eq!(schema.len(), 4);
 
+    assert_eq!(
+        schema.get("description").unwrap(),
+        "Represents a text replacement operation."
+    );
     assert!(schema.contains_key("properties"));
     assert!(schema.contains_key("required"));
 
@@ -211,6 +215,273 @@ fn partial_annotations_some_set_some_not() {
     assert!(ann.open_world_hint.is_none());
 }
 
+#[test]
+fn blocking_tool_attribute() {
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "hash_file", description = "desc", blocking = true)]
+    struct HashFile {
+        path: String,
+    }
+
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "echo", description = "desc")]
+    struct Echo {
+        message: String,
+    }
+
+    assert!(HashFile::is_blocking());
+    assert!(!Echo::is_blocking());
+}
+
+#[test]
+fn tool_timeout_attribute() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "crawl", description = "desc", timeout_ms = 2500)]
+    struct Crawl {
+        url: String,
+    }
+
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "echo", description = "desc")]
+    struct Echo {
+        message: String,
+    }
+
+    assert_eq!(
+        Crawl::timeout(),
+        Some(std::time::Duration::from_millis(2500))
+    );
+    assert_eq!(Echo::timeout(), None);
+}
+
+#[test]
+fn tool_sensitive_fields() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Credentials {
+        user: String,
+        #[json_schema(sensitive)]
+        token: String,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "deploy", description = "desc")]
+    struct Deploy {
+        #[json_schema(sensitive)]
+        api_key: Option<String>,
+        credentials: Credentials,
+        mirrors: Vec<Credentials>,
+        target: String,
+    }
+
+    let mut fields = Deploy::sensitive_fields();
+    fields.sort();
+    assert_eq!(
+        fields,
+        vec!["api_key", "credentials.token", "mirrors.token"]
+    );
+    assert_eq!(
+        Deploy::tool().input_schema.properties.unwrap()["api_key"]["writeOnly"],
+        true
+    );
+}
+
+#[test]
+fn tool_input_schema_inlines_shared_definitions() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "draw_line", description = "desc")]
+    struct DrawLine {
+        start: Point,
+        /// Where the line ends
+        end: Option<Point>,
+    }
+
+    assert_eq!(DrawLine::json_schema()["$defs"]["Point"]["type"], "object");
+
+    let point = json!({
+        "type": "object",
+        "properties": { "x": { "type": "integer" }, "y": { "type": "integer" } },
+        "required": ["x", "y"]
+    });
+    let properties = DrawLine::tool().input_schema.properties.unwrap();
+    assert_eq!(json!(properties["start"]), point);
+    assert_eq!(properties["end"]["anyOf"][1], json!({ "type": "null" }));
+    assert_eq!(properties["end"]["anyOf"][0]["required"], json!(["x", "y"]));
+    assert_eq!(properties["end"]["description"], "Where the line ends");
+    assert!(!json!(properties).to_string().contains("$ref"));
+}
+
+#[test]
+fn tool_output_schema_from_result_type() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Reading {
+        celsius: f64,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct WeatherReport {
+        city: String,
+        current: Reading,
+        forecast: Vec<Reading>,
+        alert: Option<String>,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "weather", description = "desc", output = WeatherReport)]
+    struct Weather {
+        city: String,
+    }
+
+    let output_schema = Weather::tool().output_schema.unwrap();
+    assert_eq!(output_schema.type_(), "object");
+    assert_eq!(output_schema.required, vec!["city", "current", "forecast"]);
+
+    let reading = json!({
+        "type": "object",
+        "properties": { "celsius": { "type": "number" } },
+        "required": ["celsius"]
+    });
+    let properties = output_schema.properties.unwrap();
+    assert_eq!(json!(properties["city"]), json!({ "type": "string" }));
+    assert_eq!(json!(properties["current"]), reading);
+    assert_eq!(
+        json!(properties["forecast"]),
+        json!({ "type": "array", "items": reading })
+    );
+    assert_eq!(
+        json!(properties["alert"]),
+        json!({ "type": ["string", "null"] })
+    );
+}
+
+#[test]
+fn tool_without_output_has_no_output_schema() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "echo", description = "desc")]
+    struct Echo {
+        message: String,
+    }
+
+    assert!(Echo::tool().output_schema.is_none());
+}
+
+#[test]
+fn from_request_params_deserializes_arguments() {
+    #[derive(JsonSchema, ::serde::Deserialize, Debug, PartialEq)]
+    #[mcp_tool(name = "add", description = "desc")]
+    struct Add {
+        a: i64,
+        #[serde(rename = "secondValue")]
+        b: i64,
+        note: Option<String>,
+    }
+
+    let params = Add::request_params().with_arguments(
+        json!({ "a": 1, "secondValue": 2 })
+            .as_object()
+            .unwrap()
+            .clone(),
+    );
+    assert_eq!(
+        Add::from_request_params(params).unwrap(),
+        Add {
+            a: 1,
+            b: 2,
+            note: None
+        }
+    );
+}
+
+#[test]
+fn from_request_params_reports_the_invalid_argument() {
+    #[allow(unused)]
+    #[derive(JsonSchema, ::serde::Deserialize, Debug)]
+    #[mcp_tool(name = "add", description = "desc")]
+    struct Add {
+        a: i64,
+        #[serde(rename = "secondValue")]
+        b: i64,
+        #[serde(default)]
+        scale: u32,
+    }
+
+    let arguments = |value: serde_json::Value| value.as_object().unwrap().clone();
+
+    let error: RpcError = Add::from_request_params(
+        CallToolRequestParams::new("add").with_arguments(arguments(json!({ "a": 1 }))),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.message,
+        "Invalid arguments for tool 'add': missing required argument `secondValue`"
+    );
+    assert_eq!(error.data, Some(json!({ "field": "secondValue" })));
+
+    let error = Add::from_request_params(
+        Add::request_params().with_arguments(arguments(json!({ "a": "one", "secondValue": 2 }))),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.message,
+        "Invalid arguments for tool 'add': invalid value for `a`: invalid type: string \"one\", expected i64"
+    );
+    assert_eq!(error.data, Some(json!({ "field": "a" })));
+
+    // `scale` has a serde default, so it is not reported as missing
+    let add = Add::from_request_params(
+        Add::request_params().with_arguments(arguments(json!({ "a": 1, "secondValue": 2 }))),
+    )
+    .unwrap();
+    assert_eq!(add.scale, 0);
+
+    // without arguments, the first required one is reported
+    let error = Add::from_request_params(Add::request_params()).unwrap_err();
+    assert_eq!(error.data, Some(json!({ "field": "a" })));
+}
+
+#[test]
+fn tool_validation_constraints() {
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "order", description = "desc")]
+    struct Order {
+        #[json_schema(minimum = 0, maximum = 100, multiple_of = 5)]
+        discount: i32,
+        #[json_schema(min_items = 1, max_items = 10)]
+        items: Vec<String>,
+        #[json_schema(multiple_of = 0.25)]
+        weight: Option<f64>,
+    }
+
+    let properties = Order::tool().input_schema.properties.unwrap();
+    assert_eq!(
+        json!(properties["discount"]),
+        json!({ "type": "integer", "minimum": 0, "maximum": 100, "multipleOf": 5 })
+    );
+    assert_eq!(
+        json!(properties["items"]),
+        json!({ "type": "array", "items": { "type": "string" }, "minItems": 1, "maxItems": 10 })
+    );
+    assert_eq!(
+        json!(properties["weight"]),
+        json!({ "type": ["number", "null"], "multipleOf": 0.25 })
+    );
+}
+
 #[test]
 fn execution_task_support_required() {
     #[derive(JsonSchema)]
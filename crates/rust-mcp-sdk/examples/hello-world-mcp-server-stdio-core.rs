@@ -62,6 +62,9 @@ async fn main() -> SdkResult<()> {
         task_store: None,
         client_task_store: None,
         message_observer: None,
+        audit_sink: None,
+        handler_execution: Default::default(),
+        require_initialized: false,
     });
 
     // STEP 5: Start the server
@@ -56,6 +56,7 @@ async fn main() -> SdkResult<()> {
         task_store: Some(Arc::new(InMemoryTaskStore::new(None))), // support mcp tasks: https://modelcontextprotocol.io/specification/2025-11-25/basic/utilities/tasks
         server_task_store: Some(Arc::new(InMemoryTaskStore::new(None))),
         message_observer: None,
+        max_concurrent_requests: None,
     });
     client.clone().start().await?;
 
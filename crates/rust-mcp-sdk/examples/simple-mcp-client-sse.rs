@@ -57,6 +57,7 @@ async fn main() -> SdkResult<()> {
         task_store: None,
         server_task_store: None,
         message_observer: None,
+        max_concurrent_requests: None,
     });
 
     // STEP 5: start the MCP client
@@ -0,0 +1,480 @@
+//! Structured audit trail of tool invocations.
+//!
+//! When a server is configured with an [`AuditSink`], the runtime reports every `tools/call` request
+//! it handles as a [`ToolAuditRecord`]: who made the call, when, which tool, with what arguments,
+//! and the outcome. Sensitive arguments can be masked before they reach a sink by wrapping it in a
+//! [`RedactingAuditSink`].
+use crate::auth::AuthInfo;
+use crate::schema::{
+    schema_utils::ResultFromServer, CallToolRequestParams, ContentBlock, RequestId, RpcError, Tool,
+};
+use async_trait::async_trait;
+use rust_mcp_transport::SessionId;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+pub use crate::utils::REDACTED_VALUE;
+
+/// Receives a [`ToolAuditRecord`] for every tool invocation handled by the server.
+///
+/// The runtime awaits the sink after the tool's handler completes and before the response is sent,
+/// so a record is written for every result a client receives. Implementations should be fast,
+/// slow sinks delay the responses of tool calls.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record_tool_call(&self, record: ToolAuditRecord);
+}
+
+/// The outcome of an audited tool invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolAuditOutcome {
+    /// The tool ran and returned a result.
+    Success,
+    /// The tool ran and returned a result flagged with `is_error`.
+    ToolError,
+    /// The call was task-augmented and a task was created to run the tool.
+    TaskCreated { task_id: String },
+    /// The call failed with a protocol error, e.g. unknown tool, invalid arguments or timeout.
+    Failed { code: i64, message: String },
+}
+
+/// Structured data about a single tool invocation, see [`AuditSink`].
+#[derive(Debug, Clone)]
+pub struct ToolAuditRecord {
+    /// When the call was received.
+    pub timestamp: SystemTime,
+    /// How long the tool's handler ran.
+    pub duration: Duration,
+    pub session_id: Option<SessionId>,
+    pub request_id: RequestId,
+    /// The authenticated subject, the `user_id` of the auth info, or its `client_id` if it has none.
+    pub subject: Option<String>,
+    /// The OAuth client the access token was issued to.
+    pub client_id: Option<String>,
+    /// The name of the MCP client, as sent in its `initialize` request.
+    pub client_name: Option<String>,
+    pub tool_name: String,
+    pub arguments: Option<Map<String, Value>>,
+    pub outcome: ToolAuditOutcome,
+}
+
+impl ToolAuditRecord {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        timestamp: SystemTime,
+        duration: Duration,
+        session_id: Option<SessionId>,
+        request_id: RequestId,
+        auth_info: Option<&AuthInfo>,
+        client_name: Option<String>,
+        params: CallToolRequestParams,
+        result: &Result<ResultFromServer, RpcError>,
+    ) -> Self {
+        let outcome = match result {
+            Ok(ResultFromServer::CallToolResult(result)) if result.is_error == Some(true) => {
+                ToolAuditOutcome::ToolError
+            }
+            Ok(ResultFromServer::CreateTaskResult(result)) => ToolAuditOutcome::TaskCreated {
+                task_id: result.task.task_id.clone(),
+            },
+            Ok(_) => ToolAuditOutcome::Success,
+            Err(error) => ToolAuditOutcome::Failed {
+                code: error.code,
+                message: error.message.clone(),
+            },
+        };
+        let client_id = auth_info.and_then(|auth_info| auth_info.client_id.clone());
+        Self {
+            timestamp,
+            duration,
+            session_id,
+            request_id,
+            subject: auth_info
+                .and_then(|auth_info| auth_info.user_id.clone())
+                .or_else(|| client_id.clone()),
+            client_id,
+            client_name,
+            tool_name: params.name,
+            arguments: params.arguments,
+            outcome,
+        }
+    }
+
+    /// Returns the record as a flat JSON object, with the timestamp in RFC 3339 format
+    /// and the duration in milliseconds.
+    pub fn to_json(&self) -> Value {
+        let (outcome, error) = match &self.outcome {
+            ToolAuditOutcome::Success => ("success", Value::Null),
+            ToolAuditOutcome::ToolError => ("tool_error", Value::Null),
+            ToolAuditOutcome::TaskCreated { .. } => ("task_created", Value::Null),
+            ToolAuditOutcome::Failed { code, message } => {
+                ("failed", json!({ "code": code, "message": message }))
+            }
+        };
+        let task_id = match &self.outcome {
+            ToolAuditOutcome::TaskCreated { task_id } => Some(task_id),
+            _ => None,
+        };
+
+        json!({
+            "timestamp": OffsetDateTime::from(self.timestamp).format(&Rfc3339).ok(),
+            "duration_ms": self.duration.as_millis() as u64,
+            "session_id": self.session_id,
+            "request_id": self.request_id,
+            "subject": self.subject,
+            "client_id": self.client_id,
+            "client_name": self.client_name,
+            "tool": self.tool_name,
+            "arguments": self.arguments,
+            "outcome": outcome,
+            "task_id": task_id,
+            "error": error,
+        })
+    }
+}
+
+/// Arguments to mask in audit records.
+///
+/// Each field is a dot-separated path from the root of the arguments, e.g. `password` or
+/// `credentials.token`. Arrays are traversed, so `users.password` also matches the `password`
+/// of every object in a `users` array. Matching values are replaced with [`REDACTED_VALUE`].
+///
+/// The runtime also applies the fields a tool marks with `#[json_schema(sensitive)]`, see
+/// `ServerHandler::tool_sensitive_fields()`, to audit records and to the errors of the tool's calls.
+#[derive(Debug, Clone, Default)]
+pub struct AuditRedaction {
+    fields: Vec<Vec<String>>,
+}
+
+impl AuditRedaction {
+    pub fn new<I, S>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            fields: fields
+                .into_iter()
+                .map(|field| field.as_ref().split('.').map(str::to_string).collect())
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if no field is configured.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Replaces the configured fields of `arguments` with [`REDACTED_VALUE`].
+    pub fn apply(&self, arguments: &mut Map<String, Value>) {
+        for path in &self.fields {
+            visit_object(arguments, path, &mut |value| {
+                *value = Value::String(REDACTED_VALUE.to_string())
+            });
+        }
+    }
+
+    /// Keeps the configured fields out of the error of a failed tool call.
+    ///
+    /// The fields are redacted from the error's data and structured content as they are from
+    /// the arguments. The string values they have in `arguments` are also redacted wherever a
+    /// structured value equals them, and where they appear quoted in the error's text, as
+    /// deserialization errors quote invalid values.
+    pub(crate) fn redact_error(
+        &self,
+        arguments: &Map<String, Value>,
+        result: &mut Result<ResultFromServer, RpcError>,
+    ) {
+        if self.is_empty() {
+            return;
+        }
+        let mut arguments = arguments.clone();
+        let mut values = vec![];
+        for path in &self.fields {
+            visit_object(&mut arguments, path, &mut |value| {
+                if let Value::String(value) = value {
+                    if !value.is_empty() {
+                        values.push(value.clone());
+                    }
+                }
+            });
+        }
+
+        match result {
+            Err(error) => {
+                redact_quoted(&mut error.message, &values);
+                if let Some(data) = error.data.as_mut() {
+                    self.redact_structured(data, &values);
+                }
+            }
+            Ok(ResultFromServer::CallToolResult(result)) if result.is_error == Some(true) => {
+                for content in result.content.iter_mut() {
+                    if let ContentBlock::TextContent(content) = content {
+                        redact_quoted(&mut content.text, &values);
+                    }
+                }
+                if let Some(structured_content) = result.structured_content.as_mut() {
+                    self.apply(structured_content);
+                    structured_content
+                        .values_mut()
+                        .for_each(|value| redact_equal_strings(value, &values));
+                }
+            }
+            Ok(_) => {}
+        }
+    }
+
+    fn redact_structured(&self, value: &mut Value, values: &[String]) {
+        if let Value::Object(object) = value {
+            self.apply(object);
+        }
+        redact_equal_strings(value, values);
+    }
+}
+
+/// Returns the dot-separated paths of the arguments of `tool` whose input schema marks them
+/// `"writeOnly": true`, which is how `#[json_schema(sensitive)]` marks sensitive fields.
+pub fn write_only_fields(tool: &Tool) -> Vec<String> {
+    let mut fields = vec![];
+    for (key, property) in tool.input_schema.properties.iter().flatten() {
+        collect_write_only(property, key, &mut fields);
+    }
+    fields
+}
+
+fn collect_write_only(schema: &Map<String, Value>, path: &str, fields: &mut Vec<String>) {
+    if schema.get("writeOnly").and_then(Value::as_bool) == Some(true) {
+        fields.push(path.to_string());
+        return;
+    }
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            if let Some(property) = property.as_object() {
+                collect_write_only(property, &format!("{path}.{key}"), fields);
+            }
+        }
+    }
+    // array items and nullable or enum variants share the path of their parent
+    if let Some(items) = schema.get("items").and_then(Value::as_object) {
+        collect_write_only(items, path, fields);
+    }
+    for keyword in ["oneOf", "anyOf"] {
+        for variant in schema
+            .get(keyword)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+        {
+            collect_write_only(variant, path, fields);
+        }
+    }
+}
+
+fn visit_object(object: &mut Map<String, Value>, path: &[String], f: &mut dyn FnMut(&mut Value)) {
+    let Some((key, rest)) = path.split_first() else {
+        return;
+    };
+    let Some(value) = object.get_mut(key) else {
+        return;
+    };
+    if rest.is_empty() {
+        f(value);
+    } else {
+        visit_value(value, rest, f);
+    }
+}
+
+fn visit_value(value: &mut Value, path: &[String], f: &mut dyn FnMut(&mut Value)) {
+    match value {
+        Value::Object(object) => visit_object(object, path, f),
+        Value::Array(items) => items.iter_mut().for_each(|item| visit_value(item, path, f)),
+        _ => {}
+    }
+}
+
+fn redact_quoted(text: &mut String, values: &[String]) {
+    for value in values {
+        let quoted = Value::String(value.clone()).to_string();
+        if text.contains(&quoted) {
+            *text = text.replace(
+                &quoted,
+                &Value::String(REDACTED_VALUE.to_string()).to_string(),
+            );
+        }
+    }
+}
+
+fn redact_equal_strings(value: &mut Value, values: &[String]) {
+    match value {
+        Value::String(text) if values.contains(text) => *text = REDACTED_VALUE.to_string(),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| redact_equal_strings(item, values)),
+        Value::Object(object) => object
+            .values_mut()
+            .for_each(|item| redact_equal_strings(item, values)),
+        _ => {}
+    }
+}
+
+/// An [`AuditSink`] wrapper that masks sensitive arguments before passing records to the inner sink.
+///
+/// # Example
+/// ```
+/// use rust_mcp_sdk::audit::{AuditRedaction, AuditSink, RedactingAuditSink, ToolAuditRecord};
+/// use std::sync::Arc;
+///
+/// struct StdoutSink;
+///
+/// #[async_trait::async_trait]
+/// impl AuditSink for StdoutSink {
+///     async fn record_tool_call(&self, record: ToolAuditRecord) {
+///         println!("{}", record.to_json());
+///     }
+/// }
+///
+/// let sink: Arc<dyn AuditSink> = Arc::new(RedactingAuditSink::new(
+///     Arc::new(StdoutSink),
+///     AuditRedaction::new(["password", "credentials.token"]),
+/// ));
+/// ```
+pub struct RedactingAuditSink {
+    inner: Arc<dyn AuditSink>,
+    redaction: AuditRedaction,
+}
+
+impl RedactingAuditSink {
+    pub fn new(inner: Arc<dyn AuditSink>, redaction: AuditRedaction) -> Self {
+        Self { inner, redaction }
+    }
+}
+
+#[async_trait]
+impl AuditSink for RedactingAuditSink {
+    async fn record_tool_call(&self, mut record: ToolAuditRecord) {
+        if let Some(arguments) = record.arguments.as_mut() {
+            self.redaction.apply(arguments);
+        }
+        self.inner.record_tool_call(record).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_nested_fields_and_array_items() {
+        let mut arguments = json!({
+            "password": "secret",
+            "credentials": { "token": "abc", "user": "alice" },
+            "users": [{ "password": "p1" }, { "password": "p2", "name": "bob" }],
+            "query": "select 1"
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+
+        AuditRedaction::new([
+            "password",
+            "credentials.token",
+            "users.password",
+            "missing.field",
+        ])
+        .apply(&mut arguments);
+
+        assert_eq!(
+            Value::Object(arguments),
+            json!({
+                "password": REDACTED_VALUE,
+                "credentials": { "token": REDACTED_VALUE, "user": "alice" },
+                "users": [{ "password": REDACTED_VALUE }, { "password": REDACTED_VALUE, "name": "bob" }],
+                "query": "select 1"
+            })
+        );
+    }
+
+    #[test]
+    fn redacts_sensitive_values_from_errors() {
+        let arguments = json!({ "api_key": "sk-123", "retries": "three" })
+            .as_object()
+            .cloned()
+            .unwrap();
+        let mut result = Err(RpcError::invalid_params()
+            .with_message("invalid type: string \"sk-123\", expected u32".to_string())
+            .with_data(Some(json!({ "input": "sk-123" }))));
+
+        AuditRedaction::new(["api_key"]).redact_error(&arguments, &mut result);
+
+        let error = result.unwrap_err();
+        assert_eq!(
+            error.message,
+            format!("invalid type: string \"{REDACTED_VALUE}\", expected u32")
+        );
+        assert_eq!(error.data, Some(json!({ "input": REDACTED_VALUE })));
+    }
+
+    #[test]
+    fn redacts_structured_error_fields_without_rewriting_unquoted_text() {
+        let arguments = json!({ "api_key": "a", "token": 42 })
+            .as_object()
+            .cloned()
+            .unwrap();
+        let mut result = Err(RpcError::invalid_params()
+            .with_message("a request was rejected".to_string())
+            .with_data(Some(json!({ "token": 42, "hint": "a b", "api_key": "a" }))));
+
+        AuditRedaction::new(["api_key", "token"]).redact_error(&arguments, &mut result);
+
+        let error = result.unwrap_err();
+        assert_eq!(error.message, "a request was rejected");
+        assert_eq!(
+            error.data,
+            Some(json!({ "token": REDACTED_VALUE, "hint": "a b", "api_key": REDACTED_VALUE }))
+        );
+    }
+
+    #[test]
+    fn collects_write_only_fields_of_a_tool() {
+        let schema = json!({
+            "api_key": { "type": "string", "writeOnly": true },
+            "credentials": {
+                "type": "object",
+                "properties": {
+                    "token": { "type": "string", "writeOnly": true },
+                    "user": { "type": "string" }
+                }
+            },
+            "users": {
+                "type": "array",
+                "items": { "type": "object", "properties": { "password": { "writeOnly": true } } }
+            },
+            "query": { "type": "string" }
+        });
+        let properties = schema
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.as_object().cloned().unwrap()))
+            .collect();
+        let tool = Tool {
+            annotations: None,
+            description: None,
+            execution: None,
+            icons: vec![],
+            input_schema: crate::schema::ToolInputSchema::new(vec![], Some(properties), None),
+            meta: None,
+            name: "login".to_string(),
+            output_schema: None,
+            title: None,
+        };
+
+        assert_eq!(
+            write_only_fields(&tool),
+            vec!["api_key", "credentials.token", "users.password"]
+        );
+    }
+}
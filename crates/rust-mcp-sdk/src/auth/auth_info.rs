@@ -3,7 +3,7 @@ use crate::auth::{AuthClaims, AuthenticationError, IntrospectionResponse};
 use crate::{auth::Audience, utils::unix_timestamp_to_systemtime};
 #[cfg(feature = "auth")]
 use jsonwebtoken::TokenData;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Map;
 use std::time::SystemTime;
 
@@ -38,10 +38,25 @@ pub struct AuthInfo {
 
     /// Additional data associated with the token.
     /// This field can be used to attach any extra data to the auth info.
-    #[serde(flatten, skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(
+        flatten,
+        skip_serializing_if = "std::option::Option::is_none",
+        deserialize_with = "deserialize_extra"
+    )]
     pub extra: Option<Map<String, serde_json::Value>>,
 }
 
+/// Flattened fields always deserialize, so an absent `extra` is restored as `None` rather than an empty map.
+fn deserialize_extra<'de, D>(
+    deserializer: D,
+) -> Result<Option<Map<String, serde_json::Value>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let extra = Map::deserialize(deserializer)?;
+    Ok((!extra.is_empty()).then_some(extra))
+}
+
 #[cfg(feature = "auth")]
 impl AuthInfo {
     pub fn from_token_data(
@@ -103,3 +118,68 @@ impl AuthInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn auth_info(extra: Option<Map<String, serde_json::Value>>) -> AuthInfo {
+        AuthInfo {
+            token_unique_id: "jti-1".to_string(),
+            client_id: Some("client-1".to_string()),
+            user_id: Some("user-1".to_string()),
+            scopes: Some(vec!["read".to_string(), "write".to_string()]),
+            expires_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            audience: Some(Audience::Multiple(vec![
+                "https://mcp.example.com".to_string(),
+                "https://api.example.com".to_string(),
+            ])),
+            extra,
+        }
+    }
+
+    fn round_trip(info: &AuthInfo) -> AuthInfo {
+        let serialized = serde_json::to_string(info).unwrap();
+        serde_json::from_str(&serialized).unwrap()
+    }
+
+    #[test]
+    fn auth_info_round_trips_through_json() {
+        let extra = json!({"tenant": "acme", "roles": ["admin"]})
+            .as_object()
+            .cloned();
+        let info = auth_info(extra.clone());
+        let restored = round_trip(&info);
+
+        assert_eq!(restored.token_unique_id, info.token_unique_id);
+        assert_eq!(restored.client_id, info.client_id);
+        assert_eq!(restored.user_id, info.user_id);
+        assert_eq!(restored.scopes, info.scopes);
+        assert_eq!(restored.expires_at, info.expires_at);
+        assert_eq!(restored.audience, info.audience);
+        assert_eq!(restored.extra, extra);
+    }
+
+    #[test]
+    fn auth_info_without_optional_fields_round_trips() {
+        let info = AuthInfo {
+            token_unique_id: "jti-2".to_string(),
+            client_id: None,
+            user_id: None,
+            scopes: None,
+            expires_at: None,
+            audience: Some(Audience::Single("https://mcp.example.com".to_string())),
+            extra: None,
+        };
+        let restored = round_trip(&info);
+
+        assert_eq!(restored.token_unique_id, "jti-2");
+        assert!(restored.client_id.is_none());
+        assert!(restored.scopes.is_none());
+        assert!(restored.expires_at.is_none());
+        assert_eq!(restored.audience, info.audience);
+        assert!(restored.extra.is_none());
+    }
+}
@@ -0,0 +1,401 @@
+//! Command line interface for invoking tools locally, without an MCP client.
+//!
+//! [`ToolCli`] turns a set of tools into a [`clap`] command where each tool is a subcommand and
+//! each property of its input schema is a `--<property>` flag. The parsed arguments are converted
+//! into `CallToolRequestParams`, handed to your handler, and the result is printed as JSON.
+//! Most servers will use it through the [`tool_cli!`](crate::tool_cli) macro.
+use crate::schema::{schema_utils::CallToolError, CallToolRequestParams, CallToolResult, Tool};
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser},
+    Arg, ArgAction, ArgMatches, Command,
+};
+use serde_json::{Map, Value};
+use std::{ffi::OsString, future::Future, process::ExitCode};
+
+/// How a flag value is parsed, derived from the property's JSON schema
+#[derive(Debug, Clone, Copy)]
+enum ValueKind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Json,
+}
+
+impl ValueKind {
+    fn from_schema(schema: &Map<String, Value>) -> Self {
+        match schema_type(schema) {
+            Some("string") => Self::String,
+            Some("integer") => Self::Integer,
+            Some("number") => Self::Number,
+            Some("boolean") => Self::Boolean,
+            _ => Self::Json,
+        }
+    }
+
+    fn parse(self, value: &str) -> Result<Value, String> {
+        match self {
+            Self::String => Ok(Value::String(value.to_string())),
+            Self::Integer => value
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|err| err.to_string()),
+            Self::Number => value
+                .parse::<f64>()
+                .map(Value::from)
+                .map_err(|err| err.to_string()),
+            Self::Boolean => value
+                .parse::<bool>()
+                .map(Value::Bool)
+                .map_err(|err| err.to_string()),
+            Self::Json => serde_json::from_str(value).map_err(|err| format!("invalid JSON: {err}")),
+        }
+    }
+}
+
+/// Returns the first non-null type of a schema, looking into `anyOf` wrappers of optional fields
+fn schema_type(schema: &Map<String, Value>) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::String(type_name)) => Some(type_name.as_str()),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null"),
+        _ => inner_schema(schema).and_then(schema_type),
+    }
+}
+
+/// Returns the first non-null variant of an `anyOf` schema
+fn inner_schema(schema: &Map<String, Value>) -> Option<&Map<String, Value>> {
+    schema
+        .get("anyOf")
+        .and_then(|v| v.as_array())?
+        .iter()
+        .filter_map(|v| v.as_object())
+        .find(|v| v.get("type").and_then(|t| t.as_str()) != Some("null"))
+}
+
+/// Returns the allowed string values of an enum schema, either `enum` or a `oneOf` of single-value enums
+fn enum_values(schema: &Map<String, Value>) -> Option<Vec<String>> {
+    if let Some(values) = schema.get("enum").and_then(|v| v.as_array()) {
+        return values
+            .iter()
+            .map(|v| v.as_str().map(str::to_string))
+            .collect();
+    }
+    if let Some(variants) = schema.get("oneOf").and_then(|v| v.as_array()) {
+        let values: Option<Vec<Vec<String>>> = variants
+            .iter()
+            .map(|variant| variant.as_object().and_then(enum_values))
+            .collect();
+        return values.map(|values| values.into_iter().flatten().collect());
+    }
+    inner_schema(schema).and_then(enum_values)
+}
+
+fn property_arg(name: &str, schema: &Map<String, Value>, required: bool) -> Arg {
+    let mut arg = Arg::new(name.to_string())
+        .long(name.to_string())
+        .required(required);
+
+    if let Some(description) = schema.get("description").and_then(|d| d.as_str()) {
+        arg = arg.help(description.to_string());
+    }
+
+    let (arg, item_schema) = match schema_type(schema) {
+        Some("array") => {
+            let items = schema
+                .get("items")
+                .or_else(|| inner_schema(schema).and_then(|s| s.get("items")))
+                .and_then(|items| items.as_object());
+            (arg.action(ArgAction::Append), items)
+        }
+        Some("boolean") => (
+            // `--flag` alone means true, `--flag false` is accepted as well
+            arg.num_args(0..=1).default_missing_value("true"),
+            Some(schema),
+        ),
+        _ => (arg.action(ArgAction::Set), Some(schema)),
+    };
+
+    match item_schema.and_then(enum_values) {
+        Some(values) => arg
+            .value_parser(PossibleValuesParser::new(values).map(Value::String))
+            .value_name("VALUE"),
+        None => {
+            let kind = item_schema.map_or(ValueKind::Json, ValueKind::from_schema);
+            arg.value_parser(move |value: &str| kind.parse(value))
+        }
+    }
+}
+
+/// A command line interface for a set of tools.
+///
+/// # Example
+/// ```ignore
+/// let cli = ToolCli::new(MyTools::tools());
+/// let matches = cli.command("my-server").get_matches();
+/// let params = cli.call_params(&matches)?;
+/// ```
+pub struct ToolCli {
+    tools: Vec<Tool>,
+}
+
+impl ToolCli {
+    pub fn new(tools: Vec<Tool>) -> Self {
+        Self { tools }
+    }
+
+    /// Builds the `clap` command, with a subcommand per tool and a flag per input property.
+    /// Required properties become required flags; array properties accept the flag multiple times,
+    /// and object properties take a JSON value.
+    pub fn command(&self, name: impl Into<String>) -> Command {
+        let subcommands = self.tools.iter().map(|tool| {
+            let mut subcommand = Command::new(tool.name.clone());
+            if let Some(about) = tool.description.as_ref().or(tool.title.as_ref()) {
+                subcommand = subcommand.about(about.clone());
+            }
+            let properties = tool.input_schema.properties.iter().flatten();
+            subcommand.args(properties.map(|(name, schema)| {
+                property_arg(name, schema, tool.input_schema.required.contains(name))
+            }))
+        });
+
+        Command::new(name.into())
+            .subcommand_required(true)
+            .arg_required_else_help(true)
+            .subcommands(subcommands)
+    }
+
+    /// Converts the matches of [`ToolCli::command`] into the parameters of a tool call.
+    pub fn call_params(
+        &self,
+        matches: &ArgMatches,
+    ) -> Result<CallToolRequestParams, CallToolError> {
+        let Some((tool_name, tool_matches)) = matches.subcommand() else {
+            return Err(CallToolError::invalid_arguments(
+                "",
+                Some("No tool selected".to_string()),
+            ));
+        };
+        let tool = self
+            .tools
+            .iter()
+            .find(|tool| tool.name == tool_name)
+            .ok_or_else(|| CallToolError::unknown_tool(tool_name.to_string()))?;
+
+        let mut arguments = Map::new();
+        for (name, schema) in tool.input_schema.properties.iter().flatten() {
+            if schema_type(schema) == Some("array") {
+                if let Some(values) = tool_matches.get_many::<Value>(name) {
+                    arguments.insert(name.clone(), Value::Array(values.cloned().collect()));
+                }
+            } else if let Some(value) = tool_matches.get_one::<Value>(name) {
+                arguments.insert(name.clone(), value.clone());
+            }
+        }
+
+        Ok(CallToolRequestParams::new(tool_name.to_string()).with_arguments(arguments))
+    }
+
+    /// Parses `args`, converts the selected subcommand into a tool `T` and calls `handler` with it.
+    /// The result is printed to stdout as JSON; parse and tool errors are printed to stderr.
+    ///
+    /// Returns a failure exit code if arguments are invalid, the tool fails, or its result is an error.
+    pub async fn run<T, I, S, F, Fut>(
+        &self,
+        name: impl Into<String>,
+        args: I,
+        handler: F,
+    ) -> ExitCode
+    where
+        T: TryFrom<CallToolRequestParams, Error = CallToolError>,
+        I: IntoIterator<Item = S>,
+        S: Into<OsString> + Clone,
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = Result<CallToolResult, CallToolError>>,
+    {
+        let matches = match self.command(name).try_get_matches_from(args) {
+            Ok(matches) => matches,
+            Err(err) => {
+                let _ = err.print();
+                return if err.use_stderr() {
+                    ExitCode::from(2)
+                } else {
+                    ExitCode::SUCCESS // --help and --version
+                };
+            }
+        };
+
+        let result = match self.call_params(&matches).and_then(T::try_from) {
+            Ok(tool) => handler(tool).await,
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(result) => {
+                match serde_json::to_string_pretty(&result) {
+                    Ok(json) => println!("{json}"),
+                    Err(err) => eprintln!("Failed to serialize the tool result: {err}"),
+                }
+                if result.is_error.unwrap_or(false) {
+                    ExitCode::FAILURE
+                } else {
+                    ExitCode::SUCCESS
+                }
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ToolInputSchema;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn tool(name: &str, schema: Value) -> Tool {
+        let schema = schema.as_object().unwrap();
+        let required = schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().map(|v| v.as_str().unwrap().to_string()).collect())
+            .unwrap_or_default();
+        let properties: BTreeMap<String, Map<String, Value>> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.as_object().unwrap().clone()))
+            .collect();
+        Tool {
+            name: name.to_string(),
+            description: Some(format!("The {name} tool")),
+            input_schema: ToolInputSchema::new(required, Some(properties), None),
+            annotations: None,
+            execution: None,
+            icons: vec![],
+            meta: None,
+            output_schema: None,
+            title: None,
+        }
+    }
+
+    fn cli() -> ToolCli {
+        ToolCli::new(vec![
+            tool(
+                "save_file",
+                json!({
+                    "properties": {
+                        "path": { "type": "string", "description": "Where to save" },
+                        "copies": { "type": "integer" },
+                        "ratio": { "type": ["number", "null"] },
+                        "overwrite": { "type": "boolean" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "mode": { "oneOf": [{ "enum": ["Overwrite"] }, { "enum": ["Append"] }] },
+                        "owner": { "type": "object", "properties": { "name": { "type": "string" } } }
+                    },
+                    "required": ["path", "copies"]
+                }),
+            ),
+            tool(
+                "say_hello",
+                json!({ "properties": { "name": { "type": "string" } }, "required": ["name"] }),
+            ),
+        ])
+    }
+
+    fn parse(cli: &ToolCli, args: &[&str]) -> Result<CallToolRequestParams, String> {
+        let matches = cli
+            .command("test")
+            .try_get_matches_from(std::iter::once("test").chain(args.iter().copied()))
+            .map_err(|err| err.to_string())?;
+        cli.call_params(&matches).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn converts_flags_into_typed_arguments() {
+        let cli = cli();
+        let params = parse(
+            &cli,
+            &[
+                "save_file",
+                "--path",
+                "a.txt",
+                "--copies",
+                "3",
+                "--ratio",
+                "0.5",
+                "--overwrite",
+                "--tags",
+                "x",
+                "--tags",
+                "y",
+                "--mode",
+                "Append",
+                "--owner",
+                r#"{"name":"ali"}"#,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(params.name, "save_file");
+        assert_eq!(
+            Value::Object(params.arguments.unwrap()),
+            json!({
+                "path": "a.txt",
+                "copies": 3,
+                "ratio": 0.5,
+                "overwrite": true,
+                "tags": ["x", "y"],
+                "mode": "Append",
+                "owner": { "name": "ali" }
+            })
+        );
+
+        let params = parse(&cli, &["say_hello", "--name", "world"]).unwrap();
+        assert_eq!(params.name, "say_hello");
+        assert_eq!(
+            Value::Object(params.arguments.unwrap()),
+            json!({ "name": "world" })
+        );
+    }
+
+    #[test]
+    fn omits_flags_that_are_not_given() {
+        let params = parse(&cli(), &["save_file", "--path", "a.txt", "--copies", "1"]).unwrap();
+        assert_eq!(
+            Value::Object(params.arguments.unwrap()),
+            json!({ "path": "a.txt", "copies": 1 })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let cli = cli();
+        // missing required flag
+        assert!(parse(&cli, &["save_file", "--path", "a.txt"]).is_err());
+        // wrong type
+        assert!(parse(&cli, &["save_file", "--path", "a", "--copies", "many"]).is_err());
+        // value outside of the enum
+        assert!(parse(
+            &cli,
+            &[
+                "save_file",
+                "--path",
+                "a",
+                "--copies",
+                "1",
+                "--mode",
+                "Delete"
+            ]
+        )
+        .is_err());
+        // unknown tool
+        assert!(parse(&cli, &["delete_file"]).is_err());
+    }
+}
@@ -1,7 +1,11 @@
 #[cfg(feature = "auth")]
 use crate::auth::AuthenticationError;
-use crate::schema::{ParseProtocolVersionError, RpcError};
+use crate::schema::{
+    schema_utils::{CallToolError, UnknownTool},
+    CallToolResult, ContentBlock, ParseProtocolVersionError, RequestId, RpcError,
+};
 use rust_mcp_transport::error::TransportError;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::task::JoinError;
 
@@ -33,6 +37,32 @@ pub enum McpSdkError {
 
     #[error("Server error: {description}")]
     Internal { description: String },
+
+    #[error("Request {request_id} was cancelled")]
+    RequestCancelled { request_id: RequestId },
+
+    #[error("Request {request_id} timed out after {timeout:?}")]
+    Timeout {
+        request_id: RequestId,
+        timeout: Duration,
+    },
+
+    /// A tool call returned a result flagged with `is_error`, carrying the content of that result
+    #[error("Tool '{tool}' failed: {}", text_of(.content))]
+    ToolFailed {
+        tool: String,
+        content: Vec<ContentBlock>,
+    },
+}
+
+/// Joins the text blocks of a tool result, used to describe a failed tool call.
+fn text_of(content: &[ContentBlock]) -> String {
+    content
+        .iter()
+        .filter_map(|block| block.as_text_content().ok())
+        .map(|text_content| text_content.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 // Sub-enum for protocol-related errors
@@ -45,6 +75,30 @@ pub enum ProtocolErrorKind {
 }
 
 impl McpSdkError {
+    /// Converts the request timeout reported by the transport into [`McpSdkError::Timeout`],
+    /// any other error is returned unchanged.
+    #[cfg(feature = "client")]
+    pub(crate) fn into_request_timeout(self, request_id: RequestId) -> Self {
+        match self {
+            McpSdkError::Transport(TransportError::Sdk(error))
+                if error.code
+                    == i64::from(crate::schema::schema_utils::SdkErrorCodes::REQUEST_TIMEOUT) =>
+            {
+                let timeout_ms = error
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("timeout"))
+                    .and_then(|timeout| timeout.as_u64())
+                    .unwrap_or_default();
+                McpSdkError::Timeout {
+                    request_id,
+                    timeout: Duration::from_millis(timeout_ms),
+                }
+            }
+            error => error,
+        }
+    }
+
     /// Returns the RPC error message if the error is of type `McpSdkError::RpcError`.
     pub fn rpc_error_message(&self) -> Option<&String> {
         if let McpSdkError::RpcError(rpc_error) = self {
@@ -53,3 +107,169 @@ impl McpSdkError {
         None
     }
 }
+
+/// Error type for tool handlers that keeps protocol errors apart from tool execution failures.
+///
+/// The MCP specification reports these two kinds of failures differently:
+/// - Problems with the request itself, such as an unknown tool or arguments that fail validation,
+///   are **protocol errors** and are returned to the client as a JSON-RPC error.
+/// - Failures that happen while the tool runs (an API call failed, a file was not found, ...)
+///   are **tool execution errors**. They are returned as a `CallToolResult` with `is_error: true`,
+///   so the model can see the failure and react to it.
+///
+/// A `ToolError` converts into a `CallToolError`, so it can be returned with `?` from
+/// `ServerHandler::handle_call_tool_request()`. The server runtime recognizes it and sends
+/// [`ToolError::Protocol`] as a JSON-RPC error, while [`ToolError::Execution`] becomes an error result.
+///
+/// # Example
+/// ```ignore
+/// async fn handle_call_tool_request(
+///     &self,
+///     params: CallToolRequestParams,
+///     runtime: Arc<dyn McpServer>,
+/// ) -> Result<CallToolResult, CallToolError> {
+///     let Some(args) = params.arguments else {
+///         return Err(ToolError::invalid_params("missing arguments").into());
+///     };
+///     let content = std::fs::read_to_string(path_from(args)).map_err(ToolError::execution)?;
+///     Ok(CallToolResult::text_content(vec![content.into()]))
+/// }
+/// ```
+#[derive(Debug, Error)]
+pub enum ToolError {
+    /// The request is invalid, returned to the client as a JSON-RPC error.
+    #[error("{0}")]
+    Protocol(RpcError),
+    /// The tool ran but failed, returned as a `CallToolResult` with `is_error: true`.
+    #[error("{0}")]
+    Execution(CallToolError),
+}
+
+impl ToolError {
+    /// Creates a protocol error for arguments that are missing or fail validation.
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::Protocol(RpcError::invalid_params().with_message(message.into()))
+    }
+
+    /// Creates a protocol error for a tool that does not exist, as the specification requires:
+    /// an `invalid_params` error naming the tool in its message, with `{"tool": name}` as data.
+    pub fn unknown_tool(tool_name: impl AsRef<str>) -> Self {
+        let tool_name = tool_name.as_ref();
+        Self::Protocol(
+            RpcError::invalid_params()
+                .with_message(format!("Unknown tool: {tool_name}"))
+                .with_data(Some(serde_json::json!({ "tool": tool_name }))),
+        )
+    }
+
+    /// Creates a tool execution error from any error raised while the tool was running.
+    pub fn execution<E: std::error::Error + 'static>(err: E) -> Self {
+        Self::Execution(CallToolError::new(err))
+    }
+
+    /// Creates a tool execution error from a message.
+    pub fn execution_message(message: impl Into<String>) -> Self {
+        Self::Execution(CallToolError::from_message(message))
+    }
+
+    /// Returns true if this is a protocol error.
+    pub fn is_protocol_error(&self) -> bool {
+        matches!(self, ToolError::Protocol(_))
+    }
+
+    /// Converts the error into what should be sent back for a `tools/call` request:
+    /// `Err(RpcError)` for protocol errors, or `Ok(CallToolResult)` flagged with `is_error` for execution errors.
+    pub fn into_call_tool_result(self) -> Result<CallToolResult, RpcError> {
+        match self {
+            ToolError::Protocol(rpc_error) => Err(rpc_error),
+            ToolError::Execution(call_tool_error) => Ok(call_tool_error.into()),
+        }
+    }
+}
+
+impl From<RpcError> for ToolError {
+    fn from(value: RpcError) -> Self {
+        Self::Protocol(value)
+    }
+}
+
+impl From<CallToolError> for ToolError {
+    /// Recovers a `ToolError` that was previously converted into a `CallToolError`.
+    /// `CallToolError::unknown_tool()` becomes [`ToolError::unknown_tool`], any other
+    /// `CallToolError` is treated as a tool execution error.
+    fn from(value: CallToolError) -> Self {
+        let err = match value.0.downcast::<ToolError>() {
+            Ok(tool_error) => return *tool_error,
+            Err(err) => err,
+        };
+        match err.downcast::<UnknownTool>() {
+            Ok(unknown_tool) => {
+                // some handlers pass the whole message instead of the tool name
+                let tool_name = unknown_tool.0.strip_prefix("Unknown tool: ");
+                Self::unknown_tool(tool_name.unwrap_or(&unknown_tool.0))
+            }
+            Err(err) => Self::Execution(CallToolError(err)),
+        }
+    }
+}
+
+impl From<ToolError> for CallToolError {
+    fn from(value: ToolError) -> Self {
+        match value {
+            ToolError::Execution(call_tool_error) => call_tool_error,
+            protocol_error => CallToolError::new(protocol_error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_error_survives_call_tool_error_round_trip() {
+        let call_tool_error: CallToolError = ToolError::invalid_params("'path' is required").into();
+        let tool_error = ToolError::from(call_tool_error);
+        assert!(tool_error.is_protocol_error());
+
+        let rpc_error = tool_error.into_call_tool_result().unwrap_err();
+        assert_eq!(rpc_error.code, RpcError::invalid_params().code);
+        assert_eq!(rpc_error.message, "'path' is required");
+    }
+
+    #[test]
+    fn execution_error_becomes_error_result() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+        let call_tool_error: CallToolError = ToolError::execution(io_error).into();
+
+        let tool_error = ToolError::from(call_tool_error);
+        assert!(!tool_error.is_protocol_error());
+
+        let result = tool_error.into_call_tool_result().unwrap();
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn unknown_tool_is_a_protocol_error_naming_the_tool() {
+        let tool_error = ToolError::from(CallToolError::unknown_tool("delete_file"));
+        assert!(tool_error.is_protocol_error());
+
+        let rpc_error = tool_error.into_call_tool_result().unwrap_err();
+        assert_eq!(rpc_error.code, RpcError::invalid_params().code);
+        assert_eq!(rpc_error.message, "Unknown tool: delete_file");
+        assert_eq!(
+            rpc_error.data,
+            Some(serde_json::json!({ "tool": "delete_file" }))
+        );
+
+        let tool_error = ToolError::from(CallToolError::unknown_tool("Unknown tool: delete_file"));
+        let rpc_error = tool_error.into_call_tool_result().unwrap_err();
+        assert_eq!(rpc_error.message, "Unknown tool: delete_file");
+    }
+
+    #[test]
+    fn plain_call_tool_error_is_an_execution_error() {
+        let tool_error = ToolError::from(CallToolError::from_message("boom"));
+        assert!(matches!(tool_error, ToolError::Execution(_)));
+    }
+}
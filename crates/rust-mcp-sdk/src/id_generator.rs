@@ -1,5 +1,7 @@
+mod affinity_id_generator;
 mod fast_id_generator;
 mod uuid_generator;
 pub use crate::mcp_traits::IdGenerator;
+pub use affinity_id_generator::*;
 pub use fast_id_generator::*;
 pub use uuid_generator::*;
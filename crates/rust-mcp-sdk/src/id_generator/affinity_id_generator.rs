@@ -0,0 +1,93 @@
+use super::UuidGenerator;
+use crate::mcp_traits::IdGenerator;
+
+/// An [`IdGenerator`] implementation prefixing the generated ids with a routing key, for
+/// multi-node deployments where a load balancer routes every request of a session to the
+/// node that created it.
+///
+/// Ids have the form `{routing_key}.{id}`, e.g. `node-1.4f6d3c7e-...`, so a load balancer can
+/// route on the `mcp-session-id` header prefix, or on the `mcp-routing-key` header sent with
+/// the response that creates the session. The inner generator must produce ids without `.`,
+/// which is the case for [`UuidGenerator`] and [`FastIdGenerator`](super::FastIdGenerator).
+///
+/// # Example
+/// ```ignore
+/// let id_generator = AffinityIdGenerator::new(std::env::var("NODE_ID")?);
+/// let options = AxumServerOptions {
+///     session_id_generator: Some(Arc::new(id_generator)),
+///     ..Default::default()
+/// };
+/// ```
+pub struct AffinityIdGenerator {
+    routing_key: String,
+    inner: Box<dyn IdGenerator<String>>,
+}
+
+impl AffinityIdGenerator {
+    /// Creates a generator prefixing UUID v4 ids with `routing_key`.
+    ///
+    /// # Arguments
+    /// * `routing_key` - The key identifying this node, e.g. its hostname.
+    pub fn new(routing_key: impl Into<String>) -> Self {
+        Self::with_generator(routing_key, UuidGenerator)
+    }
+
+    /// Creates a generator prefixing the ids of `generator` with `routing_key`.
+    pub fn with_generator(
+        routing_key: impl Into<String>,
+        generator: impl IdGenerator<String> + 'static,
+    ) -> Self {
+        Self {
+            routing_key: routing_key.into(),
+            inner: Box::new(generator),
+        }
+    }
+}
+
+impl<T> IdGenerator<T> for AffinityIdGenerator
+where
+    T: From<String>,
+{
+    fn generate(&self) -> T {
+        let id: String = self.inner.generate();
+        T::from(format!("{}.{}", self.routing_key, id))
+    }
+
+    /// Returns the part of `id` before its last `.`, the key the id was generated with.
+    fn routing_key(&self, id: &str) -> Option<String> {
+        id.rsplit_once('.')
+            .map(|(routing_key, _)| routing_key)
+            .filter(|routing_key| !routing_key.is_empty())
+            .map(str::to_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id_generator::FastIdGenerator;
+
+    #[test]
+    fn ids_are_prefixed_with_the_routing_key() {
+        let generator = AffinityIdGenerator::new("node-1.eu");
+        let id: String = generator.generate();
+        assert!(id.starts_with("node-1.eu."));
+        assert_eq!(
+            IdGenerator::<String>::routing_key(&generator, &id).as_deref(),
+            Some("node-1.eu")
+        );
+
+        let generator =
+            AffinityIdGenerator::with_generator("node-2", FastIdGenerator::new(Some("sid_")));
+        let id: String = generator.generate();
+        assert_eq!(
+            IdGenerator::<String>::routing_key(&generator, &id).as_deref(),
+            Some("node-2")
+        );
+
+        assert_eq!(
+            IdGenerator::<String>::routing_key(&generator, "no-routing-key"),
+            None
+        );
+    }
+}
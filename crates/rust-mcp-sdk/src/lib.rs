@@ -1,3 +1,7 @@
+#[cfg(feature = "server")]
+pub mod audit;
+#[cfg(feature = "cli")]
+pub mod cli;
 pub mod error;
 mod mcp_handlers;
 
@@ -9,6 +13,8 @@ mod mcp_traits;
 #[cfg(feature = "server")]
 pub mod session_store;
 pub mod task_store;
+#[cfg(feature = "server")]
+pub mod testing;
 mod utils;
 
 #[cfg(feature = "client")]
@@ -39,7 +45,10 @@ pub mod mcp_client {
     pub use super::mcp_handlers::mcp_client_handler_core::ClientHandlerCore;
     pub use super::mcp_runtimes::client_runtime::mcp_client_runtime as client_runtime;
     pub use super::mcp_runtimes::client_runtime::mcp_client_runtime_core as client_runtime_core;
-    pub use super::mcp_runtimes::client_runtime::{ClientRuntime, McpClientOptions};
+    pub use super::mcp_runtimes::client_runtime::{
+        ClientRuntime, McpClientOptions, DEFAULT_MAX_CONCURRENT_SERVER_REQUESTS,
+    };
+    pub use super::mcp_runtimes::ShutdownGuard;
     pub use super::mcp_traits::{McpClientHandler, ToMcpClientHandler, ToMcpClientHandlerCore};
     pub use super::utils::ensure_server_protocole_compatibility;
 }
@@ -68,16 +77,29 @@ pub mod mcp_server {
     //!   handle each message based on its type and parameters.
     //!
     //! Refer to [examples/hello-world-mcp-server-stdio-core](https://github.com/rust-mcp-stack/rust-mcp-sdk/tree/main/examples/hello-world-mcp-server-stdio-core) for an example.
-    pub use super::mcp_handlers::mcp_server_handler::ServerHandler;
+    pub use super::mcp_handlers::mcp_server_handler::{ServerHandler, ToolBoxMetadata};
     pub use super::mcp_handlers::mcp_server_handler_core::ServerHandlerCore;
+    pub use super::mcp_handlers::prompt_registry::PromptRegistry;
+    pub use super::mcp_handlers::prompt_template::{
+        check_required_arguments, render_prompt_template, substitute_arguments, PromptArguments,
+    };
+    pub use super::mcp_handlers::tool_registry::ToolRegistry;
 
     pub use super::mcp_runtimes::server_runtime::mcp_server_runtime as server_runtime;
     pub use super::mcp_runtimes::server_runtime::mcp_server_runtime_core as server_runtime_core;
-    pub use super::mcp_runtimes::server_runtime::{McpServerOptions, ServerRuntime};
+    pub use super::mcp_runtimes::server_runtime::{
+        export_openapi, server_manifest, tools_openapi_document, validate_capabilities,
+        CapabilityIssue, CapabilityIssueSeverity, HandlerExecution, HandlerRegistrations,
+        McpServerOptions, RequestContext, ServerManifest, ServerRuntime,
+    };
+    #[cfg(all(unix, feature = "unix-socket"))]
+    pub use super::mcp_runtimes::server_runtime::{UnixSocketServer, UnixSocketServerOptions};
+    pub use super::mcp_runtimes::ShutdownGuard;
 
     pub use super::utils::enforce_compatible_protocol_version;
     #[cfg(feature = "auth")]
     pub use super::utils::join_url;
+    pub use super::utils::validate_custom_method;
 
     pub use super::mcp_traits::{McpServerHandler, ToMcpServerHandler, ToMcpServerHandlerCore};
 }
@@ -89,6 +111,7 @@ pub use rust_mcp_transport::*;
 
 #[cfg(feature = "macros")]
 pub mod macros {
+    pub use super::utils::sample_arguments;
     pub use rust_mcp_macros::*;
 }
 
@@ -6,3 +6,9 @@ pub mod mcp_client_handler_core;
 pub mod mcp_server_handler;
 #[cfg(feature = "server")]
 pub mod mcp_server_handler_core;
+#[cfg(feature = "server")]
+pub mod prompt_registry;
+#[cfg(feature = "server")]
+pub mod prompt_template;
+#[cfg(feature = "server")]
+pub mod tool_registry;
@@ -1,15 +1,35 @@
 use crate::{
-    mcp_server::server_runtime::ServerRuntimeInternalHandler,
+    audit::write_only_fields, error::ToolError, mcp_traits::McpServer,
+    utils::enforce_compatible_protocol_version,
+};
+use crate::{
+    mcp_server::{
+        server_runtime::ServerRuntimeInternalHandler, HandlerRegistrations, PromptRegistry,
+        ToolRegistry,
+    },
     mcp_traits::{McpServerHandler, ToMcpServerHandler},
     schema::{
         schema_utils::{CallToolError, CustomNotification, CustomRequest},
         *,
     },
-    task_store::ServerTaskCreator,
+    task_store::{task_payload_result, ServerTaskCreator},
 };
-use crate::{mcp_traits::McpServer, utils::enforce_compatible_protocol_version};
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// The per-tool settings of a `tool_box!` enum, returned by its generated `metadata()` function.
+///
+/// See [`ServerHandler::tool_box()`].
+#[derive(Debug, Clone, Copy)]
+pub struct ToolBoxMetadata {
+    /// Whether the named tool is annotated with `#[mcp_tool(blocking = true)]`.
+    pub is_blocking_tool: fn(&str) -> bool,
+    /// The timeout set on the named tool with `#[mcp_tool(timeout_ms = ...)]`.
+    pub tool_timeout: fn(&str) -> Option<Duration>,
+    /// The arguments of the named tool marked with `#[json_schema(sensitive)]`.
+    pub tool_sensitive_fields: fn(&str) -> Vec<String>,
+}
 
 /// The `ServerHandler` trait defines how a server handles Model Context Protocol (MCP) operations.
 /// It provides default implementations for request , notification and error handlers, and must be extended or
@@ -24,6 +44,181 @@ pub trait ServerHandler: Send + Sync + 'static {
     /// The default implementation does nothing.
     async fn on_initialized(&self, runtime: Arc<dyn McpServer>) {}
 
+    /// Invoked after the server closes the session, when the client deletes it or the HTTP
+    /// server closes all sessions through `close_all_sessions()`.
+    ///
+    /// The runtime's transport is already shut down, so no message can be sent to the client.
+    /// The default implementation does nothing.
+    async fn on_session_closed(&self, runtime: Arc<dyn McpServer>) {}
+
+    /// Returns the per-tool settings of the `tool_box!` enum the handler serves, set with
+    /// `#[mcp_tool(blocking = true)]`, `#[mcp_tool(timeout_ms = ...)]` and `#[json_schema(sensitive)]`.
+    ///
+    /// The default implementations of `is_blocking_tool()`, `tool_timeout()` and `tool_sensitive_fields()`
+    /// look tools up in it. The default implementation returns `None`.
+    ///
+    /// ```ignore
+    /// fn tool_box(&self) -> Option<ToolBoxMetadata> {
+    ///     Some(MyTools::metadata())
+    /// }
+    /// ```
+    fn tool_box(&self) -> Option<ToolBoxMetadata> {
+        None
+    }
+
+    /// Indicates whether the tool named `tool_name` calls synchronous libraries or performs CPU-heavy work.
+    ///
+    /// Calls to tools reported as blocking are executed on tokio's blocking thread pool (`spawn_blocking`),
+    /// so they don't stall async IO for other requests.
+    /// The default implementation returns the tools annotated with `#[mcp_tool(blocking = true)]` in
+    /// [`tool_box()`](Self::tool_box), and `false` for every other tool.
+    fn is_blocking_tool(&self, tool_name: &str) -> bool {
+        self.tool_box()
+            .is_some_and(|tool_box| (tool_box.is_blocking_tool)(tool_name))
+    }
+
+    /// Returns the prompts served by the default `handle_list_prompts_request()` and
+    /// `handle_get_prompt_request()`.
+    ///
+    /// The default implementation returns `None`, and both requests fail with a method not found error.
+    ///
+    /// ```ignore
+    /// fn prompt_registry(&self) -> Option<&PromptRegistry> {
+    ///     Some(&self.prompts)
+    /// }
+    /// ```
+    fn prompt_registry(&self) -> Option<&PromptRegistry> {
+        None
+    }
+
+    /// Returns the tools served by the default `handle_list_tools_request()` and
+    /// `handle_call_tool_request()`, which can be registered and unregistered while the server is running.
+    ///
+    /// The default implementation returns `None`, listing tools fails with a method not found error
+    /// and calling them with an unknown tool error.
+    ///
+    /// ```ignore
+    /// fn tool_registry(&self) -> Option<&ToolRegistry> {
+    ///     Some(&self.tools)
+    /// }
+    /// ```
+    fn tool_registry(&self) -> Option<&ToolRegistry> {
+        None
+    }
+
+    /// Describes what the overridden `handle_*_request()` methods serve: the methods they implement,
+    /// e.g. `tools/list`, and the tools, resources, resource templates and prompts they list when
+    /// these are known upfront.
+    ///
+    /// Used by `validate_capabilities()`, `export_openapi()` and `server_manifest()`, which never
+    /// invoke the handler.
+    /// Methods, tools and prompts served by `tool_registry()` and `prompt_registry()` are added by the runtime.
+    /// The default implementation reports nothing.
+    ///
+    /// ```ignore
+    /// fn registrations(&self) -> HandlerRegistrations {
+    ///     HandlerRegistrations {
+    ///         methods: vec!["tools/list", "tools/call"],
+    ///         tools: Some(MyTools::tools()),
+    ///         ..Default::default()
+    ///     }
+    /// }
+    /// ```
+    fn registrations(&self) -> HandlerRegistrations {
+        HandlerRegistrations::default()
+    }
+
+    /// Returns how long a call to the tool named `tool_name` may run.
+    ///
+    /// Once the timeout elapses, the runtime cancels the tool's handler and responds to the call
+    /// with a request timeout error. The default implementation returns the timeout set with
+    /// `#[mcp_tool(timeout_ms = ...)]` in [`tool_box()`](Self::tool_box), and `None` for every other tool,
+    /// which then runs without a timeout.
+    ///
+    /// A [blocking tool](Self::is_blocking_tool) can not be interrupted: the client gets the timeout
+    /// error on time, while the blocking work keeps running on its thread until it returns.
+    ///
+    /// Overriding it can add a server-wide default for the tools without a timeout of their own:
+    ///
+    /// ```ignore
+    /// fn tool_timeout(&self, tool_name: &str) -> Option<Duration> {
+    ///     MyTools::tool_timeout(tool_name).or(Some(Duration::from_secs(30)))
+    /// }
+    /// ```
+    fn tool_timeout(&self, tool_name: &str) -> Option<Duration> {
+        self.tool_box()
+            .and_then(|tool_box| (tool_box.tool_timeout)(tool_name))
+    }
+
+    /// Returns the dot-separated paths of the arguments of the tool named `tool_name` that hold
+    /// secrets, e.g. `api_key` or `credentials.token`.
+    ///
+    /// The runtime replaces their values in audit records, and wherever they appear in the error of
+    /// a failed call, with a redaction marker. The default implementation returns the fields marked
+    /// with `#[json_schema(sensitive)]` in [`tool_box()`](Self::tool_box), and the fields the input
+    /// schema of a tool served by [`tool_registry()`](Self::tool_registry) marks `"writeOnly": true`.
+    fn tool_sensitive_fields(&self, tool_name: &str) -> Vec<String> {
+        let mut fields = self
+            .tool_box()
+            .map(|tool_box| (tool_box.tool_sensitive_fields)(tool_name))
+            .unwrap_or_default();
+        if let Some(tool) = self
+            .tool_registry()
+            .and_then(|tool_registry| tool_registry.tool(tool_name))
+        {
+            fields.extend(write_only_fields(&tool));
+        }
+        fields
+    }
+
+    /// Decides whether the client identified by `client_info` may connect, e.g. to refuse client
+    /// versions known to be incompatible with the server.
+    ///
+    /// Called by the default `handle_initialize_request()` before anything else. A returned error is sent
+    /// to the client as the response to its `initialize` request. Over HTTP, the client is rejected
+    /// before a session is created, with a `403 Forbidden` response carrying that error response.
+    /// The default implementation accepts every client.
+    ///
+    /// ```ignore
+    /// fn accept_client(&self, client_info: &Implementation) -> std::result::Result<(), RpcError> {
+    ///     if client_info.name == "buggy-client" && client_info.version.starts_with("0.") {
+    ///         return Err(RpcError::invalid_request()
+    ///             .with_message("buggy-client 0.x is not supported, please upgrade.".to_string()));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn accept_client(&self, client_info: &Implementation) -> std::result::Result<(), RpcError> {
+        Ok(())
+    }
+
+    /// Tailors the result returned to the client in response to its `initialize` request,
+    /// e.g. to provide different instructions or advertise a subset of capabilities depending on `params.client_info`.
+    ///
+    /// `base` is the server's details, with the protocol version already negotiated with the client.
+    /// Called by the default `handle_initialize_request()` before replying.
+    /// The default implementation returns `base` unchanged.
+    ///
+    /// ```ignore
+    /// fn customize_initialize(
+    ///     &self,
+    ///     params: &InitializeRequestParams,
+    ///     mut base: InitializeResult,
+    /// ) -> InitializeResult {
+    ///     if params.client_info.name == "legacy-client" {
+    ///         base.instructions = Some("Only the `search` tool is supported.".to_string());
+    ///     }
+    ///     base
+    /// }
+    /// ```
+    fn customize_initialize(
+        &self,
+        params: &InitializeRequestParams,
+        base: InitializeResult,
+    ) -> InitializeResult {
+        base
+    }
+
     /// Handles the InitializeRequest from a client.
     ///
     /// # Arguments
@@ -38,6 +233,8 @@ pub trait ServerHandler: Send + Sync + 'static {
         params: InitializeRequestParams,
         runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<InitializeResult, RpcError> {
+        self.accept_client(&params.client_info)?;
+
         let mut server_info = runtime.server_info().to_owned();
         // Provide compatibility for clients using older MCP protocol versions.
 
@@ -56,6 +253,8 @@ pub trait ServerHandler: Send + Sync + 'static {
             server_info.protocol_version = updated_protocol_version;
         }
 
+        let server_info = self.customize_initialize(&params, server_info);
+
         runtime
             .set_client_details(params)
             .await
@@ -154,13 +353,16 @@ pub trait ServerHandler: Send + Sync + 'static {
 
     /// Handles requests to list available prompts.
     ///
-    /// Default implementation returns method not found error.
+    /// Default implementation lists the prompts of [`Self::prompt_registry()`], or returns method not found error if there is none.
     /// Customize this function in your specific handler to implement behavior tailored to your MCP server's capabilities and requirements.
     async fn handle_list_prompts_request(
         &self,
         params: Option<PaginatedRequestParams>,
         runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ListPromptsResult, RpcError> {
+        if let Some(registry) = self.prompt_registry() {
+            return Ok(registry.list_prompts());
+        }
         Err(RpcError::method_not_found().with_message(format!(
             "No handler is implemented for '{}'.",
             ListPromptsRequest::method_value(),
@@ -169,13 +371,16 @@ pub trait ServerHandler: Send + Sync + 'static {
 
     /// Handles requests to get a specific prompt.
     ///
-    /// Default implementation returns method not found error.
+    /// Default implementation renders the prompt from [`Self::prompt_registry()`], or returns method not found error if there is none.
     /// Customize this function in your specific handler to implement behavior tailored to your MCP server's capabilities and requirements.
     async fn handle_get_prompt_request(
         &self,
         params: GetPromptRequestParams,
         runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<GetPromptResult, RpcError> {
+        if let Some(registry) = self.prompt_registry() {
+            return registry.get_prompt(&params);
+        }
         Err(RpcError::method_not_found().with_message(format!(
             "No handler is implemented for '{}'.",
             GetPromptRequest::method_value(),
@@ -184,13 +389,18 @@ pub trait ServerHandler: Send + Sync + 'static {
 
     /// Handles requests to list available tools.
     ///
-    /// Default implementation returns method not found error.
+    /// Default implementation lists the tools of [`Self::tool_registry()`], subscribing the session to
+    /// its tool list changes, or returns method not found error if there is none.
     /// Customize this function in your specific handler to implement behavior tailored to your MCP server's capabilities and requirements.
     async fn handle_list_tools_request(
         &self,
         params: Option<PaginatedRequestParams>,
         runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ListToolsResult, RpcError> {
+        if let Some(registry) = self.tool_registry() {
+            registry.subscribe(&runtime);
+            return Ok(registry.list_tools());
+        }
         Err(RpcError::method_not_found().with_message(format!(
             "No handler is implemented for '{}'.",
             ListToolsRequest::method_value(),
@@ -217,14 +427,17 @@ pub trait ServerHandler: Send + Sync + 'static {
 
     /// Handles requests to call a specific tool.
     ///
-    /// Default implementation returns an unknown tool error.
+    /// Default implementation calls the tool of [`Self::tool_registry()`], or returns an unknown tool error if there is none.
     /// Customize this function in your specific handler to implement behavior tailored to your MCP server's capabilities and requirements.
     async fn handle_call_tool_request(
         &self,
         params: CallToolRequestParams,
         runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<CallToolResult, CallToolError> {
-        Ok(CallToolError::unknown_tool(format!("Unknown tool: {}", params.name)).into())
+        if let Some(registry) = self.tool_registry() {
+            return registry.call_tool(params, runtime).await;
+        }
+        Err(ToolError::unknown_tool(&params.name).into())
     }
 
     /// Handles requests to enable or adjust logging level.
@@ -270,15 +483,31 @@ pub trait ServerHandler: Send + Sync + 'static {
     }
 
     /// Handles a request to retrieve the result of a completed task.
+    ///
+    /// Default implementation returns the result stored in the runtime's task store, with the metadata
+    /// stored along with it by [`TaskStore::store_task_result_with_meta()`](crate::task_store::TaskStore::store_task_result_with_meta)
+    /// added to its `_meta`. Without a task store it returns method not found error.
     async fn handle_get_task_payload_request(
         &self,
         params: GetTaskPayloadParams,
         runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<GetTaskPayloadResult, RpcError> {
-        Err(RpcError::method_not_found().with_message(format!(
-            "No handler is implemented for '{}'.",
-            GetTaskPayloadRequest::method_value(),
-        )))
+        let Some(task_store) = runtime.task_store() else {
+            return Err(RpcError::method_not_found().with_message(format!(
+                "No handler is implemented for '{}'.",
+                GetTaskPayloadRequest::method_value(),
+            )));
+        };
+        let Some((result, result_meta)) = task_store
+            .get_task_result_with_meta(&params.task_id, runtime.session_id())
+            .await
+        else {
+            return Err(RpcError::invalid_params().with_message(format!(
+                "No result is available for task '{}'.",
+                params.task_id
+            )));
+        };
+        task_payload_result(&result, result_meta)
     }
 
     /// Handles a request to cancel a task.
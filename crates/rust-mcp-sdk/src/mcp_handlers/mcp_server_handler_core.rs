@@ -1,4 +1,5 @@
 use crate::mcp_server::server_runtime_core::RuntimeCoreInternalHandler;
+use crate::mcp_server::HandlerRegistrations;
 use crate::mcp_traits::McpServer;
 use crate::mcp_traits::{McpServerHandler, ToMcpServerHandlerCore};
 use crate::schema::*;
@@ -17,6 +18,22 @@ pub trait ServerHandlerCore: Send + Sync + 'static {
     /// The default implementation does nothing.
     async fn on_initialized(&self, _runtime: Arc<dyn McpServer>) {}
 
+    /// Invoked after the server closes the session, when the client deletes it or the HTTP
+    /// server closes all sessions through `close_all_sessions()`.
+    ///
+    /// The runtime's transport is already shut down, so no message can be sent to the client.
+    /// The default implementation does nothing.
+    async fn on_session_closed(&self, _runtime: Arc<dyn McpServer>) {}
+
+    /// Describes what `handle_request()` serves: the methods it implements, e.g. `tools/list`,
+    /// and the tools and prompts it lists when these are known upfront.
+    ///
+    /// Used by `validate_capabilities()` and `export_openapi()`, which never invoke the handler.
+    /// The default implementation reports nothing.
+    fn registrations(&self) -> HandlerRegistrations {
+        HandlerRegistrations::default()
+    }
+
     /// Asynchronously handles an incoming request from the client.
     ///
     /// # Parameters
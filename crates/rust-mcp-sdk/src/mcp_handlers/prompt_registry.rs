@@ -0,0 +1,143 @@
+use super::prompt_template::{check_required_arguments, substitute_arguments, PromptArguments};
+use crate::schema::{
+    ContentBlock, GetPromptRequestParams, GetPromptResult, ListPromptsResult, Prompt,
+    PromptMessage, Role, RpcError,
+};
+
+type RenderPromptFn =
+    Box<dyn Fn(&PromptArguments) -> Result<Vec<PromptMessage>, RpcError> + Send + Sync>;
+
+struct RegisteredPrompt {
+    prompt: Prompt,
+    render: RenderPromptFn,
+}
+
+/// A set of prompts with the functions rendering their messages, answering `prompts/list`
+/// and `prompts/get` requests.
+///
+/// Return it from [`ServerHandler::prompt_registry()`](crate::mcp_server::ServerHandler::prompt_registry)
+/// and the default `handle_list_prompts_request()` and `handle_get_prompt_request()` serve the
+/// registered prompts, so no manual dispatch is needed.
+///
+/// Before a prompt is rendered, the request is checked to provide every argument the prompt
+/// declares as required.
+///
+/// # Example
+/// ```ignore
+/// let code_review = Prompt {
+///     name: "code_review".into(),
+///     title: None,
+///     description: Some("Asks for a review of a code snippet".into()),
+///     arguments: vec![PromptArgument {
+///         name: "code".into(),
+///         title: None,
+///         description: None,
+///         required: Some(true),
+///     }],
+///     icons: vec![],
+///     meta: None,
+/// };
+///
+/// let prompts = PromptRegistry::new()
+///     .with_template(
+///         code_review,
+///         vec![(Role::User, "Please review this code:\n{{code}}")],
+///     )
+///     .with_prompt(greeting_prompt(), |arguments| {
+///         let name = arguments.get("name").map(String::as_str).unwrap_or("there");
+///         Ok(vec![PromptMessage {
+///             role: Role::User,
+///             content: ContentBlock::text_content(format!("Say hello to {name}")),
+///         }])
+///     });
+/// ```
+#[derive(Default)]
+pub struct PromptRegistry {
+    prompts: Vec<RegisteredPrompt>,
+}
+
+impl PromptRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `prompt`, whose messages are built by `render` from the request arguments.
+    /// A prompt registered with the name of an existing one replaces it.
+    pub fn with_prompt<F>(mut self, prompt: Prompt, render: F) -> Self
+    where
+        F: Fn(&PromptArguments) -> Result<Vec<PromptMessage>, RpcError> + Send + Sync + 'static,
+    {
+        let registered = RegisteredPrompt {
+            prompt,
+            render: Box::new(render),
+        };
+        match self
+            .prompts
+            .iter_mut()
+            .find(|p| p.prompt.name == registered.prompt.name)
+        {
+            Some(existing) => *existing = registered,
+            None => self.prompts.push(registered),
+        }
+        self
+    }
+
+    /// Registers `prompt` with text messages in which every `{{argument}}` placeholder is
+    /// replaced by the value of that argument, see [`substitute_arguments`].
+    ///
+    /// Required arguments are checked once by [`PromptRegistry::get_prompt`] before rendering.
+    pub fn with_template<S>(self, prompt: Prompt, messages: Vec<(Role, S)>) -> Self
+    where
+        S: Into<String>,
+    {
+        let messages: Vec<(Role, String)> = messages
+            .into_iter()
+            .map(|(role, text)| (role, text.into()))
+            .collect();
+        self.with_prompt(prompt, move |arguments| {
+            Ok(messages
+                .iter()
+                .map(|(role, text)| PromptMessage {
+                    role: *role,
+                    content: ContentBlock::text_content(substitute_arguments(text, arguments)),
+                })
+                .collect())
+        })
+    }
+
+    /// Returns the registered prompts, in registration order.
+    pub fn prompts(&self) -> Vec<Prompt> {
+        self.prompts.iter().map(|p| p.prompt.clone()).collect()
+    }
+
+    /// Returns the result of a `prompts/list` request, listing every registered prompt.
+    pub fn list_prompts(&self) -> ListPromptsResult {
+        ListPromptsResult {
+            prompts: self.prompts(),
+            meta: None,
+            next_cursor: None,
+        }
+    }
+
+    /// Returns the result of a `prompts/get` request.
+    ///
+    /// Fails with an `invalid_params` error if the prompt is unknown or a required argument is missing.
+    pub fn get_prompt(&self, params: &GetPromptRequestParams) -> Result<GetPromptResult, RpcError> {
+        let registered = self
+            .prompts
+            .iter()
+            .find(|p| p.prompt.name == params.name)
+            .ok_or_else(|| {
+                RpcError::invalid_params().with_message(format!("Unknown prompt '{}'", params.name))
+            })?;
+
+        let arguments = params.arguments.clone().unwrap_or_default();
+        check_required_arguments(&registered.prompt, &arguments)?;
+
+        Ok(GetPromptResult {
+            description: registered.prompt.description.clone(),
+            messages: (registered.render)(&arguments)?,
+            meta: None,
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use crate::schema::{Prompt, RpcError};
+use std::collections::BTreeMap;
+
+/// Arguments of a `prompts/get` request, by argument name.
+pub type PromptArguments = BTreeMap<String, String>;
+
+/// Checks that `arguments` provides every argument `prompt` declares as required.
+///
+/// Fails with an `invalid_params` error naming the first missing argument.
+pub fn check_required_arguments(
+    prompt: &Prompt,
+    arguments: &PromptArguments,
+) -> Result<(), RpcError> {
+    match prompt
+        .arguments
+        .iter()
+        .find(|arg| arg.required == Some(true) && !arguments.contains_key(&arg.name))
+    {
+        Some(missing) => Err(RpcError::invalid_params().with_message(format!(
+            "Missing required argument '{}' for prompt '{}'",
+            missing.name, prompt.name
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Renders `template` for `prompt`, replacing every `{{name}}` placeholder with the value of the argument `name`.
+///
+/// Fails with an `invalid_params` error if a required argument of `prompt` is missing, see
+/// [`check_required_arguments`]. Placeholders of optional arguments that were not provided are
+/// replaced with an empty string.
+pub fn render_prompt_template(
+    template: &str,
+    prompt: &Prompt,
+    arguments: &PromptArguments,
+) -> Result<String, RpcError> {
+    check_required_arguments(prompt, arguments)?;
+    Ok(substitute_arguments(template, arguments))
+}
+
+/// Replaces every `{{name}}` placeholder in `template` with the value of the argument `name`.
+///
+/// A placeholder is a name made of ASCII letters, digits, `_`, `-` and `.` between double braces,
+/// surrounding spaces are allowed. Anything else, including braces around other text, is left as is,
+/// and values are inserted verbatim without being substituted again.
+/// Placeholders of arguments that were not provided are replaced with an empty string.
+pub fn substitute_arguments(template: &str, arguments: &PromptArguments) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        let name = after_open[..end].trim();
+        if is_placeholder_name(name) {
+            output.push_str(&rest[..start]);
+            if let Some(value) = arguments.get(name) {
+                output.push_str(value);
+            }
+            rest = &after_open[end + 2..];
+        } else {
+            // not a placeholder, keep the first brace, the second may open one (e.g. `{{{name}}}`)
+            output.push_str(&rest[..start + 1]);
+            rest = &rest[start + 1..];
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
@@ -0,0 +1,204 @@
+use crate::error::ToolError;
+use crate::mcp_traits::McpServer;
+use crate::schema::{
+    schema_utils::CallToolError, CallToolRequestParams, CallToolResult, ListToolsResult, Tool,
+};
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError, RwLock, Weak};
+
+type CallToolFn = Arc<
+    dyn Fn(CallToolRequestParams, Arc<dyn McpServer>) -> BoxFuture<'static, CallToolOutcome>
+        + Send
+        + Sync,
+>;
+
+type CallToolOutcome = Result<CallToolResult, CallToolError>;
+
+struct RegisteredTool {
+    tool: Tool,
+    call: CallToolFn,
+}
+
+/// A set of tools with the functions handling their calls, answering `tools/list` and
+/// `tools/call` requests, that can change while the server is running.
+///
+/// Return it from [`ServerHandler::tool_registry()`](crate::mcp_server::ServerHandler::tool_registry)
+/// and the default `handle_list_tools_request()` and `handle_call_tool_request()` serve the
+/// registered tools, so no manual dispatch is needed.
+///
+/// Tools registered or unregistered at runtime through [`Self::register`] and [`Self::unregister`]
+/// are announced with a `ToolListChangedNotification` to every session that listed the tools,
+/// so the server should advertise `tools.list_changed` in its capabilities.
+///
+/// # Example
+/// ```ignore
+/// struct MyServerHandler {
+///     tools: Arc<ToolRegistry>,
+/// }
+///
+/// impl ServerHandler for MyServerHandler {
+///     fn tool_registry(&self) -> Option<&ToolRegistry> {
+///         Some(&self.tools)
+///     }
+/// }
+///
+/// let tools = Arc::new(ToolRegistry::new().with_tool(greeting_tool(), |params, _runtime| async move {
+///     Ok(CallToolResult::text_content(vec!["Hello!".into()]))
+/// }));
+///
+/// // later, e.g. once a plugin is loaded
+/// tools.register(plugin_tool(), move |params, runtime| plugin.call(params, runtime)).await;
+/// ```
+#[derive(Default)]
+pub struct ToolRegistry {
+    // the locks are only held by the registry's own code, which leaves the data consistent,
+    // so a poisoned lock is recovered rather than failing every later request
+    tools: RwLock<Vec<RegisteredTool>>,
+    listeners: Mutex<Vec<Weak<dyn McpServer>>>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `tool`, whose calls are handled by `handler`, without notifying any session.
+    /// Meant for building the registry before the server starts.
+    /// A tool registered with the name of an existing one replaces it.
+    pub fn with_tool<F, Fut>(self, tool: Tool, handler: F) -> Self
+    where
+        F: Fn(CallToolRequestParams, Arc<dyn McpServer>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CallToolOutcome> + Send + 'static,
+    {
+        self.insert(tool, handler);
+        self
+    }
+
+    /// Registers `tool`, whose calls are handled by `handler`, then notifies the sessions that
+    /// listed the tools that the tool list changed.
+    /// A tool registered with the name of an existing one replaces it.
+    pub async fn register<F, Fut>(&self, tool: Tool, handler: F)
+    where
+        F: Fn(CallToolRequestParams, Arc<dyn McpServer>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CallToolOutcome> + Send + 'static,
+    {
+        self.insert(tool, handler);
+        self.notify_tool_list_changed().await;
+    }
+
+    /// Removes the tool named `name`, then notifies the sessions that listed the tools that the
+    /// tool list changed. Returns `false`, without notifying, if no such tool is registered.
+    pub async fn unregister(&self, name: &str) -> bool {
+        let removed = {
+            let mut tools = self.tools.write().unwrap_or_else(PoisonError::into_inner);
+            let count = tools.len();
+            tools.retain(|t| t.tool.name != name);
+            tools.len() != count
+        };
+        if removed {
+            self.notify_tool_list_changed().await;
+        }
+        removed
+    }
+
+    /// Returns the registered tools, in registration order.
+    pub fn tools(&self) -> Vec<Tool> {
+        self.tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|t| t.tool.clone())
+            .collect()
+    }
+
+    /// Returns the registered tool named `name`, if any.
+    pub fn tool(&self, name: &str) -> Option<Tool> {
+        self.tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|t| t.tool.name == name)
+            .map(|t| t.tool.clone())
+    }
+
+    /// Returns the result of a `tools/list` request, listing every registered tool.
+    pub fn list_tools(&self) -> ListToolsResult {
+        ListToolsResult {
+            tools: self.tools(),
+            meta: None,
+            next_cursor: None,
+        }
+    }
+
+    /// Handles a `tools/call` request by calling the handler of the requested tool.
+    ///
+    /// Fails with an unknown tool error if no tool named `params.name` is registered.
+    pub async fn call_tool(
+        &self,
+        params: CallToolRequestParams,
+        runtime: Arc<dyn McpServer>,
+    ) -> CallToolOutcome {
+        let call = self
+            .tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|t| t.tool.name == params.name)
+            .map(|t| t.call.clone());
+        match call {
+            Some(call) => call(params, runtime).await,
+            None => Err(ToolError::unknown_tool(&params.name).into()),
+        }
+    }
+
+    /// Adds the session of `runtime` to those notified when the tool list changes.
+    ///
+    /// Called by the default `handle_list_tools_request()`, sessions are dropped once their runtime is.
+    pub fn subscribe(&self, runtime: &Arc<dyn McpServer>) {
+        let mut listeners = self
+            .listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        listeners.retain(|listener| listener.strong_count() > 0);
+        let listener = Arc::downgrade(runtime);
+        if !listeners.iter().any(|l| l.ptr_eq(&listener)) {
+            listeners.push(listener);
+        }
+    }
+
+    fn insert<F, Fut>(&self, tool: Tool, handler: F)
+    where
+        F: Fn(CallToolRequestParams, Arc<dyn McpServer>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CallToolOutcome> + Send + 'static,
+    {
+        let registered = RegisteredTool {
+            tool,
+            call: Arc::new(move |params, runtime| Box::pin(handler(params, runtime))),
+        };
+        let mut tools = self.tools.write().unwrap_or_else(PoisonError::into_inner);
+        match tools
+            .iter_mut()
+            .find(|t| t.tool.name == registered.tool.name)
+        {
+            Some(existing) => *existing = registered,
+            None => tools.push(registered),
+        }
+    }
+
+    async fn notify_tool_list_changed(&self) {
+        let runtimes: Vec<_> = {
+            let mut listeners = self
+                .listeners
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            listeners.retain(|listener| listener.strong_count() > 0);
+            listeners.iter().filter_map(Weak::upgrade).collect()
+        };
+        for runtime in runtimes {
+            if let Err(err) = runtime.notify_tool_list_changed(None).await {
+                tracing::debug!("failed to notify a session of the tool list change: {err}");
+            }
+        }
+    }
+}
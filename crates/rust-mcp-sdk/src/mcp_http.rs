@@ -9,6 +9,7 @@ pub mod middleware;
 mod types;
 
 mod dns_rebinding;
+mod ip_filter;
 
 pub use app_state::*;
 pub use error::*;
@@ -21,4 +22,5 @@ pub use types::*;
 pub use dns_rebinding::*;
 pub use health_handler::*;
 pub use http;
+pub use ip_filter::*;
 pub use middleware::Middleware;
@@ -1,4 +1,10 @@
 #[cfg(feature = "server")]
+use crate::audit::AuditSink;
+#[cfg(feature = "server")]
+use crate::mcp_server::HandlerExecution;
+#[cfg(feature = "server")]
+use crate::mcp_traits::McpServer;
+#[cfg(feature = "server")]
 use crate::mcp_traits::McpServerHandler;
 #[cfg(feature = "server")]
 use crate::session_store::SessionStore;
@@ -6,9 +12,10 @@ use crate::task_store::{ClientTaskStore, ServerTaskStore};
 use crate::McpObserver;
 use crate::{id_generator::FastIdGenerator, mcp_traits::IdGenerator, schema::InitializeResult};
 use rust_mcp_schema::schema_utils::{ClientMessage, ServerMessage};
-use rust_mcp_transport::event_store::EventStore;
+use rust_mcp_transport::event_store::{EventStore, EventStoreErrorPolicy};
 use rust_mcp_transport::{SessionId, TransportOptions};
 use std::{sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
 
 /// Application state struct for the Hyper ser
 ///
@@ -26,10 +33,62 @@ pub struct McpAppState {
     pub ping_interval: Duration,
     pub transport_options: Arc<TransportOptions>,
     pub enable_json_response: bool,
+    /// Serve buffered JSON responses to clients that only accept `application/json`,
+    /// instead of rejecting their POST requests with `406 Not Acceptable`, and to clients
+    /// that accept both but prefer `application/json` through `q` values
+    pub json_response_fallback: bool,
+    /// Limits the number of long-lived SSE streams (standalone GET streams and HTTP+SSE connections)
+    /// held open at once, across all sessions. Each open stream holds a permit, `None` means unlimited.
+    pub sse_stream_permits: Option<Arc<Semaphore>>,
     /// Event store for resumability support
     /// If provided, resumability will be enabled, allowing clients to reconnect and resume messages
     pub event_store: Option<Arc<dyn EventStore>>,
+    /// What a resumable stream does when the event store fails to store one of its messages
+    pub event_store_error_policy: EventStoreErrorPolicy,
     pub task_store: Option<Arc<ServerTaskStore>>,
     pub client_task_store: Option<Arc<ClientTaskStore>>,
     pub message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    /// Receives an audit record for every tool invocation, shared by all sessions
+    #[cfg(feature = "server")]
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Strategy used to execute request handlers, shared by all sessions
+    #[cfg(feature = "server")]
+    pub handler_execution: HandlerExecution,
+    /// Reject requests received before the client's `notifications/initialized` notification
+    pub require_initialized: bool,
+    /// How long a session whose standalone SSE stream disconnected is retained, so a client
+    /// reconnecting with the same `mcp-session-id` (and `last-event-id`) resumes it.
+    /// Past the grace period the session is closed and removed, with its stored events.
+    /// `None` retains sessions until they are deleted or evicted by the session store.
+    pub session_grace_period: Option<Duration>,
+}
+
+#[cfg(feature = "server")]
+impl McpAppState {
+    /// Closes every session in the session store, e.g. before a restart so clients reconnect
+    /// to the new server instead of holding on to a session that is about to disappear.
+    ///
+    /// When `reason` is provided it is first sent to each client as a `warning` log message
+    /// notification. Each session is then shut down, its handler's `on_session_closed()`
+    /// callback runs and it is removed from the store.
+    ///
+    /// Returns the number of closed sessions.
+    pub async fn close_all_sessions(&self, reason: Option<String>) -> usize {
+        let mut closed = 0;
+        for session_id in self.session_store.keys().await {
+            let Some(runtime) = self.session_store.get(&session_id).await else {
+                continue;
+            };
+            if let Some(reason) = reason.as_ref() {
+                if let Err(err) = runtime.log_warn(reason.clone()).await {
+                    tracing::warn!("failed to notify session {session_id} of its closing: {err}");
+                }
+            }
+            runtime.close().await;
+            self.session_store.delete(&session_id).await;
+            tracing::info!("session closed by the server : {}", &session_id);
+            closed += 1;
+        }
+        closed
+    }
 }
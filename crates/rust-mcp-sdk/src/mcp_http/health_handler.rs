@@ -8,7 +8,7 @@ use crate::mcp_http::GenericBody;
 ///
 /// Many load balancers and proxies periodically send health check requests to determine if a backend is still alive.
 ///
-/// Custom path can be set in AxumServerOptions (rust-mcp-axum).
+/// Served at `/health` by default, a custom path can be set in AxumServerOptions (rust-mcp-axum).
 /// • Set `AxumServerOptions.health_endpoint = None` to disable completely
 pub trait HealthHandler: Send + Sync + 'static {
     fn call(&self, _req: http::Request<&str>) -> http::Response<GenericBody>;
@@ -2,6 +2,11 @@ use crate::auth::AuthInfo;
 use crate::mcp_http::types::GenericBody;
 use crate::schema::schema_utils::{ClientMessage, SdkError};
 #[cfg(feature = "server")]
+use crate::schema::{
+    schema_utils::{ClientJsonrpcRequest, ClientMessages},
+    Implementation, JsonrpcErrorResponse, RequestId,
+};
+#[cfg(feature = "server")]
 use crate::McpServer;
 use crate::{
     error::SdkResult,
@@ -10,25 +15,28 @@ use crate::{
 };
 #[cfg(feature = "server")]
 use crate::{
-    mcp_runtimes::server_runtime::DEFAULT_STREAM_ID,
+    mcp_runtimes::server_runtime::{SessionRuntimeOptions, DEFAULT_STREAM_ID},
     mcp_server::{server_runtime, ServerRuntime},
     mcp_traits::{IdGenerator, McpServerHandler},
 };
 use bytes::Bytes;
 use futures::stream;
 use http::{
-    header::{ACCEPT, CONNECTION, CONTENT_TYPE},
+    header::{ACCEPT, CONNECTION, CONTENT_LENGTH, CONTENT_TYPE},
     HeaderMap, HeaderValue, StatusCode,
 };
 use http_body::Frame;
 use http_body_util::{BodyExt, Full, StreamBody};
 use rust_mcp_transport::{
-    EventId, McpDispatch, SessionId, SseEvent, SseTransport, StreamId, ID_SEPARATOR,
-    MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER,
+    EventId, McpDispatch, SessionId, SseEvent, SseTransport, StreamId, TransportKind, ID_SEPARATOR,
+    MCP_PROTOCOL_VERSION_HEADER, MCP_ROUTING_KEY_HEADER, MCP_SESSION_ID_HEADER,
 };
 use serde_json::{Map, Value};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{duplex, AsyncBufReadExt, BufReader};
+#[cfg(feature = "server")]
+use tokio::sync::{OwnedSemaphorePermit, TryAcquireError};
 use tokio_stream::StreamExt;
 
 // Default Server-Sent Events (SSE) endpoint path
@@ -37,6 +45,8 @@ pub const DEFAULT_SSE_ENDPOINT: &str = "/sse";
 pub const DEFAULT_MESSAGES_ENDPOINT: &str = "/messages";
 // Default Streamable HTTP endpoint path
 pub const DEFAULT_STREAMABLE_HTTP_ENDPOINT: &str = "/mcp";
+// Default health-check endpoint path
+pub const DEFAULT_HEALTH_ENDPOINT: &str = "/health";
 const DUPLEX_BUFFER_SIZE: usize = 8192;
 
 /// Creates an initial SSE event that returns the messages endpoint
@@ -154,8 +164,25 @@ async fn create_sse_stream(
     standalone: bool,
     last_event_id: Option<EventId>,
 ) -> McpHttpResult<http::Response<GenericBody>> {
+    // only the standalone stream is held open, streams answering a POST close with its response.
+    // held by the response body, released once the stream is closed
+    let sse_stream_slot = if standalone {
+        let Ok(sse_stream_slot) = try_acquire_sse_stream_slot(&state) else {
+            return sse_streams_exhausted();
+        };
+        sse_stream_slot
+    } else {
+        None
+    };
+
     let payload_string = payload.map(|p| p.to_string());
 
+    // the payload is processed in the background, after the response is returned,
+    // record the initialized notification now so the client's next request can't outrun it
+    if let Some(payload) = payload {
+        runtime.observe_initialized_payload(payload);
+    }
+
     // TODO: this logic should be moved out after refactoing the mcp_stream.rs
     let payload_contains_request = payload_string
         .as_ref()
@@ -188,7 +215,12 @@ async fn create_sse_stream(
     )
     .map_err(|err| McpHttpError::TransportError(err.to_string()))?;
     if let Some(event_store) = event_store.clone() {
-        transport.make_resumable((*session_id).clone(), (*stream_id).clone(), event_store);
+        transport.make_resumable_with_policy(
+            (*session_id).clone(),
+            (*stream_id).clone(),
+            event_store,
+            state.event_store_error_policy,
+        );
     }
     let transport = Arc::new(transport);
 
@@ -198,6 +230,9 @@ async fn create_sse_stream(
     let transport_clone = transport.clone();
     let transport_for_remove: crate::mcp_runtimes::server_runtime::TransportType =
         transport.clone();
+    let grace_period = state.session_grace_period.filter(|_| standalone);
+    let state_clone = state.clone();
+    let session_id_clone = session_id.clone();
 
     //Start the server runtime
     tokio::spawn(async move {
@@ -216,6 +251,9 @@ async fn create_sse_stream(
         let _ = runtime
             .remove_transport(&stream_id_clone, &transport_for_remove)
             .await;
+        if let Some(grace_period) = grace_period {
+            expire_after_grace_period(runtime, &session_id_clone, state_clone, grace_period).await;
+        }
     });
 
     // Construct SSE stream
@@ -261,7 +299,8 @@ async fn create_sse_stream(
 
     // create a stream body
     let streaming_body: GenericBody =
-        http_body_util::BodyExt::boxed(StreamBody::new(message_stream.map(|res| {
+        http_body_util::BodyExt::boxed(StreamBody::new(message_stream.map(move |res| {
+            let _slot = &sse_stream_slot;
             res.map(Frame::data)
                 .map_err(|err: std::io::Error| McpHttpError::HttpError(err.to_string()))
         })));
@@ -311,10 +350,33 @@ async fn create_sse_stream(
     Ok(response)
 }
 
+/// Reserves a slot for a new SSE stream when `max_sse_streams` is configured.
+///
+/// The returned permit must be kept alive for as long as the stream is open.
+#[cfg(feature = "server")]
+fn try_acquire_sse_stream_slot(
+    state: &McpAppState,
+) -> Result<Option<OwnedSemaphorePermit>, TryAcquireError> {
+    state
+        .sse_stream_permits
+        .as_ref()
+        .map(|permits| permits.clone().try_acquire_owned())
+        .transpose()
+}
+
+#[cfg(feature = "server")]
+fn sse_streams_exhausted() -> McpHttpResult<http::Response<GenericBody>> {
+    error_response(
+        StatusCode::SERVICE_UNAVAILABLE,
+        SdkError::internal_error()
+            .with_message("Server is at maximum SSE stream capacity, try again later."),
+    )
+}
+
 // TODO: this function will be removed after refactoring the readable stream of the transports
 // so we would deserialize the string syncronousely and have more control over the flow
 // this function may incur a slight runtime cost which could be avoided after refactoring
-fn contains_request(json_str: &str) -> Result<bool, serde_json::Error> {
+pub(crate) fn contains_request(json_str: &str) -> Result<bool, serde_json::Error> {
     let value: serde_json::Value = serde_json::from_str(json_str)?;
     match value {
         serde_json::Value::Object(obj) => Ok(obj.contains_key("id") && obj.contains_key("method")),
@@ -327,19 +389,53 @@ fn contains_request(json_str: &str) -> Result<bool, serde_json::Error> {
     }
 }
 
+/// Returns true if the payload only contains responses (results or errors) to server-initiated requests
 fn is_result(json_str: &str) -> Result<bool, serde_json::Error> {
     let value: serde_json::Value = serde_json::from_str(json_str)?;
     match value {
-        serde_json::Value::Object(obj) => Ok(obj.contains_key("result")),
+        serde_json::Value::Object(obj) => {
+            Ok(obj.contains_key("result") || obj.contains_key("error"))
+        }
         serde_json::Value::Array(arr) => Ok(arr.iter().all(|item| {
             item.as_object()
-                .map(|obj| obj.contains_key("result"))
+                .map(|obj| obj.contains_key("result") || obj.contains_key("error"))
                 .unwrap_or(false)
         })),
         _ => Ok(false),
     }
 }
 
+/// Closes and removes a session whose standalone stream disconnected, unless the client
+/// resumed it within `grace_period`.
+#[cfg(feature = "server")]
+async fn expire_after_grace_period(
+    runtime: Arc<ServerRuntime>,
+    session_id: &SessionId,
+    state: Arc<McpAppState>,
+    grace_period: Duration,
+) {
+    let disconnects = runtime.record_disconnect();
+    tokio::time::sleep(grace_period).await;
+
+    // the client resumed the session, or a later disconnect now owns its expiry
+    if runtime.disconnect_count() != disconnects || runtime.default_stream_exists().await {
+        return;
+    }
+    // the session was deleted meanwhile
+    if !state.session_store.has(session_id).await {
+        return;
+    }
+
+    runtime.close().await;
+    state.session_store.delete(session_id).await;
+    if let Some(event_store) = state.event_store.as_ref() {
+        if let Err(err) = event_store.remove_by_session_id(session_id.clone()).await {
+            tracing::warn!("failed to remove the events of session {session_id}: {err}");
+        }
+    }
+    tracing::info!("session expired after its grace period : {}", session_id);
+}
+
 #[cfg(feature = "server")]
 pub(crate) async fn create_standalone_stream(
     session_id: SessionId,
@@ -379,6 +475,11 @@ pub(crate) async fn create_standalone_stream(
     )
     .await?;
 
+    // the stream was not created (e.g. out of SSE stream slots)
+    if !response.status().is_success() {
+        return Ok(response);
+    }
+
     // Wait for the DEFAULT transport to be stored in transport_map before
     // returning the SSE response to the client. The spawned start_stream task
     // inside create_sse_stream calls store_transport() asynchronously; we must
@@ -395,11 +496,27 @@ pub(crate) async fn create_standalone_stream(
     Ok(response)
 }
 
+/// Returns the id and client information of the initialize request in `payload`, if any.
+#[cfg(feature = "server")]
+fn initialize_client_info(payload: &str) -> Option<(RequestId, Implementation)> {
+    let messages = match serde_json::from_str::<ClientMessages>(payload).ok()? {
+        ClientMessages::Single(message) => vec![message],
+        ClientMessages::Batch(messages) => messages,
+    };
+    messages.into_iter().find_map(|message| match message {
+        ClientMessage::Request(ClientJsonrpcRequest::InitializeRequest(request)) => {
+            Some((request.id, request.params.client_info))
+        }
+        _ => None,
+    })
+}
+
 #[cfg(feature = "server")]
 pub(crate) async fn start_new_session(
     state: Arc<McpAppState>,
     payload: &str,
     auth_info: Option<AuthInfo>,
+    json_response: bool,
 ) -> McpHttpResult<http::Response<GenericBody>> {
     if state.session_store.is_full().await {
         return error_response(
@@ -409,37 +526,82 @@ pub(crate) async fn start_new_session(
         );
     }
 
+    if let Some((request_id, client_info)) = initialize_client_info(payload) {
+        if let Err(error) = state.handler.accept_client(&client_info) {
+            tracing::info!(
+                "client rejected : {} {}, {}",
+                client_info.name,
+                client_info.version,
+                error.message
+            );
+            // answer the initialize request itself, so the client can match the rejection to it
+            let error_response = JsonrpcErrorResponse::new(error, Some(request_id));
+            let body = Full::new(Bytes::from(
+                serde_json::to_string(&error_response).unwrap_or_default(),
+            ))
+            .map_err(|err| McpHttpError::HttpError(err.to_string()))
+            .boxed();
+            return http::Response::builder()
+                .status(StatusCode::FORBIDDEN)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body)
+                .map_err(|err| McpHttpError::HttpError(err.to_string()));
+        }
+    }
+
     let session_id: SessionId = state.id_generator.generate();
 
     let h: Arc<dyn McpServerHandler> = state.handler.clone();
     // create a new server instance with unique session_id and
-    let runtime: Arc<ServerRuntime> = server_runtime::create_server_instance(
-        Arc::clone(&state.server_details),
-        h,
-        session_id.to_owned(),
-        auth_info,
-        state.task_store.clone(),
-        state.client_task_store.clone(),
-        state.message_observer.clone(),
-    );
+    let runtime: Arc<ServerRuntime> =
+        server_runtime::create_server_instance(SessionRuntimeOptions {
+            server_details: Arc::clone(&state.server_details),
+            handler: h,
+            session_id: session_id.to_owned(),
+            auth_info,
+            task_store: state.task_store.clone(),
+            client_task_store: state.client_task_store.clone(),
+            message_observer: state.message_observer.clone(),
+            audit_sink: state.audit_sink.clone(),
+            handler_execution: state.handler_execution.clone(),
+            require_initialized: state.require_initialized,
+            transport_kind: TransportKind::StreamableHttp,
+        });
 
     tracing::info!("a new client joined : {}", &session_id);
 
-    let response = create_sse_stream(
-        runtime.clone(),
-        session_id.clone(),
-        state.clone(),
-        Some(payload),
-        false,
-        None,
-    )
-    .await;
+    let mut response = if json_response {
+        single_shot_stream(
+            runtime.clone(),
+            session_id.clone(),
+            state.clone(),
+            Some(payload),
+            false,
+        )
+        .await
+    } else {
+        create_sse_stream(
+            runtime.clone(),
+            session_id.clone(),
+            state.clone(),
+            Some(payload),
+            false,
+            None,
+        )
+        .await
+    };
 
-    if response.is_ok() {
+    if let Ok(response) = response.as_mut() {
         state
             .session_store
             .set(session_id.to_owned(), runtime.clone())
             .await;
+        // let load balancers with sticky routing pin the session to this node
+        if let Some(routing_key) = state.id_generator.routing_key(&session_id) {
+            if let Ok(value) = HeaderValue::from_str(&routing_key) {
+                response.headers_mut().insert(MCP_ROUTING_KEY_HEADER, value);
+            }
+        }
     }
     response
 }
@@ -507,6 +669,8 @@ async fn single_shot_stream(
     match response {
         Some(response_result) => match response_result {
             Ok(response_str) => {
+                // the response is fully buffered, state its length so it is never sent chunked
+                let content_length = response_str.len();
                 let body = Full::new(Bytes::from(response_str))
                     .map_err(|err| McpHttpError::HttpError(err.to_string()))
                     .boxed();
@@ -514,6 +678,7 @@ async fn single_shot_stream(
                 http::Response::builder()
                     .status(StatusCode::OK)
                     .header(CONTENT_TYPE, "application/json")
+                    .header(CONTENT_LENGTH, content_length)
                     .header(MCP_SESSION_ID_HEADER, session_id_value)
                     .body(body)
                     .map_err(|err| McpHttpError::HttpError(err.to_string()))
@@ -636,7 +801,7 @@ pub(crate) async fn delete_session(
 ) -> McpHttpResult<http::Response<GenericBody>> {
     match state.session_store.get(&session_id).await {
         Some(runtime) => {
-            runtime.shutdown().await;
+            runtime.close().await;
             state.session_store.delete(&session_id).await;
             tracing::info!("client disconnected : {}", &session_id);
 
@@ -680,28 +845,80 @@ pub(crate) fn validate_mcp_protocol_version_header(headers: &HeaderMap) -> SdkRe
     validate_mcp_protocol_version(protocol_version_header)
 }
 
-pub(crate) fn accepts_event_stream(headers: &HeaderMap) -> bool {
+/// Returns the quality (`q` value) the `Accept` header of the request gives to `media_type`,
+/// e.g. `text/event-stream`, or zero if the type is not acceptable.
+///
+/// Wildcard ranges match as per HTTP content negotiation: `*/*` accepts any type and `text/*` any
+/// `text` subtype. When several ranges match, the most specific one applies. A range without a
+/// `q` parameter has a quality of one, other parameters are ignored.
+fn media_type_quality(headers: &HeaderMap, media_type: &str) -> f32 {
     let accept_header = headers
         .get(ACCEPT)
         .and_then(|val| val.to_str().ok())
         .unwrap_or("");
+    let (main_type, sub_type) = media_type.split_once('/').unwrap_or((media_type, ""));
 
     accept_header
         .split(',')
-        .any(|val| val.trim().starts_with("text/event-stream"))
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let range = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+            let specificity = match range.split_once('/') {
+                Some(("*", "*")) => 0,
+                Some((range_type, "*")) if range_type == main_type => 1,
+                Some((range_type, range_sub_type))
+                    if range_type == main_type && range_sub_type == sub_type =>
+                {
+                    2
+                }
+                _ => return None,
+            };
+            let quality = parts
+                .find_map(|param| {
+                    param
+                        .trim()
+                        .strip_prefix("q=")
+                        .and_then(|q| q.trim().parse::<f32>().ok())
+                })
+                .map_or(1.0, |q| q.clamp(0.0, 1.0));
+            Some((specificity, quality))
+        })
+        // among equally specific ranges, the least acceptable one wins
+        .max_by(|(a, a_quality), (b, b_quality)| a.cmp(b).then(b_quality.total_cmp(a_quality)))
+        .map_or(0.0, |(_, quality)| quality)
+}
+
+/// Returns true if the `Accept` header of the request accepts `media_type`, see [`media_type_quality`].
+fn accepts_media_type(headers: &HeaderMap, media_type: &str) -> bool {
+    media_type_quality(headers, media_type) > 0.0
+}
+
+pub(crate) fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    accepts_media_type(headers, "text/event-stream")
+}
+
+/// Returns true if the client would rather receive `application/json` than `text/event-stream`:
+/// it either doesn't accept `text/event-stream` at all, or gives JSON a higher `q` value.
+pub(crate) fn prefers_json_response(headers: &HeaderMap) -> bool {
+    let json_quality = media_type_quality(headers, "application/json");
+    json_quality > 0.0 && json_quality > media_type_quality(headers, "text/event-stream")
 }
 
 pub(crate) fn valid_streaming_http_accept_header(headers: &HeaderMap) -> bool {
-    let accept_header = headers
-        .get(ACCEPT)
-        .and_then(|val| val.to_str().ok())
-        .unwrap_or("");
+    accepts_media_type(headers, "application/json") && accepts_event_stream(headers)
+}
 
-    let types: Vec<_> = accept_header.split(',').map(|v| v.trim()).collect();
+/// JSON-RPC error code of the body of `403 Forbidden` responses to requests rejected before
+/// MCP processing, e.g. by the IP filter.
+pub const FORBIDDEN_ERROR_CODE: i64 = -32003;
 
-    let has_event_stream = types.iter().any(|v| v.starts_with("text/event-stream"));
-    let has_json = types.iter().any(|v| v.starts_with("application/json"));
-    has_event_stream && has_json
+/// Returns an error with the [`FORBIDDEN_ERROR_CODE`] code and `message`.
+pub fn forbidden_error(message: &str) -> SdkError {
+    SdkError {
+        code: FORBIDDEN_ERROR_CODE,
+        data: None,
+        message: message.to_string(),
+    }
 }
 
 pub fn error_response(
@@ -720,6 +937,34 @@ pub fn error_response(
         .map_err(|err| McpHttpError::HttpError(err.to_string()))
 }
 
+/// Adds the `mcp-session-id` header to an error `response` returned for a request made within
+/// an existing session, so clients can correlate it the same way as successful responses.
+///
+/// Responses for unknown or terminated sessions are left untouched.
+#[cfg(feature = "server")]
+pub(crate) async fn echo_session_id_on_error(
+    mut response: http::Response<GenericBody>,
+    session_id: Option<SessionId>,
+    state: &McpAppState,
+) -> http::Response<GenericBody> {
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error())
+        || response.headers().contains_key(MCP_SESSION_ID_HEADER)
+    {
+        return response;
+    }
+    let Some(session_id) = session_id else {
+        return response;
+    };
+    if state.session_store.get(&session_id).await.is_none() {
+        return response;
+    }
+    if let Ok(value) = HeaderValue::from_str(&session_id) {
+        response.headers_mut().insert(MCP_SESSION_ID_HEADER, value);
+    }
+    response
+}
+
 /// Extracts the value of a query parameter from an HTTP request by key.
 ///
 /// This function parses the query string from the request URI and searches
@@ -761,6 +1006,11 @@ pub(crate) async fn handle_sse_connection(
         );
     }
 
+    // held by the response body, released once the stream is closed
+    let Ok(sse_stream_slot) = try_acquire_sse_stream_slot(&state) else {
+        return sse_streams_exhausted();
+    };
+
     let session_id: SessionId = state.id_generator.generate();
 
     let sse_message_endpoint = sse_message_endpoint.unwrap_or(DEFAULT_MESSAGES_ENDPOINT);
@@ -788,15 +1038,20 @@ pub(crate) async fn handle_sse_connection(
 
     let h: Arc<dyn McpServerHandler> = state.handler.clone();
     // create a new server instance with unique session_id and
-    let server: Arc<ServerRuntime> = server_runtime::create_server_instance(
-        Arc::clone(&state.server_details),
-        h,
-        session_id.to_owned(),
-        auth_info,
-        state.task_store.clone(),
-        state.client_task_store.clone(),
-        state.message_observer.clone(),
-    );
+    let server: Arc<ServerRuntime> =
+        server_runtime::create_server_instance(SessionRuntimeOptions {
+            server_details: Arc::clone(&state.server_details),
+            handler: h,
+            session_id: session_id.to_owned(),
+            auth_info,
+            task_store: state.task_store.clone(),
+            client_task_store: state.client_task_store.clone(),
+            message_observer: state.message_observer.clone(),
+            audit_sink: state.audit_sink.clone(),
+            handler_execution: state.handler_execution.clone(),
+            require_initialized: state.require_initialized,
+            transport_kind: TransportKind::Sse,
+        });
 
     state
         .session_store
@@ -853,7 +1108,10 @@ pub(crate) async fn handle_sse_connection(
 
     // create a stream body
     let streaming_body: GenericBody =
-        http_body_util::BodyExt::boxed(StreamBody::new(stream.map(|res| res.map(Frame::data))));
+        http_body_util::BodyExt::boxed(StreamBody::new(stream.map(move |res| {
+            let _slot = &sse_stream_slot;
+            res.map(Frame::data)
+        })));
 
     let response = http::Response::builder()
         .status(StatusCode::OK)
@@ -864,3 +1122,91 @@ pub(crate) async fn handle_sse_connection(
 
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn explicit_media_types_are_accepted() {
+        let headers = accept("application/json, text/event-stream");
+        assert!(valid_streaming_http_accept_header(&headers));
+        assert!(accepts_event_stream(&headers));
+        assert!(!prefers_json_response(&headers));
+
+        let headers = accept("application/json; charset=utf-8");
+        assert!(!valid_streaming_http_accept_header(&headers));
+        assert!(prefers_json_response(&headers));
+    }
+
+    #[test]
+    fn wildcard_media_ranges_are_accepted() {
+        let headers = accept("*/*");
+        assert!(valid_streaming_http_accept_header(&headers));
+        assert!(accepts_event_stream(&headers));
+        assert!(!prefers_json_response(&headers));
+
+        let headers = accept("application/*, text/*;q=0.5");
+        assert!(valid_streaming_http_accept_header(&headers));
+        assert!(accepts_event_stream(&headers));
+
+        let headers = accept("application/*");
+        assert!(!valid_streaming_http_accept_header(&headers));
+        assert!(prefers_json_response(&headers));
+
+        let headers = accept("text/*, application/json");
+        assert!(valid_streaming_http_accept_header(&headers));
+    }
+
+    #[test]
+    fn incompatible_media_types_are_rejected() {
+        for value in ["", "text/html", "image/*", "application/xml, text/plain"] {
+            let headers = accept(value);
+            assert!(!valid_streaming_http_accept_header(&headers), "{value}");
+            assert!(!accepts_event_stream(&headers), "{value}");
+            assert!(!prefers_json_response(&headers), "{value}");
+        }
+        assert!(!valid_streaming_http_accept_header(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn ranges_with_zero_quality_are_rejected() {
+        // the most specific range applies
+        let headers = accept("*/*, text/event-stream;q=0");
+        assert!(!accepts_event_stream(&headers));
+        assert!(prefers_json_response(&headers));
+
+        let headers = accept("text/*;q=0, text/event-stream");
+        assert!(accepts_event_stream(&headers));
+
+        let headers = accept("application/json, text/event-stream; q=0");
+        assert!(!accepts_event_stream(&headers));
+        assert!(prefers_json_response(&headers));
+    }
+
+    #[test]
+    fn quality_values_set_the_preferred_response() {
+        let headers = accept("application/json, text/event-stream;q=0.5");
+        assert!(valid_streaming_http_accept_header(&headers));
+        assert!(prefers_json_response(&headers));
+
+        let headers = accept("application/json;q=0.5, text/event-stream");
+        assert!(!prefers_json_response(&headers));
+
+        // equal preferences keep the streaming response
+        let headers = accept("application/json;q=0.8, text/event-stream;q=0.8");
+        assert!(!prefers_json_response(&headers));
+
+        let headers = accept("*/*;q=0.1, application/json");
+        assert!(prefers_json_response(&headers));
+        assert_eq!(media_type_quality(&headers, "text/event-stream"), 0.1);
+        assert_eq!(media_type_quality(&headers, "image/png"), 0.1);
+        assert_eq!(media_type_quality(&accept("text/html"), "image/png"), 0.0);
+    }
+}
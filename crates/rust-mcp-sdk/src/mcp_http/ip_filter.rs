@@ -0,0 +1,108 @@
+use super::middleware::{IpFilterMiddleware, IpNetwork};
+
+/// Shared IP allow/deny list configuration for all HTTP framework
+/// integrations.
+///
+/// Include this in every framework's server options struct. At startup,
+/// call [`resolve_ip_filter_middleware`] to optionally install the filtering
+/// middleware. The framework must store the remote address of the connection
+/// as a [`std::net::SocketAddr`] in the request extensions, otherwise every request is
+/// rejected as coming from an unknown address (see the
+/// [middleware documentation](super::middleware::IpFilterMiddleware)).
+///
+/// # Per-framework adoption
+///
+/// ```ignore
+/// // 1. Add to options struct
+/// pub ip_filter: IpFilterOptions,
+///
+/// // 2. Default it
+/// ip_filter: IpFilterOptions::default(),
+///
+/// // 3. Install middleware in your server's new()
+/// if let Some(ip_filter) = resolve_ip_filter_middleware(&opts.ip_filter)? {
+///     middlewares.push(Arc::new(ip_filter));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IpFilterOptions {
+    /// CIDR networks (e.g. `10.0.0.0/8`) or single addresses allowed to connect.
+    /// If not specified, all addresses that are not denied are allowed.
+    pub allowed_ips: Option<Vec<String>>,
+    /// CIDR networks or single addresses rejected with `403 Forbidden`.
+    /// Takes precedence over `allowed_ips`.
+    pub denied_ips: Option<Vec<String>>,
+    /// Number of reverse proxies in front of the server. When not zero, the client IP is the
+    /// `X-Forwarded-For` entry this many positions from the right, as each proxy appends the address
+    /// it received the request from. Default is `0`, the remote address of the connection is used.
+    pub trusted_proxies: usize,
+}
+
+fn parse_networks(values: Option<&Vec<String>>) -> Result<Vec<IpNetwork>, String> {
+    values
+        .into_iter()
+        .flatten()
+        .map(|value| value.parse::<IpNetwork>())
+        .collect()
+}
+
+/// Resolves the IP filter middleware from the given options.
+///
+/// Returns `Ok(None)` if neither `allowed_ips` nor `denied_ips` has any entry,
+/// or an error describing the first entry that is not a valid CIDR network or address.
+pub fn resolve_ip_filter_middleware(
+    opts: &IpFilterOptions,
+) -> Result<Option<IpFilterMiddleware>, String> {
+    let allowed_ips = parse_networks(opts.allowed_ips.as_ref())?;
+    let denied_ips = parse_networks(opts.denied_ips.as_ref())?;
+    if allowed_ips.is_empty() && denied_ips.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(IpFilterMiddleware::new(
+        allowed_ips,
+        denied_ips,
+        opts.trusted_proxies,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_lists_return_none() {
+        assert!(resolve_ip_filter_middleware(&IpFilterOptions::default())
+            .unwrap()
+            .is_none());
+        let opts = IpFilterOptions {
+            allowed_ips: Some(vec![]),
+            ..Default::default()
+        };
+        assert!(resolve_ip_filter_middleware(&opts).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_resolves_configured_lists() {
+        let opts = IpFilterOptions {
+            allowed_ips: Some(vec!["10.0.0.0/8".into()]),
+            denied_ips: Some(vec!["10.0.0.1".into()]),
+            trusted_proxies: 1,
+        };
+        let middleware = resolve_ip_filter_middleware(&opts).unwrap().unwrap();
+        assert_eq!(middleware.allowed_ips.len(), 1);
+        assert_eq!(middleware.denied_ips.len(), 1);
+        assert_eq!(middleware.trusted_proxies, 1);
+    }
+
+    #[test]
+    fn test_invalid_network_is_an_error() {
+        let opts = IpFilterOptions {
+            denied_ips: Some(vec!["10.0.0.0/8".into(), "not-an-ip".into()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_ip_filter_middleware(&opts).err().unwrap(),
+            "Invalid IP network: \"not-an-ip\""
+        );
+    }
+}
@@ -9,8 +9,9 @@ use crate::auth::AuthInfo;
 use crate::auth::AuthProvider;
 #[cfg(all(feature = "server", any(feature = "sse", feature = "streamable-http")))]
 use crate::mcp_http::http_utils::{
-    create_standalone_stream, delete_session, process_incoming_message,
-    process_incoming_message_return, start_new_session,
+    contains_request, create_standalone_stream, delete_session, echo_session_id_on_error,
+    prefers_json_response, process_incoming_message, process_incoming_message_return,
+    start_new_session,
 };
 use crate::mcp_http::McpHttpError;
 use crate::mcp_http::{middleware::compose, BoxFutureResponse, Middleware, RequestHandler};
@@ -180,8 +181,8 @@ impl McpHttpHandler {
     ///
     ///
     /// # Features
-    /// This function is only available when the `sse` feature is enabled.
-    #[cfg(feature = "sse")]
+    /// This function is only available when the `sse` and `server` features are enabled.
+    #[cfg(all(feature = "sse", feature = "server"))]
     pub async fn handle_sse_connection(
         &self,
         request: http::Request<&str>,
@@ -223,7 +224,7 @@ impl McpHttpHandler {
     /// - `SessionIdInvalid`: if the session ID does not map to a valid session in the session store.
     /// - `StreamIoError`: if an error occurs while writing to the stream.
     /// - `HttpError`: if constructing the HTTP response fails.
-    #[cfg(feature = "sse")]
+    #[cfg(all(feature = "sse", feature = "server"))]
     pub async fn handle_sse_message(
         &self,
         request: http::Request<&str>,
@@ -233,18 +234,29 @@ impl McpHttpHandler {
         handle(request, state).await
     }
 
+    /// Answers health-check requests, with the response of the custom [`HealthHandler`] if any,
+    /// otherwise `200 OK` and a JSON body reporting the number of sessions in the session store.
+    ///
+    /// Middlewares are not applied, so load balancer probes are neither authenticated nor
+    /// subject to DNS rebinding protection.
     pub async fn handle_health(
         &self,
         request: http::Request<&str>,
+        state: Arc<McpAppState>,
     ) -> McpHttpResult<http::Response<GenericBody>> {
         if let Some(health_handler) = self.health_handler.as_ref() {
             Ok(health_handler.call(request))
         } else {
-            let status = serde_json::json!({
+            #[allow(unused_mut)]
+            let mut status = serde_json::json!({
                 "status":"ok",
                 "server": env!("CARGO_PKG_NAME"),
                 "version":env!("CARGO_PKG_VERSION")
             });
+            #[cfg(feature = "server")]
+            {
+                status["sessions"] = state.session_store.keys().await.len().into();
+            }
 
             Ok(GenericBody::from_value(&status).into_json_response(http::StatusCode::OK, None))
         }
@@ -317,6 +329,25 @@ impl McpHttpHandler {
     ) -> McpHttpResult<http::Response<GenericBody>> {
         let (request, auth_info) = request.take::<AuthInfo>();
 
+        #[cfg(feature = "server")]
+        {
+            let session_id = parse_session_id_header(request.headers(), MCP_SESSION_ID_HEADER)
+                .ok()
+                .flatten();
+            let response = Self::route_streamable_http(request, state.clone(), auth_info).await?;
+            Ok(echo_session_id_on_error(response, session_id, &state).await)
+        }
+        #[cfg(not(feature = "server"))]
+        {
+            Self::route_streamable_http(request, state, auth_info).await
+        }
+    }
+
+    async fn route_streamable_http(
+        request: http::Request<&str>,
+        state: Arc<McpAppState>,
+        auth_info: Option<AuthInfo>,
+    ) -> McpHttpResult<http::Response<GenericBody>> {
         let method = request.method();
 
         let response = match method {
@@ -379,7 +410,11 @@ impl McpHttpHandler {
     ) -> McpHttpResult<http::Response<GenericBody>> {
         let headers = request.headers();
 
-        if !valid_streaming_http_accept_header(headers) {
+        // clients that can't consume SSE, or rank JSON above it with `q` values, get a
+        // buffered JSON response, if the server allows it
+        let json_only = state.json_response_fallback && prefers_json_response(headers);
+
+        if !json_only && !valid_streaming_http_accept_header(headers) {
             let error = SdkError::bad_request()
                 .with_message(r#"Client must accept both application/json and text/event-stream"#);
             return error_response(StatusCode::NOT_ACCEPTABLE, error);
@@ -411,7 +446,9 @@ impl McpHttpHandler {
         let response = match session_id {
             // has session-id => write to the existing stream
             Some(id) => {
-                if state.enable_json_response {
+                // notifications and responses carry no reply, they are acknowledged as usual
+                let json_request = json_only && contains_request(payload).unwrap_or(false);
+                if state.enable_json_response || json_request {
                     process_incoming_message_return(id, state, payload, auth_info).await
                 } else {
                     process_incoming_message(id, state, payload, auth_info).await
@@ -419,7 +456,7 @@ impl McpHttpHandler {
             }
             None => match valid_initialize_method(payload) {
                 Ok(_) => {
-                    return start_new_session(state, payload, auth_info).await;
+                    return start_new_session(state, payload, auth_info, json_only).await;
                 }
                 Err(McpSdkError::SdkError(error)) => error_response(StatusCode::BAD_REQUEST, error),
                 Err(error) => {
@@ -462,8 +499,19 @@ impl McpHttpHandler {
             }
         };
 
+        // the header takes precedence over the query parameter
         let last_event_id = match parse_session_id_header(headers, MCP_LAST_EVENT_ID_HEADER) {
-            Ok(id) => id,
+            Ok(Some(id)) => Some(id),
+            Ok(None) => match parse_last_event_id_query(&request) {
+                Ok(id) => id,
+                Err(msg) => {
+                    let error = SdkError::bad_request().with_message(
+                        format!("Invalid {LAST_EVENT_ID_QUERY_PARAM} query parameter: {msg}")
+                            .as_str(),
+                    );
+                    return error_response(StatusCode::BAD_REQUEST, error);
+                }
+            },
             Err(msg) => {
                 let error = SdkError::bad_request()
                     .with_message(format!("Invalid Mcp-Last-Event-Id header: {msg}").as_str());
@@ -561,6 +609,29 @@ fn parse_session_id_header(
     }
 }
 
+/// Query parameter carrying the last event id, for clients that can't set the
+/// `Last-Event-ID` header (e.g. some `EventSource` polyfills and proxies).
+#[cfg(feature = "server")]
+const LAST_EVENT_ID_QUERY_PARAM: &str = "lastEventId";
+
+/// Extracts and validates the last event id from the `lastEventId` query parameter,
+/// with the same rules as [`parse_session_id_header`].
+#[cfg(feature = "server")]
+fn parse_last_event_id_query(
+    request: &http::Request<&str>,
+) -> Result<Option<String>, &'static str> {
+    match query_param(request, LAST_EVENT_ID_QUERY_PARAM) {
+        None => Ok(None),
+        Some(value) => {
+            let value = percent_encoding::percent_decode_str(&value)
+                .decode_utf8()
+                .map_err(|_| "event ID is not valid percent-encoded UTF-8")?;
+            is_valid_session_id(&value)?;
+            Ok(Some(value.into_owned()))
+        }
+    }
+}
+
 #[cfg(test)]
 mod session_id_tests {
     use super::*;
@@ -668,4 +739,45 @@ mod session_id_tests {
         let err = parse_session_id_header(&headers, "mcp-session-id").unwrap_err();
         assert!(err.contains("not valid UTF-8"));
     }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn parse_last_event_id_query_decodes_value() {
+        let request = http::Request::get("/mcp?lastEventId=s1%7Cs_2%7C17")
+            .body("")
+            .unwrap();
+        let result = parse_last_event_id_query(&request).unwrap();
+        assert_eq!(result, Some("s1|s_2|17".to_string()));
+
+        let request = http::Request::get("/mcp").body("").unwrap();
+        assert!(parse_last_event_id_query(&request).unwrap().is_none());
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn parse_last_event_id_query_rejects_invalid_values() {
+        let request = http::Request::get("/mcp?lastEventId=a%20b")
+            .body("")
+            .unwrap();
+        assert!(parse_last_event_id_query(&request).is_err());
+
+        let request = http::Request::get("/mcp?lastEventId=%FF").body("").unwrap();
+        assert!(parse_last_event_id_query(&request).is_err());
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn parse_last_event_id_query_keeps_incomplete_escapes() {
+        let request = http::Request::get("/mcp?lastEventId=%4").body("").unwrap();
+        assert_eq!(
+            parse_last_event_id_query(&request).unwrap(),
+            Some("%4".to_string())
+        );
+
+        let request = http::Request::get("/mcp?lastEventId=%+4").body("").unwrap();
+        assert_eq!(
+            parse_last_event_id_query(&request).unwrap(),
+            Some("%+4".to_string())
+        );
+    }
 }
@@ -1,17 +1,23 @@
 #[cfg(feature = "auth")]
 mod auth_middleware;
+mod body_logging_middleware;
 mod cors_middleware;
 mod dns_rebind_protector;
+mod ip_filter_middleware;
 pub mod logging_middleware;
+mod security_headers_middleware;
 
 use super::types::{GenericBody, RequestHandler};
 use crate::mcp_http::McpHttpResult;
 use crate::mcp_http::{McpAppState, MiddlewareNext};
 #[cfg(feature = "auth")]
 pub use auth_middleware::*;
+pub use body_logging_middleware::*;
 pub use cors_middleware::*;
 pub use dns_rebind_protector::*;
 use http::{Request, Response};
+pub use ip_filter_middleware::*;
+pub use security_headers_middleware::*;
 use std::sync::Arc;
 
 #[async_trait::async_trait]
@@ -106,10 +112,17 @@ mod tests {
             ping_interval: Duration::from_secs(15),
             transport_options: Arc::new(rust_mcp_transport::TransportOptions::default()),
             enable_json_response: false,
+            json_response_fallback: false,
+            sse_stream_permits: None,
             event_store: None,
+            event_store_error_policy: Default::default(),
             task_store:None,
             client_task_store:None,
-            message_observer: None
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+            session_grace_period: None,
         })
     }
 
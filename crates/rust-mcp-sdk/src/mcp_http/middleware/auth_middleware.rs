@@ -306,10 +306,17 @@ mod tests {
             ping_interval: Duration::from_secs(15),
             transport_options: Arc::new(rust_mcp_transport::TransportOptions::default()),
             enable_json_response: false,
+            json_response_fallback: false,
+            sse_stream_permits: None,
             event_store: None,
+            event_store_error_policy: Default::default(),
             task_store:None,
             client_task_store:None,
-            message_observer:None
+            message_observer:None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+            session_grace_period: None,
         })
     }
 
@@ -0,0 +1,181 @@
+//! # Body Logging Middleware
+//!
+//! Logs raw HTTP request and response bodies at `debug` level, to help reproducing
+//! protocol issues. Logged bodies are bounded and scrubbed of secrets:
+//! - JSON bodies have the values of configured keys (at any depth) replaced with `[REDACTED]`
+//! - Bodies longer than `max_bytes` are truncated
+//!
+//! Server-Sent Events streams are not buffered, only their status is logged.
+//! Bodies are only read when `debug` logging is enabled for this module.
+
+use crate::mcp_http::{
+    types::GenericBody, McpAppState, McpHttpError, McpHttpResult, Middleware, MiddlewareNext,
+};
+use crate::utils::REDACTED_VALUE;
+use http::{header::CONTENT_TYPE, Request, Response};
+use http_body_util::{BodyExt, Full};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Configuration of [`BodyLoggingMiddleware`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BodyLoggingConfig {
+    /// Maximum number of bytes of a body that are logged, longer bodies are truncated. Default is 4096.
+    pub max_bytes: usize,
+    /// JSON object keys whose values are redacted before logging, matched case-insensitively.
+    /// Default is `authorization`, `password`, `secret`, `token`, `access_token`, `refresh_token`,
+    /// `client_secret` and `api_key`.
+    pub redact_keys: Vec<String>,
+}
+
+impl Default for BodyLoggingConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 4096,
+            redact_keys: [
+                "authorization",
+                "password",
+                "secret",
+                "token",
+                "access_token",
+                "refresh_token",
+                "client_secret",
+                "api_key",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Middleware logging request and response bodies at `debug` level, see [`BodyLoggingConfig`].
+pub struct BodyLoggingMiddleware {
+    config: BodyLoggingConfig,
+}
+
+impl BodyLoggingMiddleware {
+    pub fn new(config: BodyLoggingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns `body` as it is logged: redacted if it is JSON, and truncated to `max_bytes`.
+    pub fn format_body(&self, body: &str) -> String {
+        let body = match serde_json::from_str::<Value>(body) {
+            Ok(mut value) if !self.config.redact_keys.is_empty() => {
+                self.redact(&mut value);
+                value.to_string()
+            }
+            _ => body.to_string(),
+        };
+        truncate(body, self.config.max_bytes)
+    }
+
+    fn redact(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self
+                        .config
+                        .redact_keys
+                        .iter()
+                        .any(|redact_key| redact_key.eq_ignore_ascii_case(key))
+                    {
+                        *value = Value::String(REDACTED_VALUE.to_string());
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.redact(value)),
+            _ => {}
+        }
+    }
+}
+
+fn truncate(mut body: String, max_bytes: usize) -> String {
+    if body.len() <= max_bytes {
+        return body;
+    }
+    let total = body.len();
+    let mut end = max_bytes;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    body.truncate(end);
+    body.push_str(&format!("… ({end} of {total} bytes)"));
+    body
+}
+
+#[async_trait::async_trait]
+impl Middleware for BodyLoggingMiddleware {
+    async fn handle<'req>(
+        &self,
+        req: Request<&'req str>,
+        state: Arc<McpAppState>,
+        next: MiddlewareNext<'req>,
+    ) -> McpHttpResult<Response<GenericBody>> {
+        if !tracing::enabled!(tracing::Level::DEBUG) {
+            return next(req, state).await;
+        }
+
+        tracing::debug!(
+            method = %req.method(),
+            uri = %req.uri(),
+            body = %self.format_body(req.body()),
+            "HTTP request body"
+        );
+
+        let response = next(req, state).await?;
+
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        if is_event_stream {
+            tracing::debug!(status = %response.status(), "HTTP response is an event stream");
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let bytes = body
+            .collect()
+            .await
+            .map_err(|err| McpHttpError::HttpError(err.to_string()))?
+            .to_bytes();
+        tracing::debug!(
+            status = %parts.status,
+            body = %self.format_body(&String::from_utf8_lossy(&bytes)),
+            "HTTP response body"
+        );
+        let body = Full::new(bytes)
+            .map_err(|err| McpHttpError::HttpError(err.to_string()))
+            .boxed();
+        Ok(Response::from_parts(parts, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_and_truncates_bodies() {
+        let middleware = BodyLoggingMiddleware::new(BodyLoggingConfig::default());
+        let body = r#"{"arguments":{"Password":"hunter2","users":[{"token":"abc","name":"x"}]}}"#;
+        assert_eq!(
+            middleware.format_body(body),
+            r#"{"arguments":{"Password":"[REDACTED]","users":[{"name":"x","token":"[REDACTED]"}]}}"#
+        );
+
+        let middleware = BodyLoggingMiddleware::new(BodyLoggingConfig {
+            max_bytes: 4,
+            ..Default::default()
+        });
+        assert_eq!(middleware.format_body("not json"), "not … (4 of 8 bytes)");
+        assert_eq!(truncate("héllo".to_string(), 2), "h… (1 of 6 bytes)");
+    }
+}
@@ -22,7 +22,7 @@ use http::{
     },
     Method, Request, Response, StatusCode,
 };
-use rust_mcp_transport::MCP_SESSION_ID_HEADER;
+use rust_mcp_transport::{MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER};
 use std::{collections::HashSet, sync::Arc};
 
 /// Configuration for CORS behavior.
@@ -68,6 +68,23 @@ impl Default for CorsConfig {
     }
 }
 
+impl CorsConfig {
+    /// Adds the `mcp-session-id` and `mcp-protocol-version` headers to the allowed and exposed headers,
+    /// so browser-based MCP clients can send them and read them from responses.
+    pub fn with_mcp_headers(mut self) -> Self {
+        for name in [MCP_SESSION_ID_HEADER, MCP_PROTOCOL_VERSION_HEADER] {
+            let header = HeaderName::from_static(name);
+            if !self.allow_headers.contains(&header) {
+                self.allow_headers.push(header.clone());
+            }
+            if !self.expose_headers.contains(&header) {
+                self.expose_headers.push(header);
+            }
+        }
+        self
+    }
+}
+
 /// Policy for allowed origins.
 #[derive(Clone, Debug)]
 pub enum AllowOrigins {
@@ -410,10 +427,17 @@ mod tests {
             ping_interval: Duration::from_secs(15),
             transport_options: Arc::new(rust_mcp_transport::TransportOptions::default()),
             enable_json_response: false,
+            json_response_fallback: false,
+            sse_stream_permits: None,
             event_store: None,
+            event_store_error_policy: Default::default(),
             task_store:None,
             client_task_store:None,
-            message_observer:None
+            message_observer:None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+            session_grace_period: None,
         })
     }
 
@@ -0,0 +1,321 @@
+//! IP Filter Middleware
+//!
+//! Checks the client address of each request against CIDR allow and deny lists, answering
+//! `403 Forbidden` to requests from networks that should not reach the server, before any
+//! MCP processing. A request is rejected when its client address matches a `denied_ips` entry,
+//! when `allowed_ips` is not empty and none of its entries match, or when the client address
+//! can't be determined.
+//!
+//! ## Client address
+//!
+//! The client address is the remote address of the connection, read from the [`SocketAddr`] the
+//! framework integration stores in the request extensions. With axum, it is only available when the
+//! router is served with `into_make_service_with_connect_info::<SocketAddr>()`, as `AxumServer` does;
+//! a router served without it has every request rejected as coming from an unknown address.
+//!
+//! Behind reverse proxies, set `trusted_proxies` to their number. Each proxy appends the address it
+//! received the request from to the `X-Forwarded-For` header, so the client address is the entry
+//! `trusted_proxies` positions from the right. Entries further left are set by the client and are
+//! ignored, and requests with fewer entries than trusted proxies are rejected.
+
+use crate::{
+    mcp_http::{
+        error_response, forbidden_error, types::GenericBody, McpAppState, McpHttpResult,
+        Middleware, MiddlewareNext,
+    },
+    schema::schema_utils::SdkError,
+};
+use async_trait::async_trait;
+use http::{Request, Response, StatusCode};
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+};
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// An IPv4 or IPv6 network in CIDR notation, e.g. `10.0.0.0/8` or `fd00::/8`.
+///
+/// A bare address (e.g. `192.168.1.10`) is parsed as a single-host network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// Returns true if `ip` belongs to this network.
+    ///
+    /// IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`) are matched as their IPv4 equivalent.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid IP network: \"{value}\"");
+        let (addr, prefix_len) = match value.trim().split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (value.trim(), None),
+        };
+        let addr = IpAddr::from_str(addr)
+            .map_err(|_| invalid())?
+            .to_canonical();
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len.parse::<u8>().map_err(|_| invalid())?,
+            None => max_prefix_len,
+        };
+        if prefix_len > max_prefix_len {
+            return Err(invalid());
+        }
+        Ok(Self { addr, prefix_len })
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// IP Filter Middleware
+///
+/// Validates the client IP address against CIDR allow/deny lists.
+/// Returns `403 Forbidden` with a descriptive error if the address is not permitted.
+///
+/// Like [`DnsRebindProtector`](super::DnsRebindProtector), it should be placed **early**
+/// in the chain, so rejected requests are never processed.
+pub struct IpFilterMiddleware {
+    /// Networks that are allowed to connect. If empty, all addresses that are not denied are allowed.
+    pub allowed_ips: Vec<IpNetwork>,
+    /// Networks that are rejected, takes precedence over `allowed_ips`.
+    pub denied_ips: Vec<IpNetwork>,
+    /// Number of reverse proxies in front of the server. When not zero, the client IP is the
+    /// `X-Forwarded-For` entry this many positions from the right, instead of the remote address.
+    pub trusted_proxies: usize,
+}
+
+#[async_trait]
+impl Middleware for IpFilterMiddleware {
+    /// Processes the incoming request and applies the IP allow/deny lists.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` - If the client IP is permitted, forwards to next handler
+    /// * `Err` via `error_response(403, ...)` - If it is denied or can't be determined
+    async fn handle<'req>(
+        &self,
+        req: Request<&'req str>,
+        state: Arc<McpAppState>,
+        next: MiddlewareNext<'req>,
+    ) -> McpHttpResult<Response<GenericBody>> {
+        if let Err(error) = self.check(&req) {
+            return error_response(StatusCode::FORBIDDEN, error);
+        }
+        next(req, state).await
+    }
+}
+
+impl IpFilterMiddleware {
+    pub fn new(
+        allowed_ips: Vec<IpNetwork>,
+        denied_ips: Vec<IpNetwork>,
+        trusted_proxies: usize,
+    ) -> Self {
+        Self {
+            allowed_ips,
+            denied_ips,
+            trusted_proxies,
+        }
+    }
+
+    /// Resolves the client IP of `req`: the `X-Forwarded-For` entry `trusted_proxies` positions
+    /// from the right when proxies are trusted, or else the remote [`SocketAddr`] stored in the
+    /// request extensions.
+    pub fn client_ip<B>(&self, req: &Request<B>) -> Option<IpAddr> {
+        if self.trusted_proxies == 0 {
+            return req.extensions().get::<SocketAddr>().map(|addr| addr.ip());
+        }
+        let forwarded_for: Vec<&str> = req
+            .headers()
+            .get_all(X_FORWARDED_FOR)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect();
+        let index = forwarded_for.len().checked_sub(self.trusted_proxies)?;
+        IpAddr::from_str(forwarded_for[index].trim()).ok()
+    }
+
+    /// Returns true if `ip` is permitted by the allow/deny lists.
+    pub fn is_allowed(&self, ip: IpAddr) -> bool {
+        if self.denied_ips.iter().any(|network| network.contains(ip)) {
+            return false;
+        }
+        self.allowed_ips.is_empty() || self.allowed_ips.iter().any(|network| network.contains(ip))
+    }
+
+    fn check<B>(&self, req: &Request<B>) -> Result<(), SdkError> {
+        let Some(ip) = self.client_ip(req) else {
+            tracing::warn!("Rejecting request: client IP address is unknown");
+            return Err(forbidden_error("Client IP address is unknown"));
+        };
+        if !self.is_allowed(ip) {
+            return Err(forbidden_error(&format!(
+                "IP address not allowed: \"{ip}\""
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_http::FORBIDDEN_ERROR_CODE;
+
+    fn networks(values: &[&str]) -> Vec<IpNetwork> {
+        values.iter().map(|value| value.parse().unwrap()).collect()
+    }
+
+    fn request(remote: Option<&str>, forwarded_for: Option<&str>) -> Request<&'static str> {
+        let mut builder = Request::builder();
+        if let Some(forwarded_for) = forwarded_for {
+            builder = builder.header(X_FORWARDED_FOR, forwarded_for);
+        }
+        let mut req = builder.body("").unwrap();
+        if let Some(remote) = remote {
+            req.extensions_mut()
+                .insert(SocketAddr::from_str(remote).unwrap());
+        }
+        req
+    }
+
+    #[test]
+    fn test_parse_ip_network() {
+        assert_eq!(
+            "10.0.0.0/8".parse::<IpNetwork>().unwrap().to_string(),
+            "10.0.0.0/8"
+        );
+        assert_eq!(
+            "192.168.1.10".parse::<IpNetwork>().unwrap().to_string(),
+            "192.168.1.10/32"
+        );
+        assert_eq!(
+            "fd00::/8".parse::<IpNetwork>().unwrap().to_string(),
+            "fd00::/8"
+        );
+        assert!("10.0.0.0/33".parse::<IpNetwork>().is_err());
+        assert!("10.0.0/8".parse::<IpNetwork>().is_err());
+        assert!("fd00::/129".parse::<IpNetwork>().is_err());
+    }
+
+    #[test]
+    fn test_ip_network_contains() {
+        let network: IpNetwork = "10.1.0.0/16".parse().unwrap();
+        assert!(network.contains("10.1.2.3".parse().unwrap()));
+        assert!(network.contains("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!network.contains("10.2.0.1".parse().unwrap()));
+        assert!(!network.contains("fd00::1".parse().unwrap()));
+
+        let any: IpNetwork = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains("203.0.113.7".parse().unwrap()));
+
+        let network: IpNetwork = "fd00::/8".parse().unwrap();
+        assert!(network.contains("fd12::1".parse().unwrap()));
+        assert!(!network.contains("fe80::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_deny_takes_precedence_over_allow() {
+        let filter = IpFilterMiddleware::new(networks(&["10.0.0.0/8"]), networks(&["10.0.0.1"]), 0);
+        assert!(filter.is_allowed("10.0.0.2".parse().unwrap()));
+        assert!(!filter.is_allowed("10.0.0.1".parse().unwrap()));
+        assert!(!filter.is_allowed("192.168.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_unknown_client_ip_is_rejected() {
+        let filter = IpFilterMiddleware::new(vec![], networks(&["10.0.0.1"]), 0);
+        assert!(filter.check(&request(Some("127.0.0.1:5000"), None)).is_ok());
+        assert!(filter.check(&request(None, None)).is_err());
+    }
+
+    #[test]
+    fn test_rejections_use_the_forbidden_code() {
+        let filter = IpFilterMiddleware::new(vec![], networks(&["10.0.0.1"]), 0);
+        let error = filter
+            .check(&request(Some("10.0.0.1:5000"), None))
+            .unwrap_err();
+        assert_eq!(error.code, FORBIDDEN_ERROR_CODE);
+        let error = filter.check(&request(None, None)).unwrap_err();
+        assert_eq!(error.code, FORBIDDEN_ERROR_CODE);
+    }
+
+    #[test]
+    fn test_forwarded_for_only_used_when_proxies_are_trusted() {
+        let req = request(Some("127.0.0.1:5000"), Some("10.0.0.1, 192.168.0.5"));
+
+        let untrusted = IpFilterMiddleware::new(vec![], networks(&["10.0.0.1"]), 0);
+        assert_eq!(
+            untrusted.client_ip(&req),
+            Some("127.0.0.1".parse().unwrap())
+        );
+        assert!(untrusted.check(&req).is_ok());
+
+        let one_proxy = IpFilterMiddleware::new(vec![], networks(&["10.0.0.1"]), 1);
+        assert_eq!(
+            one_proxy.client_ip(&req),
+            Some("192.168.0.5".parse().unwrap())
+        );
+        assert!(one_proxy.check(&req).is_ok());
+
+        let two_proxies = IpFilterMiddleware::new(vec![], networks(&["10.0.0.1"]), 2);
+        assert_eq!(
+            two_proxies.client_ip(&req),
+            Some("10.0.0.1".parse().unwrap())
+        );
+        assert!(two_proxies.check(&req).is_err());
+    }
+
+    #[test]
+    fn test_client_set_forwarded_for_entries_are_ignored() {
+        // the client sent "10.0.0.1", the proxy appended the address it received the request from
+        let req = request(Some("127.0.0.1:5000"), Some("10.0.0.1, 203.0.113.7"));
+        let filter = IpFilterMiddleware::new(networks(&["10.0.0.0/8"]), vec![], 1);
+        assert_eq!(filter.client_ip(&req), Some("203.0.113.7".parse().unwrap()));
+        assert!(filter.check(&req).is_err());
+    }
+
+    #[test]
+    fn test_forwarded_for_with_fewer_entries_than_proxies_is_rejected() {
+        let filter = IpFilterMiddleware::new(vec![], vec![], 2);
+        let req = request(Some("127.0.0.1:5000"), Some("203.0.113.7"));
+        assert_eq!(filter.client_ip(&req), None);
+        assert!(filter.check(&req).is_err());
+        assert_eq!(
+            filter.client_ip(&request(Some("127.0.0.1:5000"), None)),
+            None
+        );
+    }
+}
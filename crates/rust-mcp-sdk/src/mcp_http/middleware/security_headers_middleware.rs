@@ -0,0 +1,146 @@
+//! # Security Headers Middleware
+//!
+//! Attaches standard security headers to HTTP responses, hardening servers that are
+//! exposed publicly:
+//! - `X-Content-Type-Options: nosniff` stops browsers from guessing content types
+//! - `Referrer-Policy` limits what is leaked in the `Referer` header
+//! - `Content-Security-Policy` (optional) restricts what a served page may load
+//!
+//! Server-Sent Events streams (`text/event-stream`) are left untouched.
+
+use crate::mcp_http::{types::GenericBody, McpAppState, McpHttpResult, Middleware, MiddlewareNext};
+use http::{
+    header::{CONTENT_SECURITY_POLICY, CONTENT_TYPE, REFERRER_POLICY, X_CONTENT_TYPE_OPTIONS},
+    HeaderName, HeaderValue, Request, Response,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Security headers attached to responses by [`SecurityHeadersMiddleware`].
+///
+/// Headers that a handler already set on a response are kept as is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecurityHeadersConfig {
+    /// If true, `X-Content-Type-Options: nosniff` is added. Default is true.
+    pub content_type_options: bool,
+    /// Value of the `Referrer-Policy` header, `None` to omit it. Default is `no-referrer`.
+    pub referrer_policy: Option<String>,
+    /// Value of the `Content-Security-Policy` header, `None` to omit it. Default is `None`.
+    pub content_security_policy: Option<String>,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            content_type_options: true,
+            referrer_policy: Some("no-referrer".to_string()),
+            content_security_policy: None,
+        }
+    }
+}
+
+/// Middleware adding the headers of a [`SecurityHeadersConfig`] to every response,
+/// except Server-Sent Events streams.
+#[derive(Clone, Default)]
+pub struct SecurityHeadersMiddleware {
+    headers: Arc<Vec<(HeaderName, HeaderValue)>>,
+}
+
+impl SecurityHeadersMiddleware {
+    /// Creates the middleware, header values that are not valid HTTP header values are skipped
+    /// with a warning.
+    pub fn new(config: SecurityHeadersConfig) -> Self {
+        let mut headers = vec![];
+        if config.content_type_options {
+            headers.push((X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")));
+        }
+        for (name, value) in [
+            (REFERRER_POLICY, config.referrer_policy),
+            (CONTENT_SECURITY_POLICY, config.content_security_policy),
+        ] {
+            let Some(value) = value else {
+                continue;
+            };
+            match HeaderValue::from_str(&value) {
+                Ok(value) => headers.push((name, value)),
+                Err(_) => tracing::warn!("Ignoring invalid value for the '{name}' header: {value}"),
+            }
+        }
+        Self {
+            headers: Arc::new(headers),
+        }
+    }
+
+    /// Adds the configured headers to `response`, unless it is a Server-Sent Events stream.
+    pub fn apply(&self, response: &mut Response<GenericBody>) {
+        let is_event_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        if is_event_stream {
+            return;
+        }
+
+        let headers = response.headers_mut();
+        for (name, value) in self.headers.iter() {
+            if !headers.contains_key(name) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for SecurityHeadersMiddleware {
+    async fn handle<'req>(
+        &self,
+        req: Request<&'req str>,
+        state: Arc<McpAppState>,
+        next: MiddlewareNext<'req>,
+    ) -> McpHttpResult<Response<GenericBody>> {
+        let mut response = next(req, state).await?;
+        self.apply(&mut response);
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_http::GenericBodyExt;
+    use http::StatusCode;
+
+    fn response(content_type: &str) -> Response<GenericBody> {
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, content_type)
+            .body(GenericBody::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn adds_configured_headers_but_skips_sse_streams() {
+        let middleware = SecurityHeadersMiddleware::new(SecurityHeadersConfig {
+            content_security_policy: Some("default-src 'none'".to_string()),
+            ..Default::default()
+        });
+
+        let mut json = response("application/json");
+        json.headers_mut()
+            .insert(REFERRER_POLICY, HeaderValue::from_static("same-origin"));
+        middleware.apply(&mut json);
+        assert_eq!(json.headers()[X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(json.headers()[REFERRER_POLICY], "same-origin");
+        assert_eq!(
+            json.headers()[CONTENT_SECURITY_POLICY],
+            "default-src 'none'"
+        );
+
+        let mut sse = response("text/event-stream");
+        middleware.apply(&mut sse);
+        assert!(!sse.headers().contains_key(X_CONTENT_TYPE_OPTIONS));
+        assert!(!sse.headers().contains_key(CONTENT_SECURITY_POLICY));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::mcp_http::{
     DEFAULT_MESSAGES_ENDPOINT, DEFAULT_SSE_ENDPOINT, DEFAULT_STREAMABLE_HTTP_ENDPOINT,
 };
+use crate::utils::remove_query_and_hash;
 
 pub const DEFAULT_MAX_REQUEST_BODY_SIZE: usize = 4 * 1024 * 1024;
 
@@ -34,6 +35,49 @@ pub struct McpMountOptions {
     pub max_request_body_size: usize,
 }
 
+impl McpMountOptions {
+    /// Validates the streamable HTTP endpoint, so a misconfigured path is reported at startup
+    /// rather than by the router of the web framework.
+    ///
+    /// The endpoint must start with `/`, must not contain a query or fragment, and must not be
+    /// the path of another route served by the server: the SSE, messages or health endpoints, or
+    /// any of `reserved_paths` (e.g. the OAuth endpoints of an auth provider).
+    ///
+    /// The endpoint may be the root path `/`. Routes are matched by their exact path, so the
+    /// endpoint then only serves requests to `/` and doesn't shadow the other routes.
+    pub fn validate(&self, reserved_paths: &[&str]) -> Result<(), String> {
+        let endpoint = self.streamable_http_endpoint.as_str();
+        if !endpoint.starts_with('/') {
+            return Err(format!(
+                "Invalid streamable HTTP endpoint '{endpoint}': it must start with '/'."
+            ));
+        }
+        if endpoint.contains(['?', '#']) {
+            return Err(format!(
+                "Invalid streamable HTTP endpoint '{endpoint}': it must not contain a query or fragment."
+            ));
+        }
+
+        let routes = [
+            ("SSE", Some(self.sse_endpoint.as_str())),
+            ("messages", Some(self.sse_messages_endpoint.as_str())),
+            ("health", self.health_endpoint.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, path)| Some((name, path?)))
+        .chain(reserved_paths.iter().map(|path| ("reserved", *path)));
+
+        for (name, path) in routes {
+            if remove_query_and_hash(path) == endpoint {
+                return Err(format!(
+                    "Invalid streamable HTTP endpoint '{endpoint}': it collides with the {name} endpoint '{path}'."
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Default for McpMountOptions {
     fn default() -> Self {
         Self {
@@ -57,3 +101,49 @@ impl Clone for McpMountOptions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_endpoint(endpoint: &str) -> McpMountOptions {
+        McpMountOptions {
+            streamable_http_endpoint: endpoint.into(),
+            health_endpoint: Some("/health".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accepts_valid_endpoints() {
+        assert!(McpMountOptions::default().validate(&[]).is_ok());
+        assert!(with_endpoint("/").validate(&[]).is_ok());
+        assert!(with_endpoint("/api/v1/mcp").validate(&[]).is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_endpoints() {
+        for endpoint in ["", "mcp", "/mcp?x=1", "/mcp#top"] {
+            assert!(with_endpoint(endpoint).validate(&[]).is_err(), "{endpoint}");
+        }
+        assert_eq!(
+            with_endpoint("mcp").validate(&[]).unwrap_err(),
+            "Invalid streamable HTTP endpoint 'mcp': it must start with '/'."
+        );
+    }
+
+    #[test]
+    fn rejects_endpoints_colliding_with_other_routes() {
+        assert_eq!(
+            with_endpoint("/health").validate(&[]).unwrap_err(),
+            "Invalid streamable HTTP endpoint '/health': it collides with the health endpoint '/health'."
+        );
+        assert!(with_endpoint(DEFAULT_SSE_ENDPOINT).validate(&[]).is_err());
+        assert!(with_endpoint(DEFAULT_MESSAGES_ENDPOINT)
+            .validate(&[])
+            .is_err());
+        assert!(with_endpoint("/oauth/token")
+            .validate(&["/oauth/token"])
+            .is_err());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod mcp_icon;
 pub mod tool_box;
+#[cfg(feature = "cli")]
+pub mod tool_cli;
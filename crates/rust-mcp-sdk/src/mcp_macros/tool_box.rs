@@ -6,6 +6,10 @@
 /// This macro creates:
 /// - An enum with the specified name containing variants for each mcp tool
 /// - A `tools()` function returning a vector of supported tools
+/// - An `is_blocking_tool()` function to look up whether a tool, by name, is marked with `#[mcp_tool(blocking = true)]`
+/// - A `tool_timeout()` function to look up the timeout of a tool, by name, set with `#[mcp_tool(timeout_ms = ...)]`
+/// - A `tool_sensitive_fields()` function to look up the arguments of a tool, by name, marked with `#[json_schema(sensitive)]`
+/// - A `metadata()` function returning these lookups as a `ToolBoxMetadata`, for `ServerHandler::tool_box()`
 /// - A `TryFrom<CallToolRequestParams>` implementation for converting requests to tool instances
 ///
 /// # Arguments
@@ -49,6 +53,59 @@ macro_rules! tool_box {
                 }
             }
 
+            /// Returns true if the tool is marked as blocking
+            pub fn is_blocking(&self) -> bool {
+                match self {
+                    $(
+                        $enum_name::$tool(_) => $tool::is_blocking(),
+                    )*
+                }
+            }
+
+            /// Returns true if `tool_name` belongs to a tool that is marked as blocking.
+            /// Can be used to implement `ServerHandler::is_blocking_tool()`.
+            pub fn is_blocking_tool(tool_name: &str) -> bool {
+                $(
+                    if tool_name == $tool::tool_name().as_str() {
+                        return $tool::is_blocking();
+                    }
+                )*
+                false
+            }
+
+            /// Returns the timeout of the tool named `tool_name`, if it sets one with `timeout_ms`.
+            /// Can be used to implement `ServerHandler::tool_timeout()`.
+            pub fn tool_timeout(tool_name: &str) -> Option<std::time::Duration> {
+                $(
+                    if tool_name == $tool::tool_name().as_str() {
+                        return $tool::timeout();
+                    }
+                )*
+                None
+            }
+
+            /// Returns the paths of the arguments of the tool named `tool_name` that are marked with
+            /// `#[json_schema(sensitive)]`. Can be used to implement `ServerHandler::tool_sensitive_fields()`.
+            pub fn tool_sensitive_fields(tool_name: &str) -> Vec<String> {
+                $(
+                    if tool_name == $tool::tool_name().as_str() {
+                        return $tool::sensitive_fields();
+                    }
+                )*
+                Vec::new()
+            }
+
+            /// Returns the per-tool settings of the tool box, to be returned from `ServerHandler::tool_box()`
+            /// so the runtime applies them without overriding `is_blocking_tool()`, `tool_timeout()`
+            /// or `tool_sensitive_fields()`.
+            pub fn metadata() -> rust_mcp_sdk::mcp_server::ToolBoxMetadata {
+                rust_mcp_sdk::mcp_server::ToolBoxMetadata {
+                    is_blocking_tool: Self::is_blocking_tool,
+                    tool_timeout: Self::tool_timeout,
+                    tool_sensitive_fields: Self::tool_sensitive_fields,
+                }
+            }
+
             /// Returns a vector containing instances of all supported tools
             pub fn tools() -> Vec<rust_mcp_sdk::schema::Tool> {
                 vec![
@@ -0,0 +1,38 @@
+#[macro_export]
+/// Runs a command line interface for the tools of a toolbox generated by [`tool_box!`](crate::tool_box).
+///
+/// Each tool becomes a subcommand and each property of its input schema a `--<property>` flag.
+/// The parsed arguments are converted into the toolbox enum and passed to the handler, which calls
+/// the tool; its result is printed as JSON. Useful to try tools locally without an MCP client.
+///
+/// Expands to a future resolving to a `std::process::ExitCode`, see [`ToolCli::run`](crate::cli::ToolCli::run).
+///
+/// # Arguments
+/// * `$tool_box` - The toolbox enum created with `tool_box!`
+/// * `$handler` - An async closure receiving the toolbox enum and returning `Result<CallToolResult, CallToolError>`
+///
+/// # Example
+/// ```ignore
+/// tool_box!(GreetingTools, [SayHelloTool, SayGoodbyeTool]);
+///
+/// #[tokio::main]
+/// async fn main() -> std::process::ExitCode {
+///     // e.g. `greeting-cli say_hello --name world`
+///     tool_cli!(GreetingTools, |tool| async move {
+///         match tool {
+///             GreetingTools::SayHelloTool(tool) => tool.call_tool(),
+///             GreetingTools::SayGoodbyeTool(tool) => tool.call_tool(),
+///         }
+///     })
+///     .await
+/// }
+/// ```
+macro_rules! tool_cli {
+    ($tool_box:ident, $handler:expr) => {
+        $crate::cli::ToolCli::new($tool_box::tools()).run::<$tool_box, _, _, _, _>(
+            env!("CARGO_PKG_NAME"),
+            std::env::args_os(),
+            $handler,
+        )
+    };
+}
@@ -2,3 +2,8 @@
 pub mod client_runtime;
 #[cfg(feature = "server")]
 pub mod server_runtime;
+#[cfg(any(feature = "client", feature = "server"))]
+mod shutdown_guard;
+
+#[cfg(any(feature = "client", feature = "server"))]
+pub use shutdown_guard::ShutdownGuard;
@@ -2,7 +2,10 @@ pub mod mcp_client_runtime;
 pub mod mcp_client_runtime_core;
 use crate::error::{McpSdkError, SdkResult};
 use crate::id_generator::FastIdGenerator;
-use crate::mcp_traits::{McpClient, McpClientHandler};
+use crate::mcp_runtimes::ShutdownGuard;
+use crate::mcp_traits::{
+    ClientMiddleware, McpClient, McpClientHandler, ProcessStderrStream, DEFAULT_MAX_LIST_PAGES,
+};
 use crate::task_store::{ClientTaskStore, ServerTaskStore, TaskStatusPoller, TaskStatusUpdate};
 use crate::utils::ensure_server_protocole_compatibility;
 use crate::McpObserver;
@@ -10,25 +13,35 @@ use crate::{
     mcp_traits::{RequestIdGen, RequestIdGenNumeric},
     schema::{
         schema_utils::{
-            ClientMessage, ClientMessages, FromMessage, MessageFromClient, NotificationFromClient,
-            RequestFromClient, ServerMessage, ServerMessages,
+            ClientJsonrpcRequest, ClientMessage, ClientMessages, FromMessage, MessageFromClient,
+            NotificationFromClient, RequestFromClient, ResultFromServer, SdkError,
+            ServerJsonrpcNotification, ServerJsonrpcRequest, ServerMessage, ServerMessages,
         },
-        InitializeRequestParams, InitializeResult, RequestId, RpcError,
+        CancelledNotificationParams, InitializeRequestParams, InitializeResult,
+        JsonrpcErrorResponse, ProgressNotificationParams, ProgressToken, RequestId, RpcError,
     },
 };
 use async_trait::async_trait;
 use futures::future::try_join_all;
 use futures::StreamExt;
-use rust_mcp_schema::schema_utils::ResultFromServer;
 use rust_mcp_schema::{GetTaskParams, GetTaskPayloadParams};
+use rust_mcp_transport::error::TransportError;
 #[cfg(feature = "streamable-http")]
 use rust_mcp_transport::{ClientStreamableTransport, StreamableTransportOptions};
-use rust_mcp_transport::{IoStream, SessionId, StreamId, TaskId, TransportDispatcher};
-use std::{sync::Arc, time::Duration};
+use rust_mcp_transport::{
+    IoStream, SessionId, StreamId, TaskId, TransportDispatcher, TransportKind,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader, Lines};
-use tokio::sync::{watch, Mutex};
+use tokio::sync::{broadcast, oneshot, watch, Mutex, OwnedSemaphorePermit, Semaphore};
 
 pub const DEFAULT_STREAM_ID: &str = "STANDALONE-STREAM";
+/// Default maximum number of server-initiated requests (e.g. sampling, elicitation, roots/list)
+/// handled concurrently, see `ClientRuntime::set_max_concurrent_server_requests()`.
+pub const DEFAULT_MAX_CONCURRENT_SERVER_REQUESTS: usize = 32;
+/// Number of stderr lines buffered for each `process_stderr()` stream, slower consumers skip lines.
+const PROCESS_STDERR_CAPACITY: usize = 256;
 
 // Define a type alias for the TransportDispatcher trait object
 type TransportDispatcherType = dyn TransportDispatcher<
@@ -39,6 +52,196 @@ type TransportDispatcherType = dyn TransportDispatcher<
     ClientMessage,
 >;
 type TransportType = Arc<TransportDispatcherType>;
+type InFlightRequests = std::sync::Mutex<HashMap<RequestId, oneshot::Sender<()>>>;
+type ProgressCallback = Arc<dyn Fn(ProgressNotificationParams) + Send + Sync>;
+type ProgressCallbacks = std::sync::Mutex<HashMap<String, ProgressCallback>>;
+
+/// Removes a request from the in-flight requests once its `send()` completes or is dropped.
+struct InFlightRequest<'a> {
+    requests: &'a InFlightRequests,
+    request_id: RequestId,
+}
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.remove(&self.request_id);
+        }
+    }
+}
+
+/// Removes a progress callback once the request of `request_with_progress()` completes or is dropped.
+struct ProgressRegistration<'a> {
+    callbacks: &'a ProgressCallbacks,
+    progress_token: String,
+}
+
+impl Drop for ProgressRegistration<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut callbacks) = self.callbacks.lock() {
+            callbacks.remove(&self.progress_token);
+        }
+    }
+}
+
+/// Sets the progress token in the `_meta` of a request,
+/// returns false for requests that do not accept one.
+fn set_progress_token(request: &mut RequestFromClient, progress_token: ProgressToken) -> bool {
+    let token = Some(progress_token);
+    match request {
+        RequestFromClient::InitializeRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::PingRequest(params) => {
+            let params = params.get_or_insert_with(Default::default);
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::ListResourcesRequest(params)
+        | RequestFromClient::ListResourceTemplatesRequest(params)
+        | RequestFromClient::ListPromptsRequest(params)
+        | RequestFromClient::ListToolsRequest(params)
+        | RequestFromClient::ListTasksRequest(params) => {
+            let params = params.get_or_insert_with(Default::default);
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::ReadResourceRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::SubscribeRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::UnsubscribeRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::GetPromptRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::CallToolRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::SetLevelRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::CompleteRequest(params) => {
+            params
+                .meta
+                .get_or_insert_with(Default::default)
+                .progress_token = token
+        }
+        RequestFromClient::GetTaskRequest(_)
+        | RequestFromClient::GetTaskPayloadRequest(_)
+        | RequestFromClient::CancelTaskRequest(_) => return false,
+        RequestFromClient::CustomRequest(request) => {
+            let meta = request
+                .params
+                .get_or_insert_with(Default::default)
+                .entry("_meta")
+                .or_insert_with(|| serde_json::Value::Object(Default::default()));
+            let Some(meta) = meta.as_object_mut() else {
+                return false;
+            };
+            meta.insert("progressToken".to_string(), serde_json::json!(token));
+        }
+    }
+    true
+}
+
+/// Returns the id of `message` if it is a request that may be cancelled,
+/// the `initialize` request must not be cancelled.
+fn cancellable_request_id(message: &ClientMessage) -> Option<RequestId> {
+    match message {
+        ClientMessage::Request(ClientJsonrpcRequest::InitializeRequest(_)) => None,
+        ClientMessage::Request(request) => Some(request.request_id().clone()),
+        _ => None,
+    }
+}
+
+/// Tees the lines of the server process's stderr to the streams returned by `process_stderr()`.
+struct ProcessStderr {
+    // `None` once stderr is closed, which ends the streams
+    sender: std::sync::Mutex<Option<broadcast::Sender<String>>>,
+}
+
+impl ProcessStderr {
+    fn new() -> Self {
+        Self {
+            sender: std::sync::Mutex::new(Some(broadcast::channel(PROCESS_STDERR_CAPACITY).0)),
+        }
+    }
+
+    /// For transports without a server process, whose streams are empty.
+    fn closed() -> Self {
+        Self {
+            sender: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn publish(&self, line: &str) {
+        if let Ok(sender) = self.sender.lock() {
+            if let Some(sender) = sender.as_ref().filter(|sender| sender.receiver_count() > 0) {
+                let _ = sender.send(line.to_string());
+            }
+        }
+    }
+
+    fn close(&self) {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+    }
+
+    fn subscribe(&self) -> ProcessStderrStream {
+        let receiver = self
+            .sender
+            .lock()
+            .ok()
+            .and_then(|sender| sender.as_ref().map(|sender| sender.subscribe()));
+        let Some(receiver) = receiver else {
+            return Box::pin(futures::stream::empty());
+        };
+        Box::pin(futures::stream::unfold(
+            receiver,
+            |mut receiver| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(line) => return Some((line, receiver)),
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!("Process stderr stream lagged: skipped {skipped} lines");
+                        }
+                    }
+                }
+            },
+        ))
+    }
+}
 
 async fn next_process_error<R>(reader: &mut Lines<BufReader<R>>) -> std::io::Result<Option<String>>
 where
@@ -63,8 +266,16 @@ where
     pub task_store: Option<Arc<ClientTaskStore>>,
     pub server_task_store: Option<Arc<ServerTaskStore>>,
     pub message_observer: Option<Arc<dyn McpObserver<ServerMessage, ClientMessage>>>,
+    /// Maximum number of requests awaiting a response at the same time, further requests wait
+    /// for one of them to complete. `None` leaves the number of requests unbounded.
+    pub max_concurrent_requests: Option<usize>,
 }
 
+/// Runtime of an MCP client, handling the transport and the messages exchanged with the server.
+///
+/// The runtime's locks (`transport_map`, `session_id`, `handlers` and `is_shut_down`) are never nested:
+/// each guard is released before another lock is acquired, and before awaiting the transport or a handler.
+/// The transport is cloned out of `transport_map` with `current_transport()` instead of being used through the guard.
 pub struct ClientRuntime {
     // A thread-safe map storing transport types
     transport_map: tokio::sync::RwLock<Option<TransportType>>,
@@ -90,6 +301,22 @@ pub struct ClientRuntime {
     task_store: Option<Arc<ClientTaskStore>>,
     server_task_store: Option<Arc<ServerTaskStore>>,
     message_observer: Option<Arc<dyn McpObserver<ServerMessage, ClientMessage>>>,
+    // Limits how many server-initiated requests are handled at the same time
+    server_request_permits: std::sync::RwLock<Arc<Semaphore>>,
+    // Requests awaiting a response, mapped to the sender that cancels them
+    in_flight_requests: InFlightRequests,
+    // Lines of the server process's stderr, for `process_stderr()` streams
+    process_stderr: ProcessStderr,
+    // Hooks invoked around every sent and received message
+    middlewares: std::sync::RwLock<Vec<Arc<dyn ClientMiddleware>>>,
+    // Page cap of the `list_all_*` methods, see `set_max_list_pages()`
+    max_list_pages: AtomicUsize,
+    // Callbacks of `request_with_progress()`, keyed by the progress token of their request
+    progress_callbacks: ProgressCallbacks,
+    // Generator for progress tokens
+    progress_token_gen: FastIdGenerator,
+    // Bounds the requests sent at the same time, see `set_max_concurrent_requests()`
+    request_permits: std::sync::RwLock<Option<Arc<Semaphore>>>,
 }
 
 impl ClientRuntime {
@@ -103,6 +330,12 @@ impl ClientRuntime {
     ) -> Self {
         let (server_details_tx, server_details_rx) =
             watch::channel::<Option<InitializeResult>>(None);
+        // only the stdio transport launches a server process
+        let process_stderr = if transport.transport_kind() == TransportKind::Stdio {
+            ProcessStderr::new()
+        } else {
+            ProcessStderr::closed()
+        };
         Self {
             transport_map: tokio::sync::RwLock::new(Some(transport)),
             handler,
@@ -119,18 +352,32 @@ impl ClientRuntime {
             task_store,
             server_task_store,
             message_observer,
+            server_request_permits: std::sync::RwLock::new(Arc::new(Semaphore::new(
+                DEFAULT_MAX_CONCURRENT_SERVER_REQUESTS,
+            ))),
+            in_flight_requests: Default::default(),
+            process_stderr,
+            middlewares: Default::default(),
+            max_list_pages: AtomicUsize::new(DEFAULT_MAX_LIST_PAGES),
+            progress_callbacks: Default::default(),
+            progress_token_gen: FastIdGenerator::new(Some("p_")),
+            request_permits: Default::default(),
         }
     }
 
     #[cfg(feature = "streamable-http")]
     pub(crate) fn new_instance(
         client_details: InitializeRequestParams,
-        transport_options: StreamableTransportOptions,
+        mut transport_options: StreamableTransportOptions,
         handler: Box<dyn McpClientHandler>,
         task_store: Option<Arc<ClientTaskStore>>,
         server_task_store: Option<Arc<ServerTaskStore>>,
         message_observer: Option<Arc<dyn McpObserver<ServerMessage, ClientMessage>>>,
     ) -> Self {
+        // all transports of this client (POST requests, standalone SSE stream) share one connection pool
+        if let Err(err) = transport_options.share_http_client() {
+            tracing::warn!("Failed to create a shared HTTP client: {err}");
+        }
         let (server_details_tx, server_details_rx) =
             watch::channel::<Option<InitializeResult>>(None);
         Self {
@@ -148,10 +395,35 @@ impl ClientRuntime {
             task_store,
             server_task_store,
             message_observer,
+            server_request_permits: std::sync::RwLock::new(Arc::new(Semaphore::new(
+                DEFAULT_MAX_CONCURRENT_SERVER_REQUESTS,
+            ))),
+            in_flight_requests: Default::default(),
+            process_stderr: ProcessStderr::closed(),
+            middlewares: Default::default(),
+            max_list_pages: AtomicUsize::new(DEFAULT_MAX_LIST_PAGES),
+            progress_callbacks: Default::default(),
+            progress_token_gen: FastIdGenerator::new(Some("p_")),
+            request_permits: Default::default(),
         }
     }
 
     async fn initialize_request(self: Arc<Self>) -> SdkResult<()> {
+        self.initialize_handshake().await?;
+
+        #[cfg(feature = "streamable-http")]
+        // try to create a sse stream for server initiated messages , if supported by the server
+        if let Err(error) = self.clone().create_sse_stream().await {
+            tracing::warn!("{error}");
+        }
+
+        // send a InitializedNotification to the server
+        self.send_notification(NotificationFromClient::InitializedNotification(None))
+            .await
+    }
+
+    /// Sends the InitializeRequest and stores the server details of a compatible response.
+    async fn initialize_handshake(&self) -> SdkResult<InitializeResult> {
         let result: ResultFromServer = self
             .request(
                 RequestFromClient::InitializeRequest(self.client_details.clone()),
@@ -159,35 +431,30 @@ impl ClientRuntime {
             )
             .await?;
 
-        if let ResultFromServer::InitializeResult(initialize_result) = result {
-            ensure_server_protocole_compatibility(
-                &self.client_details.protocol_version,
-                &initialize_result.protocol_version,
-            )?;
-            // store server details
-            self.set_server_details(initialize_result)?;
-
-            #[cfg(feature = "streamable-http")]
-            // try to create a sse stream for server initiated messages , if supported by the server
-            if let Err(error) = self.clone().create_sse_stream().await {
-                tracing::warn!("{error}");
-            }
-
-            // send a InitializedNotification to the server
-            self.send_notification(NotificationFromClient::InitializedNotification(None))
-                .await?;
-        } else {
+        let ResultFromServer::InitializeResult(initialize_result) = result else {
             return Err(RpcError::invalid_params()
                 .with_message("Incorrect response to InitializeRequest!")
                 .into());
-        }
+        };
 
-        Ok(())
+        ensure_server_protocole_compatibility(
+            &self.client_details.protocol_version,
+            &initialize_result.protocol_version,
+        )?;
+        // store server details
+        self.set_server_details(initialize_result.clone())?;
+        Ok(initialize_result)
+    }
+
+    /// Returns a receiver notified every time the server details change, once the initial
+    /// handshake completes and after each [`McpClient::reinitialize`].
+    pub fn watch_server_details(&self) -> watch::Receiver<Option<InitializeResult>> {
+        self.server_details_rx.clone()
     }
 
     pub(crate) async fn handle_message(
         &self,
-        message: ServerMessage,
+        mut message: ServerMessage,
         transport: &TransportType,
     ) -> SdkResult<Option<ClientMessage>> {
         // telemetry
@@ -195,7 +462,27 @@ impl ClientRuntime {
             observer.on_receive(&message);
         }
 
-        let response = match message {
+        if let Err(error) = self.apply_receive_middlewares(&mut message).await {
+            tracing::warn!("Message rejected by a client middleware: {error}");
+            // fail the pending request instead of leaving it waiting for a response
+            let request_id = match &message {
+                ServerMessage::Response(response) => Some(response.id.clone()),
+                ServerMessage::Error(jsonrpc_error) => jsonrpc_error.id.clone(),
+                _ => None,
+            };
+            if let Some(request_id) = request_id {
+                if let Some(tx_response) = transport.pending_request_tx(&request_id).await {
+                    let error = RpcError::internal_error().with_message(error.to_string());
+                    let _ = tx_response.send(ServerMessage::Error(JsonrpcErrorResponse::new(
+                        error,
+                        Some(request_id),
+                    )));
+                }
+            }
+            return Ok(None);
+        }
+
+        let mut response = match message {
             ServerMessage::Request(jsonrpc_request) => {
                 let request_id = jsonrpc_request.request_id().clone();
                 let result = self.handler.handle_request(jsonrpc_request, self).await;
@@ -210,6 +497,11 @@ impl ClientRuntime {
                 Some(mcp_message)
             }
             ServerMessage::Notification(jsonrpc_notification) => {
+                if let ServerJsonrpcNotification::ProgressNotification(notification) =
+                    &jsonrpc_notification
+                {
+                    self.forward_progress(&notification.params);
+                }
                 self.handler
                     .handle_notification(jsonrpc_notification.into(), self)
                     .await?;
@@ -247,22 +539,307 @@ impl ClientRuntime {
                 None
             }
         };
+        // responses to server requests are sent by the caller
+        if let Some(response) = response.as_mut() {
+            self.apply_send_middlewares(response).await?;
+        }
         Ok(response)
     }
 
-    async fn start_standalone(self: Arc<Self>) -> SdkResult<()> {
-        let self_clone = self.clone();
-        let transport_map = self_clone.transport_map.read().await;
-        let transport = transport_map.as_ref().ok_or(
+    /// Handles a single message received on a long-lived stream.
+    ///
+    /// Server-initiated requests are handled on their own task, so that a slow handler
+    /// (e.g. a sampling request waiting on an LLM) does not hold up other requests on the stream.
+    /// Each response carries the id of the request it answers, so correlation does not depend on ordering.
+    /// Notifications and responses are handled inline to preserve the order in which they were received,
+    /// as are pings, so that they are answered promptly even when all request slots are busy.
+    async fn dispatch_stream_message(
+        self: &Arc<Self>,
+        message: ServerMessage,
+        transport: &TransportType,
+    ) -> SdkResult<()> {
+        let handle_inline = match &message {
+            ServerMessage::Request(request) => {
+                matches!(request, ServerJsonrpcRequest::PingRequest(_))
+            }
+            _ => true,
+        };
+        if handle_inline {
+            if let Some(response) = self.handle_message(message, transport).await? {
+                transport
+                    .send_message(ClientMessages::Single(response), None)
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        let permit = self.acquire_server_request_permit().await?;
+
+        let runtime = Arc::clone(self);
+        let transport = transport.clone();
+        tokio::spawn(async move {
+            let _permit = permit;
+            match runtime.handle_message(message, &transport).await {
+                Ok(Some(response)) => {
+                    if let Err(error) = transport
+                        .send_message(ClientMessages::Single(response), None)
+                        .await
+                    {
+                        tracing::error!("Error sending response : {}", error)
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => tracing::error!("Error handling message : {}", error),
+            }
+        });
+        Ok(())
+    }
+
+    /// Handles a batch received on a long-lived stream.
+    ///
+    /// Batches containing requests are handled on their own task, each request taking a slot of
+    /// `set_max_concurrent_server_requests()` and running in parallel with the others.
+    /// The responses are sent back as a single batch once all of them completed.
+    async fn dispatch_stream_batch(
+        self: &Arc<Self>,
+        messages: Vec<ServerMessage>,
+        transport: &TransportType,
+    ) -> SdkResult<()> {
+        if !messages
+            .iter()
+            .any(|message| matches!(message, ServerMessage::Request(_)))
+        {
+            let handling_tasks: Vec<_> = messages
+                .into_iter()
+                .map(|message| self.handle_message(message, transport))
+                .collect();
+            let results: Vec<_> = try_join_all(handling_tasks).await?;
+            let results: Vec<_> = results.into_iter().flatten().collect();
+            if !results.is_empty() {
+                transport
+                    .send_message(ClientMessages::Batch(results), None)
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        let runtime = Arc::clone(self);
+        let transport = transport.clone();
+        tokio::spawn(async move {
+            let handling_tasks: Vec<_> = messages
+                .into_iter()
+                .map(|message| {
+                    let runtime = Arc::clone(&runtime);
+                    let transport = transport.clone();
+                    tokio::spawn(async move {
+                        let _permit = if matches!(message, ServerMessage::Request(_)) {
+                            Some(runtime.acquire_server_request_permit().await?)
+                        } else {
+                            None
+                        };
+                        runtime.handle_message(message, &transport).await
+                    })
+                })
+                .collect();
+
+            let mut results = Vec::with_capacity(handling_tasks.len());
+            for handling_task in handling_tasks {
+                match handling_task.await {
+                    Ok(Ok(Some(response))) => results.push(response),
+                    Ok(Ok(None)) => {}
+                    Ok(Err(error)) => tracing::error!("Error handling message : {}", error),
+                    Err(error) => tracing::error!("Error handling message : {}", error),
+                }
+            }
+            if !results.is_empty() {
+                if let Err(error) = transport
+                    .send_message(ClientMessages::Batch(results), None)
+                    .await
+                {
+                    tracing::error!("Error sending response : {}", error)
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Waits for a slot to handle a server-initiated request.
+    async fn acquire_server_request_permit(&self) -> SdkResult<OwnedSemaphorePermit> {
+        let server_request_permits = self
+            .server_request_permits
+            .read()
+            .map(|permits| permits.clone())
+            .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
+        let permit = server_request_permits
+            .acquire_owned()
+            .await
+            .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
+        Ok(permit)
+    }
+
+    /// Sets the maximum number of server-initiated requests (e.g. sampling, elicitation, roots/list)
+    /// handled at the same time, [`DEFAULT_MAX_CONCURRENT_SERVER_REQUESTS`] by default.
+    /// Further requests wait on the stream until a slot becomes available.
+    ///
+    /// Requests already being handled keep the limit that was set when they were received.
+    pub fn set_max_concurrent_server_requests(&self, max_concurrent_requests: usize) {
+        if let Ok(mut server_request_permits) = self.server_request_permits.write() {
+            *server_request_permits = Arc::new(Semaphore::new(max_concurrent_requests));
+        }
+    }
+
+    /// Registers a [`ClientMiddleware`] invoked around every message sent to, or received from, the server.
+    /// Middlewares run in registration order and should be added before the client is started.
+    pub fn add_middleware<M: ClientMiddleware>(&self, middleware: M) {
+        if let Ok(mut middlewares) = self.middlewares.write() {
+            middlewares.push(Arc::new(middleware));
+        }
+    }
+
+    /// Sets the maximum number of pages the `list_all_*` methods request before returning an error,
+    /// [`DEFAULT_MAX_LIST_PAGES`] by default.
+    pub fn set_max_list_pages(&self, max_pages: usize) {
+        self.max_list_pages.store(max_pages, Ordering::Relaxed);
+    }
+
+    /// Limits the number of requests awaiting a response at the same time to `max_concurrent_requests`,
+    /// further requests wait until one of them completes. `None` removes the limit.
+    ///
+    /// Requests already waiting keep the limit that was set when they were sent.
+    pub fn set_max_concurrent_requests(&self, max_concurrent_requests: Option<usize>) {
+        if let Ok(mut request_permits) = self.request_permits.write() {
+            *request_permits = max_concurrent_requests.map(|max| Arc::new(Semaphore::new(max)));
+        }
+    }
+
+    /// Returns the number of requests sent to the server that are still awaiting a response.
+    pub fn in_flight_request_count(&self) -> usize {
+        self.in_flight_requests
+            .lock()
+            .map(|requests| requests.len())
+            .unwrap_or_default()
+    }
+
+    /// Waits for a slot to send `message` if it is a request and a limit is set with `set_max_concurrent_requests()`.
+    async fn acquire_request_permit(
+        &self,
+        message: &MessageFromClient,
+    ) -> SdkResult<Option<OwnedSemaphorePermit>> {
+        if !matches!(message, MessageFromClient::RequestFromClient(_)) {
+            return Ok(None);
+        }
+        let request_permits = self
+            .request_permits
+            .read()
+            .ok()
+            .and_then(|request_permits| request_permits.clone());
+        let Some(request_permits) = request_permits else {
+            return Ok(None);
+        };
+        let permit = request_permits
+            .acquire_owned()
+            .await
+            .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?;
+        Ok(Some(permit))
+    }
+
+    /// Sends a request with a unique progress token in its `_meta`, and invokes `on_progress`
+    /// for every `notifications/progress` the server sends for it, until the request completes.
+    ///
+    /// Progress notifications are still passed to the client handler as well.
+    /// Returns an error for the task requests (`tasks/get`, `tasks/result`, `tasks/cancel`)
+    /// since they do not accept a progress token.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let result = client
+    ///     .request_with_progress(
+    ///         RequestFromClient::CallToolRequest(params),
+    ///         None,
+    ///         |progress| println!("{}/{:?}", progress.progress, progress.total),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn request_with_progress(
+        &self,
+        mut request: RequestFromClient,
+        timeout: Option<Duration>,
+        on_progress: impl Fn(ProgressNotificationParams) + Send + Sync + 'static,
+    ) -> SdkResult<ResultFromServer> {
+        use crate::IdGenerator;
+        let progress_token: String = self.progress_token_gen.generate();
+        if !set_progress_token(&mut request, ProgressToken::String(progress_token.clone())) {
+            return Err(RpcError::invalid_request()
+                .with_message(format!(
+                    "'{}' requests do not accept a progress token",
+                    request.method()
+                ))
+                .into());
+        }
+
+        if let Ok(mut callbacks) = self.progress_callbacks.lock() {
+            callbacks.insert(progress_token.clone(), Arc::new(on_progress));
+        }
+        let _registration = ProgressRegistration {
+            callbacks: &self.progress_callbacks,
+            progress_token,
+        };
+        self.request(request, timeout).await
+    }
+
+    /// Invokes the callback registered by `request_with_progress()` for the token of a progress notification.
+    fn forward_progress(&self, params: &ProgressNotificationParams) {
+        let ProgressToken::String(progress_token) = &params.progress_token else {
+            return;
+        };
+        let callback = self
+            .progress_callbacks
+            .lock()
+            .ok()
+            .and_then(|callbacks| callbacks.get(progress_token).cloned());
+        if let Some(callback) = callback {
+            callback(params.clone());
+        }
+    }
+
+    fn middlewares(&self) -> Vec<Arc<dyn ClientMiddleware>> {
+        self.middlewares
+            .read()
+            .map(|middlewares| middlewares.clone())
+            .unwrap_or_default()
+    }
+
+    async fn apply_send_middlewares(&self, message: &mut ClientMessage) -> SdkResult<()> {
+        for middleware in self.middlewares() {
+            middleware.on_send(message).await?;
+        }
+        Ok(())
+    }
+
+    async fn apply_receive_middlewares(&self, message: &mut ServerMessage) -> SdkResult<()> {
+        for middleware in self.middlewares() {
+            middleware.on_receive(message).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current transport, cloned out of `transport_map` so the lock is released
+    /// before the transport is used.
+    async fn current_transport(&self) -> SdkResult<TransportType> {
+        self.transport_map.read().await.clone().ok_or_else(|| {
             RpcError::internal_error()
-                .with_message("transport stream does not exists or is closed!".to_string()),
-        )?;
+                .with_message("transport stream does not exists or is closed!".to_string())
+                .into()
+        })
+    }
+
+    async fn start_standalone(self: Arc<Self>) -> SdkResult<()> {
+        let transport = self.current_transport().await?;
 
         //TODO: improve the flow
         let mut stream = transport.start().await?;
 
-        let mut error_io_stream = transport.error_stream().write().await;
-        let error_io_stream = error_io_stream.take();
+        let error_io_stream = transport.error_stream().write().await.take();
 
         let self_clone = Arc::clone(&self);
         let self_clone_err = Arc::clone(&self);
@@ -271,69 +848,56 @@ impl ClientRuntime {
         let err_task = tokio::spawn(async move {
             let self_ref = &*self_clone_err;
 
-            if let Some(IoStream::Readable(error_input)) = error_io_stream {
-                let mut reader = BufReader::new(error_input).lines();
-                loop {
-                    match next_process_error(&mut reader).await {
-                        Ok(Some(error_message)) => {
-                            self_ref
-                                .handler
-                                .handle_process_error(error_message, self_ref)
-                                .await?;
-                        }
-                        Ok(None) => {
-                            // Transport shutdown terminates the child and closes stderr.
-                            break;
-                        }
-                        Err(e) => {
-                            tracing::error!("Error reading from std_err: {e}");
-                            break;
+            let result = async {
+                if let Some(IoStream::Readable(error_input)) = error_io_stream {
+                    let mut reader = BufReader::new(error_input).lines();
+                    loop {
+                        match next_process_error(&mut reader).await {
+                            Ok(Some(error_message)) => {
+                                self_ref.process_stderr.publish(&error_message);
+                                self_ref
+                                    .handler
+                                    .handle_process_error(error_message, self_ref)
+                                    .await?;
+                            }
+                            Ok(None) => {
+                                // Transport shutdown terminates the child and closes stderr.
+                                break;
+                            }
+                            Err(e) => {
+                                tracing::error!("Error reading from std_err: {e}");
+                                break;
+                            }
                         }
                     }
                 }
+                Ok::<(), McpSdkError>(())
             }
+            .await;
+            // end the `process_stderr()` streams, also when the handler fails
+            self_ref.process_stderr.close();
 
-            Ok::<(), McpSdkError>(())
+            result
         });
 
-        let transport = transport.clone();
-
         // main task reading from mcp_message stream
         let main_task = tokio::spawn(async move {
             while let Some(mcp_messages) = stream.next().await {
-                let self_ref = &*self_clone;
-
                 match mcp_messages {
                     ServerMessages::Single(server_message) => {
-                        let result = self_ref.handle_message(server_message, &transport).await;
-
-                        match result {
-                            Ok(result) => {
-                                if let Some(result) = result {
-                                    transport
-                                        .send_message(ClientMessages::Single(result), None)
-                                        .await?;
-                                }
-                            }
-                            Err(error) => {
-                                tracing::error!("Error handling message : {}", error)
-                            }
+                        if let Err(error) = self_clone
+                            .dispatch_stream_message(server_message, &transport)
+                            .await
+                        {
+                            tracing::error!("Error handling message : {}", error)
                         }
                     }
                     ServerMessages::Batch(server_messages) => {
-                        let handling_tasks: Vec<_> = server_messages
-                            .into_iter()
-                            .map(|server_message| {
-                                self_ref.handle_message(server_message, &transport)
-                            })
-                            .collect();
-                        let results: Vec<_> = try_join_all(handling_tasks).await?;
-                        let results: Vec<_> = results.into_iter().flatten().collect();
-
-                        if !results.is_empty() {
-                            transport
-                                .send_message(ClientMessages::Batch(results), None)
-                                .await?;
+                        if let Err(error) = self_clone
+                            .dispatch_stream_batch(server_messages, &transport)
+                            .await
+                        {
+                            tracing::error!("Error handling message : {}", error)
                         }
                     }
                 }
@@ -381,11 +945,25 @@ impl ClientRuntime {
             .transport_options
             .as_ref()
             .ok_or(SdkError::connection_closed())?;
-        let transport = ClientStreamableTransport::new(options, session_id, standalone)?;
+        let mut transport = ClientStreamableTransport::new(options, session_id, standalone)?;
+
+        // requests sent after initialization carry the negotiated protocol version
+        if let Some(protocol_version) = self.negotiated_protocol_version() {
+            transport = transport.with_protocol_version(&protocol_version)?;
+        }
 
         Ok(transport)
     }
 
+    /// The protocol version agreed on with the server, once initialization is complete.
+    #[cfg(feature = "streamable-http")]
+    fn negotiated_protocol_version(&self) -> Option<String> {
+        self.server_details_rx
+            .borrow()
+            .as_ref()
+            .map(|server_details| server_details.protocol_version.clone())
+    }
+
     #[cfg(feature = "streamable-http")]
     pub(crate) async fn create_sse_stream(self: Arc<Self>) -> SdkResult<()> {
         let stream_id: StreamId = DEFAULT_STREAM_ID.into();
@@ -467,26 +1045,15 @@ async fn sse_reconnect_loop(
             match stream.next().await {
                 Some(ServerMessages::Single(server_message)) => {
                     reconnect_attempt = 0;
-                    let result = runtime.handle_message(server_message, &transport).await?;
-                    if let Some(response) = result {
-                        transport
-                            .send_message(ClientMessages::Single(response), None)
-                            .await?;
-                    }
+                    runtime
+                        .dispatch_stream_message(server_message, &transport)
+                        .await?;
                 }
                 Some(ServerMessages::Batch(server_messages)) => {
                     reconnect_attempt = 0;
-                    let handling_tasks: Vec<_> = server_messages
-                        .into_iter()
-                        .map(|msg| runtime.handle_message(msg, &transport))
-                        .collect();
-                    let results = try_join_all(handling_tasks).await?;
-                    let responses: Vec<_> = results.into_iter().flatten().collect();
-                    if !responses.is_empty() {
-                        transport
-                            .send_message(ClientMessages::Batch(responses), None)
-                            .await?;
-                    }
+                    runtime
+                        .dispatch_stream_batch(server_messages, &transport)
+                        .await?;
                 }
                 None => {
                     break;
@@ -532,6 +1099,64 @@ async fn try_reconnect(
 }
 
 impl ClientRuntime {
+    /// Wraps the client in a [`ShutdownGuard`] that shuts it down once dropped,
+    /// see [`ShutdownGuard`] for the caveats compared to an explicit [`shut_down()`](McpClient::shut_down).
+    pub fn shutdown_on_drop(self: Arc<Self>) -> ShutdownGuard<Self> {
+        ShutdownGuard::new(self, |runtime| {
+            Box::pin(async move {
+                if let Err(error) = runtime.shut_down().await {
+                    tracing::warn!("Failed to shut down the client on drop: {error}");
+                }
+            })
+        })
+    }
+
+    /// Runs `send_task` for the request `request_id`, until it completes or the request is cancelled
+    /// with [`cancel_request()`](McpClient::cancel_request).
+    ///
+    /// A request that times out is cancelled on the server as well, with a `notifications/cancelled` notification.
+    async fn track_in_flight<T>(
+        &self,
+        request_id: Option<RequestId>,
+        send_task: impl std::future::Future<Output = SdkResult<T>>,
+    ) -> SdkResult<T> {
+        let Some(request_id) = request_id else {
+            return send_task.await;
+        };
+
+        let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+        if let Ok(mut requests) = self.in_flight_requests.lock() {
+            requests.insert(request_id.clone(), cancel_tx);
+        }
+        let in_flight = InFlightRequest {
+            requests: &self.in_flight_requests,
+            request_id: request_id.clone(),
+        };
+
+        let result = tokio::select! {
+            result = send_task => result.map_err(|err| err.into_request_timeout(request_id)),
+            _ = cancel_rx => Err(McpSdkError::RequestCancelled { request_id }),
+        };
+        drop(in_flight);
+
+        // the server may still be processing the request, let it know the result is no longer awaited
+        if let Err(McpSdkError::Timeout {
+            request_id,
+            timeout,
+        }) = &result
+        {
+            let params = CancelledNotificationParams {
+                meta: None,
+                reason: Some(format!("Request timed out after {timeout:?}")),
+                request_id: Some(request_id.clone()),
+            };
+            if let Err(error) = self.notify_cancellation(params).await {
+                tracing::warn!("Failed to cancel request {request_id} after it timed out: {error}");
+            }
+        }
+        result
+    }
+
     #[cfg(feature = "streamable-http")]
     pub(crate) async fn start_stream(
         &self,
@@ -593,10 +1218,15 @@ impl ClientRuntime {
 
                         match mcp_messages {
                             ServerMessages::Single(server_message) => {
+                                // notifications and requests related to the request may precede its response
+                                let is_response = server_message.is_response() || server_message.is_error();
                                 let result = self.handle_message(server_message, &transport).await?;
                                 if let Some(result) = result {
                                     transport.send_message(ClientMessages::Single(result), None).await?;
                                 }
+                                if !is_response {
+                                    continue;
+                                }
                             }
                             ServerMessages::Batch(server_messages) => {
 
@@ -683,22 +1313,41 @@ impl McpClient for ClientRuntime {
         request_id: Option<RequestId>,
         request_timeout: Option<Duration>,
     ) -> SdkResult<Option<ServerMessage>> {
+        let _request_permit = self.acquire_request_permit(&message).await?;
+
         #[cfg(feature = "streamable-http")]
         {
-            if self.transport_options.is_some() {
+            if let Some(transport_options) = self.transport_options.as_ref() {
                 let outgoing_request_id = self
                     .request_id_gen
                     .request_id_for_message(&message, request_id);
-                let mcp_message = ClientMessage::from_message(message, outgoing_request_id)?;
+                let mut mcp_message = ClientMessage::from_message(message, outgoing_request_id)?;
+                self.apply_send_middlewares(&mut mcp_message).await?;
 
                 // telemetry
                 if let Some(observer) = self.message_observer.as_ref() {
                     observer.on_send(&mcp_message);
                 }
 
-                let response = self
-                    .start_stream(ClientMessages::Single(mcp_message), request_timeout)
-                    .await?;
+                let request_id = cancellable_request_id(&mcp_message);
+                // the response may be streamed back within the POST request itself,
+                // so the whole exchange is bounded rather than only the wait for the response
+                let request_timeout =
+                    request_timeout.unwrap_or(transport_options.request_options.request_timeout);
+                let send_task = async {
+                    tokio::time::timeout(
+                        request_timeout,
+                        self.start_stream(
+                            ClientMessages::Single(mcp_message),
+                            Some(request_timeout),
+                        ),
+                    )
+                    .await
+                    .map_err(|_| {
+                        TransportError::from(SdkError::request_timeout(request_timeout.as_millis()))
+                    })?
+                };
+                let response = self.track_in_flight(request_id, send_task).await?;
                 return response
                     .map(|r| r.as_single())
                     .transpose()
@@ -706,27 +1355,27 @@ impl McpClient for ClientRuntime {
             }
         }
 
-        let transport_map = self.transport_map.read().await;
-
-        let transport = transport_map.as_ref().ok_or(
-            RpcError::internal_error()
-                .with_message("transport stream does not exists or is closed!".to_string()),
-        )?;
+        let transport = self.current_transport().await?;
 
         let outgoing_request_id = self
             .request_id_gen
             .request_id_for_message(&message, request_id);
 
-        let mcp_message = ClientMessage::from_message(message, outgoing_request_id)?;
+        let mut mcp_message = ClientMessage::from_message(message, outgoing_request_id)?;
+        self.apply_send_middlewares(&mut mcp_message).await?;
 
         // telemetry
         if let Some(observer) = self.message_observer.as_ref() {
             observer.on_send(&mcp_message);
         }
 
-        let response = transport
-            .send_message(ClientMessages::Single(mcp_message), request_timeout)
-            .await?;
+        let request_id = cancellable_request_id(&mcp_message);
+        let send_task = async {
+            Ok(transport
+                .send_message(ClientMessages::Single(mcp_message), request_timeout)
+                .await?)
+        };
+        let response = self.track_in_flight(request_id, send_task).await?;
         response
             .map(|r| r.as_single())
             .transpose()
@@ -746,9 +1395,13 @@ impl McpClient for ClientRuntime {
     }
     async fn send_batch(
         &self,
-        messages: Vec<ClientMessage>,
+        mut messages: Vec<ClientMessage>,
         timeout: Option<Duration>,
     ) -> SdkResult<Option<Vec<ServerMessage>>> {
+        for message in messages.iter_mut() {
+            self.apply_send_middlewares(message).await?;
+        }
+
         #[cfg(feature = "streamable-http")]
         {
             if self.transport_options.is_some() {
@@ -768,11 +1421,7 @@ impl McpClient for ClientRuntime {
             }
         }
 
-        let transport_map = self.transport_map.read().await;
-        let transport = transport_map.as_ref().ok_or(
-            RpcError::internal_error()
-                .with_message("transport stream does not exists or is closed!".to_string()),
-        )?;
+        let transport = self.current_transport().await?;
 
         // telemetry
         if let Some(observer) = self.message_observer.as_ref() {
@@ -832,6 +1481,39 @@ impl McpClient for ClientRuntime {
         self.start_standalone().await
     }
 
+    async fn reinitialize(&self) -> SdkResult<InitializeResult> {
+        // servers refuse a second initialize within a session, the handshake opens a new one
+        #[cfg(feature = "streamable-http")]
+        let previous_session_id = self.session_id.write().await.take();
+
+        let initialize_result = match self.initialize_handshake().await {
+            Ok(initialize_result) => initialize_result,
+            Err(error) => {
+                #[cfg(feature = "streamable-http")]
+                {
+                    *self.session_id.write().await = previous_session_id;
+                }
+                return Err(error);
+            }
+        };
+        self.send_notification(NotificationFromClient::InitializedNotification(None))
+            .await?;
+
+        // ends the previous session, its standalone SSE stream reconnects to the new one
+        #[cfg(feature = "streamable-http")]
+        if let (Some(transport_options), Some(session_id)) =
+            (self.transport_options.as_ref(), previous_session_id)
+        {
+            transport_options
+                .terminate_session_with_protocol_version(
+                    Some(&session_id),
+                    Some(&initialize_result.protocol_version),
+                )
+                .await;
+        }
+        Ok(initialize_result)
+    }
+
     fn set_server_details(&self, server_details: InitializeResult) -> SdkResult<()> {
         self.server_details_tx
             .send(Some(server_details))
@@ -846,6 +1528,10 @@ impl McpClient for ClientRuntime {
         &self.client_details
     }
 
+    fn max_list_pages(&self) -> usize {
+        self.max_list_pages.load(Ordering::Relaxed)
+    }
+
     fn server_info(&self) -> Option<InitializeResult> {
         self.server_details_rx.borrow().clone()
     }
@@ -855,9 +1541,43 @@ impl McpClient for ClientRuntime {
         *result
     }
 
+    fn pending_requests(&self) -> Vec<RequestId> {
+        self.in_flight_requests
+            .lock()
+            .map(|requests| requests.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    async fn cancel_request(
+        &self,
+        request_id: &RequestId,
+        reason: Option<String>,
+    ) -> SdkResult<bool> {
+        let cancel_tx = self
+            .in_flight_requests
+            .lock()
+            .ok()
+            .and_then(|mut requests| requests.remove(request_id));
+        let Some(cancel_tx) = cancel_tx else {
+            return Ok(false);
+        };
+        // unblock the pending call, then drop the transport's response channel for the request
+        let _ = cancel_tx.send(());
+        if let Ok(transport) = self.current_transport().await {
+            let _ = transport.pending_request_tx(request_id).await;
+        }
+
+        self.notify_cancellation(CancelledNotificationParams {
+            meta: None,
+            reason,
+            request_id: Some(request_id.clone()),
+        })
+        .await?;
+        Ok(true)
+    }
+
     async fn shut_down(&self) -> SdkResult<()> {
-        let mut is_shut_down_lock = self.is_shut_down.lock().await;
-        *is_shut_down_lock = true;
+        *self.is_shut_down.lock().await = true;
 
         let mut transport_map = self.transport_map.write().await;
         let transport_option = transport_map.take();
@@ -877,13 +1597,21 @@ impl McpClient for ClientRuntime {
         Ok(())
     }
 
+    fn process_stderr(&self) -> ProcessStderrStream {
+        self.process_stderr.subscribe()
+    }
+
     async fn terminate_session(&self) {
         #[cfg(feature = "streamable-http")]
         {
             if let Some(transport_options) = self.transport_options.as_ref() {
                 let session_id = self.session_id.read().await.clone();
+                let protocol_version = self.negotiated_protocol_version();
                 transport_options
-                    .terminate_session(session_id.as_ref())
+                    .terminate_session_with_protocol_version(
+                        session_id.as_ref(),
+                        protocol_version.as_deref(),
+                    )
                     .await;
                 let _ = self.shut_down().await;
             }
@@ -932,4 +1660,27 @@ mod tests {
             "idle stderr was polled repeatedly"
         );
     }
+
+    #[tokio::test]
+    async fn process_stderr_streams_receive_lines_until_closed() {
+        let process_stderr = ProcessStderr::new();
+        process_stderr.publish("before subscribing");
+
+        let first = process_stderr.subscribe();
+        let second = process_stderr.subscribe();
+        process_stderr.publish("line 1");
+        process_stderr.publish("line 2");
+        process_stderr.close();
+
+        assert_eq!(first.collect::<Vec<_>>().await, ["line 1", "line 2"]);
+        assert_eq!(second.collect::<Vec<_>>().await, ["line 1", "line 2"]);
+        assert!(process_stderr.subscribe().next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn process_stderr_streams_of_transports_without_a_process_are_empty() {
+        let process_stderr = ProcessStderr::closed();
+        process_stderr.publish("ignored");
+        assert!(process_stderr.subscribe().next().await.is_none());
+    }
 }
@@ -20,6 +20,8 @@ use rust_mcp_schema::schema_utils::ServerJsonrpcRequest;
 #[cfg(feature = "streamable-http")]
 use rust_mcp_transport::StreamableTransportOptions;
 use rust_mcp_transport::TransportDispatcher;
+#[cfg(all(feature = "sse", feature = "streamable-http"))]
+use rust_mcp_transport::{detect_transport, ClientSseTransport, DetectedTransport, RequestOptions};
 use std::sync::Arc;
 
 /// Creates a new MCP client runtime with the specified configuration.
@@ -53,14 +55,16 @@ where
         ClientMessage,
     >,
 {
-    Arc::new(ClientRuntime::new(
+    let runtime = ClientRuntime::new(
         options.client_details,
         Arc::new(options.transport),
         options.handler,
         options.task_store,
         options.server_task_store,
         options.message_observer,
-    ))
+    );
+    runtime.set_max_concurrent_requests(options.max_concurrent_requests);
+    Arc::new(runtime)
 }
 
 #[cfg(feature = "streamable-http")]
@@ -82,6 +86,53 @@ pub fn with_transport_options(
     ))
 }
 
+/// Creates a client for the MCP server at `server_url`, using whichever HTTP transport the server implements.
+///
+/// The server is probed with [`detect_transport`] first. Streamable HTTP servers are connected
+/// as with [`with_transport_options`], servers implementing the deprecated HTTP+SSE transport through a
+/// [`ClientSseTransport`] configured from the timeout, retry, header and proxy settings of `request_options`.
+#[cfg(all(feature = "sse", feature = "streamable-http"))]
+pub async fn with_detected_transport(
+    client_details: InitializeRequestParams,
+    server_url: &str,
+    request_options: RequestOptions,
+    handler: impl ClientHandler,
+    task_store: Option<Arc<ClientTaskStore>>,
+    server_task_store: Option<Arc<ServerTaskStore>>,
+    message_observer: Option<Arc<dyn McpObserver<ServerMessage, ClientMessage>>>,
+) -> SdkResult<Arc<ClientRuntime>> {
+    match detect_transport(server_url, &client_details, &request_options).await? {
+        DetectedTransport::StreamableHttp => {
+            tracing::debug!("{server_url} implements the Streamable HTTP transport");
+            let transport_options = StreamableTransportOptions {
+                mcp_url: server_url.to_string(),
+                request_options,
+            };
+            Ok(with_transport_options(
+                client_details,
+                transport_options,
+                handler,
+                task_store,
+                server_task_store,
+                message_observer,
+            ))
+        }
+        DetectedTransport::Sse => {
+            tracing::debug!("{server_url} implements the HTTP+SSE transport");
+            let transport =
+                ClientSseTransport::<ServerMessage>::new(server_url, request_options.into())?;
+            Ok(Arc::new(ClientRuntime::new(
+                client_details,
+                Arc::new(transport),
+                Box::new(ClientInternalHandler::new(Box::new(handler))),
+                task_store,
+                server_task_store,
+                message_observer,
+            )))
+        }
+    }
+}
+
 /// Internal handler that wraps a `ClientHandler` trait object.
 /// This is used to handle incoming requests and notifications for the client.
 pub(crate) struct ClientInternalHandler<H> {
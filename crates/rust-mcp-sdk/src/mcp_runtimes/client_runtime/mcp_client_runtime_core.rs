@@ -19,10 +19,12 @@ use crate::{
     mcp_traits::{McpClient, McpClientHandler},
 };
 use async_trait::async_trait;
-use rust_mcp_schema::schema_utils::ServerJsonrpcRequest;
+use rust_mcp_schema::schema_utils::{RpcErrorCodes, ServerJsonrpcRequest};
 #[cfg(feature = "streamable-http")]
 use rust_mcp_transport::StreamableTransportOptions;
 use rust_mcp_transport::TransportDispatcher;
+#[cfg(all(feature = "sse", feature = "streamable-http"))]
+use rust_mcp_transport::{detect_transport, ClientSseTransport, DetectedTransport, RequestOptions};
 use std::sync::Arc;
 
 /// Creates a new MCP client runtime with the specified options.
@@ -41,6 +43,7 @@ use std::sync::Arc;
 ///   - `handler`: The client's core handler (typically a boxed `dyn ClientHandlerCore` or similar)
 ///     that defines the client's behavior and response logic.
 ///   - `task_store`: Optional task storage for managing asynchronous operations (if applicable).
+///   - `max_concurrent_requests`: Optional limit on the requests awaiting a response at the same time.
 ///
 /// # Returns
 ///
@@ -62,14 +65,16 @@ where
         ClientMessage,
     >,
 {
-    Arc::new(ClientRuntime::new(
+    let runtime = ClientRuntime::new(
         options.client_details,
         Arc::new(options.transport),
         options.handler,
         options.task_store,
         options.server_task_store,
         options.message_observer,
-    ))
+    );
+    runtime.set_max_concurrent_requests(options.max_concurrent_requests);
+    Arc::new(runtime)
 }
 
 #[cfg(feature = "streamable-http")]
@@ -91,6 +96,53 @@ pub fn with_transport_options(
     ))
 }
 
+/// Creates a client for the MCP server at `server_url`, using whichever HTTP transport the server implements.
+///
+/// The server is probed with [`detect_transport`] first. Streamable HTTP servers are connected
+/// as with [`with_transport_options`], servers implementing the deprecated HTTP+SSE transport through a
+/// [`ClientSseTransport`] configured from the timeout, retry, header and proxy settings of `request_options`.
+#[cfg(all(feature = "sse", feature = "streamable-http"))]
+pub async fn with_detected_transport(
+    client_details: InitializeRequestParams,
+    server_url: &str,
+    request_options: RequestOptions,
+    handler: impl ClientHandlerCore,
+    task_store: Option<Arc<ClientTaskStore>>,
+    server_task_store: Option<Arc<ServerTaskStore>>,
+    message_observer: Option<Arc<dyn McpObserver<ServerMessage, ClientMessage>>>,
+) -> SdkResult<Arc<ClientRuntime>> {
+    match detect_transport(server_url, &client_details, &request_options).await? {
+        DetectedTransport::StreamableHttp => {
+            tracing::debug!("{server_url} implements the Streamable HTTP transport");
+            let transport_options = StreamableTransportOptions {
+                mcp_url: server_url.to_string(),
+                request_options,
+            };
+            Ok(with_transport_options(
+                client_details,
+                transport_options,
+                handler,
+                task_store,
+                server_task_store,
+                message_observer,
+            ))
+        }
+        DetectedTransport::Sse => {
+            tracing::debug!("{server_url} implements the HTTP+SSE transport");
+            let transport =
+                ClientSseTransport::<ServerMessage>::new(server_url, request_options.into())?;
+            Ok(Arc::new(ClientRuntime::new(
+                client_details,
+                Arc::new(transport),
+                Box::new(ClientCoreInternalHandler::new(Box::new(handler))),
+                task_store,
+                server_task_store,
+                message_observer,
+            )))
+        }
+    }
+}
+
 pub(crate) struct ClientCoreInternalHandler<H> {
     handler: H,
 }
@@ -108,10 +160,20 @@ impl McpClientHandler for ClientCoreInternalHandler<Box<dyn ClientHandlerCore>>
         server_jsonrpc_request: ServerJsonrpcRequest,
         runtime: &dyn McpClient,
     ) -> std::result::Result<ResultFromClient, RpcError> {
+        let is_ping = matches!(server_jsonrpc_request, ServerJsonrpcRequest::PingRequest(_));
         // handle request and get the result
-        self.handler
+        match self
+            .handler
             .handle_request(server_jsonrpc_request, runtime)
             .await
+        {
+            // servers may disconnect clients that do not answer pings, so respond on the handler's
+            // behalf when it does not implement them
+            Err(error) if is_ping && error.code == i64::from(RpcErrorCodes::METHOD_NOT_FOUND) => {
+                Ok(crate::schema::Result::default().into())
+            }
+            result => result,
+        }
     }
 
     async fn handle_error(
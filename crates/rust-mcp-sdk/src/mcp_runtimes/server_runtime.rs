@@ -1,30 +1,51 @@
+mod capability_check;
+mod handler_execution;
+mod manifest;
 pub mod mcp_server_runtime;
 pub mod mcp_server_runtime_core;
+mod openapi;
+mod request_context;
+#[cfg(all(unix, feature = "unix-socket"))]
+mod unix_socket_server;
+use crate::audit::{AuditRedaction, AuditSink, ToolAuditRecord};
 use crate::auth::AuthInfo;
 use crate::error::SdkResult;
+use crate::mcp_runtimes::ShutdownGuard;
 use crate::mcp_traits::{
     McpObserver, McpServer, McpServerHandler, RequestIdGen, RequestIdGenNumeric,
 };
 use crate::schema::{
     schema_utils::{
-        ClientMessage, ClientMessages, FromMessage, MessageFromServer, SdkError, ServerMessage,
-        ServerMessages,
+        ClientJsonrpcRequest, ClientMessage, ClientMessages, FromMessage, MessageFromServer,
+        SdkError, ServerMessage, ServerMessages,
     },
     InitializeRequestParams, InitializeResult, RequestId, RpcError,
 };
 use crate::task_store::{ClientTaskStore, ServerTaskStore, TaskStatusPoller, TaskStatusUpdate};
 use crate::utils::AbortTaskOnDrop;
 use async_trait::async_trait;
+pub use capability_check::{
+    validate_capabilities, CapabilityIssue, CapabilityIssueSeverity, HandlerRegistrations,
+};
 use futures::future::try_join_all;
 use futures::{StreamExt, TryFutureExt};
+pub use handler_execution::HandlerExecution;
+pub use manifest::{server_manifest, ServerManifest};
+pub use openapi::{export_openapi, tools_openapi_document};
+pub use request_context::RequestContext;
 use rust_mcp_schema::{GetTaskParams, GetTaskPayloadParams};
 use rust_mcp_transport::SessionId;
-use rust_mcp_transport::{IoStream, TaskId, TransportDispatcher};
+use rust_mcp_transport::{IoStream, StreamId, TaskId, TransportDispatcher, TransportKind};
 use std::panic;
+#[cfg(any(feature = "sse", feature = "streamable-http"))]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::{mpsc, oneshot, watch, Notify, RwLock, RwLockReadGuard};
+#[cfg(all(unix, feature = "unix-socket"))]
+pub use unix_socket_server::{UnixSocketServer, UnixSocketServerOptions};
 
 pub const DEFAULT_STREAM_ID: &str = "STANDALONE-STREAM";
 const TASK_CHANNEL_CAPACITY: usize = 500;
@@ -38,6 +59,10 @@ tokio::task_local! {
     /// Set via `scope()` in spawned handler tasks. Read by `send()` for notification routing.
     /// Falls back to the GET standalone stream when not set (background tasks, on_initialized, etc.).
     pub(crate) static ACTIVE_REQUEST_TRANSPORT: TransportType;
+
+    /// Context of the client request whose handler is currently executing.
+    /// Set via `scope()` around each request handler. Read by `try_request_context()`.
+    pub(crate) static REQUEST_CONTEXT: RequestContext;
 }
 
 // Define a type alias for the TransportDispatcher trait object
@@ -51,13 +76,60 @@ pub(crate) type TransportType = Arc<
     >,
 >;
 
+/// The settings of a session runtime created by a server that serves many sessions,
+/// e.g. over HTTP or a Unix socket, see [`ServerRuntime::new_instance`].
+pub(crate) struct SessionRuntimeOptions {
+    pub server_details: Arc<InitializeResult>,
+    pub handler: Arc<dyn McpServerHandler>,
+    pub session_id: SessionId,
+    pub auth_info: Option<AuthInfo>,
+    pub task_store: Option<Arc<ServerTaskStore>>,
+    pub client_task_store: Option<Arc<ClientTaskStore>>,
+    pub message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    pub handler_execution: HandlerExecution,
+    pub require_initialized: bool,
+    pub transport_kind: TransportKind,
+}
+
+#[cfg(test)]
+impl SessionRuntimeOptions {
+    /// Options for a session of `handler` on `transport_kind`, without stores, observer or audit sink.
+    pub(crate) fn new(
+        server_details: Arc<InitializeResult>,
+        handler: Arc<dyn McpServerHandler>,
+        session_id: SessionId,
+        transport_kind: TransportKind,
+    ) -> Self {
+        Self {
+            server_details,
+            handler,
+            session_id,
+            auth_info: None,
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: HandlerExecution::Async,
+            require_initialized: false,
+            transport_kind,
+        }
+    }
+}
+
 /// Struct representing the runtime core of the MCP server, handling transport and client details
+///
+/// The runtime's locks (`transport_map` and `auth_info`) are never nested, and no guard is held while
+/// awaiting the transport or a handler: the transport is cloned out of `transport_map` with
+/// `current_transport()` instead of being used through the guard.
 pub struct ServerRuntime {
     // The handler for processing MCP messages
     handler: Arc<dyn McpServerHandler>,
     // Information about the server
     server_details: Arc<InitializeResult>,
     session_id: Option<SessionId>,
+    /// Kind of the transport the session was created for
+    transport_kind: TransportKind,
     /// Holds the latest DEFAULT standalone transport, which may be alive or
     /// shut down. A shut-down entry is deliberately retained as an event-store
     /// sink: `write_str` persists outgoing events to the event store before
@@ -79,6 +151,48 @@ pub struct ServerRuntime {
     task_store: Option<Arc<ServerTaskStore>>,
     client_task_store: Option<Arc<ClientTaskStore>>,
     message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    handler_execution: HandlerExecution,
+    /// Whether requests other than `initialize` and `ping` are rejected until
+    /// the client sends `notifications/initialized`
+    require_initialized: bool,
+    /// Set as soon as `notifications/initialized` is read off a transport,
+    /// before the notification is dispatched to the handler.
+    initialized_notification_received: AtomicBool,
+    /// Set when an `initialize` request is accepted, so a second one can be rejected
+    /// instead of resetting the negotiated state. Cleared again if initialization fails.
+    initialize_request_received: AtomicBool,
+    /// Ids of the streams currently served by `start_stream`, in the order they were opened
+    active_streams: std::sync::Mutex<Vec<StreamId>>,
+    /// Number of times the DEFAULT standalone stream closed, tells the latest
+    /// disconnect of a resumable session apart from earlier ones
+    #[cfg(any(feature = "sse", feature = "streamable-http"))]
+    standalone_disconnects: AtomicU64,
+}
+
+/// Keeps `stream_id` listed in the runtime's active streams until dropped.
+struct ActiveStream<'a> {
+    runtime: &'a ServerRuntime,
+    stream_id: &'a str,
+}
+
+impl<'a> ActiveStream<'a> {
+    fn register(runtime: &'a ServerRuntime, stream_id: &'a str) -> Self {
+        if let Ok(mut streams) = runtime.active_streams.lock() {
+            streams.push(stream_id.to_string());
+        }
+        Self { runtime, stream_id }
+    }
+}
+
+impl Drop for ActiveStream<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut streams) = self.runtime.active_streams.lock() {
+            if let Some(index) = streams.iter().position(|id| id == self.stream_id) {
+                streams.remove(index);
+            }
+        }
+    }
 }
 
 pub struct McpServerOptions<T>
@@ -97,6 +211,14 @@ where
     pub task_store: Option<Arc<ServerTaskStore>>,
     pub client_task_store: Option<Arc<ClientTaskStore>>,
     pub message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    /// Optional sink receiving an audit record for every tool invocation, see [`AuditSink`]
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Strategy used to execute request handlers, see [`HandlerExecution`]
+    pub handler_execution: HandlerExecution,
+    /// If true, requests other than `initialize` and `ping` are rejected with an `invalid request`
+    /// error until the client sends the `notifications/initialized` notification, as required
+    /// by the MCP lifecycle. Default is false.
+    pub require_initialized: bool,
 }
 
 #[async_trait]
@@ -144,6 +266,13 @@ impl McpServer for ServerRuntime {
         guard.clone()
     }
 
+    async fn has_back_channel(&self) -> bool {
+        if ACTIVE_REQUEST_TRANSPORT.try_with(|_| ()).is_ok() {
+            return true;
+        }
+        self.default_stream_exists().await
+    }
+
     async fn wait_for_initialization(&self) {
         loop {
             if self.client_details_rx.borrow().is_some() {
@@ -193,11 +322,7 @@ impl McpServer for ServerRuntime {
             // use the current DEFAULT transport even if it is shut down. A shut-down standalone
             // transport still persists the event to the event store so it can be replayed
             // when the client reconnects.
-            let transport_map = self.transport_map.read().await;
-            transport_map.as_ref().cloned().ok_or(
-                RpcError::internal_error()
-                    .with_message("transport stream does not exists or is closed!".to_string()),
-            )?
+            self.current_transport().await?
         } else {
             // wait for the DEFAULT standalone transport to be registered (and alive) instead of failing
             // instantly when the GET SSE stream has not been processed yet, or a shut-down transport from a previous connection is still in the map.
@@ -221,11 +346,7 @@ impl McpServer for ServerRuntime {
         messages: Vec<ServerMessage>,
         request_timeout: Option<Duration>,
     ) -> SdkResult<Option<Vec<ClientMessage>>> {
-        let transport_map = self.transport_map.read().await;
-        let transport = transport_map.as_ref().ok_or(
-            RpcError::internal_error()
-                .with_message("transport stream does not exists or is closed!".to_string()),
-        )?;
+        let transport = self.current_transport().await?;
 
         // telemetry
         if let Some(observer) = self.message_observer.as_ref() {
@@ -252,12 +373,7 @@ impl McpServer for ServerRuntime {
     /// Main runtime loop, processes incoming messages and handles requests
     async fn start(self: Arc<Self>) -> SdkResult<()> {
         let self_clone = self.clone();
-        let transport_map = self_clone.transport_map.read().await;
-
-        let transport = transport_map.as_ref().ok_or(
-            RpcError::internal_error()
-                .with_message("transport stream does not exists or is closed!".to_string()),
-        )?;
+        let transport = self.current_transport().await?;
 
         let mut stream = transport.start().await?;
 
@@ -266,6 +382,7 @@ impl McpServer for ServerRuntime {
 
         // Process incoming messages from the client
         while let Some(mcp_messages) = stream.next().await {
+            self.observe_initialized_notification(&mcp_messages);
             match mcp_messages {
                 ClientMessages::Single(client_message) => {
                     let transport = transport.clone();
@@ -347,11 +464,7 @@ impl McpServer for ServerRuntime {
     }
 
     async fn stderr_message(&self, message: String) -> SdkResult<()> {
-        let transport_map = self.transport_map.read().await;
-        let transport = transport_map.as_ref().ok_or(
-            RpcError::internal_error()
-                .with_message("transport stream does not exists or is closed!".to_string()),
-        )?;
+        let transport = self.current_transport().await?;
         let mut lock = transport.error_stream().write().await;
 
         if let Some(IoStream::Writable(stderr)) = lock.as_mut() {
@@ -365,16 +478,47 @@ impl McpServer for ServerRuntime {
     fn session_id(&self) -> Option<SessionId> {
         self.session_id.to_owned()
     }
+
+    fn transport_kind(&self) -> TransportKind {
+        self.transport_kind
+    }
+
+    fn handler_registrations(&self) -> HandlerRegistrations {
+        self.handler.registrations()
+    }
+
+    fn try_request_context(&self) -> Option<RequestContext> {
+        REQUEST_CONTEXT.try_with(|context| context.clone()).ok()
+    }
 }
 
 impl ServerRuntime {
-    pub(crate) async fn consume_payload_string(&self, payload: &str) -> SdkResult<()> {
-        let transport_map = self.transport_map.read().await;
+    /// Checks the server's declared capabilities against its handler, typically called
+    /// right before [`start()`](McpServer::start). See [`validate_capabilities`].
+    pub fn validate_capabilities(&self) -> Vec<CapabilityIssue> {
+        validate_capabilities(Arc::clone(&self.server_details), Arc::clone(&self.handler))
+    }
 
-        let transport = transport_map.as_ref().ok_or(
+    /// Wraps the server in a [`ShutdownGuard`] that closes its transport once dropped,
+    /// ending [`start()`](McpServer::start). See [`ShutdownGuard`] for the caveats.
+    pub fn shutdown_on_drop(self: Arc<Self>) -> ShutdownGuard<Self> {
+        ShutdownGuard::new(self, |runtime| {
+            Box::pin(async move { runtime.shutdown().await })
+        })
+    }
+
+    /// Returns the DEFAULT transport, cloned out of `transport_map` so the lock is released
+    /// before the transport is used.
+    async fn current_transport(&self) -> SdkResult<TransportType> {
+        self.transport_map.read().await.clone().ok_or_else(|| {
             RpcError::internal_error()
-                .with_message("stream id does not exists or is closed!".to_string()),
-        )?;
+                .with_message("transport stream does not exists or is closed!".to_string())
+                .into()
+        })
+    }
+
+    pub(crate) async fn consume_payload_string(&self, payload: &str) -> SdkResult<()> {
+        let transport = self.current_transport().await?;
 
         transport.consume_string_payload(payload).await?;
 
@@ -404,15 +548,112 @@ impl ServerRuntime {
             ClientMessage::Request(client_jsonrpc_request) => {
                 let request_id = client_jsonrpc_request.request_id().clone();
 
-                let result = self
-                    .handler
-                    .handle_request(client_jsonrpc_request, self.clone())
-                    .await;
+                let is_initialize_request = matches!(
+                    client_jsonrpc_request,
+                    ClientJsonrpcRequest::InitializeRequest(_)
+                );
+                if is_initialize_request
+                    && self
+                        .initialize_request_received
+                        .swap(true, Ordering::AcqRel)
+                {
+                    let error = RpcError::invalid_request().with_message(
+                        "Only one initialization request is allowed, the session is already initialized."
+                            .to_string(),
+                    );
+                    return Ok(Some(ServerMessage::from_message(
+                        MessageFromServer::Error(error),
+                        Some(request_id),
+                    )?));
+                }
+
+                if let Some(error) = self.check_initialized(&client_jsonrpc_request) {
+                    return Ok(Some(ServerMessage::from_message(
+                        MessageFromServer::Error(error),
+                        Some(request_id),
+                    )?));
+                }
+
+                let is_blocking = self.handler.is_blocking_request(&client_jsonrpc_request);
+                let timeout = self.handler.request_timeout(&client_jsonrpc_request);
+                let auth_info = self.auth_info_cloned().await;
+                let redaction =
+                    AuditRedaction::new(self.handler.sensitive_arguments(&client_jsonrpc_request));
+                let tool_call = match &client_jsonrpc_request {
+                    ClientJsonrpcRequest::CallToolRequest(request)
+                        if self.audit_sink.is_some() || !redaction.is_empty() =>
+                    {
+                        Some((request.params.clone(), SystemTime::now(), Instant::now()))
+                    }
+                    _ => None,
+                };
+                let request_context = RequestContext::new(
+                    &client_jsonrpc_request,
+                    self.session_id(),
+                    auth_info.clone(),
+                );
+                let handler = self.handler.clone();
+                let runtime = self.clone();
+                let method = client_jsonrpc_request.method().to_string();
+                // the timeout wraps the execution strategy rather than the handler, so it also
+                // fires for handlers running on the blocking thread pool, which keep running
+                // in the background until they return
+                let execution = self.handler_execution.run(
+                    is_blocking,
+                    REQUEST_CONTEXT.scope(request_context, async move {
+                        handler
+                            .handle_request(client_jsonrpc_request, runtime)
+                            .await
+                    }),
+                );
+                let mut result = match timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, execution)
+                        .await
+                        .unwrap_or_else(|_| {
+                            let error = SdkError::request_timeout(timeout.as_millis());
+                            Err(RpcError {
+                                code: error.code,
+                                data: error.data,
+                                message: format!(
+                                    "'{method}' request timed out after {}ms",
+                                    timeout.as_millis()
+                                ),
+                            })
+                        }),
+                    None => execution.await,
+                };
+
+                if let Some((mut params, timestamp, started)) = tool_call {
+                    let duration = started.elapsed();
+                    // keep secrets out of the error sent to the client and of the audit record
+                    if let Some(arguments) = params.arguments.as_mut() {
+                        redaction.redact_error(arguments, &mut result);
+                        redaction.apply(arguments);
+                    }
+                    if let Some(audit_sink) = self.audit_sink.as_ref() {
+                        let record = ToolAuditRecord::new(
+                            timestamp,
+                            duration,
+                            self.session_id(),
+                            request_id.clone(),
+                            auth_info.as_ref(),
+                            self.client_info().map(|client| client.client_info.name),
+                            params,
+                            &result,
+                        );
+                        audit_sink.record_tool_call(record).await;
+                    }
+                }
 
                 // create a response to send back to the client
                 let response: MessageFromServer = match result {
                     Ok(success_value) => success_value.into(),
                     Err(error_value) => {
+                        // let the client retry a failed initialization
+                        if is_initialize_request {
+                            self.initialize_request_received
+                                .store(false, Ordering::Release);
+                        }
                         // Error occurred during initialization.
                         // A likely cause could be an unsupported protocol version.
                         if !self.is_initialized() {
@@ -469,6 +710,60 @@ impl ServerRuntime {
         Ok(response)
     }
 
+    /// Records a `notifications/initialized` notification among the incoming `messages`.
+    ///
+    /// Called for each message as it is read off the transport, before it is dispatched
+    /// to a spawned handler task, so a request sent right after the notification is never
+    /// rejected because it is handled first.
+    pub(crate) fn observe_initialized_notification(&self, messages: &ClientMessages) {
+        if !self.require_initialized || self.initialized_notification_received() {
+            return;
+        }
+        let received = match messages {
+            ClientMessages::Single(message) => message.is_initialized_notification(),
+            ClientMessages::Batch(messages) => messages
+                .iter()
+                .any(|message| message.is_initialized_notification()),
+        };
+        if received {
+            self.initialized_notification_received
+                .store(true, Ordering::Release);
+        }
+    }
+
+    /// Same as [`Self::observe_initialized_notification`], for a raw JSON-RPC payload
+    /// which is handed over to a transport in the background.
+    pub(crate) fn observe_initialized_payload(&self, payload: &str) {
+        if !self.require_initialized || self.initialized_notification_received() {
+            return;
+        }
+        if let Ok(messages) = serde_json::from_str::<ClientMessages>(payload) {
+            self.observe_initialized_notification(&messages);
+        }
+    }
+
+    fn initialized_notification_received(&self) -> bool {
+        self.initialized_notification_received
+            .load(Ordering::Acquire)
+    }
+
+    /// Returns the error to respond with when `request` arrives before the session is initialized
+    fn check_initialized(&self, request: &ClientJsonrpcRequest) -> Option<RpcError> {
+        if !self.require_initialized
+            || self.initialized_notification_received()
+            || matches!(
+                request,
+                ClientJsonrpcRequest::InitializeRequest(_) | ClientJsonrpcRequest::PingRequest(_)
+            )
+        {
+            return None;
+        }
+        Some(RpcError::invalid_request().with_message(format!(
+            "Received '{}' before the 'notifications/initialized' notification, the session is not initialized yet.",
+            request.method()
+        )))
+    }
+
     pub(crate) async fn store_transport(
         &self,
         stream_id: &str,
@@ -517,14 +812,12 @@ impl ServerRuntime {
     ) -> SdkResult<TransportType> {
         let deadline = tokio::time::Instant::now() + timeout;
         loop {
-            {
-                let transport_map = self.transport_map.read().await;
-                if let Some(transport) = transport_map.as_ref() {
-                    if !transport.is_shut_down().await {
-                        // Cascade the wakeup so other parked waiters re-check the map.
-                        self.transport_ready.notify_one();
-                        return Ok(transport.clone());
-                    }
+            let transport = self.transport_map.read().await.clone();
+            if let Some(transport) = transport {
+                if !transport.is_shut_down().await {
+                    // Cascade the wakeup so other parked waiters re-check the map.
+                    self.transport_ready.notify_one();
+                    return Ok(transport);
                 }
             }
             let now = tokio::time::Instant::now();
@@ -574,9 +867,9 @@ impl ServerRuntime {
         // briefly disconnected can be replayed after it reconnects.
         // The `ptr_eq` guard ensures a stale stream's teardown never shuts
         // down the transport of a newer connection.
-        let transport_map = self.transport_map.read().await;
-        if let Some(current_transport) = transport_map.as_ref() {
-            if Arc::ptr_eq(current_transport, transport_to_remove) {
+        let current_transport = self.transport_map.read().await.clone();
+        if let Some(current_transport) = current_transport {
+            if Arc::ptr_eq(&current_transport, transport_to_remove) {
                 tracing::trace!("shutting down transport for stream id : {}", stream_id);
                 current_transport.shut_down().await?;
             }
@@ -593,14 +886,49 @@ impl ServerRuntime {
         }
     }
 
+    /// Shuts the session down, then runs the handler's `on_session_closed()` callback.
+    pub(crate) async fn close(self: &Arc<Self>) {
+        self.shutdown().await;
+        self.handler.on_session_closed(self.clone()).await;
+    }
+
+    /// Returns the ids of the streams currently open for this session, in the order they were opened.
+    ///
+    /// Includes the standalone SSE stream (`"STANDALONE-STREAM"`) and the streams answering
+    /// individual POST requests. Always empty for the stdio transport.
+    pub fn stream_ids(&self) -> Vec<StreamId> {
+        self.active_streams
+            .lock()
+            .map(|streams| streams.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of streams currently open for this session, see [`Self::stream_ids`].
+    pub fn stream_count(&self) -> usize {
+        self.active_streams
+            .lock()
+            .map(|streams| streams.len())
+            .unwrap_or_default()
+    }
+
+    /// Records that the DEFAULT standalone stream closed, returns the number of disconnects so far.
+    #[cfg(any(feature = "sse", feature = "streamable-http"))]
+    pub(crate) fn record_disconnect(&self) -> u64 {
+        self.standalone_disconnects.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Returns the number of times the DEFAULT standalone stream closed.
+    #[cfg(any(feature = "sse", feature = "streamable-http"))]
+    pub(crate) fn disconnect_count(&self) -> u64 {
+        self.standalone_disconnects.load(Ordering::SeqCst)
+    }
+
     pub(crate) async fn default_stream_exists(&self) -> bool {
-        let transport_map = self.transport_map.read().await;
-        let live_transport = if let Some(t) = transport_map.as_ref() {
-            !t.is_shut_down().await
-        } else {
-            false
-        };
-        live_transport
+        let transport = self.transport_map.read().await.clone();
+        match transport {
+            Some(transport) => !transport.is_shut_down().await,
+            None => false,
+        }
     }
 
     pub(crate) async fn start_stream(
@@ -618,6 +946,7 @@ impl ServerRuntime {
         ping_interval: Duration,
         payload: Option<String>,
     ) -> SdkResult<()> {
+        let _active_stream = ActiveStream::register(&self, stream_id);
         let mut stream = transport.start().await?;
 
         if stream_id == DEFAULT_STREAM_ID {
@@ -652,6 +981,7 @@ impl ServerRuntime {
         loop {
             tokio::select! {
                 Some(mcp_messages) = stream.next() =>{
+                    self.observe_initialized_notification(&mcp_messages);
 
                     match mcp_messages {
                         ClientMessages::Single(client_message) => {
@@ -744,15 +1074,20 @@ impl ServerRuntime {
         }
     }
 
-    pub(crate) fn new_instance(
-        server_details: Arc<InitializeResult>,
-        handler: Arc<dyn McpServerHandler>,
-        session_id: SessionId,
-        auth_info: Option<AuthInfo>,
-        task_store: Option<Arc<ServerTaskStore>>,
-        client_task_store: Option<Arc<ClientTaskStore>>,
-        message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
-    ) -> Arc<Self> {
+    pub(crate) fn new_instance(options: SessionRuntimeOptions) -> Arc<Self> {
+        let SessionRuntimeOptions {
+            server_details,
+            handler,
+            session_id,
+            auth_info,
+            task_store,
+            client_task_store,
+            message_observer,
+            audit_sink,
+            handler_execution,
+            require_initialized,
+            transport_kind,
+        } = options;
         use tokio::sync::RwLock;
 
         let (client_details_tx, client_details_rx) =
@@ -761,6 +1096,7 @@ impl ServerRuntime {
             server_details,
             handler,
             session_id: Some(session_id),
+            transport_kind,
             transport_map: tokio::sync::RwLock::new(None),
             transport_ready: Notify::new(),
             client_details_tx,
@@ -770,6 +1106,14 @@ impl ServerRuntime {
             task_store,
             client_task_store,
             message_observer,
+            audit_sink,
+            handler_execution,
+            require_initialized,
+            initialized_notification_received: AtomicBool::new(false),
+            initialize_request_received: AtomicBool::new(false),
+            active_streams: std::sync::Mutex::new(Vec::new()),
+            #[cfg(any(feature = "sse", feature = "streamable-http"))]
+            standalone_disconnects: AtomicU64::new(0),
         })
     }
 
@@ -821,6 +1165,7 @@ impl ServerRuntime {
             server_details: Arc::new(options.server_details),
             handler: options.handler,
             session_id: None,
+            transport_kind: options.transport.transport_kind(),
             transport_map: tokio::sync::RwLock::new(Some(Arc::new(options.transport))),
             transport_ready: Notify::new(),
             client_details_tx,
@@ -830,6 +1175,14 @@ impl ServerRuntime {
             task_store: options.task_store,
             client_task_store: options.client_task_store,
             message_observer: options.message_observer,
+            audit_sink: options.audit_sink,
+            handler_execution: options.handler_execution,
+            require_initialized: options.require_initialized,
+            initialized_notification_received: AtomicBool::new(false),
+            initialize_request_received: AtomicBool::new(false),
+            active_streams: std::sync::Mutex::new(Vec::new()),
+            #[cfg(any(feature = "sse", feature = "streamable-http"))]
+            standalone_disconnects: AtomicU64::new(0),
         });
 
         let runtime_clone = runtime.clone();
@@ -868,3 +1221,438 @@ impl ServerRuntime {
         runtime
     }
 }
+
+#[cfg(all(test, feature = "stdio"))]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditRedaction, RedactingAuditSink, ToolAuditOutcome, REDACTED_VALUE};
+    use crate::error::McpSdkError;
+    use crate::mcp_server::{server_runtime, ServerHandler, ToolRegistry};
+    use crate::schema::{
+        schema_utils::{CallToolError, ResultFromServer},
+        CallToolRequestParams, CallToolResult, RequestFromClient,
+    };
+    use crate::testing::{echo_server, echo_server_details, EchoServerHandler};
+    use crate::{StdioTransport, ToMcpServerHandler, TransportOptions};
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    fn initialize_message(id: i64) -> ClientMessage {
+        ClientMessage::Request(ClientJsonrpcRequest::new(
+            RequestId::Integer(id),
+            RequestFromClient::InitializeRequest(InitializeRequestParams {
+                capabilities: Default::default(),
+                client_info: crate::schema::Implementation {
+                    name: "test-client".to_string(),
+                    version: "0.1.0".to_string(),
+                    title: None,
+                    description: None,
+                    icons: vec![],
+                    website_url: None,
+                },
+                meta: None,
+                protocol_version: crate::schema::ProtocolVersion::V2025_11_25.to_string(),
+            }),
+        ))
+    }
+
+    #[tokio::test]
+    async fn rejects_second_initialize_request() {
+        let runtime = echo_server(StdioTransport::new(TransportOptions::default()).unwrap());
+        let transport = runtime.transport_map.read().await.clone().unwrap();
+
+        let response = runtime
+            .handle_message(initialize_message(0), &transport)
+            .await
+            .unwrap();
+        assert!(matches!(
+            response,
+            Some(ServerMessage::Response(response))
+                if matches!(response.result, ResultFromServer::InitializeResult(_))
+        ));
+
+        let response = runtime
+            .handle_message(initialize_message(1), &transport)
+            .await
+            .unwrap();
+        let Some(ServerMessage::Error(error)) = response else {
+            panic!("expected an error response, got {response:?}");
+        };
+        assert_eq!(error.id, Some(RequestId::Integer(1)));
+        assert_eq!(error.error.code, RpcError::invalid_request().code);
+        assert!(error
+            .error
+            .message
+            .contains("Only one initialization request"));
+    }
+
+    struct SlowToolHandler {
+        cancelled: Arc<AtomicBool>,
+    }
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait]
+    impl ServerHandler for SlowToolHandler {
+        fn tool_timeout(&self, tool_name: &str) -> Option<Duration> {
+            (tool_name == "slow").then(|| Duration::from_millis(50))
+        }
+
+        async fn handle_call_tool_request(
+            &self,
+            _params: CallToolRequestParams,
+            _runtime: Arc<dyn McpServer>,
+        ) -> std::result::Result<CallToolResult, CallToolError> {
+            let _guard = SetOnDrop(self.cancelled.clone());
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(CallToolResult::text_content(vec![]))
+        }
+    }
+
+    #[tokio::test]
+    async fn cancels_tool_calls_exceeding_their_timeout() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let runtime = server_runtime::create_server(McpServerOptions {
+            server_details: echo_server_details(),
+            transport: StdioTransport::new(TransportOptions::default()).unwrap(),
+            handler: SlowToolHandler {
+                cancelled: cancelled.clone(),
+            }
+            .to_mcp_server_handler(),
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+        });
+        let transport = runtime.transport_map.read().await.clone().unwrap();
+        runtime
+            .handle_message(initialize_message(0), &transport)
+            .await
+            .unwrap();
+
+        let call_tool = ClientMessage::Request(ClientJsonrpcRequest::new(
+            RequestId::Integer(1),
+            RequestFromClient::CallToolRequest(CallToolRequestParams::new("slow")),
+        ));
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            runtime.handle_message(call_tool, &transport),
+        )
+        .await
+        .expect("the tool call should time out")
+        .unwrap();
+
+        let Some(ServerMessage::Error(error)) = response else {
+            panic!("expected an error response, got {response:?}");
+        };
+        assert_eq!(error.id, Some(RequestId::Integer(1)));
+        assert!(error.error.message.contains("timed out after 50ms"));
+        assert_eq!(
+            error.error.code,
+            i64::from(crate::schema::schema_utils::SdkErrorCodes::REQUEST_TIMEOUT)
+        );
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+
+    struct BlockingToolHandler;
+
+    #[async_trait]
+    impl ServerHandler for BlockingToolHandler {
+        fn is_blocking_tool(&self, tool_name: &str) -> bool {
+            tool_name == "blocking"
+        }
+
+        fn tool_timeout(&self, tool_name: &str) -> Option<Duration> {
+            (tool_name == "blocking").then(|| Duration::from_millis(50))
+        }
+
+        async fn handle_call_tool_request(
+            &self,
+            _params: CallToolRequestParams,
+            _runtime: Arc<dyn McpServer>,
+        ) -> std::result::Result<CallToolResult, CallToolError> {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(CallToolResult::text_content(vec![]))
+        }
+    }
+
+    #[tokio::test]
+    async fn times_out_blocking_tool_calls() {
+        let runtime = server_runtime::create_server(McpServerOptions {
+            server_details: echo_server_details(),
+            transport: StdioTransport::new(TransportOptions::default()).unwrap(),
+            handler: BlockingToolHandler.to_mcp_server_handler(),
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+        });
+        let transport = runtime.transport_map.read().await.clone().unwrap();
+        runtime
+            .handle_message(initialize_message(0), &transport)
+            .await
+            .unwrap();
+
+        let call_tool = ClientMessage::Request(ClientJsonrpcRequest::new(
+            RequestId::Integer(1),
+            RequestFromClient::CallToolRequest(CallToolRequestParams::new("blocking")),
+        ));
+        let started = Instant::now();
+        let response = runtime.handle_message(call_tool, &transport).await.unwrap();
+
+        assert!(started.elapsed() < Duration::from_millis(500));
+        let Some(ServerMessage::Error(error)) = response else {
+            panic!("expected an error response, got {response:?}");
+        };
+        assert!(error.error.message.contains("timed out after 50ms"));
+    }
+
+    #[test]
+    fn reports_the_kind_of_transport_it_was_created_for() {
+        let runtime = echo_server(StdioTransport::new(TransportOptions::default()).unwrap());
+        assert_eq!(runtime.transport_kind(), TransportKind::Stdio);
+
+        let runtime = ServerRuntime::new_instance(SessionRuntimeOptions::new(
+            Arc::new(echo_server_details()),
+            EchoServerHandler.to_mcp_server_handler(),
+            "session".to_string(),
+            TransportKind::StreamableHttp,
+        ));
+        assert_eq!(runtime.transport_kind(), TransportKind::StreamableHttp);
+    }
+
+    struct ClosingHandler {
+        closed_session: Arc<std::sync::Mutex<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl ServerHandler for ClosingHandler {
+        async fn on_session_closed(&self, runtime: Arc<dyn McpServer>) {
+            *self.closed_session.lock().unwrap() = runtime.session_id();
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_the_close_callback_when_closed() {
+        let closed_session = Arc::new(std::sync::Mutex::new(None));
+        let runtime = ServerRuntime::new_instance(SessionRuntimeOptions::new(
+            Arc::new(echo_server_details()),
+            ClosingHandler {
+                closed_session: closed_session.clone(),
+            }
+            .to_mcp_server_handler(),
+            "session".to_string(),
+            TransportKind::StreamableHttp,
+        ));
+
+        runtime.close().await;
+        assert_eq!(closed_session.lock().unwrap().as_deref(), Some("session"));
+    }
+
+    /// An HTTP server relying on the default `close_all_sessions()`.
+    struct SessionListServer(Vec<Arc<ServerRuntime>>);
+
+    #[async_trait]
+    impl crate::McpHttpServer for SessionListServer {
+        async fn graceful_shutdown(&self) {}
+
+        async fn sessions(&self) -> Vec<SessionId> {
+            self.0.iter().filter_map(|r| r.session_id()).collect()
+        }
+
+        async fn runtime_by_session(&self, id: &SessionId) -> SdkResult<Arc<ServerRuntime>> {
+            self.0
+                .iter()
+                .find(|r| r.session_id().as_ref() == Some(id))
+                .cloned()
+                .ok_or_else(|| McpSdkError::Internal {
+                    description: format!("unknown session {id}"),
+                })
+        }
+    }
+
+    #[tokio::test]
+    async fn close_all_sessions_closes_every_session_runtime_by_default() {
+        use crate::McpHttpServer;
+
+        let closed_session = Arc::new(std::sync::Mutex::new(None));
+        let runtime = ServerRuntime::new_instance(SessionRuntimeOptions::new(
+            Arc::new(echo_server_details()),
+            ClosingHandler {
+                closed_session: closed_session.clone(),
+            }
+            .to_mcp_server_handler(),
+            "session".to_string(),
+            TransportKind::StreamableHttp,
+        ));
+
+        let server = SessionListServer(vec![runtime]);
+        assert_eq!(server.close_all_sessions(None).await, 1);
+        assert_eq!(closed_session.lock().unwrap().as_deref(), Some("session"));
+    }
+
+    #[derive(Default)]
+    struct CollectingAuditSink(std::sync::Mutex<Vec<ToolAuditRecord>>);
+
+    #[async_trait]
+    impl AuditSink for CollectingAuditSink {
+        async fn record_tool_call(&self, record: ToolAuditRecord) {
+            self.0.lock().unwrap().push(record);
+        }
+    }
+
+    #[tokio::test]
+    async fn records_tool_calls_in_audit_sink() {
+        let sink = Arc::new(CollectingAuditSink::default());
+        let runtime = server_runtime::create_server(McpServerOptions {
+            server_details: echo_server_details(),
+            transport: StdioTransport::new(TransportOptions::default()).unwrap(),
+            handler: EchoServerHandler.to_mcp_server_handler(),
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: Some(Arc::new(RedactingAuditSink::new(
+                sink.clone(),
+                AuditRedaction::new(["message"]),
+            ))),
+            handler_execution: Default::default(),
+            require_initialized: false,
+        });
+        let transport = runtime.transport_map.read().await.clone().unwrap();
+        runtime
+            .handle_message(initialize_message(0), &transport)
+            .await
+            .unwrap();
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("message".to_string(), "secret".into());
+        for (id, tool) in [(1, "echo"), (2, "unknown")] {
+            let params = CallToolRequestParams::new(tool).with_arguments(arguments.clone());
+            let call_tool = ClientMessage::Request(ClientJsonrpcRequest::new(
+                RequestId::Integer(id),
+                RequestFromClient::CallToolRequest(params),
+            ));
+            runtime.handle_message(call_tool, &transport).await.unwrap();
+        }
+
+        let records = sink.0.lock().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].tool_name, "echo");
+        assert_eq!(records[0].request_id, RequestId::Integer(1));
+        assert_eq!(records[0].client_name.as_deref(), Some("test-client"));
+        assert_eq!(records[0].outcome, ToolAuditOutcome::Success);
+        assert_eq!(
+            records[0].arguments.as_ref().unwrap()["message"],
+            REDACTED_VALUE
+        );
+        assert_eq!(records[1].tool_name, "unknown");
+        assert!(matches!(
+            records[1].outcome,
+            ToolAuditOutcome::Failed { .. } | ToolAuditOutcome::ToolError
+        ));
+    }
+
+    struct SensitiveToolHandler;
+
+    #[async_trait]
+    impl ServerHandler for SensitiveToolHandler {
+        fn tool_sensitive_fields(&self, _tool_name: &str) -> Vec<String> {
+            vec!["api_key".to_string()]
+        }
+
+        async fn handle_call_tool_request(
+            &self,
+            params: CallToolRequestParams,
+            _runtime: Arc<dyn McpServer>,
+        ) -> std::result::Result<CallToolResult, CallToolError> {
+            let api_key = params.arguments.unwrap()["api_key"].clone();
+            Err(CallToolError::from_message(format!(
+                "rejected api key {api_key}"
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn redacts_sensitive_arguments_from_errors_and_audit_records() {
+        let sink = Arc::new(CollectingAuditSink::default());
+        let runtime = server_runtime::create_server(McpServerOptions {
+            server_details: echo_server_details(),
+            transport: StdioTransport::new(TransportOptions::default()).unwrap(),
+            handler: SensitiveToolHandler.to_mcp_server_handler(),
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: Some(sink.clone()),
+            handler_execution: Default::default(),
+            require_initialized: false,
+        });
+        let transport = runtime.transport_map.read().await.clone().unwrap();
+        runtime
+            .handle_message(initialize_message(0), &transport)
+            .await
+            .unwrap();
+
+        let mut arguments = serde_json::Map::new();
+        arguments.insert("api_key".to_string(), "sk-123".into());
+        let call_tool = ClientMessage::Request(ClientJsonrpcRequest::new(
+            RequestId::Integer(1),
+            RequestFromClient::CallToolRequest(
+                CallToolRequestParams::new("login").with_arguments(arguments),
+            ),
+        ));
+        let response = runtime.handle_message(call_tool, &transport).await.unwrap();
+
+        let response = serde_json::to_string(&response).unwrap();
+        assert!(!response.contains("sk-123"));
+        assert!(response.contains(&format!(r#"rejected api key \"{REDACTED_VALUE}\""#)));
+
+        let records = sink.0.lock().unwrap();
+        assert_eq!(records[0].outcome, ToolAuditOutcome::ToolError);
+        assert_eq!(
+            records[0].arguments.as_ref().unwrap()["api_key"],
+            REDACTED_VALUE
+        );
+    }
+
+    #[test]
+    fn reports_write_only_fields_of_registered_tools_as_sensitive() {
+        let mut tool = EchoServerHandler::echo_tool();
+        let mut api_key = serde_json::Map::new();
+        api_key.insert("type".to_string(), "string".into());
+        api_key.insert("writeOnly".to_string(), true.into());
+        tool.input_schema
+            .properties
+            .get_or_insert_with(Default::default)
+            .insert("api_key".to_string(), api_key);
+
+        let handler = RegistryToolHandler {
+            tools: ToolRegistry::new().with_tool(tool, |_params, _runtime| async {
+                Ok(CallToolResult::text_content(vec![]))
+            }),
+        };
+
+        assert_eq!(handler.tool_sensitive_fields("echo"), vec!["api_key"]);
+        assert!(handler.tool_sensitive_fields("unknown").is_empty());
+    }
+
+    struct RegistryToolHandler {
+        tools: ToolRegistry,
+    }
+
+    #[async_trait]
+    impl ServerHandler for RegistryToolHandler {
+        fn tool_registry(&self) -> Option<&ToolRegistry> {
+            Some(&self.tools)
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::mcp_traits::McpServerHandler;
+use crate::schema::{InitializeResult, Prompt, Resource, ResourceTemplate, Tool};
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// How serious a [`CapabilityIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapabilityIssueSeverity {
+    /// The capability is implemented, but likely not as intended (e.g. no tools are registered).
+    Warning,
+    /// The capability is declared but the corresponding requests are not implemented,
+    /// clients relying on it will receive `Method not found` errors.
+    Error,
+}
+
+/// A mismatch between the capabilities a server declares and what its handler implements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityIssue {
+    pub severity: CapabilityIssueSeverity,
+    /// The declared capability the issue relates to, e.g. `tools` or `resources.subscribe`.
+    pub capability: &'static str,
+    pub message: String,
+}
+
+impl CapabilityIssue {
+    fn error(capability: &'static str, message: &str) -> Self {
+        Self {
+            severity: CapabilityIssueSeverity::Error,
+            capability,
+            message: message.to_string(),
+        }
+    }
+
+    fn warning(capability: &'static str, message: &str) -> Self {
+        Self {
+            severity: CapabilityIssueSeverity::Warning,
+            capability,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for CapabilityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.capability, self.message)
+    }
+}
+
+/// What a handler serves, as reported by [`McpServerHandler::registrations()`].
+///
+/// It is checked against the declared capabilities by [`validate_capabilities`], and describes the
+/// server in [`export_openapi`](super::export_openapi), without invoking the handler.
+#[derive(Debug, Clone, Default)]
+pub struct HandlerRegistrations {
+    /// Methods the handler implements, e.g. `tools/list` or `resources/subscribe`.
+    pub methods: Vec<&'static str>,
+    /// Tools registered with the handler, `None` if they are not known upfront.
+    pub tools: Option<Vec<Tool>>,
+    /// Resources listed by the handler, `None` if they are not known upfront.
+    pub resources: Option<Vec<Resource>>,
+    /// Resource templates listed by the handler, `None` if they are not known upfront.
+    pub resource_templates: Option<Vec<ResourceTemplate>>,
+    /// Prompts registered with the handler, `None` if they are not known upfront.
+    pub prompts: Option<Vec<Prompt>>,
+}
+
+impl HandlerRegistrations {
+    fn implements(&self, method: &str) -> bool {
+        self.methods.contains(&method)
+    }
+}
+
+/// Verifies that the capabilities declared in `server_details` are backed by the handler.
+///
+/// The check relies on what the handler reports through [`McpServerHandler::registrations()`],
+/// the handler itself is never invoked.
+/// A declared capability whose methods the handler does not implement is reported as an error,
+/// one that is implemented but has nothing registered is reported as a warning.
+/// Handlers that report no methods at all cannot be checked, which is reported as a single warning.
+pub fn validate_capabilities(
+    server_details: Arc<InitializeResult>,
+    handler: Arc<dyn McpServerHandler>,
+) -> Vec<CapabilityIssue> {
+    let capabilities = &server_details.capabilities;
+    let registrations = handler.registrations();
+
+    if registrations.methods.is_empty() {
+        let declares_any = capabilities.tools.is_some()
+            || capabilities.resources.is_some()
+            || capabilities.prompts.is_some()
+            || capabilities.completions.is_some();
+        if !declares_any {
+            return vec![];
+        }
+        return vec![CapabilityIssue::warning(
+            "capabilities",
+            "not checked, the handler does not report the methods it implements",
+        )];
+    }
+
+    let mut issues = vec![];
+
+    if capabilities.tools.is_some() {
+        if !registrations.implements("tools/list") {
+            issues.push(CapabilityIssue::error(
+                "tools",
+                "declared, but 'tools/list' is not implemented",
+            ));
+        } else if registrations.tools.as_ref().is_some_and(Vec::is_empty) {
+            issues.push(CapabilityIssue::warning(
+                "tools",
+                "declared, but no tools are registered",
+            ));
+        }
+    }
+
+    if let Some(resources) = capabilities.resources.as_ref() {
+        if !registrations.implements("resources/list")
+            && !registrations.implements("resources/templates/list")
+        {
+            issues.push(CapabilityIssue::error(
+                "resources",
+                "declared, but neither 'resources/list' nor 'resources/templates/list' \
+                 is implemented",
+            ));
+        }
+
+        if resources.subscribe.unwrap_or(false) && !registrations.implements("resources/subscribe")
+        {
+            issues.push(CapabilityIssue::error(
+                "resources.subscribe",
+                "declared, but resource subscriptions are not implemented",
+            ));
+        }
+    }
+
+    if capabilities.prompts.is_some() {
+        if !registrations.implements("prompts/list") {
+            issues.push(CapabilityIssue::error(
+                "prompts",
+                "declared, but 'prompts/list' is not implemented",
+            ));
+        } else if registrations.prompts.as_ref().is_some_and(Vec::is_empty) {
+            issues.push(CapabilityIssue::warning(
+                "prompts",
+                "declared, but no prompts are registered",
+            ));
+        }
+    }
+
+    if capabilities.completions.is_some() && !registrations.implements("completion/complete") {
+        issues.push(CapabilityIssue::error(
+            "completions",
+            "declared, but 'completion/complete' is not implemented",
+        ));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_server::{ServerHandler, ToolRegistry};
+    use crate::schema::{
+        ServerCapabilities, ServerCapabilitiesPrompts, ServerCapabilitiesResources,
+        ServerCapabilitiesTools,
+    };
+    use crate::testing::{echo_server_details, EchoServerHandler};
+    use crate::ToMcpServerHandler;
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct EmptyToolsHandler {
+        tools: ToolRegistry,
+    }
+
+    #[async_trait]
+    impl ServerHandler for EmptyToolsHandler {
+        fn tool_registry(&self) -> Option<&ToolRegistry> {
+            Some(&self.tools)
+        }
+    }
+
+    struct UnreportedHandler;
+
+    #[async_trait]
+    impl ServerHandler for UnreportedHandler {}
+
+    fn server_details(capabilities: ServerCapabilities) -> Arc<InitializeResult> {
+        Arc::new(InitializeResult {
+            capabilities,
+            ..echo_server_details()
+        })
+    }
+
+    #[test]
+    fn reports_no_issues_for_matching_handler() {
+        let issues = validate_capabilities(
+            Arc::new(echo_server_details()),
+            EchoServerHandler.to_mcp_server_handler(),
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn reports_declared_capabilities_without_handlers() {
+        let details = server_details(ServerCapabilities {
+            prompts: Some(ServerCapabilitiesPrompts { list_changed: None }),
+            resources: Some(ServerCapabilitiesResources {
+                list_changed: None,
+                subscribe: Some(true),
+            }),
+            ..Default::default()
+        });
+
+        let issues = validate_capabilities(details, EchoServerHandler.to_mcp_server_handler());
+        let capabilities: Vec<_> = issues.iter().map(|issue| issue.capability).collect();
+        assert_eq!(
+            capabilities,
+            vec!["resources", "resources.subscribe", "prompts"]
+        );
+        assert!(issues
+            .iter()
+            .all(|issue| issue.severity == CapabilityIssueSeverity::Error));
+    }
+
+    #[test]
+    fn warns_when_no_tools_are_registered() {
+        let details = server_details(ServerCapabilities {
+            tools: Some(ServerCapabilitiesTools { list_changed: None }),
+            ..Default::default()
+        });
+
+        let issues = validate_capabilities(
+            details,
+            EmptyToolsHandler::default().to_mcp_server_handler(),
+        );
+        assert_eq!(
+            issues,
+            vec![CapabilityIssue::warning(
+                "tools",
+                "declared, but no tools are registered"
+            )]
+        );
+    }
+
+    #[test]
+    fn warns_once_when_the_handler_reports_no_methods() {
+        let details = server_details(ServerCapabilities {
+            tools: Some(ServerCapabilitiesTools { list_changed: None }),
+            prompts: Some(ServerCapabilitiesPrompts { list_changed: None }),
+            ..Default::default()
+        });
+
+        let issues = validate_capabilities(details, UnreportedHandler.to_mcp_server_handler());
+        assert_eq!(
+            issues,
+            vec![CapabilityIssue::warning(
+                "capabilities",
+                "not checked, the handler does not report the methods it implements"
+            )]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use super::ACTIVE_REQUEST_TRANSPORT;
+use crate::schema::RpcError;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Determines how the server runtime executes request handlers.
+///
+/// By default handlers run directly on the async runtime, which is fine for tools that
+/// mostly await IO. Tools that call synchronous libraries or do CPU-heavy work can starve
+/// the reactor, in which case the runtime can either move handlers onto tokio's blocking
+/// thread pool, or cap how many handlers may execute at the same time.
+///
+/// Individual tools can also opt into the blocking thread pool regardless of the configured
+/// strategy, by overriding [`ServerHandler::is_blocking_tool()`](crate::mcp_server::ServerHandler::is_blocking_tool).
+#[derive(Debug, Clone, Default)]
+pub enum HandlerExecution {
+    /// Handlers run as regular futures on the async runtime.
+    #[default]
+    Async,
+    /// Every request handler is executed on tokio's blocking thread pool via `spawn_blocking`.
+    Blocking,
+    /// At most as many handlers as the semaphore has permits execute concurrently,
+    /// additional requests wait until a permit becomes available.
+    ///
+    /// The semaphore is shared by all clones, so a single limit applies to every session of a server.
+    Bounded(Arc<Semaphore>),
+}
+
+impl HandlerExecution {
+    /// Creates a [`HandlerExecution::Bounded`] strategy allowing up to `max_concurrent` handlers to run at once.
+    pub fn bounded(max_concurrent: usize) -> Self {
+        Self::Bounded(Arc::new(Semaphore::new(max_concurrent)))
+    }
+
+    /// Executes a handler future according to the selected strategy.
+    /// `blocking` forces the future onto the blocking thread pool, for handlers that are known to block.
+    pub(crate) async fn run<F, T>(&self, blocking: bool, future: F) -> Result<T, RpcError>
+    where
+        F: Future<Output = Result<T, RpcError>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let _permit = match self {
+            HandlerExecution::Bounded(semaphore) => {
+                Some(semaphore.clone().acquire_owned().await.map_err(|err| {
+                    RpcError::internal_error().with_message(format!(
+                        "Failed to acquire a handler execution permit: {err}"
+                    ))
+                })?)
+            }
+            _ => None,
+        };
+
+        if blocking || matches!(self, HandlerExecution::Blocking) {
+            Self::run_blocking(future).await
+        } else {
+            future.await
+        }
+    }
+
+    async fn run_blocking<F, T>(future: F) -> Result<T, RpcError>
+    where
+        F: Future<Output = Result<T, RpcError>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let handle = tokio::runtime::Handle::current();
+        // task-locals do not cross thread boundaries, carry the active transport over explicitly
+        let active_transport = ACTIVE_REQUEST_TRANSPORT.try_with(|t| t.clone()).ok();
+
+        tokio::task::spawn_blocking(move || match active_transport {
+            Some(transport) => handle.block_on(ACTIVE_REQUEST_TRANSPORT.scope(transport, future)),
+            None => handle.block_on(future),
+        })
+        .await
+        .map_err(|err| {
+            RpcError::internal_error().with_message(format!("Blocking handler failed: {err}"))
+        })?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn bounded_execution_limits_concurrency() {
+        let execution = HandlerExecution::bounded(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..6)
+            .map(|_| {
+                let execution = execution.clone();
+                let running = running.clone();
+                let peak = peak.clone();
+                tokio::spawn(async move {
+                    execution
+                        .run(false, async move {
+                            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            peak.fetch_max(now, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            running.fetch_sub(1, Ordering::SeqCst);
+                            Ok::<_, RpcError>(())
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn blocking_execution_runs_on_a_separate_thread() {
+        let caller_thread = std::thread::current().id();
+        let handler_thread = HandlerExecution::Async
+            .run(true, async {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                Ok::<_, RpcError>(std::thread::current().id())
+            })
+            .await
+            .unwrap();
+        assert_ne!(caller_thread, handler_thread);
+    }
+}
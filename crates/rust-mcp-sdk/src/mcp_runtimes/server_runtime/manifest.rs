@@ -0,0 +1,211 @@
+use super::HandlerRegistrations;
+use crate::mcp_traits::McpServerHandler;
+use crate::schema::{
+    Implementation, InitializeResult, Prompt, Resource, ResourceTemplate, ServerCapabilities, Tool,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A serializable description of everything a server offers: its info, capabilities, tools (with their
+/// schemas), resources, resource templates and prompts.
+///
+/// It can be written to a single JSON file to render documentation, or cached by clients to skip the
+/// list requests on startup. Lists are left empty for capabilities the server does not declare.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerManifest {
+    pub server_info: Implementation,
+    pub protocol_version: String,
+    pub capabilities: ServerCapabilities,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    #[serde(default)]
+    pub resource_templates: Vec<ResourceTemplate>,
+    #[serde(default)]
+    pub prompts: Vec<Prompt>,
+}
+
+impl ServerManifest {
+    /// Creates the manifest of a server named by `server_details`, listing what `registrations` reports.
+    ///
+    /// Tools, resources, resource templates and prompts are only listed for declared capabilities.
+    pub fn new(server_details: &InitializeResult, registrations: HandlerRegistrations) -> Self {
+        let capabilities = &server_details.capabilities;
+        Self {
+            server_info: server_details.server_info.clone(),
+            protocol_version: server_details.protocol_version.clone(),
+            capabilities: capabilities.clone(),
+            instructions: server_details.instructions.clone(),
+            tools: capabilities
+                .tools
+                .as_ref()
+                .and(registrations.tools)
+                .unwrap_or_default(),
+            resources: capabilities
+                .resources
+                .as_ref()
+                .and(registrations.resources)
+                .unwrap_or_default(),
+            resource_templates: capabilities
+                .resources
+                .as_ref()
+                .and(registrations.resource_templates)
+                .unwrap_or_default(),
+            prompts: capabilities
+                .prompts
+                .as_ref()
+                .and(registrations.prompts)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Builds the [`ServerManifest`] of a server from its details and the handler's
+/// [`registrations()`](McpServerHandler::registrations), without invoking the handler.
+pub fn server_manifest(
+    server_details: Arc<InitializeResult>,
+    handler: Arc<dyn McpServerHandler>,
+) -> ServerManifest {
+    ServerManifest::new(&server_details, handler.registrations())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_server::{PromptRegistry, ServerHandler};
+    use crate::schema::{Role, ServerCapabilitiesPrompts, ServerCapabilitiesResources};
+    use crate::testing::{echo_server_details, EchoServerHandler};
+    use crate::ToMcpServerHandler;
+    use async_trait::async_trait;
+
+    struct RegisteredPromptsHandler {
+        prompts: PromptRegistry,
+    }
+
+    #[async_trait]
+    impl ServerHandler for RegisteredPromptsHandler {
+        fn prompt_registry(&self) -> Option<&PromptRegistry> {
+            Some(&self.prompts)
+        }
+    }
+
+    fn prompts_handler() -> Arc<dyn McpServerHandler> {
+        let prompts = PromptRegistry::new().with_template(
+            Prompt {
+                arguments: vec![],
+                description: None,
+                icons: vec![],
+                meta: None,
+                name: "greeting".to_string(),
+                title: None,
+            },
+            vec![(Role::User, "Hello")],
+        );
+        RegisteredPromptsHandler { prompts }.to_mcp_server_handler()
+    }
+
+    struct ResourcesHandler;
+
+    #[async_trait]
+    impl ServerHandler for ResourcesHandler {
+        fn registrations(&self) -> HandlerRegistrations {
+            HandlerRegistrations {
+                methods: vec![
+                    "resources/list",
+                    "resources/templates/list",
+                    "resources/read",
+                ],
+                resources: Some(vec![Resource {
+                    annotations: None,
+                    description: None,
+                    icons: vec![],
+                    meta: None,
+                    mime_type: Some("text/markdown".to_string()),
+                    name: "readme".to_string(),
+                    size: None,
+                    title: None,
+                    uri: "file:///README.md".to_string(),
+                }]),
+                resource_templates: Some(vec![ResourceTemplate {
+                    annotations: None,
+                    description: None,
+                    icons: vec![],
+                    meta: None,
+                    mime_type: None,
+                    name: "logs".to_string(),
+                    title: None,
+                    uri_template: "file:///logs/{date}.log".to_string(),
+                }]),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn lists_registered_tools_and_round_trips() {
+        let manifest = server_manifest(
+            Arc::new(echo_server_details()),
+            EchoServerHandler.to_mcp_server_handler(),
+        );
+
+        assert_eq!(manifest.server_info.name, "rust-mcp-sdk-echo-server");
+        let tools: Vec<_> = manifest.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tools, vec![EchoServerHandler::echo_tool().name]);
+        assert!(manifest.resources.is_empty());
+        assert!(manifest.prompts.is_empty());
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        let imported: ServerManifest = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(imported).unwrap(), json);
+    }
+
+    #[test]
+    fn lists_registered_prompts_of_declared_capabilities() {
+        let mut details = echo_server_details();
+        details.capabilities.prompts = Some(ServerCapabilitiesPrompts { list_changed: None });
+
+        let manifest = server_manifest(Arc::new(details), prompts_handler());
+        let prompts: Vec<_> = manifest.prompts.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(prompts, vec!["greeting"]);
+    }
+
+    #[test]
+    fn lists_registered_resources_and_templates_of_declared_capabilities() {
+        let mut details = echo_server_details();
+        details.capabilities.resources = Some(ServerCapabilitiesResources {
+            list_changed: None,
+            subscribe: None,
+        });
+
+        let manifest = server_manifest(Arc::new(details), ResourcesHandler.to_mcp_server_handler());
+        let resources: Vec<_> = manifest.resources.iter().map(|r| r.uri.as_str()).collect();
+        assert_eq!(resources, vec!["file:///README.md"]);
+        let templates: Vec<_> = manifest
+            .resource_templates
+            .iter()
+            .map(|t| t.uri_template.as_str())
+            .collect();
+        assert_eq!(templates, vec!["file:///logs/{date}.log"]);
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["resourceTemplates"][0]["name"], "logs");
+    }
+
+    #[test]
+    fn leaves_undeclared_capabilities_empty() {
+        let manifest = server_manifest(Arc::new(echo_server_details()), prompts_handler());
+        assert!(manifest.prompts.is_empty());
+        assert!(manifest.tools.is_empty());
+
+        let manifest = server_manifest(
+            Arc::new(echo_server_details()),
+            ResourcesHandler.to_mcp_server_handler(),
+        );
+        assert!(manifest.resources.is_empty());
+        assert!(manifest.resource_templates.is_empty());
+    }
+}
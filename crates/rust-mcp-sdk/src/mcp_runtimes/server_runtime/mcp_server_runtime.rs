@@ -1,14 +1,9 @@
-use super::ServerRuntime;
+use super::{HandlerRegistrations, ServerRuntime, SessionRuntimeOptions};
 use crate::{
-    auth::AuthInfo,
-    task_store::{ClientTaskStore, ServerTaskStore},
-};
-use crate::{
-    error::SdkResult,
+    error::{SdkResult, ToolError},
     mcp_handlers::mcp_server_handler::ServerHandler,
     mcp_traits::{McpServer, McpServerHandler},
     task_store::TaskCreator,
-    McpObserver,
 };
 use crate::{
     mcp_runtimes::server_runtime::McpServerOptions,
@@ -17,13 +12,14 @@ use crate::{
             CallToolError, ClientMessage, ClientMessages, MessageFromServer, ResultFromServer,
             ServerMessage, ServerMessages,
         },
-        CallToolResult, InitializeResult, RpcError,
+        Implementation, RpcError,
     },
 };
 use async_trait::async_trait;
 use rust_mcp_schema::schema_utils::{ClientJsonrpcNotification, ClientJsonrpcRequest};
-use rust_mcp_transport::{SessionId, TransportDispatcher};
+use rust_mcp_transport::TransportDispatcher;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Creates a new MCP server runtime with the specified configuration.
 ///
@@ -56,24 +52,8 @@ where
     ServerRuntime::new(options)
 }
 
-pub(crate) fn create_server_instance(
-    server_details: Arc<InitializeResult>,
-    handler: Arc<dyn McpServerHandler>,
-    session_id: SessionId,
-    auth_info: Option<AuthInfo>,
-    task_store: Option<Arc<ServerTaskStore>>,
-    client_task_store: Option<Arc<ClientTaskStore>>,
-    message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
-) -> Arc<ServerRuntime> {
-    ServerRuntime::new_instance(
-        server_details,
-        handler,
-        session_id,
-        auth_info,
-        task_store,
-        client_task_store,
-        message_observer,
-    )
+pub(crate) fn create_server_instance(options: SessionRuntimeOptions) -> Arc<ServerRuntime> {
+    ServerRuntime::new_instance(options)
 }
 
 pub(crate) struct ServerRuntimeInternalHandler<H> {
@@ -177,7 +157,9 @@ impl McpServerHandler for ServerRuntimeInternalHandler<Box<dyn ServerHandler>> {
                 .await
                 .map(|value| value.into()),
             ClientJsonrpcRequest::CallToolRequest(call_tool_request) => {
-                let result = if call_tool_request.is_task_augmented() {
+                let result: Result<ResultFromServer, CallToolError> = if call_tool_request
+                    .is_task_augmented()
+                {
                     let Some(task_creator) = task_creator else {
                         return Err(CallToolError::from_message("Error creating a task!").into());
                     };
@@ -189,26 +171,16 @@ impl McpServerHandler for ServerRuntimeInternalHandler<Box<dyn ServerHandler>> {
                             runtime,
                         )
                         .await
-                        .map_or_else(
-                            |err| {
-                                let result: CallToolResult = CallToolError::new(err).into();
-                                result.into()
-                            },
-                            Into::into,
-                        )
+                        .map(Into::into)
                 } else {
                     self.handler
                         .handle_call_tool_request(call_tool_request.params, runtime)
                         .await
-                        .map_or_else(
-                            |err| {
-                                let result: CallToolResult = CallToolError::new(err).into();
-                                result.into()
-                            },
-                            Into::into,
-                        )
+                        .map(Into::into)
                 };
-                Ok(result)
+                // protocol errors (see `ToolError`) are sent as a JSON-RPC error,
+                // any other error is reported as a tool result with `is_error` set
+                result.or_else(|err| ToolError::from(err).into_call_tool_result().map(Into::into))
             }
             ClientJsonrpcRequest::SetLevelRequest(set_level_request) => self
                 .handler
@@ -306,4 +278,54 @@ impl McpServerHandler for ServerRuntimeInternalHandler<Box<dyn ServerHandler>> {
         }
         Ok(())
     }
+
+    fn is_blocking_request(&self, client_jsonrpc_request: &ClientJsonrpcRequest) -> bool {
+        match client_jsonrpc_request {
+            ClientJsonrpcRequest::CallToolRequest(call_tool_request) => self
+                .handler
+                .is_blocking_tool(&call_tool_request.params.name),
+            _ => false,
+        }
+    }
+
+    fn request_timeout(&self, client_jsonrpc_request: &ClientJsonrpcRequest) -> Option<Duration> {
+        match client_jsonrpc_request {
+            ClientJsonrpcRequest::CallToolRequest(call_tool_request) => {
+                self.handler.tool_timeout(&call_tool_request.params.name)
+            }
+            _ => None,
+        }
+    }
+
+    fn sensitive_arguments(&self, client_jsonrpc_request: &ClientJsonrpcRequest) -> Vec<String> {
+        match client_jsonrpc_request {
+            ClientJsonrpcRequest::CallToolRequest(call_tool_request) => self
+                .handler
+                .tool_sensitive_fields(&call_tool_request.params.name),
+            _ => vec![],
+        }
+    }
+
+    fn accept_client(&self, client_info: &Implementation) -> std::result::Result<(), RpcError> {
+        self.handler.accept_client(client_info)
+    }
+
+    async fn on_session_closed(&self, runtime: Arc<dyn McpServer>) {
+        self.handler.on_session_closed(runtime).await;
+    }
+
+    fn registrations(&self) -> HandlerRegistrations {
+        let mut registrations = self.handler.registrations();
+        if let Some(tool_registry) = self.handler.tool_registry() {
+            registrations.methods.extend(["tools/list", "tools/call"]);
+            registrations.tools = Some(tool_registry.tools());
+        }
+        if let Some(prompt_registry) = self.handler.prompt_registry() {
+            registrations
+                .methods
+                .extend(["prompts/list", "prompts/get"]);
+            registrations.prompts = Some(prompt_registry.prompts());
+        }
+        registrations
+    }
 }
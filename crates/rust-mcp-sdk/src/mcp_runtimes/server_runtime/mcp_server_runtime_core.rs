@@ -1,4 +1,4 @@
-use super::ServerRuntime;
+use super::{HandlerRegistrations, ServerRuntime};
 use crate::error::SdkResult;
 use crate::mcp_handlers::mcp_server_handler_core::ServerHandlerCore;
 use crate::mcp_runtimes::server_runtime::McpServerOptions;
@@ -102,4 +102,12 @@ impl McpServerHandler for RuntimeCoreInternalHandler<Box<dyn ServerHandlerCore>>
             .await?;
         Ok(())
     }
+
+    async fn on_session_closed(&self, runtime: Arc<dyn McpServer>) {
+        self.handler.on_session_closed(runtime).await;
+    }
+
+    fn registrations(&self) -> HandlerRegistrations {
+        self.handler.registrations()
+    }
 }
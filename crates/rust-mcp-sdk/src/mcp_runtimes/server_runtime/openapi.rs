@@ -0,0 +1,197 @@
+use crate::mcp_traits::McpServerHandler;
+use crate::schema::{InitializeResult, Tool};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Builds an OpenAPI 3.1 document describing `tools` as operations of a server named by `server_details`.
+///
+/// Each tool becomes a `POST /tools/{name}` operation whose `operationId` is the tool name and whose
+/// request body is the tool's input schema. The response body is the tool's output schema when it
+/// declares one, and a generic JSON object otherwise. Tool annotations are kept under the
+/// `x-mcp-annotations` extension.
+///
+/// # Example
+/// ```
+/// use rust_mcp_sdk::mcp_server::tools_openapi_document;
+/// use rust_mcp_sdk::testing::{echo_server_details, EchoServerHandler};
+///
+/// let document = tools_openapi_document(&echo_server_details(), &[EchoServerHandler::echo_tool()]);
+/// assert_eq!(document["openapi"], "3.1.0");
+/// assert!(document["paths"]["/tools/echo"]["post"].is_object());
+/// ```
+pub fn tools_openapi_document(server_details: &InitializeResult, tools: &[Tool]) -> Value {
+    let server_info = &server_details.server_info;
+
+    let mut info = Map::new();
+    info.insert(
+        "title".to_string(),
+        server_info
+            .title
+            .as_ref()
+            .unwrap_or(&server_info.name)
+            .clone()
+            .into(),
+    );
+    info.insert("version".to_string(), server_info.version.clone().into());
+    if let Some(description) = server_info
+        .description
+        .as_ref()
+        .or(server_details.instructions.as_ref())
+    {
+        info.insert("description".to_string(), description.clone().into());
+    }
+
+    let paths: Map<String, Value> = tools
+        .iter()
+        .map(|tool| {
+            (
+                format!("/tools/{}", tool.name),
+                json!({ "post": operation(tool) }),
+            )
+        })
+        .collect();
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": info,
+        "paths": paths,
+    })
+}
+
+fn operation(tool: &Tool) -> Value {
+    let response_schema = tool
+        .output_schema
+        .as_ref()
+        .and_then(|schema| serde_json::to_value(schema).ok())
+        .unwrap_or_else(|| json!({ "type": "object" }));
+
+    let mut operation = Map::new();
+    operation.insert("operationId".to_string(), tool.name.clone().into());
+    if let Some(title) = tool
+        .title
+        .as_ref()
+        .or(tool.annotations.as_ref().and_then(|a| a.title.as_ref()))
+    {
+        operation.insert("summary".to_string(), title.clone().into());
+    }
+    if let Some(description) = tool.description.as_ref() {
+        operation.insert("description".to_string(), description.clone().into());
+    }
+    operation.insert(
+        "requestBody".to_string(),
+        json!({
+            "required": true,
+            "content": {
+                "application/json": {
+                    "schema": serde_json::to_value(&tool.input_schema).unwrap_or_default()
+                }
+            }
+        }),
+    );
+    operation.insert(
+        "responses".to_string(),
+        json!({
+            "200": {
+                "description": "Result of the tool call",
+                "content": { "application/json": { "schema": response_schema } }
+            }
+        }),
+    );
+    if let Some(annotations) = tool
+        .annotations
+        .as_ref()
+        .and_then(|annotations| serde_json::to_value(annotations).ok())
+    {
+        operation.insert("x-mcp-annotations".to_string(), annotations);
+    }
+    Value::Object(operation)
+}
+
+/// Exports the tools registered in `handler` as an OpenAPI 3.1 document, see [`tools_openapi_document`].
+///
+/// Tools are taken from the handler's [`registrations()`](McpServerHandler::registrations), the document has
+/// no operations if the handler does not report its tools.
+pub fn export_openapi(
+    server_details: Arc<InitializeResult>,
+    handler: Arc<dyn McpServerHandler>,
+) -> Value {
+    let tools = handler.registrations().tools.unwrap_or_default();
+    tools_openapi_document(&server_details, &tools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp_server::{HandlerRegistrations, ServerHandler};
+    use crate::schema::{ToolAnnotations, ToolInputSchema};
+    use crate::testing::{echo_server_details, EchoServerHandler};
+    use crate::ToMcpServerHandler;
+    use async_trait::async_trait;
+
+    struct AnnotatedToolsHandler;
+
+    fn tool(name: &str) -> Tool {
+        Tool {
+            annotations: Some(ToolAnnotations {
+                read_only_hint: Some(true),
+                ..Default::default()
+            }),
+            description: Some(format!("The {name} tool")),
+            execution: None,
+            icons: vec![],
+            input_schema: ToolInputSchema::new(vec![], None, None),
+            meta: None,
+            name: name.to_string(),
+            output_schema: None,
+            title: None,
+        }
+    }
+
+    #[async_trait]
+    impl ServerHandler for AnnotatedToolsHandler {
+        fn registrations(&self) -> HandlerRegistrations {
+            HandlerRegistrations {
+                methods: vec!["tools/list", "tools/call"],
+                tools: Some(vec![tool("first"), tool("second")]),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn exports_tools_as_operations() {
+        let document = export_openapi(
+            Arc::new(echo_server_details()),
+            EchoServerHandler.to_mcp_server_handler(),
+        );
+
+        assert_eq!(document["openapi"], OPENAPI_VERSION);
+        assert_eq!(document["info"]["title"], "Echo Server");
+        let operation = &document["paths"]["/tools/echo"]["post"];
+        assert_eq!(operation["operationId"], "echo");
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["schema"],
+            serde_json::to_value(EchoServerHandler::echo_tool().input_schema).unwrap()
+        );
+    }
+
+    #[test]
+    fn exports_registered_tools_with_their_annotations() {
+        let document = export_openapi(
+            Arc::new(echo_server_details()),
+            AnnotatedToolsHandler.to_mcp_server_handler(),
+        );
+
+        let paths = document["paths"].as_object().unwrap();
+        assert_eq!(
+            paths.keys().collect::<Vec<_>>(),
+            vec!["/tools/first", "/tools/second"]
+        );
+        assert_eq!(
+            paths["/tools/second"]["post"]["x-mcp-annotations"]["readOnlyHint"],
+            true
+        );
+    }
+}
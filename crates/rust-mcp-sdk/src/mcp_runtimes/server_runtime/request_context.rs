@@ -0,0 +1,112 @@
+use crate::auth::AuthInfo;
+use crate::schema::{schema_utils::ClientJsonrpcRequest, ProgressToken, RequestId};
+use rust_mcp_transport::SessionId;
+
+/// Details about the client request whose handler is currently executing.
+///
+/// The runtime sets the context around every request handler, it can be retrieved from anywhere
+/// within the handler via [`McpServer::request_context()`](crate::McpServer::request_context)
+/// or [`McpServer::try_request_context()`](crate::McpServer::try_request_context).
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// Session the request belongs to, `None` for transports without sessions such as stdio.
+    pub session_id: Option<SessionId>,
+    /// Id of the request being handled.
+    pub request_id: RequestId,
+    /// Authentication details associated with the session at the time the request was received.
+    pub auth_info: Option<AuthInfo>,
+    /// Progress token supplied by the client in the request's `_meta`, if any.
+    pub progress_token: Option<ProgressToken>,
+}
+
+impl RequestContext {
+    pub(crate) fn new(
+        request: &ClientJsonrpcRequest,
+        session_id: Option<SessionId>,
+        auth_info: Option<AuthInfo>,
+    ) -> Self {
+        Self {
+            session_id,
+            request_id: request.request_id().clone(),
+            auth_info,
+            progress_token: progress_token(request),
+        }
+    }
+}
+
+/// Extracts the progress token from the `_meta` of a client request.
+fn progress_token(request: &ClientJsonrpcRequest) -> Option<ProgressToken> {
+    match request {
+        ClientJsonrpcRequest::InitializeRequest(r) => {
+            r.params.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::PingRequest(r) => {
+            r.params.as_ref()?.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::ListResourcesRequest(r) => {
+            r.params.as_ref()?.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::ListResourceTemplatesRequest(r) => {
+            r.params.as_ref()?.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::ReadResourceRequest(r) => {
+            r.params.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::SubscribeRequest(r) => r.params.meta.as_ref()?.progress_token.clone(),
+        ClientJsonrpcRequest::UnsubscribeRequest(r) => {
+            r.params.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::ListPromptsRequest(r) => {
+            r.params.as_ref()?.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::GetPromptRequest(r) => r.params.meta.as_ref()?.progress_token.clone(),
+        ClientJsonrpcRequest::ListToolsRequest(r) => {
+            r.params.as_ref()?.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::CallToolRequest(r) => r.params.meta.as_ref()?.progress_token.clone(),
+        ClientJsonrpcRequest::ListTasksRequest(r) => {
+            r.params.as_ref()?.meta.as_ref()?.progress_token.clone()
+        }
+        ClientJsonrpcRequest::SetLevelRequest(r) => r.params.meta.as_ref()?.progress_token.clone(),
+        ClientJsonrpcRequest::CompleteRequest(r) => r.params.meta.as_ref()?.progress_token.clone(),
+        ClientJsonrpcRequest::GetTaskRequest(_)
+        | ClientJsonrpcRequest::GetTaskPayloadRequest(_)
+        | ClientJsonrpcRequest::CancelTaskRequest(_) => None,
+        ClientJsonrpcRequest::CustomRequest(r) => r
+            .params
+            .as_ref()?
+            .get("_meta")?
+            .get("progressToken")
+            .and_then(|token| serde_json::from_value(token.clone()).ok()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{CallToolMeta, CallToolRequest, CallToolRequestParams};
+
+    #[test]
+    fn extracts_progress_token_from_request_meta() {
+        let params = CallToolRequestParams {
+            arguments: None,
+            meta: Some(CallToolMeta {
+                progress_token: Some(ProgressToken::String("token-1".to_string())),
+                extra: None,
+            }),
+            name: "echo".to_string(),
+            task: None,
+        };
+        let request = ClientJsonrpcRequest::CallToolRequest(CallToolRequest::new(
+            RequestId::Integer(7),
+            params,
+        ));
+
+        let context = RequestContext::new(&request, Some("session-1".to_string()), None);
+        assert_eq!(context.request_id, RequestId::Integer(7));
+        assert_eq!(context.session_id.as_deref(), Some("session-1"));
+        assert!(
+            matches!(context.progress_token, Some(ProgressToken::String(token)) if token == "token-1")
+        );
+    }
+}
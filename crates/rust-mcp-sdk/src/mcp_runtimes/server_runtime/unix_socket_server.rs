@@ -0,0 +1,247 @@
+use super::{HandlerExecution, ServerRuntime, SessionRuntimeOptions, DEFAULT_STREAM_ID};
+use crate::audit::AuditSink;
+use crate::error::SdkResult;
+use crate::id_generator::{IdGenerator, UuidGenerator};
+use crate::mcp_traits::{McpObserver, McpServerHandler};
+use crate::schema::schema_utils::{ClientMessage, ServerMessage};
+use crate::schema::InitializeResult;
+use crate::task_store::{ClientTaskStore, ServerTaskStore};
+use rust_mcp_transport::{SessionId, TransportKind, TransportOptions, UnixSocketTransport};
+use std::collections::HashMap;
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UnixListener;
+use tokio::sync::{watch, RwLock};
+
+/// Interval of the keep-alive pings sent on every session. A peer that exits closes the socket
+/// right away, the pings are only there to detect one that hangs while keeping it open, so they
+/// can be far apart.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Configuration of a [`UnixSocketServer`].
+pub struct UnixSocketServerOptions {
+    /// Path of the socket file to listen on
+    pub socket_path: PathBuf,
+    pub server_details: InitializeResult,
+    pub handler: Arc<dyn McpServerHandler>,
+    /// Options of the transport created for every accepted connection
+    pub transport_options: TransportOptions,
+    /// Generator of the id of every session, defaults to [`UuidGenerator`]
+    pub session_id_generator: Option<Arc<dyn IdGenerator<SessionId>>>,
+    pub task_store: Option<Arc<ServerTaskStore>>,
+    pub client_task_store: Option<Arc<ClientTaskStore>>,
+    pub message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    /// Optional sink receiving an audit record for every tool invocation, see [`AuditSink`]
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
+    /// Strategy used to execute request handlers, see [`HandlerExecution`]
+    pub handler_execution: HandlerExecution,
+    /// Rejects requests sent before `notifications/initialized`, see [`McpServerOptions::require_initialized`](super::McpServerOptions::require_initialized)
+    pub require_initialized: bool,
+}
+
+/// An MCP server listening on a Unix domain socket, for local server-to-server communication.
+///
+/// Every accepted connection becomes its own session, served by a dedicated [`ServerRuntime`]
+/// over a [`UnixSocketTransport`]. The socket file is removed when the server shuts down.
+///
+/// # Example
+/// ```ignore
+/// let server = Arc::new(UnixSocketServer::bind(UnixSocketServerOptions {
+///     socket_path: "/tmp/my-mcp-server.sock".into(),
+///     server_details,
+///     handler: handler.to_mcp_server_handler(),
+///     transport_options: TransportOptions::default(),
+///     session_id_generator: None,
+///     task_store: None,
+///     client_task_store: None,
+///     message_observer: None,
+///     audit_sink: None,
+///     handler_execution: HandlerExecution::default(),
+///     require_initialized: false,
+/// })?);
+///
+/// tokio::spawn({
+///     let server = server.clone();
+///     async move { server.start().await }
+/// });
+/// ```
+pub struct UnixSocketServer {
+    listener: UnixListener,
+    socket_path: PathBuf,
+    server_details: Arc<InitializeResult>,
+    handler: Arc<dyn McpServerHandler>,
+    transport_options: TransportOptions,
+    session_id_generator: Arc<dyn IdGenerator<SessionId>>,
+    task_store: Option<Arc<ServerTaskStore>>,
+    client_task_store: Option<Arc<ClientTaskStore>>,
+    message_observer: Option<Arc<dyn McpObserver<ClientMessage, ServerMessage>>>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    handler_execution: HandlerExecution,
+    require_initialized: bool,
+    sessions: Arc<RwLock<HashMap<SessionId, Arc<ServerRuntime>>>>,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl UnixSocketServer {
+    /// Binds the socket at `options.socket_path`.
+    ///
+    /// A socket file left behind by a previous run is replaced. Any other existing file, or a socket
+    /// another server is still listening on, is an error.
+    /// Must be called within a Tokio runtime.
+    pub fn bind(options: UnixSocketServerOptions) -> SdkResult<Self> {
+        remove_stale_socket(&options.socket_path)?;
+        let listener = UnixListener::bind(&options.socket_path)?;
+        let (shutdown_tx, _) = watch::channel(false);
+
+        Ok(Self {
+            listener,
+            socket_path: options.socket_path,
+            server_details: Arc::new(options.server_details),
+            handler: options.handler,
+            transport_options: options.transport_options,
+            session_id_generator: options
+                .session_id_generator
+                .unwrap_or_else(|| Arc::new(UuidGenerator {})),
+            task_store: options.task_store,
+            client_task_store: options.client_task_store,
+            message_observer: options.message_observer,
+            audit_sink: options.audit_sink,
+            handler_execution: options.handler_execution,
+            require_initialized: options.require_initialized,
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            shutdown_tx,
+        })
+    }
+
+    /// Returns the path of the socket the server listens on.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Returns the ids of the sessions currently connected.
+    pub async fn sessions(&self) -> Vec<SessionId> {
+        self.sessions.read().await.keys().cloned().collect()
+    }
+
+    /// Accepts connections until [`Self::shut_down`] is called, starting a session for each of them.
+    ///
+    /// Connections that fail to be accepted or to start their session are logged and skipped.
+    pub async fn start(&self) -> SdkResult<()> {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        if *shutdown_rx.borrow() {
+            return Ok(());
+        }
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    match accepted {
+                        Ok((stream, _)) => {
+                            // a connection that fails to set up must not stop the server
+                            if let Err(err) = self.start_session(stream).await {
+                                tracing::error!("Failed to start a Unix socket session: {err}");
+                                continue;
+                            }
+                        }
+                        Err(err) => tracing::error!("Failed to accept a Unix socket connection: {err}"),
+                    }
+                }
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    async fn start_session(&self, stream: tokio::net::UnixStream) -> SdkResult<()> {
+        let session_id = self.session_id_generator.generate();
+        let transport = UnixSocketTransport::<ClientMessage>::from_stream(
+            stream,
+            self.transport_options.clone(),
+        )?;
+
+        let runtime = ServerRuntime::new_instance(SessionRuntimeOptions {
+            server_details: self.server_details.clone(),
+            handler: self.handler.clone(),
+            session_id: session_id.clone(),
+            auth_info: None,
+            task_store: self.task_store.clone(),
+            client_task_store: self.client_task_store.clone(),
+            message_observer: self.message_observer.clone(),
+            audit_sink: self.audit_sink.clone(),
+            handler_execution: self.handler_execution.clone(),
+            require_initialized: self.require_initialized,
+            transport_kind: TransportKind::UnixSocket,
+        });
+        self.sessions
+            .write()
+            .await
+            .insert(session_id.clone(), runtime.clone());
+
+        let sessions = self.sessions.clone();
+        tokio::spawn(async move {
+            match runtime
+                .clone()
+                .start_stream(
+                    Arc::new(transport),
+                    DEFAULT_STREAM_ID,
+                    KEEP_ALIVE_INTERVAL,
+                    None,
+                )
+                .await
+            {
+                Ok(_) => tracing::trace!("session {session_id} exited gracefully."),
+                Err(err) => tracing::info!("session {session_id} exited with error : {err}"),
+            }
+            if sessions.write().await.remove(&session_id).is_some() {
+                runtime.close().await;
+            }
+        });
+        Ok(())
+    }
+
+    /// Stops accepting connections, closes every session and removes the socket file.
+    pub async fn shut_down(&self) -> SdkResult<()> {
+        self.shutdown_tx.send_replace(true);
+
+        let sessions: Vec<_> = self.sessions.write().await.drain().collect();
+        for (_, runtime) in sessions {
+            runtime.close().await;
+        }
+
+        match std::fs::remove_file(&self.socket_path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Removes the socket file of a previous run, refusing to remove anything that is not a socket
+/// or a socket another server is still listening on.
+fn remove_stale_socket(socket_path: &Path) -> SdkResult<()> {
+    match std::fs::symlink_metadata(socket_path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            if std::os::unix::net::UnixStream::connect(socket_path).is_ok() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AddrInUse,
+                    format!("{} is in use by another server", socket_path.display()),
+                )
+                .into());
+            }
+            std::fs::remove_file(socket_path)?;
+            Ok(())
+        }
+        Ok(_) => Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists and is not a socket",
+                socket_path.display()
+            ),
+        )
+        .into()),
+        Err(_) => Ok(()),
+    }
+}
@@ -0,0 +1,67 @@
+use futures::future::BoxFuture;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Shuts the wrapped runtime down when the guard is dropped.
+///
+/// Created with [`ClientRuntime::shutdown_on_drop()`](crate::mcp_client::ClientRuntime::shutdown_on_drop)
+/// or [`ServerRuntime::shutdown_on_drop()`](crate::mcp_server::ServerRuntime::shutdown_on_drop), the guard
+/// dereferences to the runtime's `Arc`, so it can be used in place of it.
+///
+/// Since `Drop` can't be async, the shutdown is spawned onto the current Tokio runtime and completes
+/// in the background, after the guard is gone. It is a best-effort cleanup:
+/// - errors are only logged, and nothing waits for the transport to be closed;
+/// - it is skipped, with a warning, when the guard is dropped outside of a Tokio runtime
+///   (e.g. after the runtime itself has shut down).
+///
+/// Prefer an explicit, awaited shutdown when the program can wait for it, and use the guard as a safety net
+/// against leaked tasks and sockets on early returns and panics.
+pub struct ShutdownGuard<R> {
+    runtime: Option<Arc<R>>,
+    shutdown: fn(Arc<R>) -> BoxFuture<'static, ()>,
+}
+
+impl<R> ShutdownGuard<R> {
+    pub(crate) fn new(runtime: Arc<R>, shutdown: fn(Arc<R>) -> BoxFuture<'static, ()>) -> Self {
+        Self {
+            runtime: Some(runtime),
+            shutdown,
+        }
+    }
+
+    /// Disarms the guard and returns the runtime, which is no longer shut down on drop.
+    pub fn into_inner(mut self) -> Arc<R> {
+        // `runtime` is only taken here, `Deref` and `Drop` never observe `None` otherwise
+        self.runtime
+            .take()
+            .expect("runtime is present until disarmed")
+    }
+}
+
+impl<R> Deref for ShutdownGuard<R> {
+    type Target = Arc<R>;
+
+    fn deref(&self) -> &Self::Target {
+        self.runtime
+            .as_ref()
+            .expect("runtime is present until disarmed")
+    }
+}
+
+impl<R> Drop for ShutdownGuard<R> {
+    fn drop(&mut self) {
+        let Some(runtime) = self.runtime.take() else {
+            return;
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn((self.shutdown)(runtime));
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "ShutdownGuard dropped outside of a Tokio runtime, skipping shutdown"
+                );
+            }
+        }
+    }
+}
@@ -1,22 +1,30 @@
+mod elicit_content;
 pub(super) mod id_generator;
 #[cfg(feature = "client")]
 mod mcp_client;
+#[cfg(feature = "client")]
+mod mcp_client_middleware;
 mod mcp_handler;
 #[cfg(feature = "server")]
 mod mcp_http_server;
 #[cfg(feature = "server")]
 mod mcp_server;
 mod request_id_gen;
+mod result_accessors;
 
 mod mcp_observer;
 pub use mcp_observer::*;
 
+pub use elicit_content::*;
 pub use id_generator::*;
 #[cfg(feature = "client")]
 pub use mcp_client::*;
+#[cfg(feature = "client")]
+pub use mcp_client_middleware::*;
 pub use mcp_handler::*;
 #[cfg(feature = "server")]
 pub use mcp_http_server::*;
 #[cfg(feature = "server")]
 pub use mcp_server::*;
 pub use request_id_gen::*;
+pub use result_accessors::*;
@@ -0,0 +1,184 @@
+use crate::schema::{
+    ElicitResult, ElicitResultAction, ElicitResultContent, ElicitResultContentPrimitive, RpcError,
+};
+use std::collections::BTreeMap;
+
+/// Constructors for the values of an elicitation response, so they can be built without
+/// spelling out the nested `ElicitResultContent` / `ElicitResultContentPrimitive` variants.
+///
+/// Each constructor produces the variant that `from_elicit_result_content()`, generated by
+/// `#[mcp_elicit]`, expects for the corresponding field type:
+/// - [`string`](Self::string) for `String` fields
+/// - [`number`](Self::number) for `i32` and `i64` fields (an `i32` field rejects values out of its range)
+/// - [`boolean`](Self::boolean) for `bool` fields
+/// - [`string_array`](Self::string_array) for `Vec<String>` fields
+///
+/// # Example
+/// ```
+/// use rust_mcp_sdk::schema::ElicitResultContent;
+/// use rust_mcp_sdk::ElicitResultContentExt;
+///
+/// let name = ElicitResultContent::string("Ali");
+/// let age = ElicitResultContent::number(21);
+/// assert!(matches!(age, ElicitResultContent::Primitive(_)));
+/// ```
+pub trait ElicitResultContentExt {
+    /// A string value.
+    fn string(value: impl Into<String>) -> ElicitResultContent;
+    /// An integer value.
+    fn number(value: impl Into<i64>) -> ElicitResultContent;
+    /// A boolean value.
+    fn boolean(value: bool) -> ElicitResultContent;
+    /// An array of strings, e.g. the selected options of a multi-select enum.
+    fn string_array<I, S>(values: I) -> ElicitResultContent
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>;
+}
+
+impl ElicitResultContentExt for ElicitResultContent {
+    fn string(value: impl Into<String>) -> ElicitResultContent {
+        ElicitResultContent::Primitive(ElicitResultContentPrimitive::String(value.into()))
+    }
+
+    fn number(value: impl Into<i64>) -> ElicitResultContent {
+        ElicitResultContent::Primitive(ElicitResultContentPrimitive::Integer(value.into()))
+    }
+
+    fn boolean(value: bool) -> ElicitResultContent {
+        ElicitResultContent::Primitive(ElicitResultContentPrimitive::Boolean(value))
+    }
+
+    fn string_array<I, S>(values: I) -> ElicitResultContent
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ElicitResultContent::StringArray(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Builder assembling the content map of an elicitation response.
+///
+/// # Example
+/// ```
+/// use rust_mcp_sdk::ElicitContentBuilder;
+///
+/// let content = ElicitContentBuilder::new()
+///     .string("name", "Ali")
+///     .number("age", 21)
+///     .boolean("subscribe", true)
+///     .string_array("expertise", ["Rust", "C++"])
+///     .build();
+/// assert_eq!(content.len(), 4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ElicitContentBuilder {
+    content: BTreeMap<String, ElicitResultContent>,
+}
+
+impl ElicitContentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of `key`, replacing any previous value.
+    pub fn value(mut self, key: impl Into<String>, value: ElicitResultContent) -> Self {
+        self.content.insert(key.into(), value);
+        self
+    }
+
+    /// Sets a string value, see [`ElicitResultContentExt::string`].
+    pub fn string(self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.value(key, ElicitResultContent::string(value))
+    }
+
+    /// Sets an integer value, see [`ElicitResultContentExt::number`].
+    pub fn number(self, key: impl Into<String>, value: impl Into<i64>) -> Self {
+        self.value(key, ElicitResultContent::number(value))
+    }
+
+    /// Sets a boolean value, see [`ElicitResultContentExt::boolean`].
+    pub fn boolean(self, key: impl Into<String>, value: bool) -> Self {
+        self.value(key, ElicitResultContent::boolean(value))
+    }
+
+    /// Sets an array of strings, see [`ElicitResultContentExt::string_array`].
+    pub fn string_array<I, S>(self, key: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.value(key, ElicitResultContent::string_array(values))
+    }
+
+    /// Returns the content map, as passed to `from_elicit_result_content()`.
+    pub fn build(self) -> BTreeMap<String, ElicitResultContent> {
+        self.content
+    }
+
+    /// Returns an `accept` [`ElicitResult`] carrying the content, as sent by a client.
+    pub fn accept(self) -> ElicitResult {
+        ElicitResult {
+            action: ElicitResultAction::Accept,
+            content: Some(self.content),
+            meta: None,
+        }
+    }
+}
+
+/// How the user responded to an elicitation request, with the accepted content parsed into `T`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElicitOutcome<T> {
+    /// The user submitted the requested input.
+    Accepted(T),
+    /// The user explicitly declined to provide the input.
+    Declined,
+    /// The user dismissed the request without making a choice.
+    Cancelled,
+}
+
+impl<T> ElicitOutcome<T> {
+    /// Returns the accepted value, or `None` if the user declined or cancelled.
+    pub fn accepted(self) -> Option<T> {
+        match self {
+            ElicitOutcome::Accepted(value) => Some(value),
+            ElicitOutcome::Declined | ElicitOutcome::Cancelled => None,
+        }
+    }
+}
+
+/// Maps an [`ElicitResult`] received from the client to an [`ElicitOutcome`].
+///
+/// # Example
+/// ```ignore
+/// let result = server.request_elicitation(UserInfo::elicit_request_params()).await?;
+/// match result.into_outcome(UserInfo::from_elicit_result_content)? {
+///     ElicitOutcome::Accepted(user_info) => { /* use the input */ }
+///     ElicitOutcome::Declined => { /* the user said no */ }
+///     ElicitOutcome::Cancelled => { /* the user dismissed the request */ }
+/// }
+/// ```
+pub trait ElicitResultExt {
+    /// Parses the content with `parse` when the action is `accept`, e.g. with the
+    /// `from_elicit_result_content()` function generated by `#[mcp_elicit]`.
+    ///
+    /// A declined or cancelled request is not an error; only a failure to parse accepted
+    /// content is.
+    fn into_outcome<T, F>(self, parse: F) -> Result<ElicitOutcome<T>, RpcError>
+    where
+        F: FnOnce(Option<BTreeMap<String, ElicitResultContent>>) -> Result<T, RpcError>;
+}
+
+impl ElicitResultExt for ElicitResult {
+    fn into_outcome<T, F>(self, parse: F) -> Result<ElicitOutcome<T>, RpcError>
+    where
+        F: FnOnce(Option<BTreeMap<String, ElicitResultContent>>) -> Result<T, RpcError>,
+    {
+        match self.action {
+            ElicitResultAction::Accept => parse(self.content).map(ElicitOutcome::Accepted),
+            ElicitResultAction::Decline => Ok(ElicitOutcome::Declined),
+            ElicitResultAction::Cancel => Ok(ElicitOutcome::Cancelled),
+        }
+    }
+}
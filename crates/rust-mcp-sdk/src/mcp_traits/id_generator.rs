@@ -9,4 +9,13 @@ where
     T: From<String>,
 {
     fn generate(&self) -> T;
+
+    /// Returns the key a load balancer can route on to reach the node that generated `id`.
+    ///
+    /// When it returns `Some`, the Streamable HTTP server sends the key in the
+    /// `mcp-routing-key` header of the response that creates a session. The default
+    /// implementation returns `None`, for ids that carry no routing information.
+    fn routing_key(&self, _id: &str) -> Option<String> {
+        None
+    }
 }
@@ -1,17 +1,19 @@
-use crate::error::SdkResult;
+use crate::error::{McpSdkError, SdkResult};
 use crate::schema::{
     schema_utils::{
         ClientMessage, McpMessage, MessageFromClient, NotificationFromClient, RequestFromClient,
         ResultFromServer, ServerMessage,
     },
-    CallToolRequestParams, CallToolResult, CompleteRequestParams, GenericResult,
-    GetPromptRequestParams, Implementation, InitializeRequestParams, InitializeResult,
-    NotificationParams, PaginatedRequestParams, ReadResourceRequestParams, RequestId,
-    RequestParams, RpcError, ServerCapabilities, SetLevelRequestParams, SubscribeRequestParams,
-    UnsubscribeRequestParams,
+    CallToolRequestParams, CallToolResult, CompleteRequestParams, ContentBlock, GenericResult,
+    GetPromptRequestParams, Icon, Implementation, InitializeRequestParams, InitializeResult,
+    NotificationParams, PaginatedRequestParams, Prompt, ReadResourceRequestParams, RequestId,
+    RequestParams, Resource, ResourceTemplate, RpcError, ServerCapabilities, SetLevelRequestParams,
+    SubscribeRequestParams, Tool, UnsubscribeRequestParams,
 };
 use crate::task_store::{ClientTaskStore, ServerTaskStore};
+use crate::utils::collect_pages;
 use async_trait::async_trait;
+use futures::Stream;
 use rust_mcp_schema::schema_utils::ServerTaskResult;
 use rust_mcp_schema::{
     schema_utils::CustomNotification, CancelledNotificationParams, ProgressNotificationParams,
@@ -22,11 +24,39 @@ use rust_mcp_schema::{
     GetTaskResult, ListTasksResult,
 };
 use rust_mcp_transport::SessionId;
-use std::{sync::Arc, time::Duration};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+pub use crate::utils::DEFAULT_MAX_LIST_PAGES;
+
+/// Stream of the lines a server process writes to its stderr, see [`McpClient::process_stderr`].
+pub type ProcessStderrStream = Pin<Box<dyn Stream<Item = String> + Send + 'static>>;
 
 #[async_trait]
 pub trait McpClient: Sync + Send {
     async fn start(self: Arc<Self>) -> SdkResult<()>;
+
+    /// Performs a fresh initialize handshake on the current transport, replacing the stored
+    /// server details with the new `InitializeResult`, which is returned.
+    ///
+    /// Useful when the server's capabilities may have changed, e.g. after a `tools/list_changed`
+    /// notification hinting at new features, or after reconnecting. Subscribers of the server details
+    /// (`watch_server_details()` on `ClientRuntime`) are notified.
+    ///
+    /// Servers accept a single initialize per session, so over Streamable HTTP the handshake opens a
+    /// new session, then terminates the previous one: state the server kept for it, such as resource
+    /// subscriptions or tasks, is lost. Transports carrying a single session, like stdio, send the
+    /// initialize over the same connection, which servers built with this SDK reject; a new client
+    /// must be created and started instead, as it must once the server process was restarted.
+    ///
+    /// The default implementation returns an error, for clients that can't redo the handshake.
+    async fn reinitialize(&self) -> SdkResult<InitializeResult> {
+        Err(McpSdkError::Internal {
+            description: "Reinitializing the session is not supported by this client.".to_string(),
+        })
+    }
+
     fn set_server_details(&self, server_details: InitializeResult) -> SdkResult<()>;
 
     async fn terminate_session(&self);
@@ -45,7 +75,46 @@ pub trait McpClient: Sync + Send {
     async fn shut_down(&self) -> SdkResult<()>;
     async fn is_shut_down(&self) -> bool;
 
+    /// Returns a new independent stream of the lines the server process writes to its stderr,
+    /// e.g. to display server logs live. Only lines received after subscribing are yielded, and
+    /// the stream ends when stderr is closed. Lines are still passed to `handle_process_error`.
+    ///
+    /// The stream is empty for transports without a server process, such as Streamable HTTP.
+    fn process_stderr(&self) -> ProcessStderrStream {
+        Box::pin(futures::stream::empty())
+    }
+
+    /// Returns the ids of the requests sent by the client that are still awaiting a response.
+    ///
+    /// The default implementation returns none, for clients that don't track their requests.
+    fn pending_requests(&self) -> Vec<RequestId> {
+        vec![]
+    }
+
+    /// Cancels an in-flight request: the pending call returns [`McpSdkError::RequestCancelled`](crate::error::McpSdkError::RequestCancelled)
+    /// and a `notifications/cancelled` notification with the optional `reason` is sent to the server.
+    ///
+    /// Returns `false` if the request is not pending (it already completed, or is the `initialize` request,
+    /// which can't be cancelled).
+    ///
+    /// The default implementation returns an error, for clients that can't cancel their requests.
+    async fn cancel_request(
+        &self,
+        _request_id: &RequestId,
+        _reason: Option<String>,
+    ) -> SdkResult<bool> {
+        Err(McpSdkError::Internal {
+            description: "Cancelling requests is not supported by this client.".to_string(),
+        })
+    }
+
     fn client_info(&self) -> &InitializeRequestParams;
+
+    /// Maximum number of pages the `list_all_*` methods request before returning an error,
+    /// protecting against servers that never stop returning a `next_cursor`.
+    fn max_list_pages(&self) -> usize {
+        DEFAULT_MAX_LIST_PAGES
+    }
     fn server_info(&self) -> Option<InitializeResult>;
 
     /// Checks whether the server has been initialized with client
@@ -53,8 +122,8 @@ pub trait McpClient: Sync + Send {
         self.server_info().is_some()
     }
 
-    /// Returns the server's name and version information once initialization is complete.
-    /// This method retrieves the server details, if available, after successful initialization.
+    /// Returns the [`Implementation`] details the server reported during initialization
+    /// (name, version, title, website and icons), or `None` before initialization is complete.
     fn server_version(&self) -> Option<Implementation> {
         self.server_info()
             .map(|server_details| server_details.server_info)
@@ -165,10 +234,32 @@ pub trait McpClient: Sync + Send {
             .map(|server_details| server_details.capabilities.completions.is_some())
     }
 
+    /// Returns the instructions the server sent in its `InitializeResult`, once initialization is complete.
+    ///
+    /// Instructions are human-oriented hints on how to use the server, suitable for displaying to the user
+    /// or for adding to an LLM's system prompt.
     fn instructions(&self) -> Option<String> {
         self.server_info()?.instructions
     }
 
+    /// Returns the human-readable title of the server, if it provided one.
+    fn server_title(&self) -> Option<String> {
+        self.server_version()?.title
+    }
+
+    /// Returns the website URL of the server, if it provided one.
+    fn server_website_url(&self) -> Option<String> {
+        self.server_version()?.website_url
+    }
+
+    /// Returns the icons the server provided for display in user interfaces.
+    /// Empty if the server did not provide any icons or initialization is not complete.
+    fn server_icons(&self) -> Vec<Icon> {
+        self.server_version()
+            .map(|implementation| implementation.icons)
+            .unwrap_or_default()
+    }
+
     async fn session_id(&self) -> Option<SessionId>;
 
     /// Returns the client's capabilities.
@@ -181,6 +272,10 @@ pub trait McpClient: Sync + Send {
     /// This function sends a `RequestFromClient` message to the server, waits for the response,
     /// and handles the result. If the response is empty or of an invalid type, an error is returned.
     /// Otherwise, it returns the result from the server.
+    ///
+    /// The response is awaited for `timeout`, or the transport's request timeout when `None`,
+    /// after which the request fails with [`McpSdkError::Timeout`](crate::error::McpSdkError::Timeout)
+    /// and is cancelled on the server with a `notifications/cancelled` notification.
     async fn request(
         &self,
         request: RequestFromClient,
@@ -359,6 +454,54 @@ pub trait McpClient: Sync + Send {
         Ok(response.try_into()?)
     }
 
+    /// Calls a tool with typed arguments and deserializes its result into `R`.
+    ///
+    /// `args` must serialize to a JSON object, or to `null` for tools without arguments.
+    /// The result is read from the structured content, or else from the first text content parsed as JSON.
+    /// A result flagged with `is_error` is returned as [`McpSdkError::ToolFailed`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// #[derive(Serialize)]
+    /// struct AddArgs { a: i64, b: i64 }
+    /// #[derive(Deserialize)]
+    /// struct Sum { sum: i64 }
+    ///
+    /// let result: Sum = client.call_tool_typed("add", AddArgs { a: 1, b: 2 }).await?;
+    /// ```
+    async fn call_tool_typed<A, R>(&self, name: &str, args: A) -> SdkResult<R>
+    where
+        Self: Sized,
+        A: Serialize + Send,
+        R: DeserializeOwned,
+    {
+        let arguments = match serde_json::to_value(args) {
+            Ok(Value::Object(arguments)) => Some(arguments),
+            Ok(Value::Null) => None,
+            Ok(_) => {
+                return Err(McpSdkError::Internal {
+                    description: format!("Arguments of tool '{name}' must be a JSON object"),
+                })
+            }
+            Err(err) => {
+                return Err(McpSdkError::Internal {
+                    description: format!(
+                        "Failed to serialize the arguments of tool '{name}': {err}"
+                    ),
+                })
+            }
+        };
+        let result = self
+            .request_tool_call(CallToolRequestParams {
+                name: name.to_string(),
+                arguments,
+                meta: None,
+                task: None,
+            })
+            .await?;
+        typed_tool_result(name, result)
+    }
+
     /// request a list of tools the server has.
     async fn request_tool_list(
         &self,
@@ -370,6 +513,50 @@ pub trait McpClient: Sync + Send {
         Ok(response.try_into()?)
     }
 
+    /// Requests every tool the server has, following `next_cursor` until the last page.
+    ///
+    /// Fails if the server returns more than [`Self::max_list_pages`] pages.
+    async fn list_all_tools(&self) -> SdkResult<Vec<Tool>> {
+        collect_pages(self.max_list_pages(), |params| async move {
+            let result = self.request_tool_list(params).await?;
+            Ok((result.tools, result.next_cursor))
+        })
+        .await
+    }
+
+    /// Requests every resource the server has, following `next_cursor` until the last page.
+    ///
+    /// Fails if the server returns more than [`Self::max_list_pages`] pages.
+    async fn list_all_resources(&self) -> SdkResult<Vec<Resource>> {
+        collect_pages(self.max_list_pages(), |params| async move {
+            let result = self.request_resource_list(params).await?;
+            Ok((result.resources, result.next_cursor))
+        })
+        .await
+    }
+
+    /// Requests every resource template the server has, following `next_cursor` until the last page.
+    ///
+    /// Fails if the server returns more than [`Self::max_list_pages`] pages.
+    async fn list_all_resource_templates(&self) -> SdkResult<Vec<ResourceTemplate>> {
+        collect_pages(self.max_list_pages(), |params| async move {
+            let result = self.request_resource_template_list(params).await?;
+            Ok((result.resource_templates, result.next_cursor))
+        })
+        .await
+    }
+
+    /// Requests every prompt the server has, following `next_cursor` until the last page.
+    ///
+    /// Fails if the server returns more than [`Self::max_list_pages`] pages.
+    async fn list_all_prompts(&self) -> SdkResult<Vec<Prompt>> {
+        collect_pages(self.max_list_pages(), |params| async move {
+            let result = self.request_prompt_list(params).await?;
+            Ok((result.prompts, result.next_cursor))
+        })
+        .await
+    }
+
     ///Send a request to retrieve the state of a task.
     async fn request_get_task(&self, params: GetTaskParams) -> SdkResult<GetTaskResult> {
         let response = self
@@ -590,3 +777,127 @@ pub trait McpClient: Sync + Send {
             .await
     }
 }
+
+/// Deserializes the result of a call to the tool `name`, see [`McpClient::call_tool_typed`].
+fn typed_tool_result<R: DeserializeOwned>(name: &str, result: CallToolResult) -> SdkResult<R> {
+    if result.is_error.unwrap_or(false) {
+        return Err(McpSdkError::ToolFailed {
+            tool: name.to_string(),
+            content: result.content,
+        });
+    }
+
+    let decoded = match (result.structured_content, result.content.first()) {
+        (Some(structured_content), _) => serde_json::from_value(Value::Object(structured_content)),
+        (None, Some(ContentBlock::TextContent(text_content))) => {
+            serde_json::from_str(&text_content.text)
+        }
+        _ => {
+            return Err(McpSdkError::Internal {
+                description: format!("Tool '{name}' returned neither structured nor text content"),
+            })
+        }
+    };
+    decoded.map_err(|err| McpSdkError::Internal {
+        description: format!("Failed to deserialize the result of tool '{name}': {err}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn pages_of(
+        pages: Vec<(Vec<u32>, Option<&str>)>,
+        max_pages: usize,
+    ) -> (SdkResult<Vec<u32>>, Vec<Option<String>>) {
+        let mut requested_cursors = vec![];
+        let mut pages = pages.into_iter();
+        let result = collect_pages(max_pages, |params| {
+            requested_cursors.push(params.and_then(|params| params.cursor));
+            let (items, next_cursor) = pages.next().expect("no page left");
+            async move { Ok((items, next_cursor.map(str::to_string))) }
+        })
+        .await;
+        (result, requested_cursors)
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Sum {
+        sum: i64,
+    }
+
+    #[test]
+    fn typed_tool_result_reads_structured_content_first() {
+        let mut structured_content = serde_json::Map::new();
+        structured_content.insert("sum".to_string(), 3.into());
+        let result = CallToolResult::text_content(vec![r#"{"sum": 0}"#.to_string().into()])
+            .with_structured_content(structured_content);
+        assert_eq!(
+            typed_tool_result::<Sum>("add", result).unwrap(),
+            Sum { sum: 3 }
+        );
+    }
+
+    #[test]
+    fn typed_tool_result_falls_back_to_text_content() {
+        let result = CallToolResult::text_content(vec![r#"{"sum": 5}"#.to_string().into()]);
+        assert_eq!(
+            typed_tool_result::<Sum>("add", result).unwrap(),
+            Sum { sum: 5 }
+        );
+
+        let result = CallToolResult::text_content(vec!["five".to_string().into()]);
+        assert!(matches!(
+            typed_tool_result::<Sum>("add", result),
+            Err(McpSdkError::Internal { .. })
+        ));
+    }
+
+    #[test]
+    fn typed_tool_result_returns_tool_errors() {
+        let mut result = CallToolResult::text_content(vec!["division by zero".to_string().into()]);
+        result.is_error = Some(true);
+        let error = typed_tool_result::<Sum>("divide", result).unwrap_err();
+        assert!(matches!(&error, McpSdkError::ToolFailed { tool, content }
+            if tool == "divide" && content.len() == 1));
+        assert_eq!(error.to_string(), "Tool 'divide' failed: division by zero");
+    }
+
+    #[tokio::test]
+    async fn collects_the_items_of_every_page() {
+        let (result, cursors) = pages_of(
+            vec![
+                (vec![1, 2], Some("a")),
+                (vec![3], Some("b")),
+                (vec![4, 5], None),
+            ],
+            DEFAULT_MAX_LIST_PAGES,
+        )
+        .await;
+        assert_eq!(result.unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            cursors,
+            vec![None, Some("a".to_string()), Some("b".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_when_the_cursor_repeats() {
+        let (result, cursors) =
+            pages_of(vec![(vec![1], Some("a")), (vec![1], Some("a"))], 10).await;
+        assert!(matches!(result, Err(McpSdkError::Internal { .. })));
+        assert_eq!(cursors.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn stops_after_max_pages() {
+        let (result, cursors) = pages_of(
+            vec![(vec![1], Some("a")), (vec![2], Some("b")), (vec![3], None)],
+            2,
+        )
+        .await;
+        assert!(matches!(result, Err(McpSdkError::Internal { .. })));
+        assert_eq!(cursors.len(), 2);
+    }
+}
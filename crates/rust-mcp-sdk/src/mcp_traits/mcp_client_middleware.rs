@@ -0,0 +1,29 @@
+use crate::error::SdkResult;
+use crate::schema::schema_utils::{ClientMessage, ServerMessage};
+use async_trait::async_trait;
+
+/// Hook into the message pipeline of a client runtime, to log, transform or reject
+/// messages centrally, e.g. to add correlation ids or enforce a policy.
+///
+/// Middlewares are registered with `ClientRuntime::add_middleware()` and run in registration
+/// order. Unlike [`McpObserver`](crate::McpObserver), they may modify messages, and the
+/// observer sees messages as they are sent to or received from the transport.
+#[async_trait]
+pub trait ClientMiddleware: Send + Sync + 'static {
+    /// Called before a message is sent to the server, `message` may be modified.
+    ///
+    /// Returning an error aborts sending, the pending call fails with that error.
+    async fn on_send(&self, message: &mut ClientMessage) -> SdkResult<()> {
+        let _ = message;
+        Ok(())
+    }
+
+    /// Called when a message is received from the server, before it is handled, `message` may be modified.
+    ///
+    /// Returning an error drops the message. If it is a response, the corresponding request
+    /// fails with an `internal error` carrying the error message.
+    async fn on_receive(&self, message: &mut ServerMessage) -> SdkResult<()> {
+        let _ = message;
+        Ok(())
+    }
+}
@@ -5,7 +5,9 @@ use rust_mcp_schema::schema_utils::{
 };
 
 #[cfg(feature = "server")]
-use crate::schema::schema_utils::ResultFromServer;
+use crate::mcp_server::HandlerRegistrations;
+#[cfg(feature = "server")]
+use crate::schema::{schema_utils::ResultFromServer, Implementation};
 
 #[cfg(feature = "client")]
 use crate::schema::schema_utils::{NotificationFromServer, ResultFromClient};
@@ -13,6 +15,8 @@ use crate::schema::schema_utils::{NotificationFromServer, ResultFromClient};
 use crate::error::SdkResult;
 use crate::schema::RpcError;
 use std::sync::Arc;
+#[cfg(feature = "server")]
+use std::time::Duration;
 
 #[cfg(feature = "client")]
 use super::mcp_client::McpClient;
@@ -37,6 +41,39 @@ pub trait McpServerHandler: Send + Sync {
         client_jsonrpc_notification: ClientJsonrpcNotification,
         runtime: Arc<dyn McpServer>,
     ) -> SdkResult<()>;
+
+    /// Returns `true` if handling the given request performs blocking work,
+    /// in which case the runtime executes it on the blocking thread pool.
+    fn is_blocking_request(&self, _client_jsonrpc_request: &ClientJsonrpcRequest) -> bool {
+        false
+    }
+
+    /// Returns how long the handler of the given request may run, in which case the runtime
+    /// cancels it once the timeout elapses and responds with an error. `None` means no timeout.
+    fn request_timeout(&self, _client_jsonrpc_request: &ClientJsonrpcRequest) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the paths of the arguments of the given request whose values must be redacted
+    /// from audit records and error messages.
+    fn sensitive_arguments(&self, _client_jsonrpc_request: &ClientJsonrpcRequest) -> Vec<String> {
+        vec![]
+    }
+
+    /// Returns an error if the client identified by `client_info` must not connect to the server,
+    /// checked before a session is created for it.
+    fn accept_client(&self, _client_info: &Implementation) -> std::result::Result<(), RpcError> {
+        Ok(())
+    }
+
+    /// Invoked after the session of `runtime` is closed by the server.
+    async fn on_session_closed(&self, _runtime: Arc<dyn McpServer>) {}
+
+    /// Describes what the handler serves, so that the declared capabilities can be checked
+    /// without invoking it, see `validate_capabilities()`.
+    fn registrations(&self) -> HandlerRegistrations {
+        HandlerRegistrations::default()
+    }
 }
 
 // Custom trait for converting ServerHandler
@@ -1,5 +1,7 @@
 use crate::error::SdkResult;
 use crate::mcp_runtimes::server_runtime::ServerRuntime;
+use crate::schema::schema_utils::NotificationFromServer;
+use crate::McpServer;
 use async_trait::async_trait;
 use rust_mcp_transport::SessionId;
 use std::sync::Arc;
@@ -12,6 +14,8 @@ use std::sync::Arc;
 /// - Graceful shutdown
 /// - Session enumeration
 /// - Per-session runtime access
+/// - Closing all sessions, e.g. before a restart
+/// - Notifying all sessions, e.g. after the tool list changed
 ///
 /// Users coding against `dyn McpHttpServer` can swap HTTP frameworks without
 /// changing their runtime interaction code.
@@ -27,4 +31,47 @@ pub trait McpHttpServer: Send + Sync {
     ///
     /// Returns an error if the session does not exist or has been closed.
     async fn runtime_by_session(&self, id: &SessionId) -> SdkResult<Arc<ServerRuntime>>;
+
+    /// Closes every active session, e.g. before a restart so clients reconnect to the new server.
+    ///
+    /// When `reason` is provided it is first sent to each client as a `warning` log message
+    /// notification. The handler's `on_session_closed()` callback runs for each session.
+    /// Returns the number of closed sessions.
+    ///
+    /// The default closes the runtime of every session in [`Self::sessions`]; servers keeping
+    /// sessions in a store override it to also remove them from the store.
+    async fn close_all_sessions(&self, reason: Option<String>) -> usize {
+        let mut closed = 0;
+        for session_id in self.sessions().await {
+            let Ok(runtime) = self.runtime_by_session(&session_id).await else {
+                continue;
+            };
+            if let Some(reason) = reason.as_ref() {
+                if let Err(err) = runtime.log_warn(reason.clone()).await {
+                    tracing::warn!("failed to notify session {session_id} of its closing: {err}");
+                }
+            }
+            runtime.close().await;
+            closed += 1;
+        }
+        closed
+    }
+
+    /// Sends `notification` to the client of every active session, e.g. a `ToolListChangedNotification`
+    /// once the server's tools changed at runtime, and returns the number of notified sessions.
+    ///
+    /// Sessions that fail to receive it, such as those without an open standalone stream, are skipped.
+    async fn notify_all_sessions(&self, notification: NotificationFromServer) -> usize {
+        let mut notified = 0;
+        for session_id in self.sessions().await {
+            let Ok(runtime) = self.runtime_by_session(&session_id).await else {
+                continue;
+            };
+            match runtime.send_notification(notification.clone()).await {
+                Ok(_) => notified += 1,
+                Err(err) => tracing::debug!("failed to notify session {session_id}: {err}"),
+            }
+        }
+        notified
+    }
 }
@@ -1,19 +1,23 @@
 use crate::auth::AuthInfo;
 use crate::error::SdkResult;
+use crate::mcp_server::{
+    tools_openapi_document, HandlerRegistrations, RequestContext, ServerManifest,
+};
 use crate::schema::{
     schema_utils::{
         ClientMessage, McpMessage, MessageFromServer, NotificationFromServer, RequestFromServer,
         ResultFromClient, ServerMessage,
     },
-    CreateMessageRequestParams, CreateMessageResult, ElicitRequestParams, ElicitResult,
+    CreateMessageRequestParams, CreateMessageResult, ElicitRequestParams, ElicitResult, Icon,
     Implementation, InitializeRequestParams, InitializeResult, ListRootsResult, LoggingLevel,
     LoggingMessageNotificationParams, NotificationParams, ProgressToken, RequestId, RequestParams,
     ResourceUpdatedNotificationParams, RpcError, ServerCapabilities,
 };
 use crate::task_store::{ClientTaskStore, CreateTaskOptions, ServerTaskStore};
+use crate::utils::validate_custom_method;
 use async_trait::async_trait;
 use rust_mcp_schema::schema_utils::{
-    ClientTaskResult, CustomNotification, CustomRequest, ServerJsonrpcRequest,
+    ClientTaskResult, CustomNotification, CustomRequest, SdkError, ServerJsonrpcRequest,
 };
 use rust_mcp_schema::{
     CancelTaskParams, CancelTaskResult, CancelledNotificationParams, CreateTaskResult,
@@ -21,7 +25,7 @@ use rust_mcp_schema::{
     ListTasksResult, PaginatedRequestParams, ProgressNotificationParams,
     TaskStatusNotificationParams,
 };
-use rust_mcp_transport::SessionId;
+use rust_mcp_transport::{SessionId, TransportKind};
 use std::{sync::Arc, time::Duration};
 use tokio::sync::RwLockReadGuard;
 
@@ -102,6 +106,43 @@ pub trait McpServer: Sync + Send {
 
     fn session_id(&self) -> Option<SessionId>;
 
+    /// Returns the kind of transport the session runs on, e.g. to only stream progress
+    /// notifications when the client keeps a connection open to receive them.
+    ///
+    /// [`TransportKind::InProcess`] unless overridden.
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::InProcess
+    }
+
+    /// Returns the id of the client request currently being handled.
+    ///
+    /// The id is available for the whole duration of a request handler, including code that runs on the
+    /// blocking thread pool, so it can be attached to logs or progress notifications without passing it around.
+    /// Returns `None` outside of a request handler, e.g. in notification handlers, `on_initialized()`
+    /// or tasks spawned with `tokio::spawn`.
+    fn current_request_id(&self) -> Option<RequestId> {
+        self.try_request_context().map(|context| context.request_id)
+    }
+
+    /// Returns the [`RequestContext`] of the client request currently being handled,
+    /// or `None` when called outside of a request handler.
+    fn try_request_context(&self) -> Option<RequestContext> {
+        None
+    }
+
+    /// Returns the [`RequestContext`] of the client request currently being handled,
+    /// holding the session id, request id, auth info and progress token of the request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a request handler, e.g. in notification handlers, `on_initialized()`
+    /// or tasks spawned with `tokio::spawn` that do not propagate the context.
+    /// Use [`McpServer::try_request_context()`] when the call site is not guaranteed to run within a request.
+    fn request_context(&self) -> RequestContext {
+        self.try_request_context()
+            .expect("request_context() called outside of a request handler")
+    }
+
     async fn send(
         &self,
         message: MessageFromServer,
@@ -115,18 +156,72 @@ pub trait McpServer: Sync + Send {
         request_timeout: Option<Duration>,
     ) -> SdkResult<Option<Vec<ClientMessage>>>;
 
+    /// Returns true if messages can currently reach the client without being requested:
+    /// while handling a request, or when the session's standalone stream is open.
+    ///
+    /// The default implementation derives it from [`McpServer::transport_kind()`]: every transport
+    /// but Streamable HTTP keeps a connection open to the client, while a Streamable HTTP session
+    /// only has one while a stream is open, which implementors have to track themselves.
+    async fn has_back_channel(&self) -> bool {
+        self.transport_kind() != TransportKind::StreamableHttp
+    }
+
+    /// Describes what the server's handler serves, see [`McpServerHandler::registrations()`](crate::mcp_traits::McpServerHandler::registrations).
+    /// The default implementation reports nothing.
+    fn handler_registrations(&self) -> HandlerRegistrations {
+        HandlerRegistrations::default()
+    }
+
+    /// Exports the server's tools as an OpenAPI 3.1 document, where each tool is an operation
+    /// with its input schema as the request body. See [`tools_openapi_document`].
+    ///
+    /// Tools are taken from [`handler_registrations()`](Self::handler_registrations), without invoking the handler.
+    fn export_openapi(&self) -> serde_json::Value {
+        let tools = self.handler_registrations().tools.unwrap_or_default();
+        tools_openapi_document(self.server_info(), &tools)
+    }
+
+    /// Builds the [`ServerManifest`] describing the server's info, capabilities, tools, resources,
+    /// resource templates and prompts. See [`server_manifest`](crate::mcp_server::server_manifest).
+    ///
+    /// Tools, resources, resource templates and prompts are taken from
+    /// [`handler_registrations()`](Self::handler_registrations), without invoking the handler.
+    fn manifest(&self) -> ServerManifest {
+        ServerManifest::new(self.server_info(), self.handler_registrations())
+    }
+
     /// Checks whether the server has been initialized with client
     fn is_initialized(&self) -> bool {
         self.client_info().is_some()
     }
 
-    /// Returns the client's name and version information once initialization is complete.
-    /// This method retrieves the client details, if available, after successful initialization.
+    /// Returns the [`Implementation`] details the client reported in its `initialize` request
+    /// (name, version, title, website and icons), or `None` before initialization is complete.
+    ///
+    /// These are the values received from the client, not the server's own [`McpServer::server_info()`].
     fn client_version(&self) -> Option<Implementation> {
         self.client_info()
             .map(|client_details| client_details.client_info)
     }
 
+    /// Returns the human-readable title of the client, if it provided one.
+    fn client_title(&self) -> Option<String> {
+        self.client_version()?.title
+    }
+
+    /// Returns the website URL of the client, if it provided one.
+    fn client_website_url(&self) -> Option<String> {
+        self.client_version()?.website_url
+    }
+
+    /// Returns the icons the client provided for display in user interfaces.
+    /// Empty if the client did not provide any icons or initialization is not complete.
+    fn client_icons(&self) -> Vec<Icon> {
+        self.client_version()
+            .map(|implementation| implementation.icons)
+            .unwrap_or_default()
+    }
+
     /// Returns the server's capabilities.
     fn capabilities(&self) -> &ServerCapabilities {
         &self.server_info().capabilities
@@ -513,6 +608,32 @@ pub trait McpServer: Sync + Send {
             .await
     }
 
+    /// Pushes a vendor-specific notification with an arbitrary `method` and `params` to the client,
+    /// where it is received by `ClientHandler::handle_custom_notification()`.
+    ///
+    /// Unlike [`McpServer::notify_custom`], the method name is validated with [`validate_custom_method`](crate::mcp_server::validate_custom_method)
+    /// and the notification is only sent when a back-channel is open, see [`McpServer::has_back_channel`].
+    /// It fails with a `connection_closed` error otherwise, instead of being stored for a later replay.
+    async fn send_custom_notification(
+        &self,
+        method: &str,
+        params: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> SdkResult<()> {
+        validate_custom_method(method)?;
+        if !self.has_back_channel().await {
+            return Err(SdkError::connection_closed()
+                .with_message(&format!(
+                    "No open stream to send the '{method}' notification to"
+                ))
+                .into());
+        }
+        self.notify_custom(CustomNotification {
+            method: method.to_string(),
+            params,
+        })
+        .await
+    }
+
     #[deprecated(since = "0.8.0", note = "Use `request_root_list()` instead.")]
     async fn list_roots(&self, params: Option<RequestParams>) -> SdkResult<ListRootsResult> {
         let response = self
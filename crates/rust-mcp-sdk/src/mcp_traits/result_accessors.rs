@@ -0,0 +1,226 @@
+use crate::schema::{
+    schema_utils::{
+        ClientJsonrpcResponse, ClientMessage, MessageFromClient, MessageFromServer,
+        ResultFromClient, ResultFromServer, ServerJsonrpcResponse, ServerMessage,
+    },
+    CallToolResult, CancelTaskResult, CompleteResult, CreateMessageResult, CreateTaskResult,
+    ElicitResult, GetPromptResult, GetTaskPayloadResult, GetTaskResult, InitializeResult,
+    ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListRootsResult,
+    ListTasksResult, ListToolsResult, ReadResourceResult, Result as GenericResult,
+};
+
+// Generates `as_*` accessors returning the inner result when `$source()` holds the given variant.
+macro_rules! result_accessors {
+    ($source:ident, $enum:ident, $( $method:ident => $variant:ident : $type:ty ),* $(,)?) => {
+        $(
+            #[doc = concat!("Returns the `", stringify!($variant), "`, if this is one.")]
+            fn $method(&self) -> Option<&$type> {
+                match self.$source()? {
+                    $enum::$variant(result) => Some(result),
+                    _ => None,
+                }
+            }
+        )*
+    };
+}
+
+/// Flat accessors over results sent by a server, so a specific result can be picked out of a
+/// `ServerMessage`, `ServerJsonrpcResponse` or `ResultFromServer` without matching the nested enums.
+///
+/// # Example
+/// ```
+/// use rust_mcp_sdk::schema::{CallToolResult, RequestId, ServerJsonrpcResponse};
+/// use rust_mcp_sdk::ServerResultAccessors;
+///
+/// let response = ServerJsonrpcResponse::new(
+///     RequestId::Integer(1),
+///     CallToolResult::text_content(vec!["hello".into()]).into(),
+/// );
+/// assert!(response.as_call_tool_result().is_some());
+/// assert!(response.as_list_tools_result().is_none());
+/// ```
+pub trait ServerResultAccessors {
+    /// Returns the result carried by the message, `None` if it is not a successful response.
+    fn result_from_server(&self) -> Option<&ResultFromServer>;
+
+    result_accessors!(result_from_server, ResultFromServer,
+        as_initialize_result => InitializeResult: InitializeResult,
+        as_list_resources_result => ListResourcesResult: ListResourcesResult,
+        as_list_resource_templates_result => ListResourceTemplatesResult: ListResourceTemplatesResult,
+        as_read_resource_result => ReadResourceResult: ReadResourceResult,
+        as_list_prompts_result => ListPromptsResult: ListPromptsResult,
+        as_get_prompt_result => GetPromptResult: GetPromptResult,
+        as_list_tools_result => ListToolsResult: ListToolsResult,
+        as_call_tool_result => CallToolResult: CallToolResult,
+        as_get_task_result => GetTaskResult: GetTaskResult,
+        as_cancel_task_result => CancelTaskResult: CancelTaskResult,
+        as_list_tasks_result => ListTasksResult: ListTasksResult,
+        as_complete_result => CompleteResult: CompleteResult,
+        as_create_task_result => CreateTaskResult: CreateTaskResult,
+        as_generic_result => Result: GenericResult,
+        as_get_task_payload_result => GetTaskPayloadResult: GetTaskPayloadResult,
+    );
+}
+
+impl ServerResultAccessors for ResultFromServer {
+    fn result_from_server(&self) -> Option<&ResultFromServer> {
+        Some(self)
+    }
+}
+
+impl ServerResultAccessors for ServerJsonrpcResponse {
+    fn result_from_server(&self) -> Option<&ResultFromServer> {
+        Some(&self.result)
+    }
+}
+
+impl ServerResultAccessors for ServerMessage {
+    fn result_from_server(&self) -> Option<&ResultFromServer> {
+        match self {
+            ServerMessage::Response(response) => Some(&response.result),
+            _ => None,
+        }
+    }
+}
+
+impl ServerResultAccessors for MessageFromServer {
+    fn result_from_server(&self) -> Option<&ResultFromServer> {
+        match self {
+            MessageFromServer::ResultFromServer(result) => Some(result),
+            _ => None,
+        }
+    }
+}
+
+/// Flat accessors over results sent by a client, so a specific result can be picked out of a
+/// `ClientMessage`, `ClientJsonrpcResponse` or `ResultFromClient` without matching the nested enums.
+pub trait ClientResultAccessors {
+    /// Returns the result carried by the message, `None` if it is not a successful response.
+    fn result_from_client(&self) -> Option<&ResultFromClient>;
+
+    result_accessors!(result_from_client, ResultFromClient,
+        as_get_task_result => GetTaskResult: GetTaskResult,
+        as_cancel_task_result => CancelTaskResult: CancelTaskResult,
+        as_list_tasks_result => ListTasksResult: ListTasksResult,
+        as_create_message_result => CreateMessageResult: CreateMessageResult,
+        as_list_roots_result => ListRootsResult: ListRootsResult,
+        as_elicit_result => ElicitResult: ElicitResult,
+        as_create_task_result => CreateTaskResult: CreateTaskResult,
+        as_generic_result => Result: GenericResult,
+        as_get_task_payload_result => GetTaskPayloadResult: GetTaskPayloadResult,
+    );
+}
+
+impl ClientResultAccessors for ResultFromClient {
+    fn result_from_client(&self) -> Option<&ResultFromClient> {
+        Some(self)
+    }
+}
+
+impl ClientResultAccessors for ClientJsonrpcResponse {
+    fn result_from_client(&self) -> Option<&ResultFromClient> {
+        Some(&self.result)
+    }
+}
+
+impl ClientResultAccessors for ClientMessage {
+    fn result_from_client(&self) -> Option<&ResultFromClient> {
+        match self {
+            ClientMessage::Response(response) => Some(&response.result),
+            _ => None,
+        }
+    }
+}
+
+impl ClientResultAccessors for MessageFromClient {
+    fn result_from_client(&self) -> Option<&ResultFromClient> {
+        match self {
+            MessageFromClient::ResultFromClient(result) => Some(result),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{
+        schema_utils::{
+            ClientJsonrpcRequest, RequestFromClient, RequestFromServer, ServerJsonrpcRequest,
+        },
+        RequestId,
+    };
+
+    fn list_tools_result() -> ListToolsResult {
+        ListToolsResult {
+            meta: None,
+            next_cursor: None,
+            tools: vec![],
+        }
+    }
+
+    fn list_roots_result() -> ListRootsResult {
+        ListRootsResult {
+            meta: None,
+            roots: vec![],
+        }
+    }
+
+    #[test]
+    fn server_accessors_pick_the_matching_result() {
+        let result: ResultFromServer = list_tools_result().into();
+        assert!(result.as_list_tools_result().is_some());
+        assert!(result.as_call_tool_result().is_none());
+
+        let response = ServerJsonrpcResponse::new(RequestId::Integer(1), result.clone());
+        assert!(response.as_list_tools_result().is_some());
+        assert!(response.as_initialize_result().is_none());
+
+        let message = ServerMessage::Response(response);
+        assert!(message.as_list_tools_result().is_some());
+
+        let message = MessageFromServer::ResultFromServer(result);
+        assert!(message.as_list_tools_result().is_some());
+    }
+
+    #[test]
+    fn server_accessors_return_none_for_other_messages() {
+        let request =
+            ServerJsonrpcRequest::new(RequestId::Integer(1), RequestFromServer::PingRequest(None));
+        let message = ServerMessage::Request(request);
+        assert!(message.result_from_server().is_none());
+        assert!(message.as_generic_result().is_none());
+
+        let message = MessageFromServer::RequestFromServer(RequestFromServer::PingRequest(None));
+        assert!(message.result_from_server().is_none());
+    }
+
+    #[test]
+    fn client_accessors_pick_the_matching_result() {
+        let result: ResultFromClient = list_roots_result().into();
+        assert!(result.as_list_roots_result().is_some());
+        assert!(result.as_elicit_result().is_none());
+
+        let response = ClientJsonrpcResponse::new(RequestId::Integer(1), result.clone());
+        assert!(response.as_list_roots_result().is_some());
+        assert!(response.as_create_message_result().is_none());
+
+        let message = ClientMessage::Response(response);
+        assert!(message.as_list_roots_result().is_some());
+
+        let message = MessageFromClient::ResultFromClient(result);
+        assert!(message.as_list_roots_result().is_some());
+    }
+
+    #[test]
+    fn client_accessors_return_none_for_other_messages() {
+        let request =
+            ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None));
+        let message = ClientMessage::Request(request);
+        assert!(message.result_from_client().is_none());
+        assert!(message.as_generic_result().is_none());
+
+        let message = MessageFromClient::RequestFromClient(RequestFromClient::PingRequest(None));
+        assert!(message.result_from_client().is_none());
+    }
+}
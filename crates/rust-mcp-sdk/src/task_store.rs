@@ -6,7 +6,8 @@ use rust_mcp_schema::{
     schema_utils::{
         ClientJsonrpcRequest, ResultFromClient, ResultFromServer, ServerJsonrpcRequest,
     },
-    ListTasksResult, RequestId, Task, TaskStatus, TaskStatusNotificationParams,
+    GetTaskPayloadResult, ListTasksResult, RequestId, RpcError, Task, TaskStatus,
+    TaskStatusNotificationParams,
 };
 use std::{fmt::Debug, pin::Pin, sync::Arc};
 
@@ -177,6 +178,52 @@ where
     /// The stored result
     async fn get_task_result(&self, task_id: &str, session_id: Option<String>) -> Option<Res>;
 
+    /// Stores the result of a task along with metadata about it (e.g. timing or cost),
+    /// and sets its final status.
+    ///
+    /// The default implementation ignores `result_meta` and calls [`Self::store_task_result`],
+    /// stores that can persist the metadata should override it together with [`Self::get_task_result_with_meta`].
+    ///
+    /// # Arguments
+    /// * `task_id` - The task identifier
+    /// * `status` - The final status: 'completed' for success, 'failed' for errors
+    /// * `result` - The result to store
+    /// * `result_meta` - Optional metadata describing the result
+    /// * `session_id` - Optional session ID for binding the operation to a specific session
+    async fn store_task_result_with_meta(
+        &self,
+        task_id: &str,
+        status: TaskStatus,
+        result: Res,
+        result_meta: Option<serde_json::Map<String, serde_json::Value>>,
+        session_id: Option<&String>,
+    ) -> ()
+    where
+        Res: Send + 'static,
+    {
+        let _ = result_meta;
+        self.store_task_result(task_id, status, result, session_id)
+            .await
+    }
+
+    /// Retrieves the stored result of a task and the metadata stored with it.
+    ///
+    /// # Arguments
+    /// * `task_id` - The task identifier
+    /// * `session_id` - Optional session ID for binding the query to a specific session
+    ///
+    /// # Returns
+    /// The stored result, and its metadata if any was stored with [`Self::store_task_result_with_meta`]
+    async fn get_task_result_with_meta(
+        &self,
+        task_id: &str,
+        session_id: Option<String>,
+    ) -> Option<(Res, Option<serde_json::Map<String, serde_json::Value>>)> {
+        self.get_task_result(task_id, session_id)
+            .await
+            .map(|result| (result, None))
+    }
+
     /// Updates a task's status (e.g., to 'cancelled', 'failed', 'completed').
     ///
     /// # Arguments
@@ -212,3 +259,29 @@ pub type ClientTaskCreator = TaskCreator<ServerJsonrpcRequest, ResultFromClient>
 
 pub type ServerTaskStore = dyn TaskStore<ClientJsonrpcRequest, ResultFromServer>;
 pub type ClientTaskStore = dyn TaskStore<ServerJsonrpcRequest, ResultFromClient>;
+
+/// Builds the response to a `tasks/result` request from a stored task `result`, adding the
+/// `result_meta` stored along with it to the `_meta` of the result.
+pub(crate) fn task_payload_result<Res: serde::Serialize>(
+    result: &Res,
+    result_meta: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<GetTaskPayloadResult, RpcError> {
+    let serde_json::Value::Object(mut extra) = serde_json::to_value(result)
+        .map_err(|err| RpcError::internal_error().with_message(err.to_string()))?
+    else {
+        return Err(RpcError::internal_error()
+            .with_message("The stored task result is not a JSON object.".to_string()));
+    };
+    let mut meta = match extra.remove("_meta") {
+        Some(serde_json::Value::Object(meta)) => Some(meta),
+        _ => None,
+    };
+    if let Some(result_meta) = result_meta {
+        meta.get_or_insert_with(Default::default)
+            .extend(result_meta);
+    }
+    Ok(GetTaskPayloadResult {
+        meta,
+        extra: Some(extra),
+    })
+}
@@ -16,7 +16,7 @@ use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::oneshot::Receiver;
-use tokio::sync::{oneshot, RwLock};
+use tokio::sync::{mpsc, oneshot, RwLock};
 use tokio::task::JoinHandle;
 
 /// Parameters returned by a task status polling callback.
@@ -44,6 +44,9 @@ pub type TaskStatusPoller = Box<
 ///
 type ScheduledPoll = (Instant, TaskId, Option<SessionId>);
 
+/// A published status change and the session of its task.
+type StatusChange = (TaskStatusNotificationParams, Option<String>);
+
 /// Configuration for the in-memory task store.
 ///
 /// All fields have sensible defaults; use [`Default`] or construct
@@ -51,9 +54,12 @@ type ScheduledPoll = (Instant, TaskId, Option<SessionId>);
 pub struct InMemoryTaskStoreOptions {
     /// Page size for task listing (default: `50`).
     pub page_size: usize,
-    /// Capacity of the status-change broadcast channel. Slow subscribers
-    /// that fall behind by more than this many events receive a `Lagged`
-    /// error (default: `64`).
+    /// Capacity of the status-change broadcast channel (default: `64`).
+    ///
+    /// Subscribers that fall behind by more than this many events skip the oldest
+    /// ones, which is logged as a warning. Terminal status changes (`completed`,
+    /// `failed`, `cancelled`) are delivered on a separate unbounded channel and are
+    /// never skipped, so a slow subscriber always learns how a task ended.
     pub broadcast_capacity: usize,
     /// Default poll interval for tasks that do not specify one
     /// (default: `1250` ms). The receiver can override this per-task
@@ -81,6 +87,8 @@ where
     page_size: usize,
     default_poll_interval: Duration,
     broadcast: tokio::sync::broadcast::Sender<(TaskStatusNotificationParams, Option<String>)>,
+    // one sender per subscriber, for terminal status changes that must not be skipped
+    terminal_subscribers: Mutex<Vec<mpsc::UnboundedSender<StatusChange>>>,
     polling_task_handle: Mutex<Option<JoinHandle<()>>>,
 }
 
@@ -90,8 +98,9 @@ struct TaskEntry<Req, Res> {
     #[allow(unused)]
     request: Req, // original request that created the task
     result: Option<Res>, // stored only after store_task_result
+    result_meta: Option<serde_json::Map<String, serde_json::Value>>, // metadata stored with the result
     #[allow(unused)]
-    expires_at: Option<i64>, // Unix millis, for reference (optional now)
+    expires_at: Option<i64>,                     // Unix millis, for reference (optional now)
     meta: Option<serde_json::Map<String, serde_json::Value>>,
     result_tx: Option<tokio::sync::oneshot::Sender<(TaskStatus, Option<Res>)>>,
 }
@@ -163,6 +172,51 @@ impl<Req, Res> InMemoryTaskStoreInner<Req, Res> {
             .and_then(|session_map| session_map.remove(task_id))
     }
 
+    /// Applies the outcome of a round of polling: tasks that reached a terminal status are removed
+    /// and their waiters notified, the others are scheduled for their next poll.
+    /// Returns the time to sleep until the next poll is due.
+    pub(crate) fn apply_poll_results(
+        &mut self,
+        poll_results: Vec<(TaskId, Option<SessionId>, SdkResult<TaskStatusUpdate>)>,
+        default_poll_interval: Duration,
+    ) -> Duration {
+        let default_poll_interval = default_poll_interval.as_millis() as i64;
+        let mut to_reschedule: Vec<(TaskId, Option<SessionId>, i64)> = Vec::new();
+
+        for (task_id, session_id, update) in poll_results {
+            match update {
+                Ok((task_status, _)) if task_status.is_terminal() => {
+                    if let Some(task_entry) = self.remove_task(&task_id, &session_id) {
+                        if let Some(result_tx) = task_entry.result_tx {
+                            let _ = result_tx.send((task_status, task_entry.result));
+                        }
+                    }
+                }
+                Ok((_, poll_interval)) => {
+                    let poll_interval = poll_interval.unwrap_or(default_poll_interval);
+                    to_reschedule.push((task_id, session_id, poll_interval));
+                }
+                Err(err) => {
+                    tracing::error!(
+                        task_id = %task_id,
+                        error = %err,
+                        "task poll callback failed, re-scheduling"
+                    );
+                    if let Some(task_entry) = self.get_task(&task_id, &session_id) {
+                        let poll_interval = task_entry
+                            .task
+                            .poll_interval
+                            .unwrap_or(default_poll_interval);
+                        to_reschedule.push((task_id, session_id, poll_interval));
+                    }
+                }
+            }
+        }
+
+        self.re_schedule(&mut to_reschedule);
+        self.next_sleep_duration()
+    }
+
     pub(crate) fn next_sleep_duration(&self) -> Duration {
         let now = Instant::now();
 
@@ -268,6 +322,7 @@ where
                 poll_schedule: Some(BinaryHeap::new()),
             })),
             broadcast: tokio::sync::broadcast::channel(opts.broadcast_capacity).0,
+            terminal_subscribers: Mutex::new(vec![]),
             page_size: opts.page_size,
             default_poll_interval: opts.default_poll_interval,
             id_gen: Arc::new(FastIdGenerator::new(Some("tsk"))),
@@ -281,13 +336,11 @@ where
     Req: Debug + Clone + Send + Sync + serde::Deserialize<'static> + serde::Serialize + 'static,
     Res: Debug + Clone + Send + Sync + serde::Deserialize<'static> + serde::Serialize + 'static,
 {
-    async fn notify_status_change(
-        &self,
-        task_entry: &TaskEntry<Req, Res>,
-        session_id: Option<&String>,
-    ) {
+    /// Builds the status notification of a task, published once the `inner` lock is released
+    /// since subscribers may call back into the store.
+    fn status_notification(task_entry: &TaskEntry<Req, Res>) -> TaskStatusNotificationParams {
         let task = &task_entry.task;
-        let params = TaskStatusNotificationParams {
+        TaskStatusNotificationParams {
             created_at: task.created_at.to_owned(),
             last_updated_at: task.last_updated_at.to_owned(),
             meta: task_entry.meta.clone(),
@@ -296,8 +349,7 @@ where
             status_message: task.status_message.clone(),
             task_id: task.task_id.clone(),
             ttl: task.ttl,
-        };
-        self.publish_status_change(params, session_id).await;
+        }
     }
 }
 
@@ -312,9 +364,22 @@ where
         event: TaskStatusNotificationParams,
         session_id: Option<&String>,
     ) {
-        let _ = self.broadcast.send((event, session_id.cloned()));
+        let event = (event, session_id.cloned());
+        if !event.0.status.is_terminal() {
+            let _ = self.broadcast.send(event);
+            return;
+        }
+        // senders of dropped streams are removed here
+        if let Ok(mut terminal_subscribers) = self.terminal_subscribers.lock() {
+            terminal_subscribers.retain(|terminal_tx| terminal_tx.send(event.clone()).is_ok());
+        }
     }
 
+    /// Returns a stream of every status change published after this call.
+    ///
+    /// Non-terminal changes go through a broadcast channel of `broadcast_capacity` events, and
+    /// are skipped when the stream falls further behind. Terminal changes are never skipped,
+    /// they are yielded once the non-terminal changes received before them have been consumed.
     fn subscribe(
         &self,
     ) -> Option<
@@ -323,15 +388,28 @@ where
         >,
     > {
         let rx = self.broadcast.subscribe();
-        let stream = stream::unfold(rx, |mut rx| async move {
+        let (terminal_tx, terminal_rx) = mpsc::unbounded_channel();
+        if let Ok(mut terminal_subscribers) = self.terminal_subscribers.lock() {
+            terminal_subscribers.push(terminal_tx);
+        }
+
+        let stream = stream::unfold((rx, terminal_rx), |(mut rx, mut terminal_rx)| async move {
             loop {
-                match rx.recv().await {
-                    Ok(item) => return Some((item, rx)),
-                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
-                        tracing::warn!("Broadcast lagged: skipped {} messages", skipped);
-                        continue;
-                    }
+                tokio::select! {
+                    biased;
+                    item = rx.recv() => match item {
+                        Ok(item) => return Some((item, (rx, terminal_rx))),
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            // the store is gone, only the buffered terminal changes are left
+                            let item = terminal_rx.recv().await?;
+                            return Some((item, (rx, terminal_rx)));
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!("Broadcast lagged: skipped {} messages", skipped);
+                            continue;
+                        }
+                    },
+                    Some(item) = terminal_rx.recv() => return Some((item, (rx, terminal_rx))),
                 }
             }
         });
@@ -370,6 +448,7 @@ where
             task: task.clone(),
             request,
             result: None,
+            result_meta: None,
             expires_at: task_params
                 .ttl
                 .map(|ttl| current_utc_time(Some(ttl)).unix_timestamp()),
@@ -465,63 +544,21 @@ where
         let default_poll_interval = self.default_poll_interval;
         let handle = tokio::spawn(async move {
             loop {
-                let mut to_reschedule: Vec<(TaskId, Option<SessionId>, i64)> = Vec::new();
-                let tasks_to_poll = {
-                    let mut guard = inner.write().await;
-                    guard.tasks_to_poll()
-                };
+                let tasks_to_poll = inner.write().await.tasks_to_poll();
                 tracing::debug!(count = tasks_to_poll.len(), "polling tasks");
 
+                // the callbacks are awaited without holding the lock, their outcomes are
+                // applied afterwards in a single write
+                let mut poll_results = Vec::with_capacity(tasks_to_poll.len());
                 for (task_id, session_id) in tasks_to_poll {
-                    match get_task_callback(task_id.clone(), session_id.clone()).await {
-                        Ok((task_status, poll_interval)) => {
-                            if task_status.is_terminal() {
-                                let mut guard = inner.write().await;
-                                let entry = guard.remove_task(&task_id, &session_id);
-                                if let Some(task_entry) = entry {
-                                    if let Some(result_tx) = task_entry.result_tx {
-                                        let _ = result_tx.send((task_status, task_entry.result));
-                                    }
-                                }
-                            } else {
-                                to_reschedule.push((
-                                    task_id.clone(),
-                                    session_id,
-                                    poll_interval
-                                        .unwrap_or(default_poll_interval.as_millis() as i64),
-                                ));
-                            }
-                        }
-                        Err(err) => {
-                            tracing::error!(
-                                task_id = %task_id,
-                                error = %err,
-                                "task poll callback failed, re-scheduling"
-                            );
-                            let guard = inner.read().await;
-                            if let Some(get_task) = guard.get_task(&task_id, &session_id) {
-                                to_reschedule.push((
-                                    task_id,
-                                    session_id,
-                                    get_task
-                                        .task
-                                        .poll_interval
-                                        .unwrap_or(default_poll_interval.as_millis() as i64),
-                                ));
-                            }
-                        }
-                    }
+                    let update = get_task_callback(task_id.clone(), session_id.clone()).await;
+                    poll_results.push((task_id, session_id, update));
                 }
 
-                if !to_reschedule.is_empty() {
-                    let mut guard = inner.write().await;
-                    guard.re_schedule(&mut to_reschedule)
-                }
-
-                let sleep_duration = {
-                    let guard = inner.read().await;
-                    guard.next_sleep_duration()
-                };
+                let sleep_duration = inner
+                    .write()
+                    .await
+                    .apply_poll_results(poll_results, default_poll_interval);
 
                 tokio::time::sleep(sleep_duration).await;
             }
@@ -580,21 +617,41 @@ where
         result: Res,
         session_id: Option<&String>,
     ) -> () {
-        let mut inner = self.inner.write().await;
-        if let Some(session_map) = inner.tasks.get_mut(&session_id.map(|v| v.to_string())) {
-            if let Some(entry) = session_map.get_mut(task_id) {
-                let status_has_changed = entry.task.status != status;
-
-                entry.task.status = status;
-                entry.result = Some(result.clone());
-                entry.task.last_updated_at = iso8601_time(current_utc_time(None));
-                entry.task.status_message = None;
-                tracing::debug!("Task result stored: {entry}");
-
-                if status_has_changed {
-                    self.notify_status_change(entry, session_id).await;
-                }
-            }
+        self.store_task_result_with_meta(task_id, status, result, None, session_id)
+            .await
+    }
+
+    async fn store_task_result_with_meta(
+        &self,
+        task_id: &str,
+        status: TaskStatus,
+        result: Res,
+        result_meta: Option<serde_json::Map<String, serde_json::Value>>,
+        session_id: Option<&String>,
+    ) -> () {
+        let status_change = {
+            let mut inner = self.inner.write().await;
+            let Some(entry) = inner
+                .tasks
+                .get_mut(&session_id.map(|v| v.to_string()))
+                .and_then(|session_map| session_map.get_mut(task_id))
+            else {
+                return;
+            };
+            let status_has_changed = entry.task.status != status;
+
+            entry.task.status = status;
+            entry.result = Some(result.clone());
+            entry.result_meta = result_meta;
+            entry.task.last_updated_at = iso8601_time(current_utc_time(None));
+            entry.task.status_message = None;
+            tracing::debug!("Task result stored: {entry}");
+
+            status_has_changed.then(|| Self::status_notification(entry))
+        };
+
+        if let Some(params) = status_change {
+            self.publish_status_change(params, session_id).await;
         }
     }
 
@@ -607,6 +664,16 @@ where
             .and_then(|entry| entry.result.clone())
     }
 
+    async fn get_task_result_with_meta(
+        &self,
+        task_id: &str,
+        session_id: Option<String>,
+    ) -> Option<(Res, Option<serde_json::Map<String, serde_json::Value>>)> {
+        let inner = self.inner.read().await;
+        let entry = inner.tasks.get(&session_id)?.get(task_id)?;
+        Some((entry.result.clone()?, entry.result_meta.clone()))
+    }
+
     async fn update_task_status(
         &self,
         task_id: &str,
@@ -614,17 +681,28 @@ where
         status_message: Option<String>,
         session_id: Option<String>,
     ) -> () {
-        let mut inner = self.inner.write().await;
-        if let Some(session_map) = inner.tasks.get_mut(&session_id) {
-            if let Some(entry) = session_map.get_mut(task_id) {
-                if entry.task.status != status {
-                    self.notify_status_change(entry, session_id.as_ref()).await;
-                }
-                entry.task.status = status;
-                entry.task.status_message = status_message;
-                entry.task.last_updated_at = iso8601_time(current_utc_time(None));
-                tracing::debug!("Task status updated: {entry}");
-            }
+        let status_change = {
+            let mut inner = self.inner.write().await;
+            let Some(entry) = inner
+                .tasks
+                .get_mut(&session_id)
+                .and_then(|session_map| session_map.get_mut(task_id))
+            else {
+                return;
+            };
+            let status_has_changed = entry.task.status != status;
+
+            entry.task.status = status;
+            entry.task.status_message = status_message;
+            entry.task.last_updated_at = iso8601_time(current_utc_time(None));
+            tracing::debug!("Task status updated: {entry}");
+
+            status_has_changed.then(|| Self::status_notification(entry))
+        };
+
+        if let Some(params) = status_change {
+            self.publish_status_change(params, session_id.as_ref())
+                .await;
         }
     }
 
@@ -680,6 +758,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::StreamExt;
     use std::time::Duration;
     use tokio::time::{advance, pause, resume};
 
@@ -767,6 +846,34 @@ mod tests {
         assert_eq!(task.status, TaskStatus::InputRequired);
     }
 
+    #[tokio::test]
+    async fn status_change_is_published_with_the_new_status() {
+        let store = InMemoryTaskStore::<serde_json::Value, serde_json::Value>::new(None);
+        let mut stream = store.subscribe().unwrap();
+        let created = store
+            .create_task(create_options(None), 112.into(), dummy_request(), None)
+            .await;
+
+        store
+            .update_task_status(&created.task_id, TaskStatus::InputRequired, None, None)
+            .await;
+        let (params, _) = stream.next().await.unwrap();
+        assert_eq!(params.status, TaskStatus::InputRequired);
+
+        store
+            .store_task_result(
+                &created.task_id,
+                TaskStatus::Completed,
+                serde_json::json!({}),
+                None,
+            )
+            .await;
+        let (params, _) = stream.next().await.unwrap();
+        assert_eq!(params.status, TaskStatus::Completed);
+        let task = store.get_task(&created.task_id, None).await.unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+    }
+
     #[tokio::test]
     async fn store_and_retrieve_task_result() {
         let store = InMemoryTaskStore::<serde_json::Value, serde_json::Value>::new(None);
@@ -796,7 +903,47 @@ mod tests {
         assert_eq!(task.status, TaskStatus::Completed);
 
         let stored = store.get_task_result(&created.task_id, None).await;
-        assert_eq!(stored, Some(result));
+        assert_eq!(stored, Some(result.clone()));
+
+        let stored = store
+            .get_task_result_with_meta(&created.task_id, None)
+            .await;
+        assert_eq!(stored, Some((result, None)));
+    }
+
+    #[tokio::test]
+    async fn store_and_retrieve_task_result_with_meta() {
+        let store = InMemoryTaskStore::<serde_json::Value, serde_json::Value>::new(None);
+        let created = store
+            .create_task(create_options(None), 334.into(), dummy_request(), None)
+            .await;
+        assert!(store
+            .get_task_result_with_meta(&created.task_id, None)
+            .await
+            .is_none());
+
+        let result = serde_json::json!({ "content": [] });
+        let result_meta = serde_json::json!({ "duration_ms": 1250, "cost": 0.02 });
+        store
+            .store_task_result_with_meta(
+                &created.task_id,
+                TaskStatus::Completed,
+                result.clone(),
+                result_meta.as_object().cloned(),
+                None,
+            )
+            .await;
+
+        let (stored, stored_meta) = store
+            .get_task_result_with_meta(&created.task_id, None)
+            .await
+            .unwrap();
+        assert_eq!(stored, result);
+        assert_eq!(stored_meta, result_meta.as_object().cloned());
+        assert_eq!(
+            store.get_task(&created.task_id, None).await.unwrap().status,
+            TaskStatus::Completed
+        );
     }
 
     #[tokio::test]
@@ -1005,6 +1152,41 @@ mod tests {
         assert_eq!(ids[2], task1.task_id);
     }
 
+    #[tokio::test]
+    async fn terminal_status_change_is_not_skipped_by_lagging_subscribers() {
+        let store = InMemoryTaskStore::<serde_json::Value, serde_json::Value>::with_options(
+            InMemoryTaskStoreOptions {
+                broadcast_capacity: 2,
+                ..Default::default()
+            },
+        );
+        let stream = store.subscribe().unwrap();
+        let created = store
+            .create_task(create_options(None), 113.into(), dummy_request(), None)
+            .await;
+
+        for status in [TaskStatus::InputRequired, TaskStatus::Working].repeat(5) {
+            store
+                .update_task_status(&created.task_id, status, None, None)
+                .await;
+        }
+        store
+            .update_task_status(&created.task_id, TaskStatus::Failed, None, None)
+            .await;
+        drop(store);
+
+        let statuses: Vec<_> = stream.map(|(params, _)| params.status).collect().await;
+        // only the last `broadcast_capacity` non-terminal changes are left, the terminal one comes last
+        assert_eq!(
+            statuses,
+            vec![
+                TaskStatus::InputRequired,
+                TaskStatus::Working,
+                TaskStatus::Failed
+            ]
+        );
+    }
+
     #[test]
     fn options_default_values() {
         let opts = InMemoryTaskStoreOptions::default();
@@ -1449,6 +1631,82 @@ mod polling_tests {
         assert_eq!(result.1, Some(Value::from("task result")));
     }
 
+    #[tokio::test]
+    async fn store_stays_usable_while_poll_callbacks_run() {
+        let store = Arc::new(InMemoryTaskStore::<serde_json::Value, serde_json::Value>::new(None));
+
+        // every poll reads and writes the store it is polled for while it is awaited
+        let polled_store = store.clone();
+        let callback: TaskStatusPoller = Box::new(move |task_id, session_id| {
+            let store = polled_store.clone();
+            Box::pin(async move {
+                store.list_tasks(None, session_id.clone()).await;
+                store
+                    .update_task_status(
+                        &task_id,
+                        TaskStatus::Working,
+                        Some("polled".to_string()),
+                        session_id,
+                    )
+                    .await;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok((TaskStatus::Completed, None))
+            })
+        });
+        store.start_task_polling(callback).unwrap();
+
+        let mut tasks = vec![];
+        for i in 0..10 {
+            let task = store
+                .create_task(
+                    CreateTaskOptions {
+                        poll_interval: Some(50),
+                        ttl: Some(60_000),
+                        meta: None,
+                    },
+                    i.into(),
+                    dummy_request(),
+                    None,
+                )
+                .await;
+            tasks.push(task.task_id);
+        }
+
+        let waiters = futures::future::join_all(
+            tasks
+                .iter()
+                .map(|task_id| store.wait_for_task_result(task_id, None)),
+        );
+        let creations = async {
+            for i in 10..30 {
+                store
+                    .create_task(
+                        CreateTaskOptions {
+                            poll_interval: Some(60_000),
+                            ttl: Some(60_000),
+                            meta: None,
+                        },
+                        i.into(),
+                        dummy_request(),
+                        None,
+                    )
+                    .await;
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        };
+
+        let (results, _) = tokio::time::timeout(
+            Duration::from_secs(5),
+            futures::future::join(waiters, creations),
+        )
+        .await
+        .expect("polling blocked the task store");
+
+        for result in results {
+            assert_eq!(result.unwrap().0, TaskStatus::Completed);
+        }
+    }
+
     #[tokio::test]
     async fn uses_default_poll_interval_when_task_has_none() {
         let store = InMemoryTaskStore::<serde_json::Value, serde_json::Value>::with_options(
@@ -0,0 +1,166 @@
+//! Built-in servers for smoke testing transports and clients.
+//!
+//! When a client (for example the MCP inspector) fails to talk to your server, pointing it at
+//! [`echo_server()`] over the same transport helps tell whether the problem lies in your handler
+//! or in the transport / connection layer.
+//!
+//! ```no_run
+//! # async fn run() -> rust_mcp_sdk::error::SdkResult<()> {
+//! use rust_mcp_sdk::{testing, McpServer, StdioTransport, TransportOptions};
+//!
+//! let transport = StdioTransport::new(TransportOptions::default())?;
+//! let server = testing::echo_server(transport);
+//! server.start().await
+//! # }
+//! ```
+//!
+//! For HTTP transports, pass [`echo_server_details()`] and [`EchoServerHandler`] to the HTTP server
+//! (e.g. `create_axum_server` or `create_actix_server`) in place of your own details and handler.
+use crate::mcp_server::{
+    server_runtime, HandlerRegistrations, McpServerOptions, ServerHandler, ServerRuntime,
+};
+use crate::schema::{
+    schema_utils::{
+        CallToolError, ClientMessage, ClientMessages, MessageFromServer, ServerMessage,
+        ServerMessages,
+    },
+    CallToolRequestParams, CallToolResult, Implementation, InitializeResult, ListToolsResult,
+    PaginatedRequestParams, ProtocolVersion, RpcError, ServerCapabilities, ServerCapabilitiesTools,
+    TextContent, Tool, ToolInputSchema,
+};
+use crate::{McpServer, ToMcpServerHandler};
+use async_trait::async_trait;
+use rust_mcp_transport::TransportDispatcher;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Name of the single tool exposed by the echo server.
+pub const ECHO_TOOL_NAME: &str = "echo";
+
+/// A minimal [`ServerHandler`] exposing a single `echo` tool that returns its `message` argument.
+///
+/// Initialization and ping are answered by the default [`ServerHandler`] implementations.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EchoServerHandler;
+
+impl EchoServerHandler {
+    /// Returns the definition of the `echo` tool.
+    pub fn echo_tool() -> Tool {
+        let mut message = Map::new();
+        message.insert("type".to_string(), json!("string"));
+        message.insert("description".to_string(), json!("Text to echo back"));
+
+        Tool {
+            annotations: None,
+            description: Some("Echoes back the provided message".to_string()),
+            execution: None,
+            icons: vec![],
+            input_schema: ToolInputSchema::new(
+                vec!["message".to_string()],
+                Some(BTreeMap::from([("message".to_string(), message)])),
+                None,
+            ),
+            meta: None,
+            name: ECHO_TOOL_NAME.to_string(),
+            output_schema: None,
+            title: Some("Echo".to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerHandler for EchoServerHandler {
+    fn registrations(&self) -> HandlerRegistrations {
+        HandlerRegistrations {
+            methods: vec!["tools/list", "tools/call"],
+            tools: Some(vec![Self::echo_tool()]),
+            ..Default::default()
+        }
+    }
+
+    async fn handle_list_tools_request(
+        &self,
+        _params: Option<PaginatedRequestParams>,
+        _runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<ListToolsResult, RpcError> {
+        Ok(ListToolsResult {
+            meta: None,
+            next_cursor: None,
+            tools: vec![Self::echo_tool()],
+        })
+    }
+
+    async fn handle_call_tool_request(
+        &self,
+        params: CallToolRequestParams,
+        _runtime: Arc<dyn McpServer>,
+    ) -> std::result::Result<CallToolResult, CallToolError> {
+        if params.name != ECHO_TOOL_NAME {
+            return Err(CallToolError::unknown_tool(params.name));
+        }
+
+        let message = params
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("message"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                CallToolError::invalid_arguments(
+                    ECHO_TOOL_NAME,
+                    Some("'message' must be a string".to_string()),
+                )
+            })?;
+
+        Ok(CallToolResult::text_content(vec![TextContent::from(
+            message.to_string(),
+        )]))
+    }
+}
+
+/// Server details advertised by the echo server, declaring the `tools` capability only.
+pub fn echo_server_details() -> InitializeResult {
+    InitializeResult {
+        server_info: Implementation {
+            name: "rust-mcp-sdk-echo-server".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            title: Some("Echo Server".to_string()),
+            description: Some("Built-in echo server for smoke testing".to_string()),
+            icons: vec![],
+            website_url: None,
+        },
+        capabilities: ServerCapabilities {
+            tools: Some(ServerCapabilitiesTools { list_changed: None }),
+            ..Default::default()
+        },
+        meta: None,
+        instructions: Some(format!(
+            "Call the '{ECHO_TOOL_NAME}' tool with a 'message' argument to get it back."
+        )),
+        protocol_version: ProtocolVersion::V2025_11_25.to_string(),
+    }
+}
+
+/// Creates a ready-to-start [`ServerRuntime`] serving [`EchoServerHandler`] over the given transport.
+pub fn echo_server<T>(transport: T) -> Arc<ServerRuntime>
+where
+    T: TransportDispatcher<
+        ClientMessages,
+        MessageFromServer,
+        ClientMessage,
+        ServerMessages,
+        ServerMessage,
+    >,
+{
+    server_runtime::create_server(McpServerOptions {
+        server_details: echo_server_details(),
+        transport,
+        handler: EchoServerHandler.to_mcp_server_handler(),
+        task_store: None,
+        client_task_store: None,
+        message_observer: None,
+        audit_sink: None,
+        handler_execution: Default::default(),
+        require_initialized: false,
+    })
+}
@@ -1,12 +1,22 @@
 use crate::error::{McpSdkError, ProtocolErrorKind, SdkResult};
-use crate::schema::{ClientMessages, ProtocolVersion, SdkError};
+use crate::schema::{ClientMessages, PaginatedRequestParams, ProtocolVersion, RpcError, SdkError};
 use std::cmp::Ordering;
+use std::future::Future;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use time::format_description::well_known::Iso8601;
 use time::OffsetDateTime;
 #[cfg(feature = "auth")]
 use url::Url;
 
+/// Value that replaces secrets in audit records and logged bodies.
+#[cfg(any(
+    feature = "server",
+    feature = "sse",
+    feature = "streamable-http",
+    feature = "auth"
+))]
+pub const REDACTED_VALUE: &str = "[REDACTED]";
+
 /// A guard type that automatically aborts a Tokio task when dropped.
 ///
 /// This ensures that the associated task does not outlive the scope
@@ -182,6 +192,28 @@ pub(crate) fn remove_query_and_hash(endpoint: &str) -> String {
     }
 }
 
+/// Checks that `method` can be used as the method of a custom (vendor-specific) message.
+///
+/// The method must be non-empty and free of whitespace and control characters. Methods starting with
+/// `rpc.` are reserved by JSON-RPC and methods starting with `notifications/` by the MCP specification,
+/// vendor extensions should use their own prefix instead, e.g. `acme/report`.
+pub fn validate_custom_method(method: &str) -> SdkResult<()> {
+    let reason = if method.is_empty() {
+        "must not be empty"
+    } else if method.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        "must not contain whitespace or control characters"
+    } else if method.starts_with("rpc.") {
+        "is reserved by JSON-RPC"
+    } else if method.starts_with("notifications/") {
+        "is reserved by the MCP specification"
+    } else {
+        return Ok(());
+    };
+    Err(RpcError::invalid_params()
+        .with_message(format!("Invalid custom method '{method}': {reason}"))
+        .into())
+}
+
 /// Checks if the input string is valid JSON and represents an "initialize" method request.
 pub fn valid_initialize_method(json_str: &str) -> SdkResult<()> {
     // Attempt to deserialize the input string into ClientMessages
@@ -275,6 +307,149 @@ pub fn join_url(base: &Url, segment: &str) -> Result<Url, url::ParseError> {
     Ok(url)
 }
 
+/// Default number of pages the `list_all_*` methods of `McpClient` and the server manifest request
+/// before giving up.
+pub const DEFAULT_MAX_LIST_PAGES: usize = 1000;
+
+/// Requests the pages of a paginated list one after the other, passing the `next_cursor` of each
+/// page to the request of the next one, and returns the items of all pages.
+///
+/// Fails after `max_pages` pages, or when a page returns the cursor it was requested with.
+pub async fn collect_pages<T, F, Fut>(max_pages: usize, mut request_page: F) -> SdkResult<Vec<T>>
+where
+    F: FnMut(Option<PaginatedRequestParams>) -> Fut,
+    Fut: Future<Output = SdkResult<(Vec<T>, Option<String>)>>,
+{
+    let mut items = vec![];
+    let mut cursor: Option<String> = None;
+    for _ in 0..max_pages {
+        let params = cursor.clone().map(|cursor| PaginatedRequestParams {
+            cursor: Some(cursor),
+            meta: None,
+        });
+        let (page, next_cursor) = request_page(params).await?;
+        items.extend(page);
+        match next_cursor {
+            None => return Ok(items),
+            Some(next_cursor) if cursor.as_ref() == Some(&next_cursor) => {
+                return Err(McpSdkError::Internal {
+                    description: format!(
+                        "The server returned the same cursor '{next_cursor}' twice in a row"
+                    ),
+                });
+            }
+            next_cursor => cursor = next_cursor,
+        }
+    }
+    Err(McpSdkError::Internal {
+        description: format!("The list has more than {max_pages} pages"),
+    })
+}
+
+/// Builds example arguments from a tool's input schema, with placeholder values of the right
+/// type for each field. Defaults, `const` values and the first `enum` variant are used when
+/// available, optional fields are left out and nested objects are filled in.
+///
+/// `schema` must have its `$defs` inlined. The `sample_arguments()` method generated by
+/// `#[mcp_tool]` calls this with the tool's input schema.
+#[cfg(feature = "macros")]
+pub fn sample_arguments(schema: &serde_json::Map<String, serde_json::Value>) -> serde_json::Value {
+    use serde_json::Value;
+
+    if let Some(value) = schema.get("default").or_else(|| schema.get("const")) {
+        return value.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(|v| v.as_array())
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+    // derived enums and nullable wrappers: sample the first non-null variant
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema.get(keyword).and_then(|v| v.as_array()) {
+            if let Some(variant) = variants
+                .iter()
+                .filter_map(|v| v.as_object())
+                .find(|v| v.get("type").and_then(|t| t.as_str()) != Some("null"))
+            {
+                return sample_arguments(variant);
+            }
+        }
+    }
+
+    let type_name = match schema.get("type") {
+        Some(Value::String(t)) => t.as_str(),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null")
+            .unwrap_or("null"),
+        _ if schema.contains_key("properties") => "object",
+        _ => "null",
+    };
+
+    match type_name {
+        "object" => {
+            // optional fields are not listed in `required` and are left out of the sample
+            let required: Vec<&str> = schema
+                .get("required")
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|item| item.as_str()).collect())
+                .unwrap_or_default();
+            let properties = schema
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .filter(|(key, _)| required.contains(&key.as_str()))
+                        .filter_map(|(key, value)| {
+                            value
+                                .as_object()
+                                .map(|value| (key.clone(), sample_arguments(value)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Value::Object(properties)
+        }
+        "array" => Value::Array(
+            schema
+                .get("items")
+                .and_then(|items| items.as_object())
+                .map(sample_arguments)
+                .into_iter()
+                .collect(),
+        ),
+        "string" => {
+            let placeholder = match schema.get("format").and_then(|f| f.as_str()) {
+                Some("date-time") => "2025-01-01T00:00:00Z",
+                Some("date") => "2025-01-01",
+                Some("time") => "00:00:00",
+                Some("email") => "user@example.com",
+                Some("uri") | Some("url") => "https://example.com",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                _ => "string",
+            };
+            Value::String(placeholder.to_string())
+        }
+        "integer" => {
+            let minimum = schema.get("minimum").and_then(|m| m.as_i64());
+            let maximum = schema.get("maximum").and_then(|m| m.as_i64());
+            Value::from(minimum.unwrap_or(maximum.map_or(0, |max| max.min(0))))
+        }
+        "number" => {
+            let minimum = schema.get("minimum").and_then(|m| m.as_f64());
+            let maximum = schema.get("maximum").and_then(|m| m.as_f64());
+            Value::from(minimum.unwrap_or(maximum.map_or(0.0, |max| max.min(0.0))))
+        }
+        "boolean" => Value::Bool(false),
+        _ => Value::Null,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,6 +468,25 @@ mod tests {
         assert_eq!(remove_query_and_hash("/"), "/");
     }
 
+    #[test]
+    fn test_validate_custom_method() {
+        assert!(validate_custom_method("acme/report").is_ok());
+        assert!(validate_custom_method("vendor.event_pushed").is_ok());
+
+        for method in [
+            "",
+            "acme report",
+            "acme/\nreport",
+            "rpc.discover",
+            "notifications/message",
+        ] {
+            let err = validate_custom_method(method).unwrap_err();
+            assert!(
+                matches!(err, McpSdkError::RpcError(rpc_error) if rpc_error.code == RpcError::invalid_params().code)
+            );
+        }
+    }
+
     #[test]
     fn test_join_url() {
         let expect = "http://example.com/api/user/userinfo";
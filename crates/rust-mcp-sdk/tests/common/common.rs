@@ -12,6 +12,7 @@ use rust_mcp_sdk::auth::{AuthInfo, AuthenticationError, OauthTokenVerifier};
 use rust_mcp_sdk::mcp_client::ClientHandler;
 use rust_mcp_sdk::mcp_icon;
 use rust_mcp_sdk::schema::{ClientCapabilities, Implementation, InitializeRequestParams};
+use rust_mcp_sdk::SseParser;
 use std::collections::HashMap;
 use std::process;
 use std::sync::Once;
@@ -176,60 +177,99 @@ pub async fn send_option_request(
 
 use futures::stream::Stream;
 
-// stream: &mut impl Stream<Item = Result<hyper::body::Bytes, hyper::Error>>,
-/// reads sse events and return them as (id, event, data) tuple
-pub async fn read_sse_event_from_stream(
+/// A single server-sent event, as received by the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedSseEvent {
+    pub id: Option<String>,
+    pub event: Option<String>,
+    pub data: String,
+}
+
+impl ReceivedSseEvent {
+    /// Splits the event id into its `session-.-stream-.-timestamp` components.
+    pub fn parsed_id(&self) -> Option<(&str, &str, u128)> {
+        let mut parts = self.id.as_deref()?.split("-.-");
+        let session_id = parts.next()?;
+        let stream_id = parts.next()?;
+        let time_stamp = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some((session_id, stream_id, time_stamp))
+    }
+
+    pub fn into_tuple(self) -> (Option<String>, Option<String>, String) {
+        (self.id, self.event, self.data)
+    }
+}
+
+/// Asserts that every event carries a well-formed event id and that ids are strictly
+/// increasing within each stream, which is what resumability via `Last-Event-ID` relies on.
+pub fn assert_monotonic_event_ids(events: &[ReceivedSseEvent]) {
+    let mut last_per_stream: HashMap<String, u128> = HashMap::new();
+    for (index, event) in events.iter().enumerate() {
+        let (_, stream_id, time_stamp) = event
+            .parsed_id()
+            .unwrap_or_else(|| panic!("event #{index} has a missing or invalid id: {event:?}"));
+        if let Some(previous) = last_per_stream.insert(stream_id.to_string(), time_stamp) {
+            assert!(
+                time_stamp > previous,
+                "event #{index} id is not greater than the previous id on stream '{stream_id}': {time_stamp} <= {previous}"
+            );
+        }
+    }
+}
+
+/// Reads up to `event_count` sse events from the stream, skipping events without data.
+pub async fn read_sse_events_from_stream(
     stream: &mut (impl Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin),
     event_count: usize,
-) -> Option<Vec<(Option<String>, Option<String>, String)>> {
-    let mut buffer = String::new();
+) -> Option<Vec<ReceivedSseEvent>> {
+    let mut parser = SseParser::new();
     let mut events = vec![];
 
     while let Some(item) = stream.next().await {
-        match item {
-            Ok(chunk) => {
-                let chunk_str = std::str::from_utf8(&chunk).unwrap();
-                buffer.push_str(chunk_str);
-
-                while let Some(pos) = buffer.find("\n\n") {
-                    let (event_str, rest) = buffer.split_at(pos);
-                    let mut id = None;
-                    let mut event = None;
-                    let mut data = None;
-
-                    // Process the event string
-                    for line in event_str.lines() {
-                        if line.starts_with("id:") {
-                            id = Some(line.trim_start_matches("id:").trim().to_string());
-                        } else if line.starts_with("event:") {
-                            event = Some(line.trim_start_matches("event:").trim().to_string());
-                        } else if line.starts_with("data:") {
-                            data = Some(line.trim_start_matches("data:").trim().to_string());
-                        }
-                    }
-
-                    // Update buffer after processing
-                    buffer = rest[2..].to_string(); // Skip "\n\n"
-
-                    // Only include events with data
-                    if let Some(data) = data {
-                        events.push((id, event, data));
-                        if events.len().eq(&event_count) {
-                            return Some(events);
-                        }
-                    }
-                }
-            }
-            Err(_e) => {
-                return None;
+        let chunk = item.ok()?;
+        for event in parser.process_new_chunk(chunk) {
+            let Some(data) = event.data else {
+                continue;
+            };
+            events.push(ReceivedSseEvent {
+                id: event.id,
+                event: event.event,
+                data: String::from_utf8_lossy(&data).into_owned(),
+            });
+            if events.len() == event_count {
+                return Some(events);
             }
         }
     }
-    if !events.is_empty() {
-        Some(events)
-    } else {
-        None
-    }
+
+    (!events.is_empty()).then_some(events)
+}
+
+/// Reads up to `event_count` sse events from the response body.
+pub async fn read_sse_events(
+    response: Response,
+    event_count: usize,
+) -> Option<Vec<ReceivedSseEvent>> {
+    let mut stream = response.bytes_stream();
+    read_sse_events_from_stream(&mut stream, event_count).await
+}
+
+/// reads sse events and return them as (id, event, data) tuple
+pub async fn read_sse_event_from_stream(
+    stream: &mut (impl Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin),
+    event_count: usize,
+) -> Option<Vec<(Option<String>, Option<String>, String)>> {
+    read_sse_events_from_stream(stream, event_count)
+        .await
+        .map(|events| {
+            events
+                .into_iter()
+                .map(ReceivedSseEvent::into_tuple)
+                .collect()
+        })
 }
 
 /// return sse event as (id, event, data) tuple
@@ -238,8 +278,7 @@ pub async fn read_sse_event(
     event_count: usize,
 ) -> Option<Vec<(Option<String>, Option<String>, String)>> {
     let mut stream = response.bytes_stream();
-    let events = read_sse_event_from_stream(&mut stream, event_count).await;
-    events
+    read_sse_event_from_stream(&mut stream, event_count).await
 }
 
 pub fn test_client_info() -> InitializeRequestParams {
@@ -2,8 +2,9 @@ use async_trait::async_trait;
 use rust_mcp_schema::{
     schema_utils::{MessageFromServer, RequestFromServer},
     CreateTaskResult, ElicitRequestParams, ElicitResult, ElicitResultAction, ElicitResultContent,
-    ElicitResultContentPrimitive, LoggingMessageNotificationParams, PingRequest, RequestParams,
-    RpcError, TaskStatus, TaskStatusNotificationParams,
+    ElicitResultContentPrimitive, ListRootsResult, LoggingMessageNotificationParams,
+    NotificationParams, PingRequest, RequestParams, Root, RpcError, TaskStatus,
+    TaskStatusNotificationParams,
 };
 use rust_mcp_sdk::{
     mcp_client::ClientHandler,
@@ -12,11 +13,21 @@ use rust_mcp_sdk::{
     McpClient,
 };
 use serde_json::json;
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::sync::RwLock;
 
 use crate::common::task_runner::{McpTaskRunner, TaskJobInfo};
 
+/// Time the test client takes to answer a `roots/list` request, simulating a slow handler.
+pub const LIST_ROOTS_DELAY: Duration = Duration::from_millis(300);
+
 pub mod test_client_common {
     use rust_mcp_schema::{
         schema_utils::MessageFromServer, ClientCapabilities, ClientElicitation, ClientRoots,
@@ -38,6 +49,7 @@ pub mod test_client_common {
         Mock, MockServer, ResponseTemplate,
     };
 
+    use super::ConcurrencyCounter;
     use crate::common::{
         create_sse_response, task_runner::McpTaskRunner, test_server_common::INITIALIZE_RESPONSE,
         wait_for_n_requests,
@@ -87,6 +99,20 @@ pub mod test_client_common {
         mcp_url: &str,
         custom_headers: Option<HashMap<String, String>>,
     ) -> (Arc<ClientRuntime>, Arc<RwLock<Vec<MessageFromServer>>>) {
+        let (client, message_history, _) =
+            create_client_with_concurrency_counter(mcp_url, custom_headers).await;
+        (client, message_history)
+    }
+
+    /// Same as `create_client`, also returning the counter of `roots/list` requests handled at the same time.
+    pub async fn create_client_with_concurrency_counter(
+        mcp_url: &str,
+        custom_headers: Option<HashMap<String, String>>,
+    ) -> (
+        Arc<ClientRuntime>,
+        Arc<RwLock<Vec<MessageFromServer>>>,
+        Arc<ConcurrencyCounter>,
+    ) {
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::EnvFilter::try_from_default_env()
@@ -108,9 +134,11 @@ pub mod test_client_common {
         };
 
         let message_history = Arc::new(RwLock::new(vec![]));
+        let list_roots_concurrency = Arc::new(ConcurrencyCounter::default());
         let handler = super::TestClientHandler {
             message_history: message_history.clone(),
             mcp_task_runner: McpTaskRunner::new(),
+            list_roots_concurrency: list_roots_concurrency.clone(),
         };
 
         let client = client_runtime::with_transport_options(
@@ -123,7 +151,7 @@ pub mod test_client_common {
         );
 
         // client.clone().start().await.unwrap();
-        (client, message_history)
+        (client, message_history, list_roots_concurrency)
     }
 
     pub async fn initialize_client(
@@ -175,10 +203,33 @@ pub mod test_client_common {
     }
 }
 
+/// Counts the calls in progress at the same time, and the highest number reached.
+#[derive(Default)]
+pub struct ConcurrencyCounter {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl ConcurrencyCounter {
+    fn enter(&self) {
+        let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(current, Ordering::SeqCst);
+    }
+
+    fn exit(&self) {
+        self.current.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
 // Test handler
 pub struct TestClientHandler {
     message_history: Arc<RwLock<Vec<MessageFromServer>>>,
     mcp_task_runner: McpTaskRunner,
+    list_roots_concurrency: Arc<ConcurrencyCounter>,
 }
 
 impl TestClientHandler {
@@ -206,6 +257,28 @@ impl ClientHandler for TestClientHandler {
         })
     }
 
+    async fn handle_list_roots_request(
+        &self,
+        params: Option<RequestParams>,
+        _runtime: &dyn McpClient,
+    ) -> std::result::Result<ListRootsResult, RpcError> {
+        self.register_message(&MessageFromServer::RequestFromServer(
+            RequestFromServer::ListRootsRequest(params),
+        ))
+        .await;
+        self.list_roots_concurrency.enter();
+        tokio::time::sleep(LIST_ROOTS_DELAY).await;
+        self.list_roots_concurrency.exit();
+        Ok(ListRootsResult {
+            meta: None,
+            roots: vec![Root {
+                meta: None,
+                name: Some("workspace".to_string()),
+                uri: "file:///workspace".to_string(),
+            }],
+        })
+    }
+
     async fn handle_logging_message_notification(
         &self,
         params: LoggingMessageNotificationParams,
@@ -218,6 +291,18 @@ impl ClientHandler for TestClientHandler {
         Ok(())
     }
 
+    async fn handle_tool_list_changed_notification(
+        &self,
+        params: Option<NotificationParams>,
+        _runtime: &dyn McpClient,
+    ) -> std::result::Result<(), RpcError> {
+        self.register_message(&MessageFromServer::NotificationFromServer(
+            NotificationFromServer::ToolListChangedNotification(params),
+        ))
+        .await;
+        Ok(())
+    }
+
     async fn handle_task_status_notification(
         &self,
         params: TaskStatusNotificationParams,
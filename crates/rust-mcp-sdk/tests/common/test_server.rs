@@ -131,6 +131,44 @@ pub mod test_server_common {
                     let tool = DisplayAuthInfo {};
                     Ok(tool.call_tool(runtime.auth_info_cloned().await).unwrap())
                 }
+                "current_request_id" => {
+                    let request_id = runtime
+                        .current_request_id()
+                        .map(|id| id.to_string())
+                        .unwrap_or_default();
+                    Ok(CallToolResult::text_content(vec![request_id.into()]))
+                }
+                "sleep" => {
+                    let duration_ms = params
+                        .arguments
+                        .as_ref()
+                        .and_then(|arguments| arguments.get("duration_ms"))
+                        .and_then(Value::as_u64)
+                        .unwrap_or(1000);
+                    tokio::time::sleep(Duration::from_millis(duration_ms)).await;
+                    Ok(CallToolResult::text_content(vec!["done".into()]))
+                }
+                "report_progress" => {
+                    let progress_token = runtime.request_context().progress_token;
+                    for step in 1..=3 {
+                        runtime
+                            .report_progress(progress_token.clone(), step as f64, Some(3.0), None)
+                            .await
+                            .unwrap();
+                    }
+                    Ok(CallToolResult::text_content(vec!["done".into()]))
+                }
+                "request_context" => {
+                    let context = runtime.request_context();
+                    let details = serde_json::json!({
+                        "session_id": context.session_id,
+                        "request_id": context.request_id,
+                        "progress_token": context.progress_token,
+                    });
+                    Ok(CallToolResult::text_content(vec![details
+                        .to_string()
+                        .into()]))
+                }
                 _ => Ok(
                     CallToolError::unknown_tool(format!("Unknown tool: {}", params.name)).into(),
                 ),
@@ -27,6 +27,7 @@ async fn tets_client_launch_npx_server() {
         task_store: None,
         server_task_store: None,
         message_observer: None,
+        max_concurrent_requests: None,
     });
 
     client.clone().start().await.unwrap();
@@ -59,6 +60,7 @@ async fn tets_client_launch_uvx_server() {
         task_store: None,
         server_task_store: None,
         message_observer: None,
+        max_concurrent_requests: None,
     });
     client.clone().start().await.unwrap();
     let server_capabilities = client.server_capabilities().unwrap();
@@ -0,0 +1,106 @@
+#[path = "common/common.rs"]
+pub mod common;
+
+use common::{random_port, test_client_common::create_client, ONE_MILLISECOND};
+use mcp_axum::{create_axum_server, AxumRuntime, AxumServerOptions};
+use rust_mcp_sdk::{
+    error::McpSdkError,
+    mcp_server::ToMcpServerHandler,
+    schema::{CallToolRequestParams, ContentBlock},
+    testing::{echo_server_details, EchoServerHandler, ECHO_TOOL_NAME},
+    McpClient,
+};
+use serde_json::json;
+use std::time::Duration;
+
+// should serve the echo tool and answer ping over streamable http
+#[tokio::test]
+async fn should_serve_echo_tool() {
+    let options = AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    };
+    let streamable_url = options.streamable_http_url();
+    let server = create_axum_server(
+        echo_server_details(),
+        EchoServerHandler.to_mcp_server_handler(),
+        options,
+    );
+    let axum_runtime = AxumRuntime::create(server).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(75)).await;
+
+    let (client, _) = create_client(&streamable_url, None).await;
+    client.clone().start().await.unwrap();
+
+    assert_eq!(
+        client.server_info().unwrap().server_info.name,
+        "rust-mcp-sdk-echo-server"
+    );
+    assert!(client.ping(None, None).await.is_ok());
+
+    let tools = client.request_tool_list(None).await.unwrap();
+    assert_eq!(tools.tools.len(), 1);
+    assert_eq!(tools.tools[0].name, ECHO_TOOL_NAME);
+
+    let result = client
+        .request_tool_call(CallToolRequestParams {
+            arguments: Some(json!({"message": "hello"}).as_object().unwrap().clone()),
+            meta: None,
+            name: ECHO_TOOL_NAME.to_string(),
+            task: None,
+        })
+        .await
+        .unwrap();
+    let ContentBlock::TextContent(content) = &result.content[0] else {
+        panic!("expected a text content!")
+    };
+    assert_eq!(content.text, "hello");
+
+    let result = client
+        .request_tool_call(CallToolRequestParams {
+            arguments: None,
+            meta: None,
+            name: ECHO_TOOL_NAME.to_string(),
+            task: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should round trip typed arguments and results through the echo tool
+#[tokio::test]
+async fn should_call_echo_tool_with_typed_arguments() {
+    let options = AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    };
+    let streamable_url = options.streamable_http_url();
+    let server = create_axum_server(
+        echo_server_details(),
+        EchoServerHandler.to_mcp_server_handler(),
+        options,
+    );
+    let axum_runtime = AxumRuntime::create(server).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(75)).await;
+
+    let (client, _) = create_client(&streamable_url, None).await;
+    client.clone().start().await.unwrap();
+
+    // the echoed message is the JSON text the result is read from
+    let result: serde_json::Value = client
+        .call_tool_typed(ECHO_TOOL_NAME, json!({"message": r#"{"sum": 3}"#}))
+        .await
+        .unwrap();
+    assert_eq!(result, json!({"sum": 3}));
+
+    let error = client
+        .call_tool_typed::<_, serde_json::Value>(ECHO_TOOL_NAME, ())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, McpSdkError::ToolFailed { tool, .. } if tool == ECHO_TOOL_NAME));
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
@@ -104,3 +104,187 @@ fn test_mcp_tool() {
     assert!(schema_properties.contains_key("field1"));
     assert!(schema_properties.contains_key("field2"));
 }
+
+#[test]
+fn test_elicit_content_builder() {
+    use rust_mcp_macros::{mcp_elicit, JsonSchema};
+    use rust_mcp_schema::{ElicitResultAction, ElicitResultContent};
+    use rust_mcp_sdk::{ElicitContentBuilder, ElicitResultContentExt};
+
+    #[mcp_elicit(message = "Please enter your info", mode = form)]
+    #[derive(JsonSchema)]
+    pub struct UserInfo {
+        pub name: String,
+        pub age: i32,
+        pub score: i64,
+        pub subscribe: bool,
+        pub tags: Vec<String>,
+    }
+
+    let result = ElicitContentBuilder::new()
+        .string("name", "Ali")
+        .number("age", 21)
+        .number("score", i64::MAX)
+        .boolean("subscribe", true)
+        .string_array("tags", ["Rust", "C++"])
+        .accept();
+    assert_eq!(result.action, ElicitResultAction::Accept);
+
+    let user = UserInfo::from_elicit_result_content(result.content).unwrap();
+    assert_eq!(user.name, "Ali");
+    assert_eq!(user.age, 21);
+    assert_eq!(user.score, i64::MAX);
+    assert!(user.subscribe);
+    assert_eq!(user.tags, vec!["Rust".to_string(), "C++".to_string()]);
+
+    // an i32 field rejects a number out of its range
+    let content = ElicitContentBuilder::new()
+        .string("name", "Ali")
+        .number("age", i64::from(i32::MAX) + 1)
+        .number("score", 0)
+        .boolean("subscribe", false)
+        .value(
+            "tags",
+            ElicitResultContent::string_array(Vec::<String>::new()),
+        )
+        .build();
+    assert!(UserInfo::from_elicit_result_content(Some(content)).is_err());
+}
+
+#[test]
+fn test_elicit_outcome() {
+    use rust_mcp_macros::{mcp_elicit, JsonSchema};
+    use rust_mcp_schema::{ElicitResult, ElicitResultAction};
+    use rust_mcp_sdk::{ElicitContentBuilder, ElicitOutcome, ElicitResultExt};
+
+    #[mcp_elicit(message = "Please enter your name", mode = form)]
+    #[derive(Debug, PartialEq, JsonSchema)]
+    pub struct UserName {
+        pub name: String,
+    }
+
+    let accepted = ElicitContentBuilder::new().string("name", "Ali").accept();
+    assert_eq!(
+        accepted
+            .into_outcome(UserName::from_elicit_result_content)
+            .unwrap(),
+        ElicitOutcome::Accepted(UserName {
+            name: "Ali".to_string()
+        })
+    );
+
+    let declined = ElicitResult {
+        action: ElicitResultAction::Decline,
+        content: None,
+        meta: None,
+    };
+    assert_eq!(
+        declined
+            .into_outcome(UserName::from_elicit_result_content)
+            .unwrap(),
+        ElicitOutcome::Declined
+    );
+
+    let cancelled = ElicitResult {
+        action: ElicitResultAction::Cancel,
+        content: None,
+        meta: None,
+    };
+    assert_eq!(
+        cancelled
+            .into_outcome(UserName::from_elicit_result_content)
+            .unwrap(),
+        ElicitOutcome::Cancelled
+    );
+
+    // accepted content that does not match the form is still an error
+    let malformed = ElicitContentBuilder::new().number("name", 1).accept();
+    assert!(malformed
+        .into_outcome(UserName::from_elicit_result_content)
+        .is_err());
+}
+
+#[test]
+fn test_mcp_tool_sample_arguments() {
+    use rust_mcp_sdk::macros::{mcp_tool, JsonSchema};
+    use serde_json::json;
+
+    #[derive(JsonSchema, serde::Deserialize, Debug, PartialEq)]
+    enum Mode {
+        Overwrite,
+        Append,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema, serde::Deserialize, Debug)]
+    struct Owner {
+        name: String,
+        #[json_schema(format = "email")]
+        email: String,
+        nickname: Option<String>,
+    }
+
+    #[allow(unused)]
+    #[mcp_tool(name = "save_file", description = "Save a file")]
+    #[derive(JsonSchema, serde::Deserialize, Debug)]
+    struct SaveFileTool {
+        path: String,
+        #[json_schema(minimum = 1)]
+        copies: u32,
+        ratio: f64,
+        overwrite: bool,
+        tags: Vec<String>,
+        mode: Mode,
+        owner: Owner,
+        #[json_schema(default = "utf-8")]
+        encoding: String,
+        comment: Option<String>,
+    }
+
+    let sample = SaveFileTool::sample_arguments();
+    assert_eq!(
+        sample,
+        json!({
+            "path": "string",
+            "copies": 1,
+            "ratio": 0.0,
+            "overwrite": false,
+            "tags": ["string"],
+            "mode": "Overwrite",
+            "owner": { "name": "string", "email": "user@example.com" },
+            "encoding": "utf-8"
+        })
+    );
+
+    // the sample is valid input for the tool
+    let tool: SaveFileTool = serde_json::from_value(sample).unwrap();
+    assert_eq!(tool.mode, Mode::Overwrite);
+    assert!(tool.comment.is_none());
+    assert!(tool.owner.nickname.is_none());
+}
+
+#[test]
+fn test_mcp_tool_sample_arguments_inline_shared_definitions() {
+    use rust_mcp_sdk::macros::{mcp_tool, JsonSchema};
+    use serde_json::json;
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[allow(unused)]
+    #[derive(JsonSchema)]
+    #[mcp_tool(name = "draw_line", description = "desc")]
+    struct DrawLine {
+        start: Point,
+        end: Option<Point>,
+    }
+
+    assert_eq!(
+        DrawLine::sample_arguments(),
+        json!({ "start": { "x": 0, "y": 0 } })
+    );
+}
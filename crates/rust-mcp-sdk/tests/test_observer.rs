@@ -66,6 +66,7 @@ async fn test_client_observer_receives_and_sends_messages() {
         task_store: None,
         server_task_store: None,
         message_observer: Some(observer.clone()),
+        max_concurrent_requests: None,
     });
 
     client.clone().start().await.unwrap();
@@ -0,0 +1,177 @@
+use rust_mcp_sdk::mcp_server::{
+    render_prompt_template, substitute_arguments, PromptArguments, PromptRegistry,
+};
+use rust_mcp_sdk::schema::{
+    ContentBlock, GetPromptRequestParams, Prompt, PromptArgument, PromptMessage, Role,
+};
+
+fn prompt(name: &str, arguments: Vec<PromptArgument>) -> Prompt {
+    Prompt {
+        name: name.to_string(),
+        title: None,
+        description: Some(format!("The {name} prompt")),
+        arguments,
+        icons: vec![],
+        meta: None,
+    }
+}
+
+fn argument(name: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        title: None,
+        description: None,
+        required: Some(required),
+    }
+}
+
+fn get_params(name: &str, arguments: &[(&str, &str)]) -> GetPromptRequestParams {
+    GetPromptRequestParams {
+        name: name.to_string(),
+        arguments: Some(
+            arguments
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        ),
+        meta: None,
+    }
+}
+
+fn registry() -> PromptRegistry {
+    PromptRegistry::new()
+        .with_template(
+            prompt(
+                "code_review",
+                vec![argument("code", true), argument("language", false)],
+            ),
+            vec![(Role::User, "Review this {{language}} code:\n{{ code }}")],
+        )
+        .with_prompt(prompt("greeting", vec![]), |arguments| {
+            let name = arguments.get("name").map(String::as_str).unwrap_or("there");
+            Ok(vec![PromptMessage {
+                role: Role::Assistant,
+                content: ContentBlock::text_content(format!("Hello {name}!")),
+            }])
+        })
+}
+
+fn message_text(message: &PromptMessage) -> &str {
+    match &message.content {
+        ContentBlock::TextContent(text) => &text.text,
+        other => panic!("Expected text content, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_list_prompts_in_registration_order() {
+    let result = registry().list_prompts();
+    let names: Vec<_> = result.prompts.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["code_review", "greeting"]);
+    assert!(result.next_cursor.is_none());
+}
+
+#[test]
+fn test_registering_same_name_replaces_prompt() {
+    let registry = registry().with_template(prompt("greeting", vec![]), vec![(Role::User, "Hi")]);
+    assert_eq!(registry.prompts().len(), 2);
+
+    let result = registry.get_prompt(&get_params("greeting", &[])).unwrap();
+    assert_eq!(message_text(&result.messages[0]), "Hi");
+}
+
+#[test]
+fn test_get_prompt_substitutes_arguments() {
+    let result = registry()
+        .get_prompt(&get_params(
+            "code_review",
+            &[("code", "fn main() {}"), ("language", "Rust")],
+        ))
+        .unwrap();
+    assert_eq!(
+        result.description.as_deref(),
+        Some("The code_review prompt")
+    );
+    assert_eq!(result.messages.len(), 1);
+    assert_eq!(result.messages[0].role, Role::User);
+    assert_eq!(
+        message_text(&result.messages[0]),
+        "Review this Rust code:\nfn main() {}"
+    );
+}
+
+#[test]
+fn test_get_prompt_with_render_function() {
+    let result = registry()
+        .get_prompt(&get_params("greeting", &[("name", "Ada")]))
+        .unwrap();
+    assert_eq!(result.messages[0].role, Role::Assistant);
+    assert_eq!(message_text(&result.messages[0]), "Hello Ada!");
+}
+
+#[test]
+fn test_get_prompt_missing_required_argument() {
+    let error = registry()
+        .get_prompt(&get_params("code_review", &[("language", "Rust")]))
+        .unwrap_err();
+    assert_eq!(
+        error.message,
+        "Missing required argument 'code' for prompt 'code_review'"
+    );
+}
+
+#[test]
+fn test_get_unknown_prompt() {
+    let error = registry()
+        .get_prompt(&get_params("unknown", &[]))
+        .unwrap_err();
+    assert_eq!(error.message, "Unknown prompt 'unknown'");
+}
+
+#[test]
+fn test_substitute_arguments() {
+    let arguments: PromptArguments = [("name".to_string(), "Ada".to_string())].into();
+    assert_eq!(substitute_arguments("Hi {{name}}", &arguments), "Hi Ada");
+    assert_eq!(substitute_arguments("Hi {{missing}}!", &arguments), "Hi !");
+    assert_eq!(substitute_arguments("{ {{name}} }", &arguments), "{ Ada }");
+    assert_eq!(substitute_arguments("Hi {{name", &arguments), "Hi {{name");
+    assert_eq!(
+        substitute_arguments("{{ not a name }} {{name}}", &arguments),
+        "{{ not a name }} Ada"
+    );
+    assert_eq!(substitute_arguments("{{{name}}}", &arguments), "{Ada}");
+}
+
+#[test]
+fn test_substitute_arguments_does_not_expand_values() {
+    let arguments: PromptArguments = [
+        ("name".to_string(), "{{other}}".to_string()),
+        ("other".to_string(), "oops".to_string()),
+    ]
+    .into();
+    assert_eq!(
+        substitute_arguments("Hi {{name}}", &arguments),
+        "Hi {{other}}"
+    );
+}
+
+#[test]
+fn test_render_prompt_template() {
+    let prompt = prompt(
+        "summary",
+        vec![argument("text", true), argument("style", false)],
+    );
+
+    let arguments: PromptArguments = [("text".to_string(), "a long story".to_string())].into();
+    assert_eq!(
+        render_prompt_template("Summarize {{text}}{{style}}", &prompt, &arguments).unwrap(),
+        "Summarize a long story"
+    );
+
+    let arguments: PromptArguments = [("style".to_string(), "briefly".to_string())].into();
+    let error = render_prompt_template("Summarize {{text}}", &prompt, &arguments).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Missing required argument 'text' for prompt 'summary'"
+    );
+}
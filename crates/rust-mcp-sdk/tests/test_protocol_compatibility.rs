@@ -4,7 +4,11 @@ pub mod common;
 mod protocol_compatibility_on_server {
 
     use rust_mcp_sdk::mcp_server::{McpServerOptions, ServerHandler, ToMcpServerHandler};
-    use rust_mcp_sdk::schema::{InitializeResult, RpcError, INTERNAL_ERROR};
+    use rust_mcp_sdk::schema::{
+        Implementation, InitializeRequestParams, InitializeResult, RpcError, INTERNAL_ERROR,
+        INVALID_REQUEST,
+    };
+    use rust_mcp_sdk::McpServer;
 
     use crate::common::task_runner::McpTaskRunner;
     use crate::common::{
@@ -36,6 +40,9 @@ mod protocol_compatibility_on_server {
             task_store: None,
             client_task_store: None,
             message_observer: None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
         });
 
         handler
@@ -65,4 +72,86 @@ mod protocol_compatibility_on_server {
         assert!(result.is_err());
         assert!(matches!(result, Err(err) if err.code == INTERNAL_ERROR));
     }
+
+    struct PerClientHandler;
+
+    impl ServerHandler for PerClientHandler {
+        fn customize_initialize(
+            &self,
+            params: &InitializeRequestParams,
+            mut base: InitializeResult,
+        ) -> InitializeResult {
+            base.instructions = Some(format!("Welcome {}", params.client_info.name));
+            base
+        }
+    }
+
+    #[tokio::test]
+    async fn tets_initialize_result_customized_per_client() {
+        let transport =
+            rust_mcp_sdk::StdioTransport::new(rust_mcp_sdk::TransportOptions::default()).unwrap();
+        let runtime = rust_mcp_sdk::mcp_server::server_runtime::create_server(McpServerOptions {
+            server_details: test_server_details(),
+            transport,
+            handler: PerClientHandler.to_mcp_server_handler(),
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+        });
+
+        let mut initialize_request = test_client_info();
+        initialize_request.protocol_version = "2024_11_05".to_string();
+
+        let result = PerClientHandler
+            .handle_initialize_request(initialize_request, runtime)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.instructions.as_deref(),
+            Some("Welcome test-rust-mcp-client")
+        );
+        // the negotiated protocol version is kept
+        assert_eq!(result.protocol_version, "2024_11_05");
+    }
+
+    struct ClientVersionCheckHandler;
+
+    impl ServerHandler for ClientVersionCheckHandler {
+        fn accept_client(&self, client_info: &Implementation) -> Result<(), RpcError> {
+            if client_info.version == "0.1.0" {
+                return Err(RpcError::invalid_request()
+                    .with_message(format!("{} 0.1.0 is not supported", client_info.name)));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn tets_initialize_rejected_for_refused_client() {
+        let transport =
+            rust_mcp_sdk::StdioTransport::new(rust_mcp_sdk::TransportOptions::default()).unwrap();
+        let runtime = rust_mcp_sdk::mcp_server::server_runtime::create_server(McpServerOptions {
+            server_details: test_server_details(),
+            transport,
+            handler: ClientVersionCheckHandler.to_mcp_server_handler(),
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: Default::default(),
+            require_initialized: false,
+        });
+
+        let result = ClientVersionCheckHandler
+            .handle_initialize_request(test_client_info(), runtime.clone())
+            .await;
+        let error = result.unwrap_err();
+        assert_eq!(error.code, INVALID_REQUEST);
+        assert_eq!(error.message, "test-rust-mcp-client 0.1.0 is not supported");
+        // the client is not recorded as initialized
+        assert!(runtime.client_info().is_none());
+    }
 }
@@ -4,16 +4,22 @@ mod tets_server_sse {
     use std::{sync::Arc, time::Duration};
 
     use crate::common::{
-        sse_data, sse_event,
+        random_port, sse_data, sse_event, test_client_info,
         test_server_common::{
-            collect_sse_lines, create_test_server, TestIdGenerator, INITIALIZE_REQUEST,
+            collect_sse_lines, create_start_server, create_test_server, LaunchedServer,
+            TestIdGenerator, INITIALIZE_REQUEST,
         },
+        TestClientHandler,
     };
     use mcp_axum::AxumServerOptions;
     use reqwest::Client;
-    use rust_mcp_sdk::schema::{
-        schema_utils::{ResultFromServer, ServerMessage},
-        ServerResult,
+    use rust_mcp_sdk::{
+        mcp_client::{client_runtime, ClientRuntime, McpClientOptions},
+        schema::{
+            schema_utils::{ResultFromServer, ServerMessage},
+            CallToolRequestParams, CallToolResult, ContentBlock,
+        },
+        ClientSseTransport, ClientSseTransportOptions, McpClient, ToMcpClientHandler,
     };
     use tokio::time::sleep;
 
@@ -211,4 +217,124 @@ mod tets_server_sse {
         handle.graceful_shutdown(Some(Duration::from_millis(1)));
         server_task.await.unwrap();
     }
+
+    fn sse_client(sse_url: &str) -> Arc<ClientRuntime> {
+        let transport = ClientSseTransport::new(
+            sse_url,
+            ClientSseTransportOptions {
+                request_timeout: Duration::from_secs(5),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        client_runtime::create_client(McpClientOptions {
+            client_details: test_client_info(),
+            transport,
+            handler: TestClientHandler {}.to_mcp_client_handler(),
+            task_store: None,
+            server_task_store: None,
+            message_observer: None,
+            max_concurrent_requests: None,
+        })
+    }
+
+    fn say_hello_params(name: &str) -> CallToolRequestParams {
+        CallToolRequestParams {
+            arguments: Some(serde_json::Map::from_iter([(
+                "name".to_string(),
+                serde_json::Value::String(name.to_string()),
+            )])),
+            meta: None,
+            name: "say_hello".to_string(),
+            task: None,
+        }
+    }
+
+    fn text_of(result: &CallToolResult) -> &str {
+        let ContentBlock::TextContent(content) = &result.content[0] else {
+            panic!("expected a text content!")
+        };
+        &content.text
+    }
+
+    // should initialize and call a tool through the `endpoint` event and the messages endpoint
+    #[tokio::test]
+    async fn tets_sse_client_round_trip() {
+        let LaunchedServer {
+            axum_runtime,
+            sse_url,
+            ..
+        } = create_start_server(AxumServerOptions {
+            port: random_port(),
+            custom_messages_endpoint: Some("/custom-msg-endpoint?something=true".to_string()),
+            session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+                "AAA-BBB-CCC".to_string()
+            ]))),
+            ..Default::default()
+        })
+        .await;
+
+        let client = sse_client(&sse_url);
+        client.clone().start().await.unwrap();
+
+        // the server details came back over the SSE stream, answering the posted initialize request
+        let server_info = client.server_info().unwrap();
+        assert_eq!(server_info.server_info.name, "Test MCP Server");
+        assert!(client.server_has_tools().unwrap());
+        assert_eq!(
+            axum_runtime.sessions().await,
+            vec!["AAA-BBB-CCC".to_string()]
+        );
+
+        let tools = client.request_tool_list(None).await.unwrap();
+        assert!(tools.tools.iter().any(|tool| tool.name == "say_hello"));
+
+        let result = client
+            .request_tool_call(say_hello_params("SSE"))
+            .await
+            .unwrap();
+        assert_eq!(text_of(&result), "Hello, SSE!");
+
+        client.shut_down().await.unwrap();
+        axum_runtime.graceful_shutdown(Some(Duration::from_millis(1)));
+    }
+
+    // should route the responses of each session to its own SSE stream
+    #[tokio::test]
+    async fn tets_sse_clients_receive_their_own_responses() {
+        let LaunchedServer {
+            axum_runtime,
+            sse_url,
+            ..
+        } = create_start_server(AxumServerOptions {
+            port: random_port(),
+            session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+                "AAA-BBB-CCC".to_string(),
+                "DDD-EEE-FFF".to_string(),
+            ]))),
+            ..Default::default()
+        })
+        .await;
+
+        let first = sse_client(&sse_url);
+        first.clone().start().await.unwrap();
+        let second = sse_client(&sse_url);
+        second.clone().start().await.unwrap();
+
+        let mut sessions = axum_runtime.sessions().await;
+        sessions.sort();
+        assert_eq!(sessions, vec!["AAA-BBB-CCC", "DDD-EEE-FFF"]);
+
+        let (first_result, second_result) = tokio::join!(
+            first.request_tool_call(say_hello_params("first")),
+            second.request_tool_call(say_hello_params("second"))
+        );
+        assert_eq!(text_of(&first_result.unwrap()), "Hello, first!");
+        assert_eq!(text_of(&second_result.unwrap()), "Hello, second!");
+
+        first.shut_down().await.unwrap();
+        second.shut_down().await.unwrap();
+        axum_runtime.graceful_shutdown(Some(Duration::from_millis(1)));
+    }
 }
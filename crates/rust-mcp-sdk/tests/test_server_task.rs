@@ -3,20 +3,24 @@ pub mod common;
 mod test_streamable_http_server;
 
 use crate::common::{
-    init_tracing, read_sse_event, sample_tools::TaskAugmentedTool, send_post_request,
-    task_runner::TaskJobInfo, ONE_MILLISECOND,
+    init_tracing, random_port, read_sse_event, sample_tools::TaskAugmentedTool, send_post_request,
+    task_runner::TaskJobInfo, test_client_common::create_client,
+    test_server_common::create_start_server, ONE_MILLISECOND,
 };
 use http::StatusCode;
+use mcp_axum::AxumServerOptions;
 use rust_mcp_macros::{mcp_elicit, JsonSchema};
 use rust_mcp_schema::{
     schema_utils::{ClientJsonrpcRequest, RequestFromClient},
     CallToolResult, CreateTaskResult, ElicitRequestParams, ElicitResult, ElicitResultAction,
-    ElicitResultContent, ElicitResultContentPrimitive, GetTaskResult, RequestId, Task,
-    TaskMetadata, TaskStatus,
+    ElicitResultContent, ElicitResultContentPrimitive, GetTaskPayloadParams, GetTaskResult,
+    RequestId, Task, TaskMetadata, TaskStatus,
 };
 use rust_mcp_sdk::schema::{
     ClientJsonrpcResponse, ResultFromServer, ServerJsonrpcNotification, ServerJsonrpcResponse,
 };
+use rust_mcp_sdk::task_store::{CreateTaskOptions, InMemoryTaskStore};
+use rust_mcp_sdk::McpClient;
 use serde_json::json;
 use std::{collections::BTreeMap, panic, sync::Arc, time::Duration};
 use test_streamable_http_server::*;
@@ -236,3 +240,59 @@ async fn test_server_task_wait_for_result() {
 
     axum_server.graceful_shutdown(ONE_MILLISECOND);
 }
+
+// should surface the metadata stored with a task result in the `_meta` of the tasks/result response
+#[tokio::test]
+async fn test_server_task_result_meta_round_trip() {
+    init_tracing();
+    let server = create_start_server(AxumServerOptions {
+        port: random_port(),
+        task_store: Some(Arc::new(InMemoryTaskStore::new(None))),
+        ..Default::default()
+    })
+    .await;
+
+    let (client, _) = create_client(&server.streamable_url, None).await;
+    client.clone().start().await.unwrap();
+    let session_id = client.session_id().await;
+
+    let store = server.axum_runtime.task_store().unwrap();
+    let task = store
+        .create_task(
+            CreateTaskOptions {
+                ttl: None,
+                poll_interval: None,
+                meta: None,
+            },
+            RequestId::Integer(1),
+            ClientJsonrpcRequest::new(
+                RequestId::Integer(1),
+                RequestFromClient::CallToolRequest(TaskAugmentedTool::request_params()),
+            ),
+            session_id.clone(),
+        )
+        .await;
+    let result_meta = json!({ "duration_ms": 1250, "cost": 0.02 });
+    store
+        .store_task_result_with_meta(
+            &task.task_id,
+            TaskStatus::Completed,
+            CallToolResult::text_content(vec!["task-completed".into()]).into(),
+            result_meta.as_object().cloned(),
+            session_id.as_ref(),
+        )
+        .await;
+
+    let result = client
+        .request_get_task_payload(GetTaskPayloadParams {
+            task_id: task.task_id,
+        })
+        .await
+        .unwrap();
+    let text_content = result.content[0].as_text_content().unwrap();
+    assert_eq!(text_content.text, "task-completed");
+    assert_eq!(result.meta, result_meta.as_object().cloned());
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
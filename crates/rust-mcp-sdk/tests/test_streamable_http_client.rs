@@ -4,24 +4,39 @@ pub mod common;
 use crate::common::{
     create_sse_response, debug_wiremock, random_port,
     test_client_common::{
-        initialize_client, InitializedClient, INITIALIZE_REQUEST, TEST_SESSION_ID,
+        initialize_client, test_client_details, InitializedClient, INITIALIZE_REQUEST,
+        TEST_SESSION_ID,
     },
     test_server_common::{
         create_start_server, LaunchedServer, TestIdGenerator, INITIALIZE_RESPONSE,
     },
     wait_for_n_requests, wiremock_request, MockBuilder, SimpleMockServer, SseEvent,
+    ONE_MILLISECOND,
 };
-use common::test_client_common::create_client;
+use async_trait::async_trait;
+use common::test_client_common::{create_client, create_client_with_concurrency_counter};
 use http::{Method, StatusCode};
-use mcp_axum::AxumServerOptions;
+use mcp_axum::{AxumRuntime, AxumServerOptions};
 use rust_mcp_schema::{
     schema_utils::{
-        ClientJsonrpcRequest, ClientMessage, CustomRequest, MessageFromServer, RequestFromClient,
-        RequestFromServer, ResultFromServer, RpcMessage, ServerMessage,
+        ClientJsonrpcNotification, ClientJsonrpcRequest, ClientMessage, CustomRequest, McpMessage,
+        MessageFromServer, RequestFromClient, RequestFromServer, ResultFromServer, RpcMessage,
+        ServerMessage,
     },
     RequestId,
 };
-use rust_mcp_sdk::{error::McpSdkError, McpClient, TransportError, MCP_LAST_EVENT_ID_HEADER};
+use rust_mcp_sdk::{
+    detect_transport,
+    error::McpSdkError,
+    error::SdkResult,
+    mcp_client::{client_runtime, client_runtime_core, ClientHandler, ClientHandlerCore},
+    schema::{
+        schema_utils::{NotificationFromServer, ResultFromClient, ServerJsonrpcRequest},
+        CallToolRequestParams, LoggingLevel, LoggingMessageNotificationParams, RpcError,
+    },
+    ClientMiddleware, DetectedTransport, McpClient, McpObserver, RequestOptions,
+    StreamableTransportOptions, TransportError, MCP_LAST_EVENT_ID_HEADER,
+};
 use serde_json::{json, Map, Value};
 use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
 use wiremock::{
@@ -186,6 +201,65 @@ async fn should_store_session_id_received_during_initialization() {
     wait_for_n_requests(&mock_server, 2, None).await;
 }
 
+// should send the negotiated protocol version on every request after initialization
+#[tokio::test]
+async fn should_send_negotiated_protocol_version_header() {
+    let mock_server = MockServer::start().await;
+
+    // the server negotiates a lower version than the one requested by the client
+    let initialize_response = INITIALIZE_RESPONSE.replace("2025-11-25", "2025-06-18");
+    Mock::given(method("POST"))
+        .and(path("/mcp"))
+        .and(body_json_string(INITIALIZE_REQUEST))
+        .respond_with(
+            create_sse_response(&initialize_response)
+                .append_header("mcp-session-id", TEST_SESSION_ID),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/mcp"))
+        .and(body_json_string(
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        ))
+        .and(header("mcp-protocol-version", "2025-06-18"))
+        .respond_with(ResponseTemplate::new(202))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/mcp"))
+        .and(header("mcp-protocol-version", "2025-06-18"))
+        .respond_with(ResponseTemplate::new(405))
+        .expect(1..)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/mcp"))
+        .and(header("mcp-protocol-version", "2025-06-18"))
+        .respond_with(ResponseTemplate::new(202))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mcp_url = format!("{}/mcp", mock_server.uri());
+    let (client, _) = create_client(&mcp_url, None).await;
+    client.clone().start().await.unwrap();
+    wait_for_n_requests(&mock_server, 3, None).await;
+
+    // the version is not known yet when the initialize request is sent
+    let initialize_request = wiremock_request(&mock_server, 0).await;
+    assert!(!initialize_request
+        .headers
+        .contains_key(HeaderName::from_str("mcp-protocol-version").unwrap()));
+
+    client.terminate_session().await;
+}
+
 // should terminate session with DELETE request
 #[tokio::test]
 async fn should_terminate_session_with_delete_request() {
@@ -395,6 +469,770 @@ async fn should_receive_server_initiated_messaged() {
     assert!(matches!(v, Value::Number(value) if value.as_i64().unwrap()==1515)) //1515 is passed from TestClientHandler
 }
 
+// should handle server-initiated requests concurrently and answer each with its own response
+#[tokio::test]
+async fn should_handle_server_initiated_requests_concurrently() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        enable_json_response: Some(false),
+        ..Default::default()
+    };
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = create_start_server(server_options).await;
+
+    let (client, message_history, list_roots_concurrency) =
+        create_client_with_concurrency_counter(&streamable_url, None).await;
+    client.clone().start().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let axum_runtime = Arc::new(axum_runtime);
+    request_root_lists(&axum_runtime, 4).await;
+
+    // all four requests are in progress at the same time, instead of one after another
+    assert_eq!(list_roots_concurrency.peak(), 4);
+
+    let list_roots_requests = message_history
+        .read()
+        .await
+        .iter()
+        .filter(|m| {
+            matches!(
+                m,
+                MessageFromServer::RequestFromServer(RequestFromServer::ListRootsRequest(_))
+            )
+        })
+        .count();
+    assert_eq!(list_roots_requests, 4);
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should not handle more server-initiated requests at the same time than the configured limit
+#[tokio::test]
+async fn should_limit_concurrent_server_initiated_requests() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        enable_json_response: Some(false),
+        ..Default::default()
+    };
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = create_start_server(server_options).await;
+
+    let (client, _, list_roots_concurrency) =
+        create_client_with_concurrency_counter(&streamable_url, None).await;
+    client.set_max_concurrent_server_requests(2);
+    client.clone().start().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let axum_runtime = Arc::new(axum_runtime);
+    request_root_lists(&axum_runtime, 4).await;
+
+    assert_eq!(list_roots_concurrency.peak(), 2);
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+/// Sends `count` concurrent `roots/list` requests to the client of session "AAA-BBB-CCC", checking each response.
+async fn request_root_lists(axum_runtime: &Arc<AxumRuntime>, count: usize) {
+    let tasks: Vec<_> = (0..count)
+        .map(|_| {
+            let axum_runtime = axum_runtime.clone();
+            tokio::spawn(async move {
+                axum_runtime
+                    .request_root_list(&"AAA-BBB-CCC".to_string(), None)
+                    .await
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        let result = task.await.unwrap().unwrap();
+        assert_eq!(result.roots.len(), 1);
+        assert_eq!(result.roots[0].uri, "file:///workspace");
+    }
+}
+
+struct DefaultClientHandler;
+impl ClientHandler for DefaultClientHandler {}
+
+// a core handler that implements none of the requests, including ping
+struct EmptyCoreClientHandler;
+#[async_trait]
+impl ClientHandlerCore for EmptyCoreClientHandler {
+    async fn handle_request(
+        &self,
+        _request: ServerJsonrpcRequest,
+        _runtime: &dyn McpClient,
+    ) -> std::result::Result<ResultFromClient, RpcError> {
+        Err(RpcError::method_not_found())
+    }
+
+    async fn handle_notification(
+        &self,
+        _notification: NotificationFromServer,
+        _runtime: &dyn McpClient,
+    ) -> std::result::Result<(), RpcError> {
+        Ok(())
+    }
+
+    async fn handle_error(
+        &self,
+        _error: &RpcError,
+        _runtime: &dyn McpClient,
+    ) -> std::result::Result<(), RpcError> {
+        Ok(())
+    }
+}
+
+// should pass the origin validation of a server with DNS rebinding protection
+#[tokio::test]
+async fn should_send_configured_origin_header() {
+    let port = random_port();
+    let origin = format!("http://127.0.0.1:{port}");
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = create_start_server(AxumServerOptions {
+        port,
+        dns_rebinding: rust_mcp_sdk::mcp_http::DnsRebindingOptions {
+            allowed_origins: Some(vec![origin.clone()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await;
+
+    let transport_options = |origin: Option<String>| StreamableTransportOptions {
+        mcp_url: streamable_url.clone(),
+        request_options: RequestOptions {
+            request_timeout: Duration::from_secs(2),
+            max_retries: Some(0),
+            origin,
+            ..RequestOptions::default()
+        },
+    };
+
+    // without an `Origin` header the server rejects the initialize request
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        transport_options(None),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    assert!(client.clone().start().await.is_err());
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        transport_options(Some(origin)),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    client.ping(None, None).await.unwrap();
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    axum_runtime.await_server().await.unwrap()
+}
+
+async fn launch_server_for_ping() -> LaunchedServer {
+    create_start_server(AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        enable_json_response: Some(false),
+        ..Default::default()
+    })
+    .await
+}
+
+fn ping_transport_options(streamable_url: &str) -> StreamableTransportOptions {
+    StreamableTransportOptions {
+        mcp_url: streamable_url.to_string(),
+        request_options: RequestOptions {
+            request_timeout: Duration::from_secs(2),
+            ..RequestOptions::default()
+        },
+    }
+}
+
+// should answer server pings over the standalone stream when the handler does not override ping
+#[tokio::test]
+async fn should_answer_server_ping_with_default_handler() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let result = axum_runtime
+        .ping(
+            &"AAA-BBB-CCC".to_string(),
+            None,
+            Some(Duration::from_secs(1)),
+        )
+        .await;
+    assert!(result.is_ok());
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should answer server pings on behalf of core handlers that do not implement ping
+#[tokio::test]
+async fn should_answer_server_ping_with_core_handler() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime_core::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        EmptyCoreClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let result = axum_runtime
+        .ping(
+            &"AAA-BBB-CCC".to_string(),
+            None,
+            Some(Duration::from_secs(1)),
+        )
+        .await;
+    assert!(result.is_ok());
+
+    // other requests are still reported as unsupported
+    let error = axum_runtime
+        .request_root_list(&"AAA-BBB-CCC".to_string(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, McpSdkError::RpcError(error) if error.code == -32601));
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should shut the client down once its shutdown guard is dropped, unless the guard is disarmed
+#[tokio::test]
+async fn should_shut_down_client_when_guard_is_dropped() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let new_client = || {
+        client_runtime::with_transport_options(
+            test_client_details(),
+            ping_transport_options(&streamable_url),
+            DefaultClientHandler,
+            None,
+            None,
+            None,
+        )
+    };
+
+    let guard = new_client().shutdown_on_drop();
+    guard.clone().start().await.unwrap();
+    let client = Arc::clone(&guard);
+    assert!(!client.is_shut_down().await);
+
+    drop(guard);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(client.is_shut_down().await);
+
+    let client = new_client().shutdown_on_drop().into_inner();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!client.is_shut_down().await);
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should fail with a timeout error when the response takes longer than the request timeout
+#[tokio::test]
+async fn should_time_out_requests_awaiting_a_response() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+
+    let mut arguments = Map::new();
+    arguments.insert("duration_ms".to_string(), json!(1500));
+    let result = client
+        .request(
+            RequestFromClient::CallToolRequest(CallToolRequestParams {
+                arguments: Some(arguments),
+                meta: None,
+                name: "sleep".to_string(),
+                task: None,
+            }),
+            Some(Duration::from_millis(200)),
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(McpSdkError::Timeout { timeout, .. }) if timeout == Duration::from_millis(200))
+    );
+    assert!(client.pending_requests().is_empty());
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should invoke the progress callback for the progress notifications of its request
+#[tokio::test]
+async fn should_forward_progress_notifications_to_the_request_callback() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+
+    let progress = Arc::new(std::sync::Mutex::new(vec![]));
+    let received = progress.clone();
+    let result = client
+        .request_with_progress(
+            RequestFromClient::CallToolRequest(CallToolRequestParams {
+                arguments: None,
+                meta: None,
+                name: "report_progress".to_string(),
+                task: None,
+            }),
+            None,
+            move |params| {
+                received
+                    .lock()
+                    .unwrap()
+                    .push((params.progress, params.total));
+            },
+        )
+        .await;
+
+    result.unwrap();
+    assert_eq!(
+        *progress.lock().unwrap(),
+        vec![(1.0, Some(3.0)), (2.0, Some(3.0)), (3.0, Some(3.0))]
+    );
+
+    // task requests do not accept a progress token
+    let result = client
+        .request_with_progress(
+            RequestFromClient::GetTaskRequest(rust_mcp_schema::GetTaskParams {
+                task_id: "task-1".to_string(),
+            }),
+            None,
+            |_| {},
+        )
+        .await;
+    assert!(matches!(result, Err(McpSdkError::RpcError(_))));
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should run a fresh initialize handshake in a new session, ending the previous one
+#[tokio::test]
+async fn should_reinitialize_in_a_new_session() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = create_start_server(AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string(),
+            "DDD-EEE-FFF".to_string(),
+        ]))),
+        enable_json_response: Some(false),
+        ..Default::default()
+    })
+    .await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    let session_id = client.session_id().await;
+
+    let mut server_details = client.watch_server_details();
+    server_details.mark_unchanged();
+
+    let result = client.reinitialize().await.unwrap();
+    assert_eq!(
+        result.server_info.name,
+        client.server_info().unwrap().server_info.name
+    );
+    assert!(server_details.has_changed().unwrap());
+
+    assert_eq!(session_id.as_deref(), Some("AAA-BBB-CCC"));
+    let new_session_id = client.session_id().await;
+    assert_eq!(new_session_id.as_deref(), Some("DDD-EEE-FFF"));
+    assert_eq!(axum_runtime.sessions().await, vec![new_session_id.unwrap()]);
+
+    // the new session serves requests
+    assert!(client.ping(None, None).await.is_ok());
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should hold requests back once the maximum number of concurrent requests is reached
+#[tokio::test]
+async fn should_limit_concurrent_requests() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    client.set_max_concurrent_requests(Some(2));
+
+    let calls: Vec<_> = (0..6)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let mut arguments = Map::new();
+                arguments.insert("duration_ms".to_string(), json!(300));
+                client
+                    .request_tool_call(CallToolRequestParams {
+                        arguments: Some(arguments),
+                        meta: None,
+                        name: "sleep".to_string(),
+                        task: None,
+                    })
+                    .await
+            })
+        })
+        .collect();
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(client.in_flight_request_count(), 2);
+
+    // waiting requests are not rejected, they are sent as soon as a slot is free
+    for call in calls {
+        assert!(call.await.unwrap().is_ok());
+    }
+    assert_eq!(client.in_flight_request_count(), 0);
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// records the cancellations received by the server
+#[derive(Default)]
+struct CancellationObserver {
+    cancelled: std::sync::Mutex<Vec<(Option<RequestId>, Option<String>)>>,
+}
+
+impl McpObserver<ClientMessage, ServerMessage> for CancellationObserver {
+    fn on_receive(&self, message: &ClientMessage) {
+        if let ClientMessage::Notification(ClientJsonrpcNotification::CancelledNotification(
+            notification,
+        )) = message
+        {
+            self.cancelled.lock().unwrap().push((
+                notification.params.request_id.clone(),
+                notification.params.reason.clone(),
+            ));
+        }
+    }
+}
+
+// should cancel a request that timed out on the server as well
+#[tokio::test]
+async fn should_notify_the_server_when_a_request_times_out() {
+    let observer = Arc::new(CancellationObserver::default());
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = create_start_server(AxumServerOptions {
+        port: random_port(),
+        enable_json_response: Some(false),
+        message_observer: Some(observer.clone()),
+        ..Default::default()
+    })
+    .await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+
+    let mut arguments = Map::new();
+    arguments.insert("duration_ms".to_string(), json!(1500));
+    let result = client
+        .request(
+            RequestFromClient::CallToolRequest(CallToolRequestParams {
+                arguments: Some(arguments),
+                meta: None,
+                name: "sleep".to_string(),
+                task: None,
+            }),
+            Some(Duration::from_millis(200)),
+        )
+        .await;
+    let Err(McpSdkError::Timeout { request_id, .. }) = result else {
+        panic!("expected a timeout error, got {result:?}");
+    };
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let cancelled = observer.cancelled.lock().unwrap().clone();
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled[0].0, Some(request_id));
+    assert_eq!(
+        cancelled[0].1.as_deref(),
+        Some("Request timed out after 200ms")
+    );
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should not deadlock when tool calls, server notifications and shutdown run concurrently
+#[tokio::test]
+async fn should_not_deadlock_under_concurrent_calls_notifications_and_shutdown() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    let session_id = client.session_id().await.unwrap();
+
+    let stress = async {
+        let calls: Vec<_> = (0..20)
+            .map(|index| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    let mut arguments = Map::new();
+                    arguments.insert("duration_ms".to_string(), json!(10 * index));
+                    client
+                        .request_tool_call(CallToolRequestParams {
+                            arguments: Some(arguments),
+                            meta: None,
+                            name: "sleep".to_string(),
+                            task: None,
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        let notifications = (0..20).map(|index| {
+            axum_runtime.notify_log_message(
+                &session_id,
+                LoggingMessageNotificationParams {
+                    data: json!(format!("notification {index}")),
+                    level: LoggingLevel::Info,
+                    logger: None,
+                    meta: None,
+                },
+            )
+        });
+        futures::future::join_all(notifications).await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        client.shut_down().await.unwrap();
+
+        // calls still in flight may complete or fail, but must not hang
+        for call in calls {
+            let _ = call.await.unwrap();
+        }
+    };
+
+    tokio::time::timeout(Duration::from_secs(10), stress)
+        .await
+        .expect("the runtime did not settle, a lock is likely held across an await");
+    assert!(client.is_shut_down().await);
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should list in-flight requests and cancel them individually
+#[tokio::test]
+async fn should_list_and_cancel_pending_requests() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        ..
+    } = launch_server_for_ping().await;
+
+    let client = client_runtime::with_transport_options(
+        test_client_details(),
+        ping_transport_options(&streamable_url),
+        DefaultClientHandler,
+        None,
+        None,
+        None,
+    );
+    client.clone().start().await.unwrap();
+    assert!(client.pending_requests().is_empty());
+
+    let client_clone = client.clone();
+    let call = tokio::spawn(async move {
+        let mut arguments = Map::new();
+        arguments.insert("duration_ms".to_string(), json!(1500));
+        client_clone
+            .request_tool_call(CallToolRequestParams {
+                arguments: Some(arguments),
+                meta: None,
+                name: "sleep".to_string(),
+                task: None,
+            })
+            .await
+    });
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let pending = client.pending_requests();
+    assert_eq!(pending.len(), 1);
+
+    let cancelled = client
+        .cancel_request(&pending[0], Some("no longer needed".to_string()))
+        .await
+        .unwrap();
+    assert!(cancelled);
+
+    let result = call.await.unwrap();
+    assert!(
+        matches!(result, Err(McpSdkError::RequestCancelled { request_id }) if request_id == pending[0])
+    );
+    assert!(client.pending_requests().is_empty());
+
+    // requests that are no longer pending can't be cancelled
+    let cancelled = client.cancel_request(&pending[0], None).await.unwrap();
+    assert!(!cancelled);
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+// should detect the transport of the server and connect through it
+#[tokio::test]
+async fn should_detect_server_transport() {
+    let LaunchedServer {
+        axum_runtime,
+        streamable_url,
+        sse_url,
+        ..
+    } = create_start_server(AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    })
+    .await;
+
+    let transport = detect_transport(
+        &streamable_url,
+        &test_client_details(),
+        &RequestOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(transport, DetectedTransport::StreamableHttp);
+    // the session opened by the probe is terminated
+    assert!(axum_runtime.sessions().await.is_empty());
+
+    let transport = detect_transport(&sse_url, &test_client_details(), &RequestOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(transport, DetectedTransport::Sse);
+
+    for server_url in [&streamable_url, &sse_url] {
+        let client = client_runtime::with_detected_transport(
+            test_client_details(),
+            server_url,
+            RequestOptions::default(),
+            DefaultClientHandler,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        client.clone().start().await.unwrap();
+        assert!(client.server_info().is_some());
+        assert!(client.ping(None, None).await.is_ok());
+        client.shut_down().await.unwrap();
+    }
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
 // should attempt initial GET connection and handle 405 gracefully
 #[tokio::test]
 async fn should_attempt_initial_get_connection_and_handle_405_gracefully() {
@@ -847,3 +1685,146 @@ async fn should_not_reconnect_a_post_initiated_stream_that_fails() {
 // should support custom reconnection options
 // uses custom fetch implementation if provided
 // should have exponential backoff with configurable maxRetries
+
+// should expose the instructions and implementation details received from the server
+#[tokio::test]
+async fn should_expose_server_instructions_and_implementation() {
+    let mock_server = MockServer::start().await;
+
+    let initialize_response = json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "result": {
+            "protocolVersion": "2025-11-25",
+            "capabilities": {"tools": {}},
+            "serverInfo": {
+                "name": "instructed-server",
+                "version": "2.1.0",
+                "title": "Instructed Server",
+                "websiteUrl": "https://example.com/instructed-server",
+                "icons": [{"src": "https://example.com/icon.png", "mimeType": "image/png"}]
+            },
+            "instructions": "Call `search` before `fetch`."
+        }
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/mcp"))
+        .and(body_json_string(INITIALIZE_REQUEST))
+        .respond_with(create_sse_response(&initialize_response.to_string()))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/mcp"))
+        .and(body_json_string(
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        ))
+        .respond_with(ResponseTemplate::new(202))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mcp_url = format!("{}/mcp", mock_server.uri());
+    let (client, _) = create_client(&mcp_url, None).await;
+
+    assert!(client.instructions().is_none());
+    assert!(client.server_version().is_none());
+    assert!(client.server_icons().is_empty());
+
+    client.clone().start().await.unwrap();
+    wait_for_n_requests(&mock_server, 2, None).await;
+
+    assert_eq!(
+        client.instructions().as_deref(),
+        Some("Call `search` before `fetch`.")
+    );
+
+    let implementation = client.server_version().unwrap();
+    assert_eq!(implementation.name, "instructed-server");
+    assert_eq!(implementation.version, "2.1.0");
+    assert_eq!(implementation.title.as_deref(), Some("Instructed Server"));
+    assert_eq!(
+        implementation.website_url.as_deref(),
+        Some("https://example.com/instructed-server")
+    );
+
+    assert_eq!(client.server_title().as_deref(), Some("Instructed Server"));
+    assert_eq!(
+        client.server_website_url().as_deref(),
+        Some("https://example.com/instructed-server")
+    );
+    let icons = client.server_icons();
+    assert_eq!(icons.len(), 1);
+    assert_eq!(icons[0].src, "https://example.com/icon.png");
+}
+
+struct PolicyMiddleware {
+    sent: Arc<std::sync::Mutex<Vec<String>>>,
+    received: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl ClientMiddleware for PolicyMiddleware {
+    async fn on_send(&self, message: &mut ClientMessage) -> SdkResult<()> {
+        if let ClientMessage::Request(ClientJsonrpcRequest::CallToolRequest(request)) = message {
+            if request.params.name == "blocked" {
+                return Err(RpcError::invalid_request()
+                    .with_message("tool is blocked by policy")
+                    .into());
+            }
+        }
+        if let Some(method) = message.method() {
+            self.sent.lock().unwrap().push(method.to_string());
+        }
+        Ok(())
+    }
+
+    async fn on_receive(&self, message: &mut ServerMessage) -> SdkResult<()> {
+        if message.is_response() {
+            self.received
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn client_middleware_intercepts_and_rejects_messages() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    };
+    let LaunchedServer { streamable_url, .. } = create_start_server(server_options).await;
+
+    let (client, _) = create_client(&streamable_url, None).await;
+    let sent = Arc::new(std::sync::Mutex::new(vec![]));
+    let received = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    client.add_middleware(PolicyMiddleware {
+        sent: sent.clone(),
+        received: received.clone(),
+    });
+
+    client.clone().start().await.unwrap();
+    client.request_tool_list(None).await.unwrap();
+
+    let result = client
+        .request_tool_call(CallToolRequestParams {
+            arguments: None,
+            meta: None,
+            name: "blocked".to_string(),
+            task: None,
+        })
+        .await;
+    let Err(McpSdkError::RpcError(error)) = result else {
+        panic!("the request should be rejected by the middleware");
+    };
+    assert_eq!(error.message, "tool is blocked by policy");
+
+    let sent = sent.lock().unwrap().clone();
+    assert!(sent.contains(&"initialize".to_string()));
+    assert!(sent.contains(&"tools/list".to_string()));
+    assert!(!sent.contains(&"tools/call".to_string()));
+    assert!(received.load(std::sync::atomic::Ordering::SeqCst) >= 2);
+}
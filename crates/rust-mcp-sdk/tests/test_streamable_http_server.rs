@@ -1,14 +1,20 @@
 use crate::common::{
-    random_port, read_sse_event, read_sse_event_from_stream, send_delete_request, send_get_request,
-    send_option_request, send_post_request,
+    assert_monotonic_event_ids, random_port, read_sse_event, read_sse_event_from_stream,
+    read_sse_events, send_delete_request, send_get_request, send_option_request, send_post_request,
     test_server_common::{
-        create_start_server, initialize_request, LaunchedServer, TestIdGenerator,
+        create_start_server, initialize_request, test_server_details, LaunchedServer,
+        TestIdGenerator,
     },
     TestTokenVerifier, ONE_MILLISECOND,
 };
-use http::header::{ACCEPT, ACCESS_CONTROL_ALLOW_ORIGIN, AUTHORIZATION, CONTENT_TYPE};
+use async_trait::async_trait;
+use http::header::{
+    ACCEPT, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+    ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE, AUTHORIZATION, CONTENT_TYPE,
+};
+use http::Method;
 use http::StatusCode;
-use mcp_axum::AxumServerOptions;
+use mcp_axum::{create_axum_server, AxumRuntime, AxumServerOptions};
 use rust_mcp_macros::{mcp_elicit, JsonSchema};
 use rust_mcp_schema::{
     schema_utils::{
@@ -17,21 +23,31 @@ use rust_mcp_schema::{
         SdkError, SdkErrorCodes, ServerJsonrpcNotification, ServerJsonrpcRequest,
         ServerJsonrpcResponse, ServerMessages,
     },
-    CallToolRequestParams, ElicitResult, ElicitResultContent, ListRootsResult, LoggingLevel,
-    LoggingMessageNotificationParams, RequestId, ServerRequest,
+    CallToolMeta, CallToolRequestParams, ElicitResult, ElicitResultContent, Implementation,
+    JsonrpcErrorResponse, ListRootsResult, LoggingLevel, LoggingMessageNotificationParams,
+    ProgressToken, RequestId, RpcError, ServerRequest,
+};
+use rust_mcp_sdk::mcp_http::{
+    middleware::{AllowOrigins, CorsConfig},
+    DnsRebindingOptions,
 };
-use rust_mcp_sdk::mcp_http::DnsRebindingOptions;
 use rust_mcp_sdk::{
     auth::{AuthInfo, AuthMetadataBuilder, AuthProvider, RemoteAuthProvider},
-    event_store::InMemoryEventStore,
+    event_store::{
+        EventStore, EventStoreEntry, EventStoreError, EventStoreErrorPolicy, InMemoryEventStore,
+    },
+    id_generator::AffinityIdGenerator,
+    mcp_server::{ServerHandler, ToMcpServerHandler},
     schema::ResultFromClient,
     session_store::InMemorySessionStore,
     task_store::InMemoryTaskStore,
+    McpHttpServer, McpServer,
 };
 use serde_json::{json, Map, Value};
 use std::{
     collections::BTreeMap,
     collections::HashMap,
+    collections::HashSet,
     error::Error,
     sync::Arc,
     time::{Duration, SystemTime},
@@ -177,6 +193,39 @@ async fn should_initialize_server_and_generate_session_id() {
     server.axum_runtime.await_server().await.unwrap()
 }
 
+// should send the routing key of a new session for sticky load balancing
+#[tokio::test]
+async fn should_send_the_routing_key_of_a_new_session() {
+    let json_rpc_message: ClientJsonrpcRequest =
+        ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request().into());
+
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(AffinityIdGenerator::new("node-1"))),
+        ..Default::default()
+    };
+
+    let server = create_start_server(server_options).await;
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        None,
+        None,
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let session_id = response.headers().get("mcp-session-id").unwrap();
+    assert!(session_id.to_str().unwrap().starts_with("node-1."));
+    assert_eq!(response.headers().get("mcp-routing-key").unwrap(), "node-1");
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
 // should reject batch initialize request
 #[tokio::test]
 async fn should_reject_batch_initialize_request() {
@@ -315,6 +364,119 @@ async fn should_call_a_tool_and_return_the_result() {
     server.axum_runtime.await_server().await.unwrap()
 }
 
+// should expose the id of the request being handled to the handler
+#[tokio::test]
+async fn should_expose_current_request_id_to_handler() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::String("req-42".to_string()),
+        RequestFromClient::CallToolRequest(CallToolRequestParams {
+            arguments: None,
+            name: "current_request_id".to_string(),
+            meta: None,
+            task: None,
+        }),
+    );
+
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        Some(&session_id),
+        None,
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let events = read_sse_event(response, 1).await.unwrap();
+    let message: ServerJsonrpcResponse = serde_json::from_str(&events[0].2).unwrap();
+
+    let ResultFromServer::CallToolResult(result) = message.result else {
+        panic!("invalid CallToolResult")
+    };
+
+    assert_eq!(
+        result.content[0].as_text_content().unwrap().text,
+        RequestId::String("req-42".to_string()).to_string()
+    );
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+#[tokio::test]
+async fn should_expose_request_context_to_handler() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::Integer(7),
+        RequestFromClient::CallToolRequest(CallToolRequestParams {
+            arguments: None,
+            name: "request_context".to_string(),
+            meta: Some(CallToolMeta {
+                progress_token: Some(ProgressToken::String("progress-1".to_string())),
+                extra: None,
+            }),
+            task: None,
+        }),
+    );
+
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        Some(&session_id),
+        None,
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let events = read_sse_event(response, 1).await.unwrap();
+    let message: ServerJsonrpcResponse = serde_json::from_str(&events[0].2).unwrap();
+
+    let ResultFromServer::CallToolResult(result) = message.result else {
+        panic!("invalid CallToolResult")
+    };
+
+    let details: serde_json::Value =
+        serde_json::from_str(&result.content[0].as_text_content().unwrap().text).unwrap();
+    assert_eq!(details["session_id"], json!(session_id));
+    assert_eq!(details["request_id"], json!(7));
+    assert_eq!(details["progress_token"], json!("progress-1"));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+#[tokio::test]
+async fn should_expose_client_implementation_details() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let runtime = server
+        .axum_runtime
+        .runtime_by_session(&session_id)
+        .await
+        .unwrap();
+
+    // values received from the client, not the server's own details
+    let implementation = runtime.client_version().unwrap();
+    assert_eq!(implementation.name, "test-server");
+    assert_eq!(implementation.version, "0.1.0");
+    assert_ne!(implementation.name, runtime.server_info().server_info.name);
+
+    assert!(runtime.client_title().is_none());
+    assert!(runtime.client_website_url().is_none());
+    let icons = runtime.client_icons();
+    assert_eq!(icons.len(), 1);
+    assert_eq!(icons[0].mime_type.as_deref(), Some("image/png"));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
 // should reject requests without a valid session ID
 #[tokio::test]
 async fn should_reject_requests_without_a_valid_session_id() {
@@ -390,6 +552,42 @@ pub async fn get_standalone_stream(
     response
 }
 
+// should report the streams open for a session
+#[tokio::test]
+async fn should_report_open_streams_per_session() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    // the stream answering `initialize` is closed once the response is sent
+    assert_eq!(
+        server.axum_runtime.stream_count(&session_id).await.unwrap(),
+        0
+    );
+
+    let response = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        server.axum_runtime.stream_ids(&session_id).await.unwrap(),
+        vec!["STANDALONE-STREAM".to_string()]
+    );
+
+    // a rejected second standalone stream is not counted
+    let second = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+    assert_eq!(second.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        server.axum_runtime.stream_count(&session_id).await.unwrap(),
+        1
+    );
+
+    assert!(server
+        .axum_runtime
+        .stream_count(&"unknown-session".to_string())
+        .await
+        .is_err());
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
 // should establish standalone SSE stream and receive server-initiated messages
 #[tokio::test]
 async fn should_establish_standalone_stream_and_receive_server_messages() {
@@ -676,6 +874,39 @@ async fn reject_post_requests_without_accept_header() {
     server.axum_runtime.await_server().await.unwrap()
 }
 
+// should accept POST requests with a wildcard Accept header
+#[tokio::test]
+async fn should_accept_post_requests_with_wildcard_accept_header() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::ListToolsRequest(None),
+    );
+
+    for accept in ["*/*", "application/*, text/*"] {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type", "application/json");
+        headers.insert("Accept", accept);
+        headers.insert("mcp-session-id", &session_id);
+        headers.insert("mcp-protocol-version", "2025-03-26");
+
+        let response = send_post_request(
+            &server.streamable_url,
+            &serde_json::to_string(&json_rpc_message).unwrap(),
+            Some(&session_id),
+            Some(headers),
+        )
+        .await
+        .expect("Request failed");
+
+        assert_eq!(response.status(), StatusCode::OK, "Accept: {accept}");
+    }
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
 //should reject unsupported Content-Type
 #[tokio::test]
 async fn should_reject_unsupported_content_type() {
@@ -900,107 +1131,324 @@ async fn should_properly_handle_delete_requests_and_close_session() {
     server.axum_runtime.await_server().await.unwrap()
 }
 
-// should reject DELETE requests with invalid session ID
+// should close all sessions and reject their later requests
 #[tokio::test]
-async fn should_reject_delete_requests_with_invalid_session_id() {
-    let (server, _session_id) = initialize_server(None, None).await.unwrap();
+async fn should_close_all_sessions() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let closed = server
+        .axum_runtime
+        .close_all_sessions(Some("Server is restarting".to_string()))
+        .await;
+    assert_eq!(closed, 1);
+    assert!(server.axum_runtime.sessions().await.is_empty());
 
     let mut headers = HashMap::new();
     headers.insert("Content-Type", "text/plain");
     headers.insert("Accept", "application/json, text/event-stream");
-    headers.insert("mcp-session-id", "invalid-session-id");
+    headers.insert("mcp-session-id", &session_id);
     headers.insert("mcp-protocol-version", "2025-03-26");
 
-    let response = send_delete_request(
-        &server.streamable_url,
-        Some("invalid-session-id"),
-        Some(headers),
-    )
-    .await
-    .expect("Request failed");
-
+    let response = send_delete_request(&server.streamable_url, Some(&session_id), Some(headers))
+        .await
+        .expect("Request failed");
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 
-    let error_data: SdkError = response.json().await.unwrap();
-
-    assert_eq!(error_data.code, SdkErrorCodes::SESSION_NOT_FOUND as i64); // Typescript sdk uses -32001 code
-    assert!(error_data.message.contains("Session not found"));
+    assert_eq!(server.axum_runtime.close_all_sessions(None).await, 0);
 
     server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
     server.axum_runtime.await_server().await.unwrap()
 }
 
-/**
- * protocol version header validation
- */
-
-// should accept requests without protocol version header
+// should push custom notifications over the standalone stream only while it is open
 #[tokio::test]
-async fn should_accept_requests_without_protocol_version_header() {
+async fn should_send_custom_notifications_over_an_open_stream() {
     let (server, session_id) = initialize_server(None, None).await.unwrap();
 
-    let mut headers = HashMap::new();
-    headers.insert("Content-Type", "application/json");
-    headers.insert("Accept", "application/json, text/event-stream");
+    // no standalone stream is open yet
+    let result = server
+        .axum_runtime
+        .send_custom_notification(&session_id, "acme/report", None)
+        .await;
+    assert!(result.is_err());
 
-    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
-        RequestId::Integer(1),
-        RequestFromClient::ListToolsRequest(None).into(),
-    );
+    let response = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
 
-    let response = send_post_request(
-        &server.streamable_url,
-        &serde_json::to_string(&json_rpc_message).unwrap(),
-        Some(&session_id),
-        Some(headers),
-    )
-    .await
-    .expect("Request failed");
+    let result = server
+        .axum_runtime
+        .send_custom_notification(&session_id, "notifications/message", None)
+        .await;
+    assert!(result.is_err());
 
-    assert_eq!(response.status(), StatusCode::OK);
+    let mut params = Map::new();
+    params.insert("status".to_string(), json!("ready"));
+    server
+        .axum_runtime
+        .send_custom_notification(&session_id, "acme/report", Some(params))
+        .await
+        .unwrap();
+
+    let events = read_sse_event(response, 1).await.unwrap();
+    let message: ServerJsonrpcNotification = serde_json::from_str(&events[0].2).unwrap();
+    let ServerJsonrpcNotification::CustomNotification(notification) = message else {
+        panic!("invalid message received!");
+    };
+    assert_eq!(notification.method, "acme/report");
+    assert_eq!(notification.params.unwrap()["status"], json!("ready"));
 
     server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
-    server.axum_runtime.await_server().await.unwrap()
 }
 
-// should reject requests with unsupported protocol version
+// should notify every session with an open standalone stream that the tool list changed
 #[tokio::test]
-async fn should_reject_requests_with_unsupported_protocol_version() {
+async fn should_notify_all_sessions_of_tool_list_changes() {
     let (server, session_id) = initialize_server(None, None).await.unwrap();
 
-    let mut headers = HashMap::new();
-    headers.insert("Content-Type", "application/json");
-    headers.insert("Accept", "application/json, text/event-stream");
-    headers.insert("mcp-protocol-version", "1999-15-21");
-
-    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
-        RequestId::Integer(1),
-        RequestFromClient::ListToolsRequest(None).into(),
+    // no standalone stream is open yet
+    assert_eq!(
+        server.axum_runtime.notify_all_tool_list_changed(None).await,
+        0
     );
 
-    let response = send_post_request(
-        &server.streamable_url,
-        &serde_json::to_string(&json_rpc_message).unwrap(),
-        Some(&session_id),
-        Some(headers),
-    )
-    .await
-    .expect("Request failed");
+    let response = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
 
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-    let error_data: SdkError = response.json().await.unwrap();
+    assert_eq!(
+        server.axum_runtime.notify_all_tool_list_changed(None).await,
+        1
+    );
 
-    assert_eq!(error_data.code, SdkErrorCodes::BAD_REQUEST as i64); // Typescript sdk uses -32000 code
-    assert!(error_data.message.contains("Unsupported protocol version"));
+    let events = read_sse_event(response, 1).await.unwrap();
+    let message: ServerJsonrpcNotification = serde_json::from_str(&events[0].2).unwrap();
+    assert!(matches!(
+        message,
+        ServerJsonrpcNotification::ToolListChangedNotification(_)
+    ));
 
     server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
-    server.axum_runtime.await_server().await.unwrap()
 }
 
-// should handle protocol version validation for get requests
+// should retain a disconnected session for its grace period, then expire it
 #[tokio::test]
-async fn should_handle_protocol_version_validation_for_get_requests() {
-    let (server, session_id) = initialize_server(None, None).await.unwrap();
+async fn should_expire_disconnected_sessions_after_their_grace_period() {
+    let event_store = Arc::new(InMemoryEventStore::default());
+    let server = create_start_server(AxumServerOptions {
+        port: random_port(),
+        ping_interval: Duration::from_millis(100),
+        event_store: Some(event_store.clone()),
+        session_grace_period: Some(Duration::from_millis(600)),
+        ..Default::default()
+    })
+    .await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let json_rpc_message: ClientJsonrpcRequest =
+        ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request().into());
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        None,
+        None,
+    )
+    .await
+    .expect("Request failed");
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_owned();
+
+    // a client resuming within the grace period keeps its session
+    let response = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    drop(response);
+    let response = loop {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let response = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+        if response.status() != StatusCode::CONFLICT {
+            break response;
+        }
+    };
+    assert_eq!(response.status(), StatusCode::OK);
+    tokio::time::sleep(Duration::from_millis(900)).await;
+    assert_eq!(
+        server.axum_runtime.sessions().await,
+        vec![session_id.clone()]
+    );
+
+    // a session not resumed in time is removed, with its events
+    drop(response);
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert!(server.axum_runtime.sessions().await.is_empty());
+    assert_eq!(event_store.count().await.unwrap(), 0);
+
+    let response = send_delete_request(&server.streamable_url, Some(&session_id), None)
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should reject DELETE requests with invalid session ID
+#[tokio::test]
+async fn should_reject_delete_requests_with_invalid_session_id() {
+    let (server, _session_id) = initialize_server(None, None).await.unwrap();
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "text/plain");
+    headers.insert("Accept", "application/json, text/event-stream");
+    headers.insert("mcp-session-id", "invalid-session-id");
+    headers.insert("mcp-protocol-version", "2025-03-26");
+
+    let response = send_delete_request(
+        &server.streamable_url,
+        Some("invalid-session-id"),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let error_data: SdkError = response.json().await.unwrap();
+
+    assert_eq!(error_data.code, SdkErrorCodes::SESSION_NOT_FOUND as i64); // Typescript sdk uses -32001 code
+    assert!(error_data.message.contains("Session not found"));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+/**
+ * protocol version header validation
+ */
+
+// should accept requests without protocol version header
+#[tokio::test]
+async fn should_accept_requests_without_protocol_version_header() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json, text/event-stream");
+
+    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::ListToolsRequest(None).into(),
+    );
+
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        Some(&session_id),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should reject requests with unsupported protocol version
+#[tokio::test]
+async fn should_reject_requests_with_unsupported_protocol_version() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json, text/event-stream");
+    headers.insert("mcp-protocol-version", "1999-15-21");
+
+    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::ListToolsRequest(None).into(),
+    );
+
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        Some(&session_id),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let error_data: SdkError = response.json().await.unwrap();
+
+    assert_eq!(error_data.code, SdkErrorCodes::BAD_REQUEST as i64); // Typescript sdk uses -32000 code
+    assert!(error_data.message.contains("Unsupported protocol version"));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should echo the session id on error responses for requests within a valid session
+#[tokio::test]
+async fn should_echo_session_id_on_error_responses() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
+
+    let list_tools = serde_json::to_string(&ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::ListToolsRequest(None).into(),
+    ))
+    .unwrap();
+
+    // protocol version rejection
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json, text/event-stream");
+    headers.insert("mcp-protocol-version", "1999-15-21");
+    let response = send_post_request(
+        &server.streamable_url,
+        &list_tools,
+        Some(&session_id),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers().get("mcp-session-id").unwrap(),
+        session_id.as_str()
+    );
+
+    // GET that doesn't accept an event stream
+    let mut headers = HashMap::new();
+    headers.insert("Accept", "application/json");
+    headers.insert("mcp-session-id", session_id.as_str());
+    let response = send_get_request(&server.streamable_url, Some(headers))
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(
+        response.headers().get("mcp-session-id").unwrap(),
+        session_id.as_str()
+    );
+
+    // unknown sessions are not echoed
+    let response = send_post_request(&server.streamable_url, &list_tools, Some("unknown"), None)
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get("mcp-session-id").is_none());
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should handle protocol version validation for get requests
+#[tokio::test]
+async fn should_handle_protocol_version_validation_for_get_requests() {
+    let (server, session_id) = initialize_server(None, None).await.unwrap();
 
     let mut headers = HashMap::new();
     headers.insert("Content-Type", "application/json");
@@ -1095,6 +1543,43 @@ async fn should_return_json_response_for_a_single_request() {
     server.axum_runtime.await_server().await.unwrap()
 }
 
+// should send buffered JSON responses with a Content-Length rather than chunked
+#[tokio::test]
+async fn should_send_content_length_with_json_response() {
+    let (server, session_id) = initialize_server(Some(true), None).await.unwrap();
+
+    let json_rpc_message: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::ListToolsRequest(None).into(),
+    );
+
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&json_rpc_message).unwrap(),
+        Some(&session_id),
+        None,
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("transfer-encoding").is_none());
+    let content_length: usize = response
+        .headers()
+        .get("content-length")
+        .expect("content-length header is missing")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let body = response.bytes().await.unwrap();
+    assert_eq!(content_length, body.len());
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
 // should return JSON response for batch requests
 #[tokio::test]
 async fn should_return_json_response_for_a_batch_request() {
@@ -1541,22 +2026,28 @@ async fn should_store_and_include_event_ids_in_server_sse_messages() {
         .await;
 
     // read two events
-    let events = read_sse_event(response, 2).await.unwrap();
+    let events = read_sse_events(response, 2).await.unwrap();
     assert_eq!(events.len(), 2);
-    // verify we got the notification with an event ID
-    let (first_id, _, data) = events[0].clone();
-    let (second_id, _, _) = events[0].clone();
-
-    let message: ServerJsonrpcNotification = serde_json::from_str(&data).unwrap();
+    // verify we got both notifications with increasing event IDs on the same stream
+    assert_monotonic_event_ids(&events);
+    assert_eq!(
+        events[0].parsed_id().unwrap().1,
+        events[1].parsed_id().unwrap().1
+    );
 
+    let message: ServerJsonrpcNotification = serde_json::from_str(&events[0].data).unwrap();
     let ServerJsonrpcNotification::LoggingMessageNotification(notification1) = message else {
         panic!("invalid message received!");
     };
-
     assert_eq!(notification1.params.data.as_str().unwrap(), "notification1");
 
-    let first_id = first_id.unwrap();
-    assert!(second_id.is_some());
+    let message: ServerJsonrpcNotification = serde_json::from_str(&events[1].data).unwrap();
+    let ServerJsonrpcNotification::LoggingMessageNotification(notification2) = message else {
+        panic!("invalid message received!");
+    };
+    assert_eq!(notification2.params.data.as_str().unwrap(), "notification2");
+
+    let first_id = events[0].id.clone().unwrap();
 
     //messages should be stored and accessible
     let events = server
@@ -1646,6 +2137,70 @@ async fn should_store_and_replay_mcp_server_tool_notifications() {
     assert_eq!(notification1.params.data.as_str().unwrap(), "notification2");
 }
 
+// should replay missed notifications using the lastEventId query parameter
+#[tokio::test]
+async fn should_replay_notifications_with_last_event_id_query_param() {
+    common::init_tracing();
+    let (server, session_id) = initialize_server(Some(true), None).await.unwrap();
+    let response = get_standalone_stream(&server.streamable_url, &session_id, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let notify = |data: &'static str| {
+        server.axum_runtime.notify_log_message(
+            &session_id,
+            LoggingMessageNotificationParams {
+                data: json!(data),
+                level: LoggingLevel::Info,
+                logger: None,
+                meta: None,
+            },
+        )
+    };
+
+    let _ = notify("notification1").await;
+    let events = read_sse_event(response, 1).await.unwrap();
+    let first_id = events[0].0.clone().unwrap();
+
+    // wait so server detect the disconnect
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    tokio::task::yield_now().await;
+    let _ = notify("notification2").await;
+
+    // reconnect, passing the event id in the query string only
+    let mut url = Url::parse(&server.streamable_url).unwrap();
+    url.query_pairs_mut().append_pair("lastEventId", &first_id);
+    let mut headers = HashMap::new();
+    headers.insert("Accept", "text/event-stream");
+    headers.insert("mcp-session-id", session_id.as_str());
+    let response = send_get_request(url.as_str(), Some(headers.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let events = read_sse_event(response, 1).await.unwrap();
+    let message: ServerJsonrpcNotification = serde_json::from_str(&events[0].2).unwrap();
+    let ServerJsonrpcNotification::LoggingMessageNotification(notification) = message else {
+        panic!("invalid message received!");
+    };
+    assert_eq!(notification.params.data.as_str().unwrap(), "notification2");
+
+    // the header takes precedence, an invalid query parameter is ignored
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let mut url = Url::parse(&server.streamable_url).unwrap();
+    url.set_query(Some("lastEventId=%FF"));
+    headers.insert("last-event-id", first_id.as_str());
+    let response = send_get_request(url.as_str(), Some(headers.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    drop(response);
+
+    // without the header, the invalid query parameter is rejected
+    headers.remove("last-event-id");
+    let response = send_get_request(url.as_str(), Some(headers)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn metadata_requires_get_method() {
     common::init_tracing();
@@ -1713,7 +2268,104 @@ async fn should_support_options_preflight_requests() {
 }
 
 #[tokio::test]
-// should call a tool with authInfo when authenticated
+// answers CORS preflight requests on the streamable http endpoint and exposes the MCP headers
+async fn should_answer_cors_preflight_requests_when_cors_is_enabled() {
+    let server = create_start_server(AxumServerOptions {
+        port: random_port(),
+        cors: Some(CorsConfig {
+            allow_origins: AllowOrigins::List(HashSet::from(["https://app.example.com".into()])),
+            allow_methods: vec![Method::GET, Method::POST, Method::DELETE, Method::OPTIONS],
+            max_age: Some(600),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let mut headers = HashMap::new();
+    headers.insert("Origin", "https://app.example.com");
+    headers.insert("Access-Control-Request-Method", "POST");
+    headers.insert(
+        "Access-Control-Request-Headers",
+        "content-type, mcp-protocol-version",
+    );
+    let response = send_option_request(&server.streamable_url, Some(headers))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response_headers = response.headers();
+    assert_eq!(
+        response_headers[ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://app.example.com"
+    );
+    assert_eq!(response_headers[ACCESS_CONTROL_MAX_AGE], "600");
+    assert!(response_headers[ACCESS_CONTROL_ALLOW_METHODS]
+        .to_str()
+        .unwrap()
+        .contains("DELETE"));
+
+    // origins outside of the list are rejected
+    let mut headers = HashMap::new();
+    headers.insert("Origin", "https://evil.example.com");
+    headers.insert("Access-Control-Request-Method", "POST");
+    let response = send_option_request(&server.streamable_url, Some(headers))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // actual requests expose the session id to the client
+    let init = ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request());
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json, text/event-stream");
+    headers.insert("Origin", "https://app.example.com");
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&init).unwrap(),
+        None,
+        Some(headers),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let exposed = response.headers()[ACCESS_CONTROL_EXPOSE_HEADERS]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(exposed.contains("mcp-session-id"));
+    assert!(exposed.contains("mcp-protocol-version"));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+#[tokio::test]
+// should not answer preflight requests when cors is not configured
+async fn should_not_answer_cors_preflight_requests_by_default() {
+    let server = create_start_server(AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    })
+    .await;
+
+    let mut headers = HashMap::new();
+    headers.insert("Origin", "https://app.example.com");
+    headers.insert("Access-Control-Request-Method", "POST");
+    let response = send_option_request(&server.streamable_url, Some(headers))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert!(response
+        .headers()
+        .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+        .is_none());
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
+#[tokio::test]
+// should call a tool with authInfo when authenticated
 async fn should_call_a_tool_with_auth_info_when_authenticated() {
     common::init_tracing();
     let auth_map = HashMap::new();
@@ -1918,6 +2570,52 @@ async fn should_reject_new_session_when_at_capacity() {
     server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
     server.axum_runtime.await_server().await.unwrap()
 }
+
+struct ClientVersionCheckHandler;
+
+impl ServerHandler for ClientVersionCheckHandler {
+    fn accept_client(&self, client_info: &Implementation) -> Result<(), RpcError> {
+        if client_info.version.starts_with("0.") {
+            return Err(RpcError::invalid_request()
+                .with_message(format!("{} 0.x is not supported", client_info.name)));
+        }
+        Ok(())
+    }
+}
+
+// should reject clients refused by the handler before creating a session
+#[tokio::test]
+async fn should_reject_clients_refused_by_the_handler() {
+    let options = AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    };
+    let streamable_url = options.streamable_http_url();
+    let server = create_axum_server(
+        test_server_details(),
+        ClientVersionCheckHandler.to_mcp_server_handler(),
+        options,
+    );
+    let axum_runtime = AxumRuntime::create(server).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(75)).await;
+
+    let init = ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request());
+    let body = serde_json::to_string(&init).unwrap();
+    let response = send_post_request(&streamable_url, &body, None, None)
+        .await
+        .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(response.headers().get("mcp-session-id").is_none());
+    let error: JsonrpcErrorResponse = response.json().await.unwrap();
+    assert_eq!(error.id, Some(RequestId::Integer(0)));
+    assert_eq!(error.error.code, RpcError::invalid_request().code);
+    assert_eq!(error.error.message, "test-server 0.x is not supported");
+    assert!(axum_runtime.sessions().await.is_empty());
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
+
 // should reject an oversized / malformed Mcp-Session-Id header
 #[tokio::test]
 async fn should_reject_malformed_session_id_header() {
@@ -1942,9 +2640,529 @@ async fn should_reject_malformed_session_id_header() {
     server.axum_runtime.await_server().await.unwrap()
 }
 
+// should answer clients accepting only application/json with buffered JSON when the fallback is enabled
+#[tokio::test]
+async fn should_return_json_response_to_json_only_clients_with_fallback() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        json_response_fallback: Some(true),
+        ..Default::default()
+    };
+
+    let server = create_start_server(server_options).await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json");
+
+    // initialize starts a session and is answered with JSON rather than an SSE stream
+    let init = ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request());
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&init).unwrap(),
+        None,
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(
+        response.headers().get("mcp-session-id").unwrap(),
+        "AAA-BBB-CCC"
+    );
+    let message = response.json::<ServerJsonrpcResponse>().await.unwrap();
+    assert!(matches!(
+        message.result,
+        ResultFromServer::InitializeResult(_)
+    ));
+
+    // notifications are acknowledged without a body
+    let initialized = ClientMessage::from_message(
+        MessageFromClient::NotificationFromClient(NotificationFromClient::InitializedNotification(
+            None,
+        )),
+        None,
+    )
+    .unwrap();
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&initialized).unwrap(),
+        Some("AAA-BBB-CCC"),
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // requests within the session are answered with JSON as well
+    let list_tools: ClientJsonrpcRequest = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::ListToolsRequest(None).into(),
+    );
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&list_tools).unwrap(),
+        Some("AAA-BBB-CCC"),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    let message = response.json::<ServerJsonrpcResponse>().await.unwrap();
+    let ResultFromServer::ListToolsResult(result) = message.result else {
+        panic!("invalid ListToolsResult")
+    };
+    assert_eq!(result.tools.len(), 2);
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should answer clients preferring application/json by q value with buffered JSON when the fallback is enabled
+#[tokio::test]
+async fn should_return_json_response_to_clients_preferring_json_with_fallback() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string(),
+            "DDD-EEE-FFF".to_string(),
+        ]))),
+        json_response_fallback: Some(true),
+        ..Default::default()
+    };
+
+    let server = create_start_server(server_options).await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let init = ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request());
+
+    for (accept, content_type) in [
+        (
+            "application/json, text/event-stream;q=0.5",
+            "application/json",
+        ),
+        (
+            "application/json;q=0.5, text/event-stream",
+            "text/event-stream",
+        ),
+    ] {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type", "application/json");
+        headers.insert("Accept", accept);
+
+        let response = send_post_request(
+            &server.streamable_url,
+            &serde_json::to_string(&init).unwrap(),
+            None,
+            Some(headers),
+        )
+        .await
+        .expect("Request failed");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            content_type,
+            "{accept}"
+        );
+    }
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should reject requests sent before the initialized notification when require_initialized is set
+#[tokio::test]
+async fn should_reject_requests_before_initialized_notification() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        json_response_fallback: Some(true),
+        require_initialized: true,
+        ..Default::default()
+    };
+
+    let server = create_start_server(server_options).await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json");
+
+    let init = ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request());
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&init).unwrap(),
+        None,
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let list_tools = |id: i64| {
+        serde_json::to_string(&ClientJsonrpcRequest::new(
+            RequestId::Integer(id),
+            RequestFromClient::ListToolsRequest(None).into(),
+        ))
+        .unwrap()
+    };
+
+    // a request between the initialize response and the initialized notification is rejected
+    let response = send_post_request(
+        &server.streamable_url,
+        &list_tools(1),
+        Some("AAA-BBB-CCC"),
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+    let message: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        message["error"]["code"],
+        SdkErrorCodes::INVALID_REQUEST as i64
+    );
+    assert!(message["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("notifications/initialized"));
+
+    // pings are allowed before initialization completes
+    let ping = ClientJsonrpcRequest::new(
+        RequestId::Integer(2),
+        RequestFromClient::PingRequest(None).into(),
+    );
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&ping).unwrap(),
+        Some("AAA-BBB-CCC"),
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+    let message: serde_json::Value = response.json().await.unwrap();
+    assert!(message.get("result").is_some());
+
+    let initialized = ClientMessage::from_message(
+        MessageFromClient::NotificationFromClient(NotificationFromClient::InitializedNotification(
+            None,
+        )),
+        None,
+    )
+    .unwrap();
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&initialized).unwrap(),
+        Some("AAA-BBB-CCC"),
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // sent right after the notification is acknowledged, while it may still be processed
+    let response = send_post_request(
+        &server.streamable_url,
+        &list_tools(3),
+        Some("AAA-BBB-CCC"),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+    let message = response.json::<ServerJsonrpcResponse>().await.unwrap();
+    assert!(matches!(
+        message.result,
+        ResultFromServer::ListToolsResult(_)
+    ));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should reject a second initialization request on an initialized session
+#[tokio::test]
+async fn should_reject_second_initialization_request() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        json_response_fallback: Some(true),
+        ..Default::default()
+    };
+
+    let server = create_start_server(server_options).await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json");
+
+    let init = ClientJsonrpcRequest::new(RequestId::Integer(0), initialize_request());
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&init).unwrap(),
+        None,
+        Some(headers.clone()),
+    )
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let second_init = ClientJsonrpcRequest::new(RequestId::Integer(1), initialize_request());
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&second_init).unwrap(),
+        Some("AAA-BBB-CCC"),
+        Some(headers),
+    )
+    .await
+    .expect("Request failed");
+    let message: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(message["id"], 1);
+    assert_eq!(
+        message["error"]["code"],
+        SdkErrorCodes::INVALID_REQUEST as i64
+    );
+    assert!(message["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Only one initialization request is allowed"));
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should reject new SSE streams with 503 once max_sse_streams are open
+#[tokio::test]
+async fn should_limit_concurrent_sse_streams() {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA".to_string(),
+            "BBB".to_string(),
+            "CCC".to_string(),
+        ]))),
+        json_response_fallback: Some(true),
+        max_sse_streams: Some(2),
+        ..Default::default()
+    };
+
+    let server = create_start_server(server_options).await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    // JSON-only clients, so initialization doesn't take up an SSE stream
+    let mut post_headers = HashMap::new();
+    post_headers.insert("Content-Type", "application/json");
+    post_headers.insert("Accept", "application/json");
+    let init = serde_json::to_string(&ClientJsonrpcRequest::new(
+        RequestId::Integer(0),
+        initialize_request(),
+    ))
+    .unwrap();
+    for _ in 0..3 {
+        let response = send_post_request(
+            &server.streamable_url,
+            &init,
+            None,
+            Some(post_headers.clone()),
+        )
+        .await
+        .expect("Request failed");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let open_stream = |session_id: &'static str| {
+        let mut headers = HashMap::new();
+        headers.insert("Accept", "text/event-stream");
+        headers.insert("mcp-session-id", session_id);
+        send_get_request(&server.streamable_url, Some(headers))
+    };
+
+    let first_stream = open_stream("AAA").await.expect("Request failed");
+    assert_eq!(first_stream.status(), StatusCode::OK);
+    let second_stream = open_stream("BBB").await.expect("Request failed");
+    assert_eq!(second_stream.status(), StatusCode::OK);
+
+    let response = open_stream("CCC").await.expect("Request failed");
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let error_data: SdkError = response.json().await.unwrap();
+    assert!(error_data.message.contains("maximum SSE stream capacity"));
+
+    // closing a stream frees its slot
+    drop(first_stream);
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    let third_stream = open_stream("CCC").await.expect("Request failed");
+    assert_eq!(third_stream.status(), StatusCode::OK);
+
+    // streams answering a POST request don't count toward the limit
+    let ping = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::PingRequest(None).into(),
+    );
+    let response = send_post_request(
+        &server.streamable_url,
+        &serde_json::to_string(&ping).unwrap(),
+        Some("AAA"),
+        None,
+    )
+    .await
+    .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+
+    drop(second_stream);
+    drop(third_stream);
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+/// An event store whose storage backend is down
+struct FailingEventStore;
+
+#[async_trait]
+impl EventStore for FailingEventStore {
+    async fn store_event(
+        &self,
+        _session_id: String,
+        _stream_id: String,
+        _timestamp: u128,
+        _message: String,
+    ) -> Result<String, EventStoreError> {
+        Err("event store is unavailable".into())
+    }
+    async fn remove_by_session_id(&self, _session_id: String) -> Result<(), EventStoreError> {
+        Ok(())
+    }
+    async fn remove_stream_in_session(
+        &self,
+        _session_id: String,
+        _stream_id: String,
+    ) -> Result<(), EventStoreError> {
+        Ok(())
+    }
+    async fn clear(&self) -> Result<(), EventStoreError> {
+        Ok(())
+    }
+    async fn events_after(
+        &self,
+        _last_event_id: String,
+    ) -> Result<Option<EventStoreEntry>, EventStoreError> {
+        Ok(None)
+    }
+    async fn count(&self) -> Result<usize, EventStoreError> {
+        Ok(0)
+    }
+}
+
+async fn start_server_with_failing_event_store(
+    event_store_error_policy: EventStoreErrorPolicy,
+) -> (LaunchedServer, reqwest::Response) {
+    let server_options = AxumServerOptions {
+        port: random_port(),
+        session_id_generator: Some(Arc::new(TestIdGenerator::new(vec![
+            "AAA-BBB-CCC".to_string()
+        ]))),
+        json_response_fallback: Some(true),
+        event_store: Some(Arc::new(FailingEventStore)),
+        event_store_error_policy,
+        ..Default::default()
+    };
+    let server = create_start_server(server_options).await;
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    // initialize as a JSON-only client, so the response doesn't go through the event store
+    let mut headers = HashMap::new();
+    headers.insert("Content-Type", "application/json");
+    headers.insert("Accept", "application/json");
+    let init = serde_json::to_string(&ClientJsonrpcRequest::new(
+        RequestId::Integer(0),
+        initialize_request(),
+    ))
+    .unwrap();
+    let response = send_post_request(&server.streamable_url, &init, None, Some(headers))
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stream = get_standalone_stream(&server.streamable_url, "AAA-BBB-CCC", None).await;
+    assert_eq!(stream.status(), StatusCode::OK);
+    (server, stream)
+}
+
+fn log_message(data: &str) -> LoggingMessageNotificationParams {
+    LoggingMessageNotificationParams {
+        data: json!(data),
+        level: LoggingLevel::Info,
+        logger: None,
+        meta: None,
+    }
+}
+
+// should keep delivering messages without event ids when the event store fails, by default
+#[tokio::test]
+async fn should_continue_without_resumability_when_event_store_fails() {
+    common::init_tracing();
+    let (server, stream) =
+        start_server_with_failing_event_store(EventStoreErrorPolicy::default()).await;
+    let session_id = "AAA-BBB-CCC".to_string();
+
+    for data in ["notification1", "notification2"] {
+        server
+            .axum_runtime
+            .notify_log_message(&session_id, log_message(data))
+            .await
+            .expect("notification should be sent despite the event store error");
+    }
+
+    let events = read_sse_events(stream, 2).await.unwrap();
+    assert_eq!(events.len(), 2);
+    for (event, data) in events.iter().zip(["notification1", "notification2"]) {
+        assert!(event.id.is_none());
+        let message: ServerJsonrpcNotification = serde_json::from_str(&event.data).unwrap();
+        let ServerJsonrpcNotification::LoggingMessageNotification(notification) = message else {
+            panic!("invalid message received!");
+        };
+        assert_eq!(notification.params.data.as_str().unwrap(), data);
+    }
+}
+
+// should fail sending a message when the event store fails and the FailSend policy is set
+#[tokio::test]
+async fn should_fail_sending_when_event_store_fails_with_fail_send_policy() {
+    common::init_tracing();
+    let (server, _stream) =
+        start_server_with_failing_event_store(EventStoreErrorPolicy::FailSend).await;
+
+    let result = server
+        .axum_runtime
+        .notify_log_message(&"AAA-BBB-CCC".to_string(), log_message("notification1"))
+        .await;
+    let error = result.expect_err("sending should fail when the event could not be stored");
+    assert!(error.to_string().contains("event store is unavailable"));
+}
+
 // should return 400 error for invalid JSON-RPC messages
 // should keep stream open after sending server notifications
-// NA: should reject second initialization request
 // NA: should pass request info to tool callback
 // NA: should reject second SSE stream even in stateless mode
 // should reject requests to uninitialized server
@@ -1967,3 +3185,56 @@ async fn should_reject_malformed_session_id_header() {
 // should propagate errors from async onsessionclosed callback
 // should handle both async callbacks together
 // should validate both host and origin when both are configured
+
+// should report the number of sessions on the default health endpoint, without authentication
+#[tokio::test]
+async fn should_report_sessions_on_health_endpoint_without_auth() {
+    let (server, _session_id) = initialize_server(None, Some(HashMap::new())).await.unwrap();
+    let health_url = server.streamable_url.replace("/mcp", "/health");
+
+    let response = send_get_request(&health_url, None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let status: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(status["status"], "ok");
+    assert_eq!(status["sessions"], 1);
+
+    // the MCP endpoint still requires a token
+    let response = send_get_request(&server.streamable_url, None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
+
+// should answer health checks whatever host they target, despite DNS rebinding protection
+#[tokio::test]
+async fn should_skip_dns_rebinding_protection_on_health_endpoint() {
+    let server = create_start_server(AxumServerOptions {
+        port: random_port(),
+        dns_rebinding: DnsRebindingOptions {
+            allowed_hosts: Some(vec!["example.com:3001".to_string()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await;
+    let health_url = server.streamable_url.replace("/mcp", "/health");
+
+    let mut headers = HashMap::new();
+    headers.insert("Origin", "http://internal-probe");
+    let response = send_get_request(&health_url, Some(headers)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let status: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(status["sessions"], 0);
+
+    // the MCP endpoint rejects the host
+    let response = send_get_request(&server.streamable_url, None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    server.axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+    server.axum_runtime.await_server().await.unwrap()
+}
@@ -2,8 +2,22 @@
 pub mod common;
 
 use common::sample_tools::{SayGoodbyeTool, SayHelloTool};
+use rust_mcp_sdk::macros::{mcp_tool, JsonSchema};
 use rust_mcp_sdk::tool_box;
 
+#[mcp_tool(
+    name = "compress_file",
+    description = "Compresses a file synchronously",
+    blocking = true,
+    timeout_ms = 30000
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct CompressFileTool {
+    pub path: String,
+    #[json_schema(sensitive)]
+    pub encryption_key: Option<String>,
+}
+
 // Define tool box without trailing comma
 tool_box!(FileSystemToolsNoComma, [SayHelloTool, SayGoodbyeTool]);
 
@@ -11,6 +25,8 @@ tool_box!(FileSystemToolsNoComma, [SayHelloTool, SayGoodbyeTool]);
 // Related Issue: https://github.com/rust-mcp-stack/rust-mcp-sdk/issues/57
 tool_box!(FileSystemTools, [SayHelloTool, SayGoodbyeTool,]);
 
+tool_box!(MixedTools, [SayHelloTool, CompressFileTool]);
+
 #[test]
 fn test_tools_with_trailing_comma() {
     let tools = FileSystemTools::tools();
@@ -26,3 +42,62 @@ fn test_tools_without_trailing_comma() {
     assert_eq!(tools[0].name, "say_hello");
     assert_eq!(tools[1].name, "say_goodbye");
 }
+
+#[test]
+fn test_blocking_tool_lookup() {
+    assert!(MixedTools::is_blocking_tool("compress_file"));
+    assert!(!MixedTools::is_blocking_tool("say_hello"));
+    assert!(!MixedTools::is_blocking_tool("unknown_tool"));
+
+    let tool = MixedTools::CompressFileTool(CompressFileTool {
+        path: "/tmp/file.txt".to_string(),
+        encryption_key: None,
+    });
+    assert!(tool.is_blocking());
+}
+
+#[test]
+fn test_tool_timeout_lookup() {
+    assert_eq!(
+        MixedTools::tool_timeout("compress_file"),
+        Some(std::time::Duration::from_secs(30))
+    );
+    assert_eq!(MixedTools::tool_timeout("say_hello"), None);
+    assert_eq!(MixedTools::tool_timeout("unknown_tool"), None);
+}
+
+#[test]
+fn test_tool_sensitive_fields_lookup() {
+    assert_eq!(
+        MixedTools::tool_sensitive_fields("compress_file"),
+        vec!["encryption_key".to_string()]
+    );
+    assert!(MixedTools::tool_sensitive_fields("say_hello").is_empty());
+    assert!(MixedTools::tool_sensitive_fields("unknown_tool").is_empty());
+}
+
+struct MixedToolsHandler;
+
+impl rust_mcp_sdk::mcp_server::ServerHandler for MixedToolsHandler {
+    fn tool_box(&self) -> Option<rust_mcp_sdk::mcp_server::ToolBoxMetadata> {
+        Some(MixedTools::metadata())
+    }
+}
+
+#[test]
+fn test_server_handler_defaults_use_tool_box_metadata() {
+    use rust_mcp_sdk::mcp_server::ServerHandler;
+
+    let handler = MixedToolsHandler;
+    assert!(handler.is_blocking_tool("compress_file"));
+    assert!(!handler.is_blocking_tool("say_hello"));
+    assert_eq!(
+        handler.tool_timeout("compress_file"),
+        Some(std::time::Duration::from_secs(30))
+    );
+    assert_eq!(handler.tool_timeout("say_hello"), None);
+    assert_eq!(
+        handler.tool_sensitive_fields("compress_file"),
+        vec!["encryption_key".to_string()]
+    );
+}
@@ -0,0 +1,149 @@
+#[path = "common/common.rs"]
+pub mod common;
+
+use common::{
+    random_port, test_client_common::create_client, test_server_common::test_server_details,
+    ONE_MILLISECOND,
+};
+use mcp_axum::{create_axum_server, AxumRuntime, AxumServerOptions};
+use rust_mcp_sdk::{
+    mcp_server::{ServerHandler, ToMcpServerHandler, ToolRegistry},
+    schema::{
+        schema_utils::{MessageFromServer, NotificationFromServer},
+        CallToolRequestParams, CallToolResult, ContentBlock, ServerCapabilitiesTools, Tool,
+        ToolInputSchema,
+    },
+    McpClient,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct RegistryServerHandler {
+    tools: Arc<ToolRegistry>,
+}
+
+impl ServerHandler for RegistryServerHandler {
+    fn tool_registry(&self) -> Option<&ToolRegistry> {
+        Some(&self.tools)
+    }
+}
+
+fn tool(name: &str) -> Tool {
+    Tool {
+        annotations: None,
+        description: Some(format!("The {name} tool")),
+        execution: None,
+        icons: vec![],
+        input_schema: ToolInputSchema::new(vec![], None, None),
+        meta: None,
+        name: name.to_string(),
+        output_schema: None,
+        title: None,
+    }
+}
+
+fn call_params(name: &str) -> CallToolRequestParams {
+    CallToolRequestParams {
+        arguments: None,
+        meta: None,
+        name: name.to_string(),
+        task: None,
+    }
+}
+
+fn text_of(result: &CallToolResult) -> &str {
+    let ContentBlock::TextContent(content) = &result.content[0] else {
+        panic!("expected a text content!")
+    };
+    &content.text
+}
+
+// should serve tools registered and unregistered at runtime, notifying the client of each change
+#[tokio::test]
+async fn should_register_and_unregister_tools_at_runtime() {
+    let tools = Arc::new(ToolRegistry::new().with_tool(tool("greet"), |_, _| async {
+        Ok(CallToolResult::text_content(vec!["Hello!".into()]))
+    }));
+
+    let mut server_details = test_server_details();
+    server_details.capabilities.tools = Some(ServerCapabilitiesTools {
+        list_changed: Some(true),
+    });
+
+    let options = AxumServerOptions {
+        port: random_port(),
+        ..Default::default()
+    };
+    let streamable_url = options.streamable_http_url();
+    let server = create_axum_server(
+        server_details,
+        RegistryServerHandler {
+            tools: tools.clone(),
+        }
+        .to_mcp_server_handler(),
+        options,
+    );
+    let axum_runtime = AxumRuntime::create(server).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(75)).await;
+
+    let (client, message_history) = create_client(&streamable_url, None).await;
+    client.clone().start().await.unwrap();
+
+    let result = client.request_tool_list(None).await.unwrap();
+    assert_eq!(result.tools.len(), 1);
+    let result = client
+        .request_tool_call(call_params("greet"))
+        .await
+        .unwrap();
+    assert_eq!(text_of(&result), "Hello!");
+
+    tools
+        .register(tool("farewell"), |_, _| async {
+            Ok(CallToolResult::text_content(vec!["Goodbye!".into()]))
+        })
+        .await;
+
+    let result = client.request_tool_list(None).await.unwrap();
+    let names: Vec<_> = result.tools.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["greet", "farewell"]);
+    let result = client
+        .request_tool_call(call_params("farewell"))
+        .await
+        .unwrap();
+    assert_eq!(text_of(&result), "Goodbye!");
+
+    assert!(tools.unregister("greet").await);
+    assert!(!tools.unregister("greet").await);
+
+    let result = client.request_tool_list(None).await.unwrap();
+    assert_eq!(result.tools.len(), 1);
+    let result = client.request_tool_call(call_params("greet")).await;
+    assert!(result.is_err() || result.unwrap().is_error == Some(true));
+
+    // one notification for the registration and one for the removal
+    let notified = tokio::time::timeout(Duration::from_secs(2), async {
+        loop {
+            let count = message_history
+                .read()
+                .await
+                .iter()
+                .filter(|message| {
+                    matches!(
+                        message,
+                        MessageFromServer::NotificationFromServer(
+                            NotificationFromServer::ToolListChangedNotification(_)
+                        )
+                    )
+                })
+                .count();
+            if count == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+    })
+    .await;
+    assert!(notified.is_ok());
+
+    axum_runtime.graceful_shutdown(ONE_MILLISECOND);
+}
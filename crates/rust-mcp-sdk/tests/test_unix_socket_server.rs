@@ -0,0 +1,185 @@
+#![cfg(all(unix, feature = "unix-socket"))]
+
+use rust_mcp_sdk::{
+    error::McpSdkError,
+    mcp_server::{HandlerExecution, ToMcpServerHandler, UnixSocketServer, UnixSocketServerOptions},
+    testing::{echo_server_details, EchoServerHandler},
+    TransportOptions,
+};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+const INITIALIZE_REQUEST: &str = r#"{"jsonrpc":"2.0","id":0,"method":"initialize","params":{"protocolVersion":"2025-11-25","capabilities":{},"clientInfo":{"name":"unix-socket-test","version":"0.1.0"}}}"#;
+
+fn socket_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rust-mcp-sdk-{name}-{}.sock", std::process::id()))
+}
+
+fn start_server(socket_path: PathBuf) -> Arc<UnixSocketServer> {
+    let server = Arc::new(
+        UnixSocketServer::bind(UnixSocketServerOptions {
+            socket_path,
+            server_details: echo_server_details(),
+            handler: EchoServerHandler.to_mcp_server_handler(),
+            transport_options: TransportOptions::default(),
+            session_id_generator: None,
+            task_store: None,
+            client_task_store: None,
+            message_observer: None,
+            audit_sink: None,
+            handler_execution: HandlerExecution::default(),
+            require_initialized: false,
+        })
+        .unwrap(),
+    );
+    tokio::spawn({
+        let server = server.clone();
+        async move { server.start().await }
+    });
+    server
+}
+
+async fn wait_for_sessions(server: &UnixSocketServer, count: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while server.sessions().await.len() != count {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("unexpected number of sessions");
+}
+
+// every connection should be served as its own session
+#[tokio::test]
+async fn should_serve_a_session_per_connection() {
+    let socket_path = socket_path("sessions");
+    let server = start_server(socket_path.clone());
+
+    let mut connections = vec![];
+    for _ in 0..2 {
+        let stream = UnixStream::connect(&socket_path).await.unwrap();
+        let (read_half, mut write_half) = stream.into_split();
+        write_half
+            .write_all(format!("{INITIALIZE_REQUEST}\n").as_bytes())
+            .await
+            .unwrap();
+
+        let mut lines = BufReader::new(read_half).lines();
+        let response: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(response["id"], 0);
+        assert_eq!(
+            response["result"]["serverInfo"]["name"],
+            "rust-mcp-sdk-echo-server"
+        );
+        connections.push((lines, write_half));
+    }
+    wait_for_sessions(&server, 2).await;
+
+    // closing a connection ends its session
+    connections.pop();
+    wait_for_sessions(&server, 1).await;
+
+    server.shut_down().await.unwrap();
+}
+
+// shutting down should close the sessions and remove the socket file
+#[tokio::test]
+async fn should_remove_the_socket_file_on_shut_down() {
+    let socket_path = socket_path("shutdown");
+    let server = start_server(socket_path.clone());
+    assert!(socket_path.exists());
+
+    let stream = UnixStream::connect(&socket_path).await.unwrap();
+    wait_for_sessions(&server, 1).await;
+
+    server.shut_down().await.unwrap();
+    assert!(server.sessions().await.is_empty());
+    assert!(!socket_path.exists());
+
+    // the closed session closes the connection
+    let mut lines = BufReader::new(stream).lines();
+    let closed = tokio::time::timeout(Duration::from_secs(5), lines.next_line())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(closed, None);
+}
+
+// a file that is not a socket should never be replaced
+#[tokio::test]
+async fn should_not_replace_a_regular_file() {
+    let socket_path = socket_path("regular-file");
+    std::fs::write(&socket_path, "not a socket").unwrap();
+
+    let result = UnixSocketServer::bind(UnixSocketServerOptions {
+        socket_path: socket_path.clone(),
+        server_details: echo_server_details(),
+        handler: EchoServerHandler.to_mcp_server_handler(),
+        transport_options: TransportOptions::default(),
+        session_id_generator: None,
+        task_store: None,
+        client_task_store: None,
+        message_observer: None,
+        audit_sink: None,
+        handler_execution: HandlerExecution::default(),
+        require_initialized: false,
+    });
+    assert!(result.is_err());
+    assert!(socket_path.exists());
+    std::fs::remove_file(&socket_path).unwrap();
+}
+
+// a socket another server is still listening on should not be taken over
+#[tokio::test]
+async fn should_not_replace_a_socket_in_use() {
+    let socket_path = socket_path("in-use");
+    let server = start_server(socket_path.clone());
+
+    let result = UnixSocketServer::bind(UnixSocketServerOptions {
+        socket_path: socket_path.clone(),
+        server_details: echo_server_details(),
+        handler: EchoServerHandler.to_mcp_server_handler(),
+        transport_options: TransportOptions::default(),
+        session_id_generator: None,
+        task_store: None,
+        client_task_store: None,
+        message_observer: None,
+        audit_sink: None,
+        handler_execution: HandlerExecution::default(),
+        require_initialized: false,
+    });
+    let Err(McpSdkError::Io(err)) = result else {
+        panic!("binding a socket in use should fail");
+    };
+    assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+
+    // the first server still serves connections
+    let stream = UnixStream::connect(&socket_path).await.unwrap();
+    let (read_half, mut write_half) = stream.into_split();
+    write_half
+        .write_all(format!("{INITIALIZE_REQUEST}\n").as_bytes())
+        .await
+        .unwrap();
+    let mut lines = BufReader::new(read_half).lines();
+    let response: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+    assert_eq!(response["id"], 0);
+
+    server.shut_down().await.unwrap();
+}
+
+// a socket file left behind without a listener should be replaced
+#[tokio::test]
+async fn should_replace_a_stale_socket() {
+    let socket_path = socket_path("stale");
+    drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+    assert!(socket_path.exists());
+
+    let server = start_server(socket_path.clone());
+    UnixStream::connect(&socket_path).await.unwrap();
+    server.shut_down().await.unwrap();
+}
@@ -1,25 +1,20 @@
 use crate::error::{TransportError, TransportResult};
 use crate::mcp_stream::MCPStream;
 use crate::message_dispatcher::MessageDispatcher;
-use crate::transport::Transport;
+use crate::transport::{Transport, TransportKind};
 use crate::utils::{
-    extract_origin, http_post, CancellationTokenSource, ReadableChannel, SseStream, WritableChannel,
+    extract_origin, http_post, impl_client_dispatch, shut_down_client, validate_headers,
+    with_origin, with_proxy, CancellationTokenSource, ReadableChannel, SseStream, WritableChannel,
 };
-use crate::{IoStream, McpDispatch, TransportDispatcher, TransportOptions};
+use crate::{IoStream, McpDispatch, TransportOptions};
 use async_trait::async_trait;
 use bytes::Bytes;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::header::HeaderMap;
 use reqwest::Client;
 use tokio::sync::oneshot::Sender;
 use tokio::task::JoinHandle;
 
-use crate::schema::{
-    schema_utils::{
-        ClientMessage, ClientMessages, McpMessage, MessageFromClient, SdkError, ServerMessage,
-        ServerMessages,
-    },
-    RequestId,
-};
+use crate::schema::{schema_utils::McpMessage, RequestId};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::pin::Pin;
@@ -31,7 +26,6 @@ use tokio::sync::{mpsc, oneshot, Mutex};
 const DEFAULT_CHANNEL_CAPACITY: usize = 64;
 const DEFAULT_MAX_RETRY: usize = 5;
 const DEFAULT_RETRY_TIME_SECONDS: u64 = 1;
-const SHUTDOWN_TIMEOUT_SECONDS: u64 = 5;
 
 /// Configuration options for the Client SSE Transport
 ///
@@ -40,9 +34,27 @@ pub struct ClientSseTransportOptions {
     pub request_timeout: Duration,
     pub max_line_length: usize,
     pub channel_capacity: usize,
+    /// Delay before reconnecting the SSE stream. A `retry:` field sent by the server replaces it.
     pub retry_delay: Option<Duration>,
     pub max_retries: Option<usize>,
+    /// Reconnects the SSE stream with exponential backoff when it drops, replacing the fixed
+    /// `retry_delay` and `max_retries`. Requests awaiting a response are only failed once all
+    /// attempts are used up.
+    pub reconnect: Option<ReconnectPolicy>,
     pub custom_headers: Option<HashMap<String, String>>,
+    /// URL of an HTTP or HTTPS proxy used for both the SSE GET stream and the POST requests,
+    /// e.g. `http://proxy.corp.example:3128`. Credentials can be included in the URL.
+    ///
+    /// When `None`, the `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` environment variables are honored.
+    /// In both cases hosts listed in `NO_PROXY` are reached directly; local servers are not excluded
+    /// automatically, so add `localhost,127.0.0.1` to `NO_PROXY` when a proxy is set globally.
+    pub proxy: Option<String>,
+    /// Value of the `Origin` header sent with the SSE GET stream and the POST requests, omitted when `None`.
+    ///
+    /// Servers with DNS rebinding protection and `allowed_origins` configured reject requests without a
+    /// matching `Origin`. Set it to one of the server's allowed origins, usually the origin of the server
+    /// itself: scheme, host and port without a path, e.g. `http://localhost:8080`.
+    pub origin: Option<String>,
 }
 
 /// Provides default values for ClientSseTransportOptions
@@ -54,11 +66,67 @@ impl Default for ClientSseTransportOptions {
             channel_capacity: TransportOptions::default().channel_capacity,
             retry_delay: None,
             max_retries: None,
+            reconnect: None,
             custom_headers: None,
+            proxy: None,
+            origin: None,
+        }
+    }
+}
+
+/// Exponential backoff policy for reconnecting the SSE stream of a [`ClientSseTransport`].
+///
+/// The delay before the n-th consecutive attempt is `initial_delay * 2^(n-1)`, capped at `max_delay`.
+/// A `retry:` field sent by the server replaces `initial_delay`.
+/// Every reconnection sends the `Last-Event-ID` header, so servers with an event store replay the missed events.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Maximum number of consecutive reconnection attempts before giving up
+    pub max_retries: usize,
+    /// Delay before the first reconnection attempt
+    pub initial_delay: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_delay: Duration,
+    /// Fraction of each delay, between 0.0 and 1.0, randomly added or subtracted,
+    /// so that many clients dropped at once don't reconnect all at the same time
+    pub jitter: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRY,
+            initial_delay: Duration::from_secs(DEFAULT_RETRY_TIME_SECONDS),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Returns the delay before the reconnection `attempt`, starting at 1, with `initial_delay` as the base delay.
+    pub fn delay(&self, attempt: usize, initial_delay: Duration) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let factor = 2u32.checked_pow(exponent).unwrap_or(u32::MAX);
+        let delay = initial_delay.saturating_mul(factor).min(self.max_delay);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
         }
+        // uniformly distributed in [-jitter, jitter]
+        let offset = jitter * (2.0 * random_unit() - 1.0);
+        delay.mul_f64(1.0 + offset)
     }
 }
 
+/// Returns a random number in `[0, 1)`, good enough to spread out reconnections.
+fn random_unit() -> f64 {
+    use std::hash::BuildHasher;
+    let random = std::collections::hash_map::RandomState::new().hash_one(std::time::Instant::now());
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
 /// Client-side Server-Sent Events (SSE) transport implementation
 ///
 /// Manages SSE connections, HTTP POST requests, and message streaming for client-server communication.
@@ -86,6 +154,8 @@ where
     retry_delay: Duration,
     /// Maximum number of retry attempts
     max_retries: usize,
+    /// Exponential backoff replacing `retry_delay` and `max_retries` when set
+    reconnect: Option<ReconnectPolicy>,
     /// Optional custom HTTP headers
     custom_headers: Option<HeaderMap>,
     sse_task: tokio::sync::RwLock<Option<tokio::task::JoinHandle<()>>>,
@@ -110,7 +180,11 @@ where
     /// # Returns
     /// * `TransportResult<Self>` - The initialized transport or an error
     pub fn new(server_url: &str, options: ClientSseTransportOptions) -> TransportResult<Self> {
-        let client = Client::new();
+        let client = with_proxy(Client::builder(), options.proxy.as_deref())?
+            .build()
+            .map_err(|err| TransportError::Configuration {
+                message: format!("Failed to build the HTTP client: {err}"),
+            })?;
 
         let base_url = match extract_origin(server_url) {
             Some(url) => url,
@@ -122,15 +196,17 @@ where
         };
 
         let headers = match &options.custom_headers {
-            Some(h) => Some(Self::validate_headers(h)?),
+            Some(h) => Some(validate_headers(h)?),
             None => None,
         };
+        let headers = with_origin(headers, options.origin.as_deref())?;
 
         Ok(Self {
             client,
             base_url,
             sse_url: server_url.to_string(),
             max_retries: options.max_retries.unwrap_or(DEFAULT_MAX_RETRY),
+            reconnect: options.reconnect,
             retry_delay: options
                 .retry_delay
                 .unwrap_or(Duration::from_secs(DEFAULT_RETRY_TIME_SECONDS)),
@@ -148,32 +224,6 @@ where
         })
     }
 
-    /// Validates and converts a HashMap of headers into a HeaderMap
-    ///
-    /// # Arguments
-    /// * `headers` - The HashMap of header names and values
-    ///
-    /// # Returns
-    /// * `TransportResult<HeaderMap>` - The validated HeaderMap or an error
-    fn validate_headers(headers: &HashMap<String, String>) -> TransportResult<HeaderMap> {
-        let mut header_map = HeaderMap::new();
-
-        for (key, value) in headers {
-            let header_name =
-                key.parse::<HeaderName>()
-                    .map_err(|e| TransportError::Configuration {
-                        message: format!("Invalid header name: {e}"),
-                    })?;
-            let header_value =
-                HeaderValue::from_str(value).map_err(|e| TransportError::Configuration {
-                    message: format!("Invalid header value: {e}"),
-                })?;
-            header_map.insert(header_name, header_value);
-        }
-
-        Ok(header_map)
-    }
-
     /// Validates the message endpoint URL
     ///
     /// Ensures the endpoint is either relative to the base URL or matches the base URL's origin.
@@ -260,15 +310,25 @@ where
             sse_url,
             max_retries,
             retry_delay,
+            reconnect: self.reconnect.clone(),
             read_tx,
         };
 
         // Spawn task to handle SSE stream with reconnection
         let cancellation_token_sse = cancellation_token.clone();
+        let pending_requests = self.pending_requests.clone();
         let sse_task_handle = tokio::spawn(async move {
             read_stream
-                .run(endpoint_event_tx, cancellation_token_sse, &custom_headers)
+                .run(
+                    endpoint_event_tx,
+                    cancellation_token_sse.clone(),
+                    &custom_headers,
+                )
                 .await;
+            // the stream could not be re-established, responses will never arrive
+            if !cancellation_token_sse.is_cancelled() {
+                pending_requests.lock().await.clear();
+            }
         });
         let mut sse_task_lock = self.sse_task.write().await;
         *sse_task_lock = Some(sse_task_handle);
@@ -381,6 +441,10 @@ where
         *result
     }
 
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::Sse
+    }
+
     // Shuts down the transport, terminating any subprocess and signaling closure.
     ///
     /// Sends a shutdown signal via the watch channel and kills the subprocess if present.
@@ -391,42 +455,15 @@ where
     /// # Errors
     /// Returns a `TransportError` if the shutdown signal fails or the process cannot be killed.
     async fn shut_down(&self) -> TransportResult<()> {
-        // Trigger cancellation
-        let mut cancellation_lock = self.shutdown_source.write().await;
-        if let Some(source) = cancellation_lock.as_ref() {
-            source.cancel()?;
-        }
-        *cancellation_lock = None; // Clear cancellation_source
-
-        // Mark as shut down
-        let mut is_shut_down_lock = self.is_shut_down.lock().await;
-        *is_shut_down_lock = true;
-
-        // Get task handles
-        let sse_task = self.sse_task.write().await.take();
-        let post_task = self.post_task.write().await.take();
-
-        // Wait for tasks to complete with a timeout
-        let timeout = Duration::from_secs(SHUTDOWN_TIMEOUT_SECONDS);
-        let shutdown_future = async {
-            if let Some(post_handle) = post_task {
-                let _ = post_handle.await;
-            }
-            if let Some(sse_handle) = sse_task {
-                let _ = sse_handle.await;
-            }
-            Ok::<(), TransportError>(())
-        };
-
-        tokio::select! {
-            result = shutdown_future => {
-                result // result of task completion
-            }
-            _ = tokio::time::sleep(timeout) => {
-                tracing::warn!("Shutdown timed out after {:?}", timeout);
-                Err(TransportError::ShutdownTimeout)
-            }
-        }
+        shut_down_client(
+            &self.shutdown_source,
+            &self.is_shut_down,
+            vec![
+                self.post_task.write().await.take(),
+                self.sse_task.write().await.take(),
+            ],
+        )
+        .await
     }
 
     async fn pending_request_tx(&self, request_id: &RequestId) -> Option<Sender<M>> {
@@ -435,54 +472,108 @@ where
     }
 }
 
-#[async_trait]
-impl McpDispatch<ServerMessages, ClientMessages, ServerMessage, ClientMessage>
-    for ClientSseTransport<ServerMessage>
-{
-    async fn send_message(
-        &self,
-        message: ClientMessages,
-        request_timeout: Option<Duration>,
-    ) -> TransportResult<Option<ServerMessages>> {
-        let sender = self.message_sender.read().await;
-        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
-        sender.send_message(message, request_timeout).await
-    }
-
-    async fn send(
-        &self,
-        message: ClientMessage,
-        request_timeout: Option<Duration>,
-    ) -> TransportResult<Option<ServerMessage>> {
-        let sender = self.message_sender.read().await;
-        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
-        sender.send(message, request_timeout).await
+impl_client_dispatch!(ClientSseTransport);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::schema_utils::ServerMessage;
+    use crate::utils::http_get;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn reconnect_delay_grows_exponentially_up_to_the_max_delay() {
+        let policy = ReconnectPolicy {
+            max_retries: 10,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: 0.0,
+        };
+        let delays: Vec<_> = (1..=6)
+            .map(|attempt| policy.delay(attempt, policy.initial_delay).as_millis())
+            .collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+
+        let policy = ReconnectPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for _ in 0..20 {
+            let delay = policy.delay(2, policy.initial_delay);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(300));
+        }
     }
 
-    async fn send_batch(
-        &self,
-        message: Vec<ClientMessage>,
-        request_timeout: Option<Duration>,
-    ) -> TransportResult<Option<Vec<ServerMessage>>> {
-        let sender = self.message_sender.read().await;
-        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
-        sender.send_batch(message, request_timeout).await
+    #[tokio::test]
+    async fn sse_and_post_requests_are_sent_through_the_configured_proxy() {
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/sse"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let transport = ClientSseTransport::<ServerMessage>::new(
+            "http://mcp.invalid/sse",
+            ClientSseTransportOptions {
+                proxy: Some(proxy.uri()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // the target host does not resolve, requests only succeed if they go through the proxy
+        http_get(&transport.client, &transport.sse_url, None, None)
+            .await
+            .unwrap();
+        http_post(
+            &transport.client,
+            &format!("{}/messages", transport.base_url),
+            "{}".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
     }
 
-    async fn write_str(&self, payload: &str, skip_store: bool) -> TransportResult<()> {
-        let sender = self.message_sender.read().await;
-        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
-        sender.write_str(payload, skip_store).await
+    #[test]
+    fn configured_origin_is_added_to_the_request_headers() {
+        let mut custom_headers = HashMap::new();
+        custom_headers.insert("X-Custom-Header".to_string(), "CustomValue".to_string());
+
+        let transport = ClientSseTransport::<ServerMessage>::new(
+            "http://localhost:8080/sse",
+            ClientSseTransportOptions {
+                custom_headers: Some(custom_headers),
+                origin: Some("http://localhost:8080".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let headers = transport.custom_headers.unwrap();
+        assert_eq!(headers.get("origin").unwrap(), "http://localhost:8080");
+        assert_eq!(headers.get("x-custom-header").unwrap(), "CustomValue");
+
+        let invalid_origin = ClientSseTransport::<ServerMessage>::new(
+            "http://localhost:8080/sse",
+            ClientSseTransportOptions {
+                origin: Some("http://localhost\n".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            invalid_origin,
+            Err(TransportError::Configuration { .. })
+        ));
     }
 }
-
-impl
-    TransportDispatcher<
-        ServerMessages,
-        MessageFromClient,
-        ServerMessage,
-        ClientMessages,
-        ClientMessage,
-    > for ClientSseTransport<ServerMessage>
-{
-}
@@ -9,11 +9,13 @@ use crate::schema::{
     RequestId,
 };
 use crate::utils::{
-    http_delete, http_post, CancellationTokenSource, ReadableChannel, StreamableHttpStream,
-    WritableChannel,
+    http_delete, http_post, with_origin, with_proxy, CancellationTokenSource, ReadableChannel,
+    StreamableHttpStream, WritableChannel,
 };
 use crate::{error::TransportResult, IoStream, McpDispatch, MessageDispatcher, Transport};
-use crate::{SessionId, TransportDispatcher, TransportOptions};
+use crate::{
+    SessionId, TransportDispatcher, TransportKind, TransportOptions, MCP_PROTOCOL_VERSION_HEADER,
+};
 use async_trait::async_trait;
 use bytes::Bytes;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
@@ -30,6 +32,8 @@ const DEFAULT_CHANNEL_CAPACITY: usize = 64;
 const DEFAULT_MAX_RETRY: usize = 5;
 const DEFAULT_RETRY_TIME_SECONDS: u64 = 1;
 const SHUTDOWN_TIMEOUT_SECONDS: u64 = 5;
+const MCP_PROTOCOL_VERSION_HEADER_NAME: HeaderName =
+    HeaderName::from_static(MCP_PROTOCOL_VERSION_HEADER);
 
 pub struct StreamableTransportOptions {
     pub mcp_url: String,
@@ -37,9 +41,38 @@ pub struct StreamableTransportOptions {
 }
 
 impl StreamableTransportOptions {
+    /// Builds the pooled HTTP client once and stores it in `request_options`, so that every
+    /// transport created from these options reuses the same connections.
+    pub fn share_http_client(&mut self) -> TransportResult<()> {
+        if self.request_options.http_client.is_none() {
+            self.request_options.http_client = Some(self.request_options.http_client()?);
+        }
+        Ok(())
+    }
+
     pub async fn terminate_session(&self, session_id: Option<&SessionId>) {
-        let client = Client::new();
-        match http_delete(&client, &self.mcp_url, session_id, None).await {
+        self.terminate_session_with_protocol_version(session_id, None)
+            .await
+    }
+
+    /// Same as [`Self::terminate_session`], also sending the protocol version negotiated
+    /// during initialization in the `mcp-protocol-version` header.
+    pub async fn terminate_session_with_protocol_version(
+        &self,
+        session_id: Option<&SessionId>,
+        protocol_version: Option<&str>,
+    ) {
+        let client = self
+            .request_options
+            .http_client()
+            .unwrap_or_else(|_| Client::new());
+        let mut headers = self.request_options.request_headers().unwrap_or_default();
+        if let Some(version) = protocol_version.and_then(|v| HeaderValue::from_str(v).ok()) {
+            headers
+                .get_or_insert_with(HeaderMap::new)
+                .insert(MCP_PROTOCOL_VERSION_HEADER_NAME, version);
+        }
+        match http_delete(&client, &self.mcp_url, session_id, headers.as_ref()).await {
             Ok(_) => {}
             Err(TransportError::Http(status_code)) => {
                 tracing::info!("Session termination failed with status code {status_code}",);
@@ -58,6 +91,37 @@ pub struct RequestOptions {
     pub retry_delay: Option<Duration>,
     pub max_retries: Option<usize>,
     pub custom_headers: Option<HashMap<String, String>>,
+    /// Maximum number of idle connections kept alive per host. `None` keeps reqwest's default (no limit).
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long an idle connection is kept in the pool before being closed. `None` keeps reqwest's default (90 seconds).
+    pub pool_idle_timeout: Option<Duration>,
+    /// Maximum time allowed to establish a TCP connection, including the TLS handshake.
+    /// A dead or unreachable server fails with [`TransportError::ConnectTimeout`] instead of hanging.
+    /// `None` waits for the operating system's connect timeout.
+    pub connect_timeout: Option<Duration>,
+    /// Maximum time to wait for the next chunk of a response body.
+    ///
+    /// Unlike `request_timeout`, which bounds how long the runtime waits for the response to an MCP request,
+    /// this bounds how long an HTTP connection may stay silent. Leave it unset, or set it above the server's
+    /// keep-alive interval, when using long-lived SSE streams.
+    pub read_timeout: Option<Duration>,
+    /// URL of an HTTP or HTTPS proxy used for both the POST requests and the SSE GET stream,
+    /// e.g. `http://proxy.corp.example:3128`. Credentials can be included in the URL.
+    ///
+    /// When `None`, the `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` environment variables are honored.
+    /// In both cases hosts listed in `NO_PROXY` are reached directly; local servers are not excluded
+    /// automatically, so add `localhost,127.0.0.1` to `NO_PROXY` when a proxy is set globally.
+    pub proxy: Option<String>,
+    /// Value of the `Origin` header sent with every request, omitted when `None`.
+    ///
+    /// Servers with DNS rebinding protection and `allowed_origins` configured reject requests without a
+    /// matching `Origin`. Set it to one of the server's allowed origins, usually the origin of the server
+    /// itself: scheme, host and port without a path, e.g. `http://localhost:8080`.
+    pub origin: Option<String>,
+    /// HTTP client used for every request of the transport.
+    /// When `None`, a client is built from the pool, timeout and proxy settings above. Clones of a `reqwest::Client`
+    /// share one connection pool, so a client can also be shared with the rest of an application.
+    pub http_client: Option<Client>,
 }
 
 impl Default for RequestOptions {
@@ -69,7 +133,56 @@ impl Default for RequestOptions {
             retry_delay: None,
             max_retries: None,
             custom_headers: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            connect_timeout: None,
+            read_timeout: None,
+            proxy: None,
+            origin: None,
+            http_client: None,
+        }
+    }
+}
+
+impl RequestOptions {
+    /// Returns the `custom_headers` and the `Origin` header sent with every request.
+    pub fn request_headers(&self) -> TransportResult<Option<HeaderMap>> {
+        let headers = match &self.custom_headers {
+            Some(h) => Some(ClientStreamableTransport::<ServerMessages>::validate_headers(h)?),
+            None => None,
+        };
+        with_origin(headers, self.origin.as_deref())
+    }
+
+    /// Returns the configured `http_client`, or builds a new pooled client from the pool, timeout and proxy settings.
+    pub fn http_client(&self) -> TransportResult<Client> {
+        if let Some(client) = self.http_client.as_ref() {
+            return Ok(client.clone());
+        }
+
+        self.client_builder()?
+            .build()
+            .map_err(|err| TransportError::Configuration {
+                message: format!("Failed to build the HTTP client: {err}"),
+            })
+    }
+
+    /// Returns a client builder configured from the pool, timeout and proxy settings.
+    fn client_builder(&self) -> TransportResult<reqwest::ClientBuilder> {
+        let mut builder = Client::builder();
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(idle_timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(idle_timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(read_timeout) = self.read_timeout {
+            builder = builder.read_timeout(read_timeout);
         }
+        with_proxy(builder, self.proxy.as_deref())
     }
 }
 
@@ -115,12 +228,9 @@ where
         session_id: Option<SessionId>,
         standalone: bool,
     ) -> TransportResult<Self> {
-        let client = Client::new();
+        let client = options.request_options.http_client()?;
 
-        let headers = match &options.request_options.custom_headers {
-            Some(h) => Some(Self::validate_headers(h)?),
-            None => None,
-        };
+        let headers = options.request_options.request_headers()?;
 
         let mcp_server_url = options.mcp_url.to_owned();
         Ok(Self {
@@ -150,7 +260,22 @@ where
         })
     }
 
-    fn validate_headers(headers: &HashMap<String, String>) -> TransportResult<HeaderMap> {
+    /// Sends the protocol version negotiated during initialization in the `mcp-protocol-version`
+    /// header of every request made by this transport, as required after initialization.
+    pub fn with_protocol_version(mut self, protocol_version: &str) -> TransportResult<Self> {
+        let value =
+            HeaderValue::from_str(protocol_version).map_err(|e| TransportError::Configuration {
+                message: format!("Invalid protocol version: {e}"),
+            })?;
+        self.custom_headers
+            .get_or_insert_with(HeaderMap::new)
+            .insert(MCP_PROTOCOL_VERSION_HEADER_NAME, value);
+        Ok(self)
+    }
+
+    pub(crate) fn validate_headers(
+        headers: &HashMap<String, String>,
+    ) -> TransportResult<HeaderMap> {
         let mut header_map = HeaderMap::new();
         for (key, value) in headers {
             let header_name =
@@ -444,6 +569,10 @@ where
         let result = self.is_shut_down.lock().await;
         *result
     }
+
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::StreamableHttp
+    }
     async fn consume_string_payload(&self, _: &str) -> TransportResult<()> {
         Err(TransportError::Internal(
             "Invalid invocation of consume_string_payload() function for ClientStreamableTransport"
@@ -527,3 +656,158 @@ impl
     > for ClientStreamableTransport<ServerMessage>
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::http_post;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Starts a minimal keep-alive HTTP server, returning its url and the number of accepted connections.
+    async fn start_counting_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buffer = [0u8; 4096];
+                    // one read per request is enough for the small payloads used here
+                    while let Ok(read) = socket.read(&mut buffer).await {
+                        if read == 0 {
+                            break;
+                        }
+                        let response = "HTTP/1.1 202 Accepted\r\ncontent-length: 0\r\n\r\n";
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        (url, connections)
+    }
+
+    async fn post_twice(options: &StreamableTransportOptions) {
+        for _ in 0..2 {
+            let client = options.request_options.http_client().unwrap();
+            http_post(&client, &options.mcp_url, "{}".to_string(), None, None)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_http_client_reuses_connections() {
+        let (mcp_url, connections) = start_counting_server().await;
+        let mut options = StreamableTransportOptions {
+            mcp_url,
+            request_options: RequestOptions::default(),
+        };
+        options.share_http_client().unwrap();
+
+        post_twice(&options).await;
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn pool_settings_are_applied_to_the_http_client() {
+        let (mcp_url, connections) = start_counting_server().await;
+        let mut options = StreamableTransportOptions {
+            mcp_url,
+            request_options: RequestOptions {
+                pool_max_idle_per_host: Some(0),
+                ..RequestOptions::default()
+            },
+        };
+        options.share_http_client().unwrap();
+
+        // without idle connections in the pool, every request opens a new connection
+        post_twice(&options).await;
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_are_sent_through_the_configured_proxy() {
+        let proxy = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/mcp"))
+            .respond_with(wiremock::ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let options = RequestOptions {
+            proxy: Some(proxy.uri()),
+            ..RequestOptions::default()
+        };
+        let client = options.http_client().unwrap();
+
+        // the target host does not resolve, the request only succeeds if it goes through the proxy
+        http_post(
+            &client,
+            "http://mcp.invalid/mcp",
+            "{}".to_string(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let received = proxy.received_requests().await.unwrap();
+        assert_eq!(received[0].url.host_str(), Some("mcp.invalid"));
+    }
+
+    #[test]
+    fn invalid_proxy_url_is_a_configuration_error() {
+        let options = RequestOptions {
+            proxy: Some("not a url".to_string()),
+            ..RequestOptions::default()
+        };
+        assert!(matches!(
+            options.http_client(),
+            Err(TransportError::Configuration { .. })
+        ));
+    }
+
+    /// Resolves no host name, so connecting never completes.
+    struct StalledResolver;
+
+    impl reqwest::dns::Resolve for StalledResolver {
+        fn resolve(&self, _name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+            Box::pin(futures::future::pending())
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_timeout_is_reported_as_a_distinct_error() {
+        let options = RequestOptions {
+            connect_timeout: Some(Duration::from_millis(200)),
+            ..RequestOptions::default()
+        };
+        let client = options
+            .client_builder()
+            .unwrap()
+            .dns_resolver(Arc::new(StalledResolver))
+            .build()
+            .unwrap();
+
+        let result = http_post(
+            &client,
+            "http://mcp.invalid/mcp",
+            "{}".to_string(),
+            None,
+            None,
+        )
+        .await;
+        assert!(
+            matches!(result, Err(TransportError::ConnectTimeout(_))),
+            "unexpected result: {result:?}"
+        );
+    }
+}
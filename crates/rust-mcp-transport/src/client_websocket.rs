@@ -0,0 +1,440 @@
+use crate::error::{TransportError, TransportResult};
+use crate::mcp_stream::MCPStream;
+use crate::message_dispatcher::MessageDispatcher;
+use crate::transport::{Transport, TransportKind};
+use crate::utils::{
+    impl_client_dispatch, shut_down_client, validate_headers, CancellationTokenSource,
+    ReadableChannel, WebSocketConnection, WritableChannel,
+};
+use crate::{IoStream, McpDispatch, TransportOptions};
+use async_trait::async_trait;
+use bytes::Bytes;
+use rustls::ClientConfig;
+use tokio::sync::oneshot::Sender;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderMap;
+
+use crate::schema::{schema_utils::McpMessage, RequestId};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{BufReader, BufWriter};
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+const DEFAULT_CHANNEL_CAPACITY: usize = 64;
+const DEFAULT_RETRY_TIME_SECONDS: u64 = 1;
+
+/// Configuration options for the Client WebSocket Transport
+///
+/// Defines settings for request timeouts, keep-alive pings, reconnection and custom handshake headers.
+pub struct ClientWebSocketTransportOptions {
+    pub request_timeout: Duration,
+    pub max_line_length: usize,
+    pub channel_capacity: usize,
+    /// Headers sent with the opening handshake, e.g. `Authorization`.
+    pub custom_headers: Option<HashMap<String, String>>,
+    /// Interval between ping frames keeping the connection alive, no pings are sent when `None`.
+    pub ping_interval: Option<Duration>,
+    /// Number of attempts to reopen the socket after the server closes it, defaults to 0 (no reconnection).
+    ///
+    /// A reopened socket starts a new connection on the server, the client must initialize it again
+    /// unless the server keeps the session across connections.
+    pub max_retries: Option<usize>,
+    /// Delay between reconnection attempts, defaults to 1 second.
+    pub retry_delay: Option<Duration>,
+}
+
+/// Provides default values for ClientWebSocketTransportOptions
+impl Default for ClientWebSocketTransportOptions {
+    fn default() -> Self {
+        Self {
+            request_timeout: TransportOptions::default().timeout,
+            max_line_length: TransportOptions::default().max_line_length,
+            channel_capacity: TransportOptions::default().channel_capacity,
+            custom_headers: None,
+            ping_interval: None,
+            max_retries: None,
+            retry_delay: None,
+        }
+    }
+}
+
+/// Client-side WebSocket transport implementation
+///
+/// Exchanges JSON-RPC messages with the server over a single WebSocket, one message per text frame.
+/// Requests and notifications initiated by the server arrive on the same socket.
+pub struct ClientWebSocketTransport<R>
+where
+    R: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+{
+    /// Optional cancellation token source for shutting down the transport
+    shutdown_source: tokio::sync::RwLock<Option<CancellationTokenSource>>,
+    /// Flag indicating if the transport is shut down
+    is_shut_down: Mutex<bool>,
+    /// Timeout duration for MCP messages
+    request_timeout: Duration,
+    /// Maximum line length for incoming messages
+    max_line_length: usize,
+    /// Capacity of the incoming-message channel buffer
+    channel_capacity: usize,
+    /// URL of the WebSocket endpoint
+    ws_url: String,
+    /// Custom headers sent with the opening handshake
+    custom_headers: HeaderMap,
+    /// Interval between ping frames
+    ping_interval: Option<Duration>,
+    /// Delay between reconnection attempts
+    retry_delay: Duration,
+    /// Maximum number of reconnection attempts
+    max_retries: usize,
+    ws_task: tokio::sync::RwLock<Option<tokio::task::JoinHandle<()>>>,
+    message_sender: Arc<tokio::sync::RwLock<Option<MessageDispatcher<R>>>>,
+    error_stream: tokio::sync::RwLock<Option<IoStream>>,
+    pending_requests: Arc<Mutex<HashMap<RequestId, tokio::sync::oneshot::Sender<R>>>>,
+}
+
+impl<R> ClientWebSocketTransport<R>
+where
+    R: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+{
+    /// Creates a new ClientWebSocketTransport instance
+    ///
+    /// Initializes the transport with the provided server URL and options.
+    ///
+    /// # Arguments
+    /// * `server_url` - The `ws://` or `wss://` URL of the server
+    /// * `options` - Configuration options for the transport
+    ///
+    /// # Returns
+    /// * `TransportResult<Self>` - The initialized transport or an error
+    pub fn new(
+        server_url: &str,
+        options: ClientWebSocketTransportOptions,
+    ) -> TransportResult<Self> {
+        server_url
+            .into_client_request()
+            .map_err(|err| TransportError::Configuration {
+                message: format!("Invalid WebSocket URL: {err}"),
+            })?;
+
+        let custom_headers = match &options.custom_headers {
+            Some(h) => validate_headers(h)?,
+            None => HeaderMap::new(),
+        };
+
+        Ok(Self {
+            ws_url: server_url.to_string(),
+            custom_headers,
+            ping_interval: options.ping_interval,
+            max_retries: options.max_retries.unwrap_or_default(),
+            retry_delay: options
+                .retry_delay
+                .unwrap_or(Duration::from_secs(DEFAULT_RETRY_TIME_SECONDS)),
+            shutdown_source: tokio::sync::RwLock::new(None),
+            is_shut_down: Mutex::new(false),
+            request_timeout: options.request_timeout,
+            max_line_length: options.max_line_length,
+            channel_capacity: options.channel_capacity,
+            ws_task: tokio::sync::RwLock::new(None),
+            message_sender: Arc::new(tokio::sync::RwLock::new(None)),
+            error_stream: tokio::sync::RwLock::new(None),
+            pending_requests: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Builds the TLS configuration for `wss://` URLs, trusting the Mozilla root certificates
+    fn tls_config() -> TransportResult<Arc<ClientConfig>> {
+        let config =
+            ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .map_err(|err| TransportError::Configuration {
+                    message: format!("Failed to build the TLS configuration: {err}"),
+                })?
+                .with_root_certificates(rustls::RootCertStore {
+                    roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+                })
+                .with_no_client_auth();
+        Ok(Arc::new(config))
+    }
+
+    pub(crate) async fn set_message_sender(&self, sender: MessageDispatcher<R>) {
+        let mut lock = self.message_sender.write().await;
+        *lock = Some(sender);
+    }
+
+    pub(crate) async fn set_error_stream(
+        &self,
+        error_stream: Pin<Box<dyn tokio::io::AsyncRead + Send + Sync>>,
+    ) {
+        let mut lock = self.error_stream.write().await;
+        *lock = Some(IoStream::Readable(error_stream));
+    }
+}
+
+#[async_trait]
+impl<R, S, M, OR, OM> Transport<R, S, M, OR, OM> for ClientWebSocketTransport<M>
+where
+    R: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+    S: McpMessage + Clone + Send + Sync + serde::Serialize + 'static,
+    M: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+    OR: Clone + Send + Sync + serde::Serialize + 'static,
+    OM: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+{
+    /// Starts the transport, opening the WebSocket and the message streams
+    ///
+    /// Fails if the first connection can not be opened; reconnections after the socket
+    /// closes happen in the background, up to `max_retries` times.
+    ///
+    /// # Returns
+    /// * `TransportResult<ReceiverStream<R>>` - The stream of incoming messages
+    async fn start(&self) -> TransportResult<tokio_stream::wrappers::ReceiverStream<R>>
+    where
+        MessageDispatcher<M>: McpDispatch<R, OR, M, OM>,
+    {
+        let (write_tx, write_rx) = mpsc::channel::<Bytes>(DEFAULT_CHANNEL_CAPACITY);
+        let (read_tx, read_rx) = mpsc::channel::<Bytes>(DEFAULT_CHANNEL_CAPACITY);
+
+        let connection = WebSocketConnection {
+            url: self.ws_url.clone(),
+            headers: self.custom_headers.clone(),
+            tls_config: Self::tls_config()?,
+            ping_interval: self.ping_interval,
+            max_retries: self.max_retries,
+            retry_delay: self.retry_delay,
+            read_tx,
+            pending_requests: self.pending_requests.clone(),
+        };
+        let socket = connection.connect().await?;
+
+        // Create CancellationTokenSource and token
+        let (cancellation_source, cancellation_token) = CancellationTokenSource::new();
+        let mut lock = self.shutdown_source.write().await;
+        *lock = Some(cancellation_source);
+
+        // Spawn task exchanging frames with the server, reconnecting if configured
+        let cancellation_token_ws = cancellation_token.clone();
+        let pending_requests = self.pending_requests.clone();
+        let ws_task_handle = tokio::spawn(async move {
+            connection
+                .run(socket, write_rx, cancellation_token_ws.clone())
+                .await;
+            // the socket could not be reopened, responses will never arrive
+            if !cancellation_token_ws.is_cancelled() {
+                pending_requests.lock().await.clear();
+            }
+        });
+        let mut ws_task_lock = self.ws_task.write().await;
+        *ws_task_lock = Some(ws_task_handle);
+
+        // Create writable stream
+        let writable: Mutex<Pin<Box<dyn tokio::io::AsyncWrite + Send + Sync>>> =
+            Mutex::new(Box::pin(BufWriter::new(WritableChannel { write_tx })));
+
+        // Create readable stream
+        let readable: Pin<Box<dyn tokio::io::AsyncRead + Send + Sync>> =
+            Box::pin(BufReader::new(ReadableChannel {
+                read_rx,
+                buffer: Bytes::new(),
+            }));
+
+        let (stream, sender, error_stream) = MCPStream::create(
+            readable,
+            writable,
+            IoStream::Writable(Box::pin(tokio::io::stderr())),
+            self.pending_requests.clone(),
+            self.request_timeout,
+            self.max_line_length,
+            cancellation_token,
+            self.channel_capacity,
+        );
+
+        self.set_message_sender(sender).await;
+
+        if let IoStream::Readable(error_stream) = error_stream {
+            self.set_error_stream(error_stream).await;
+        }
+
+        Ok(stream)
+    }
+
+    fn message_sender(&self) -> Arc<tokio::sync::RwLock<Option<MessageDispatcher<M>>>> {
+        self.message_sender.clone() as _
+    }
+
+    fn error_stream(&self) -> &tokio::sync::RwLock<Option<IoStream>> {
+        &self.error_stream as _
+    }
+
+    async fn consume_string_payload(&self, _payload: &str) -> TransportResult<()> {
+        Err(TransportError::Internal(
+            "Invalid invocation of consume_string_payload() function for ClientWebSocketTransport"
+                .to_string(),
+        ))
+    }
+
+    async fn keep_alive(
+        &self,
+        _: Duration,
+        _: oneshot::Sender<()>,
+    ) -> TransportResult<JoinHandle<()>> {
+        Err(TransportError::Internal(
+            "Invalid invocation of keep_alive() function for ClientWebSocketTransport".to_string(),
+        ))
+    }
+
+    /// Checks if the transport has been shut down
+    ///
+    /// # Returns
+    /// * `bool` - True if the transport is shut down, false otherwise
+    async fn is_shut_down(&self) -> bool {
+        let result = self.is_shut_down.lock().await;
+        *result
+    }
+
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::WebSocket
+    }
+
+    /// Shuts down the transport, closing the WebSocket and signaling closure.
+    ///
+    /// # Returns
+    /// A `TransportResult` indicating success or failure.
+    ///
+    /// # Errors
+    /// Returns a `TransportError` if the shutdown signal fails or the socket task does not finish in time.
+    async fn shut_down(&self) -> TransportResult<()> {
+        shut_down_client(
+            &self.shutdown_source,
+            &self.is_shut_down,
+            vec![self.ws_task.write().await.take()],
+        )
+        .await
+    }
+
+    async fn pending_request_tx(&self, request_id: &RequestId) -> Option<Sender<M>> {
+        let mut pending_requests = self.pending_requests.lock().await;
+        pending_requests.remove(request_id)
+    }
+}
+
+impl_client_dispatch!(ClientWebSocketTransport);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::schema_utils::{
+        ClientMessage, ClientMessages, MessageFromClient, ServerMessage, ServerMessages,
+    };
+    use futures::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::Message;
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)]
+    async fn messages_are_exchanged_as_text_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut authorization = None;
+            let mut socket = tokio_tungstenite::accept_hdr_async(
+                stream,
+                |request: &Request, response: Response| {
+                    authorization = request
+                        .headers()
+                        .get("authorization")
+                        .map(|value| value.to_str().unwrap().to_string());
+                    Ok(response)
+                },
+            )
+            .await
+            .unwrap();
+
+            let frame = socket.next().await.unwrap().unwrap();
+            // a server-initiated request, pretty printed over several lines
+            socket
+                .send(Message::text(
+                    "{\n  \"jsonrpc\": \"2.0\",\n  \"id\": 1,\n  \"method\": \"ping\"\n}",
+                ))
+                .await
+                .unwrap();
+            (authorization, frame, socket)
+        });
+
+        let mut custom_headers = HashMap::new();
+        custom_headers.insert("Authorization".to_string(), "Bearer token".to_string());
+        let transport = ClientWebSocketTransport::<ServerMessage>::new(
+            &format!("ws://{address}/mcp"),
+            ClientWebSocketTransportOptions {
+                custom_headers: Some(custom_headers),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut stream = Transport::<
+            ServerMessages,
+            MessageFromClient,
+            ServerMessage,
+            ClientMessages,
+            ClientMessage,
+        >::start(&transport)
+        .await
+        .unwrap();
+
+        transport
+            .write_str(
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+                true,
+            )
+            .await
+            .unwrap();
+
+        let (authorization, frame, _socket) = server.await.unwrap();
+        assert_eq!(authorization.as_deref(), Some("Bearer token"));
+        assert_eq!(
+            frame,
+            Message::text(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+        );
+
+        let message = stream.next().await.unwrap();
+        assert!(matches!(
+            message,
+            ServerMessages::Single(ServerMessage::Request(_))
+        ));
+
+        Transport::<ServerMessages, MessageFromClient, ServerMessage, ClientMessages, ClientMessage>::shut_down(&transport)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn invalid_url_and_headers_are_rejected() {
+        let invalid_url = ClientWebSocketTransport::<ServerMessage>::new(
+            "http:// not a url",
+            ClientWebSocketTransportOptions::default(),
+        );
+        assert!(matches!(
+            invalid_url,
+            Err(TransportError::Configuration { .. })
+        ));
+
+        let mut custom_headers = HashMap::new();
+        custom_headers.insert("X-Custom".to_string(), "invalid\nvalue".to_string());
+        let invalid_header = ClientWebSocketTransport::<ServerMessage>::new(
+            "ws://localhost:8080/mcp",
+            ClientWebSocketTransportOptions {
+                custom_headers: Some(custom_headers),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            invalid_header,
+            Err(TransportError::Configuration { .. })
+        ));
+    }
+}
@@ -1,3 +1,5 @@
 pub const MCP_SESSION_ID_HEADER: &str = "mcp-session-id";
 pub const MCP_PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
 pub const MCP_LAST_EVENT_ID_HEADER: &str = "last-event-id";
+/// Carries the routing key of a newly created session, see `IdGenerator::routing_key()` in rust-mcp-sdk
+pub const MCP_ROUTING_KEY_HEADER: &str = "mcp-routing-key";
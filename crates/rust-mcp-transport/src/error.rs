@@ -100,7 +100,11 @@ pub enum TransportError {
 
     #[cfg(any(feature = "sse", feature = "streamable-http"))]
     #[error("HTTP connection error: {0}")]
-    HttpConnection(#[from] ReqwestError),
+    HttpConnection(ReqwestError),
+
+    #[cfg(any(feature = "sse", feature = "streamable-http"))]
+    #[error("Timed out connecting to the server: {0}")]
+    ConnectTimeout(ReqwestError),
 
     #[cfg(any(feature = "sse", feature = "streamable-http"))]
     #[error("HTTP error: {0}")]
@@ -133,3 +137,14 @@ pub enum TransportError {
     #[error("Shutdown timed out")]
     ShutdownTimeout,
 }
+
+#[cfg(any(feature = "sse", feature = "streamable-http"))]
+impl From<ReqwestError> for TransportError {
+    fn from(error: ReqwestError) -> Self {
+        if error.is_connect() && error.is_timeout() {
+            TransportError::ConnectTimeout(error)
+        } else {
+            TransportError::HttpConnection(error)
+        }
+    }
+}
@@ -1,7 +1,9 @@
+mod batched_event_store;
 mod in_memory_event_store;
 
 use crate::{EventId, SessionId, StreamId};
 use async_trait::async_trait;
+pub use batched_event_store::*;
 pub use in_memory_event_store::*;
 use thiserror::Error;
 
@@ -12,6 +14,15 @@ pub struct EventStoreEntry {
     pub messages: Vec<String>,
 }
 
+/// An event to be stored with [`EventStore::store_events`].
+#[derive(Debug, Clone)]
+pub struct NewEvent {
+    pub session_id: SessionId,
+    pub stream_id: StreamId,
+    pub timestamp: u128,
+    pub message: String,
+}
+
 #[derive(Debug, Error)]
 #[error("{message}")]
 pub struct EventStoreError {
@@ -32,6 +43,18 @@ impl From<String> for EventStoreError {
     }
 }
 
+/// What a resumable stream does when the event store fails to store one of its messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventStoreErrorPolicy {
+    /// Log the error and keep sending messages on the stream without event ids,
+    /// resumability is disabled for the rest of that stream.
+    #[default]
+    ContinueWithoutResumability,
+    /// Fail sending the message, so the error is reported to the caller.
+    FailSend,
+}
+
 type EventStoreResult<T> = Result<T, EventStoreError>;
 
 /// Trait defining the interface for event storage and retrieval, used by the MCP server
@@ -59,6 +82,29 @@ pub trait EventStore: Send + Sync {
         message: String,
     ) -> EventStoreResult<EventId>;
 
+    /// Stores multiple events at once and returns their generated event IDs, in the same order.
+    /// Used by [`BatchedEventStore`] to write accumulated events together.
+    /// Default implementation calls `store_event` for each event; stores should override it
+    /// to write the whole batch under a single lock or round-trip.
+    ///
+    /// # Parameters
+    /// - `events`: The events to store, in the order they were produced.
+    async fn store_events(&self, events: Vec<NewEvent>) -> EventStoreResult<Vec<EventId>> {
+        let mut event_ids = Vec::with_capacity(events.len());
+        for event in events {
+            event_ids.push(
+                self.store_event(
+                    event.session_id,
+                    event.stream_id,
+                    event.timestamp,
+                    event.message,
+                )
+                .await?,
+            );
+        }
+        Ok(event_ids)
+    }
+
     /// Removes all events associated with a given session ID.
     /// Used to clean up all events for a session when it is no longer needed (e.g., session ended).
     ///
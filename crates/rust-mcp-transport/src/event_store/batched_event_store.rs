@@ -0,0 +1,229 @@
+use crate::event_store::{EventStore, EventStoreEntry, EventStoreResult, NewEvent};
+use crate::{EventId, SessionId, StreamId};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+const DEFAULT_MAX_BATCH_SIZE: usize = 64;
+const DEFAULT_MAX_DELAY: Duration = Duration::from_millis(2);
+const COMMAND_CHANNEL_CAPACITY: usize = 1024;
+
+/// Settings of a [`BatchedEventStore`].
+#[derive(Debug, Clone)]
+pub struct BatchedEventStoreOptions {
+    /// Maximum number of events written to the inner store at once (default: 64).
+    pub max_batch_size: usize,
+    /// How long the first event of a batch waits for more events before the batch is written (default: 2ms).
+    pub max_delay: Duration,
+}
+
+impl Default for BatchedEventStoreOptions {
+    fn default() -> Self {
+        Self {
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+}
+
+enum Command {
+    Store {
+        event: NewEvent,
+        resp_tx: oneshot::Sender<EventStoreResult<EventId>>,
+    },
+    Flush {
+        resp_tx: oneshot::Sender<()>,
+    },
+}
+
+/// An [`EventStore`] wrapper that writes events to the inner store in batches.
+///
+/// Events stored within `max_delay` of each other, e.g. by concurrent streams under high notification
+/// rates, are accumulated and written with a single [`EventStore::store_events`] call, which lets the inner
+/// store take its lock (or make its round-trip) once per batch instead of once per event.
+///
+/// `store_event` still resolves only once the event is written, so an event id is never sent to a client
+/// before the event can be replayed. Pending events are flushed before any other operation is delegated,
+/// so [`EventStore::events_after`] always sees every event stored before it was called.
+///
+/// Must be created within a Tokio runtime, as batches are written by a background task.
+///
+/// # Example
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use rust_mcp_transport::event_store::{BatchedEventStore, InMemoryEventStore};
+/// use std::sync::Arc;
+///
+/// let event_store = BatchedEventStore::new(
+///     Arc::new(InMemoryEventStore::default()),
+///     Default::default(),
+/// );
+/// # }
+/// ```
+pub struct BatchedEventStore {
+    inner: Arc<dyn EventStore>,
+    command_tx: mpsc::Sender<Command>,
+}
+
+impl BatchedEventStore {
+    pub fn new(inner: Arc<dyn EventStore>, options: BatchedEventStoreOptions) -> Self {
+        let (command_tx, command_rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+        tokio::spawn(write_batches(inner.clone(), command_rx, options));
+        Self { inner, command_tx }
+    }
+
+    /// Waits until all the events stored so far are written to the inner store.
+    pub async fn flush(&self) {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .command_tx
+            .send(Command::Flush { resp_tx })
+            .await
+            .is_ok()
+        {
+            let _ = resp_rx.await;
+        }
+    }
+}
+
+async fn write_batches(
+    inner: Arc<dyn EventStore>,
+    mut command_rx: mpsc::Receiver<Command>,
+    options: BatchedEventStoreOptions,
+) {
+    let max_batch_size = options.max_batch_size.max(1);
+
+    while let Some(command) = command_rx.recv().await {
+        let mut batch = vec![];
+        let mut flushed = vec![];
+        match command {
+            Command::Store { event, resp_tx } => batch.push((event, resp_tx)),
+            Command::Flush { resp_tx } => {
+                let _ = resp_tx.send(());
+                continue;
+            }
+        }
+
+        let deadline = tokio::time::sleep(options.max_delay);
+        tokio::pin!(deadline);
+        while batch.len() < max_batch_size {
+            tokio::select! {
+                command = command_rx.recv() => match command {
+                    Some(Command::Store { event, resp_tx }) => batch.push((event, resp_tx)),
+                    Some(Command::Flush { resp_tx }) => {
+                        flushed.push(resp_tx);
+                        break;
+                    }
+                    None => break,
+                },
+                _ = &mut deadline => break,
+            }
+        }
+
+        let (events, senders): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+        match inner.store_events(events).await {
+            Ok(event_ids) => {
+                for (resp_tx, event_id) in senders.into_iter().zip(event_ids) {
+                    let _ = resp_tx.send(Ok(event_id));
+                }
+            }
+            Err(error) => {
+                for resp_tx in senders {
+                    let _ = resp_tx.send(Err(error.message.clone().into()));
+                }
+            }
+        }
+        for resp_tx in flushed {
+            let _ = resp_tx.send(());
+        }
+    }
+}
+
+#[async_trait]
+impl EventStore for BatchedEventStore {
+    async fn store_event(
+        &self,
+        session_id: SessionId,
+        stream_id: StreamId,
+        timestamp: u128,
+        message: String,
+    ) -> EventStoreResult<EventId> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        let event = NewEvent {
+            session_id,
+            stream_id,
+            timestamp,
+            message,
+        };
+        self.command_tx
+            .send(Command::Store { event, resp_tx })
+            .await
+            .map_err(|_| "Event store batching task has stopped.")?;
+        resp_rx
+            .await
+            .map_err(|_| "Event store batching task has stopped.")?
+    }
+
+    async fn store_events(&self, events: Vec<NewEvent>) -> EventStoreResult<Vec<EventId>> {
+        let mut receivers = Vec::with_capacity(events.len());
+        for event in events {
+            let (resp_tx, resp_rx) = oneshot::channel();
+            self.command_tx
+                .send(Command::Store { event, resp_tx })
+                .await
+                .map_err(|_| "Event store batching task has stopped.")?;
+            receivers.push(resp_rx);
+        }
+
+        let mut event_ids = Vec::with_capacity(receivers.len());
+        for resp_rx in receivers {
+            event_ids.push(
+                resp_rx
+                    .await
+                    .map_err(|_| "Event store batching task has stopped.")??,
+            );
+        }
+        Ok(event_ids)
+    }
+
+    async fn remove_by_session_id(&self, session_id: SessionId) -> EventStoreResult<()> {
+        self.flush().await;
+        self.inner.remove_by_session_id(session_id).await
+    }
+
+    async fn remove_stream_in_session(
+        &self,
+        session_id: SessionId,
+        stream_id: StreamId,
+    ) -> EventStoreResult<()> {
+        self.flush().await;
+        self.inner
+            .remove_stream_in_session(session_id, stream_id)
+            .await
+    }
+
+    async fn clear(&self) -> EventStoreResult<()> {
+        self.flush().await;
+        self.inner.clear().await
+    }
+
+    async fn events_after(
+        &self,
+        last_event_id: EventId,
+    ) -> EventStoreResult<Option<EventStoreEntry>> {
+        self.flush().await;
+        self.inner.events_after(last_event_id).await
+    }
+
+    async fn prune_excess_events(&self, session_id: Option<SessionId>) -> EventStoreResult<()> {
+        self.flush().await;
+        self.inner.prune_excess_events(session_id).await
+    }
+
+    async fn count(&self) -> EventStoreResult<usize> {
+        self.flush().await;
+        self.inner.count().await
+    }
+}
@@ -1,6 +1,6 @@
 use crate::event_store::EventStoreResult;
 use crate::{
-    event_store::{EventStore, EventStoreEntry},
+    event_store::{EventStore, EventStoreEntry, NewEvent},
     EventId, SessionId, StreamId,
 };
 use async_trait::async_trait;
@@ -79,6 +79,35 @@ impl InMemoryEventStore {
         format!("{session_id}{ID_SEPARATOR}{stream_id}{ID_SEPARATOR}{time_stamp}")
     }
 
+    /// Appends an event to its session's queue, removing the oldest event if the session
+    /// reaches `max_events_per_session`.
+    fn push_event(
+        &self,
+        storage_map: &mut HashMap<SessionId, VecDeque<EventEntry>>,
+        session_id: SessionId,
+        stream_id: StreamId,
+        time_stamp: u128,
+        message: String,
+    ) {
+        tracing::trace!(
+            "Storing event for session: {session_id}, stream_id: {stream_id}, message: '{message}', {time_stamp} ",
+        );
+
+        let session_map = storage_map
+            .entry(session_id)
+            .or_insert_with(|| VecDeque::with_capacity(self.max_events_per_session));
+
+        if session_map.len() == self.max_events_per_session {
+            session_map.pop_front(); // remove the oldest if full
+        }
+
+        session_map.push_back(EventEntry {
+            stream_id,
+            time_stamp,
+            message,
+        });
+    }
+
     /// Parses an event ID into its session, stream, and timestamp components.
     ///
     /// The event ID must follow the format `session-.-stream-.-timestamp`.
@@ -159,28 +188,32 @@ impl EventStore for InMemoryEventStore {
         let event_id = self.generate_event_id(&session_id, &stream_id, time_stamp);
 
         let mut storage_map = self.storage_map.write().await;
+        self.push_event(&mut storage_map, session_id, stream_id, time_stamp, message);
 
-        tracing::trace!(
-            "Storing event for session: {session_id}, stream_id: {stream_id}, message: '{message}', {time_stamp} ",
-        );
-
-        let session_map = storage_map
-            .entry(session_id)
-            .or_insert_with(|| VecDeque::with_capacity(self.max_events_per_session));
-
-        if session_map.len() == self.max_events_per_session {
-            session_map.pop_front(); // remove the oldest if full
-        }
-
-        let entry = EventEntry {
-            stream_id,
-            time_stamp,
-            message,
-        };
+        Ok(event_id)
+    }
 
-        session_map.push_back(entry);
+    /// Stores a batch of events while holding the write lock once, returning their `event_id`s in order.
+    async fn store_events(&self, events: Vec<NewEvent>) -> EventStoreResult<Vec<EventId>> {
+        let mut storage_map = self.storage_map.write().await;
 
-        Ok(event_id)
+        let event_ids = events
+            .into_iter()
+            .map(|event| {
+                let event_id =
+                    self.generate_event_id(&event.session_id, &event.stream_id, event.timestamp);
+                self.push_event(
+                    &mut storage_map,
+                    event.session_id,
+                    event.stream_id,
+                    event.timestamp,
+                    event.message,
+                );
+                event_id
+            })
+            .collect();
+
+        Ok(event_ids)
     }
 
     /// Removes all events associated with a given stream ID within a specific session.
@@ -6,6 +6,8 @@
 mod client_sse;
 #[cfg(feature = "streamable-http")]
 mod client_streamable_http;
+#[cfg(feature = "websocket")]
+mod client_websocket;
 mod constants;
 pub mod error;
 pub mod event_store;
@@ -17,12 +19,18 @@ mod sse;
 #[cfg(feature = "stdio")]
 mod stdio;
 mod transport;
+#[cfg(all(feature = "sse", feature = "streamable-http"))]
+mod transport_detection;
+#[cfg(all(unix, feature = "unix-socket"))]
+mod unix_socket;
 mod utils;
 
 #[cfg(feature = "sse")]
 pub use client_sse::*;
 #[cfg(feature = "streamable-http")]
 pub use client_streamable_http::*;
+#[cfg(feature = "websocket")]
+pub use client_websocket::*;
 pub use constants::*;
 pub use message_dispatcher::*;
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
@@ -30,9 +38,13 @@ pub use sse::*;
 #[cfg(feature = "stdio")]
 pub use stdio::*;
 pub use transport::*;
+#[cfg(all(feature = "sse", feature = "streamable-http"))]
+pub use transport_detection::*;
+#[cfg(all(unix, feature = "unix-socket"))]
+pub use unix_socket::*;
 
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
-pub use utils::SseEvent;
+pub use utils::{SseEvent, SseParser};
 
 // Type alias for session identifier, represented as a String
 pub type SessionId = String;
@@ -3,7 +3,7 @@ use crate::schema::{RequestId, RpcError};
 use crate::utils::{await_timeout, current_timestamp};
 use crate::McpDispatch;
 use crate::{
-    event_store::EventStore,
+    event_store::{EventStore, EventStoreErrorPolicy},
     schema::{
         schema_utils::{
             self, ClientMessage, ClientMessages, McpMessage, RpcMessage, ServerMessage,
@@ -17,6 +17,7 @@ use async_trait::async_trait;
 use futures::future::join_all;
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::AsyncWriteExt;
@@ -46,6 +47,8 @@ pub struct MessageDispatcher<R> {
     session_id: Option<SessionId>,
     stream_id: Option<StreamId>,
     event_store: Option<Arc<dyn EventStore>>,
+    event_store_error_policy: EventStoreErrorPolicy,
+    resumability_lost: AtomicBool,
 }
 
 impl<R> MessageDispatcher<R> {
@@ -72,6 +75,8 @@ impl<R> MessageDispatcher<R> {
             session_id: None,
             stream_id: None,
             event_store: None,
+            event_store_error_policy: EventStoreErrorPolicy::default(),
+            resumability_lost: AtomicBool::new(false),
         }
     }
 
@@ -91,6 +96,8 @@ impl<R> MessageDispatcher<R> {
             session_id: None,
             stream_id: None,
             event_store: None,
+            event_store_error_policy: EventStoreErrorPolicy::default(),
+            resumability_lost: AtomicBool::new(false),
         }
     }
 
@@ -101,10 +108,28 @@ impl<R> MessageDispatcher<R> {
         session_id: SessionId,
         stream_id: StreamId,
         event_store: Arc<dyn EventStore>,
+    ) {
+        self.make_resumable_with_policy(
+            session_id,
+            stream_id,
+            event_store,
+            EventStoreErrorPolicy::default(),
+        );
+    }
+
+    /// Same as [`make_resumable`](Self::make_resumable), where `event_store_error_policy` decides
+    /// what happens to a message the event store fails to store.
+    pub fn make_resumable_with_policy(
+        &mut self,
+        session_id: SessionId,
+        stream_id: StreamId,
+        event_store: Arc<dyn EventStore>,
+        event_store_error_policy: EventStoreErrorPolicy,
     ) {
         self.session_id = Some(session_id);
         self.stream_id = Some(stream_id);
         self.event_store = Some(event_store);
+        self.event_store_error_policy = event_store_error_policy;
     }
 
     async fn store_pending_request(
@@ -118,6 +143,25 @@ impl<R> MessageDispatcher<R> {
         rx_response
     }
 
+    /// Waits for the response to a pending request.
+    /// A request that times out or loses its channel is removed from the pending requests,
+    /// a late response is then ignored instead of being held in the map forever.
+    async fn await_response(
+        &self,
+        request_id: Option<&RequestId>,
+        rx_response: oneshot::Receiver<R>,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<R> {
+        let result =
+            await_timeout(rx_response, request_timeout.unwrap_or(self.request_timeout)).await;
+        if result.is_err() {
+            if let Some(request_id) = request_id {
+                self.pending_requests.lock().await.remove(request_id);
+            }
+        }
+        result
+    }
+
     async fn store_pending_request_for_message<M: McpMessage + RpcMessage>(
         &self,
         message: &M,
@@ -173,7 +217,10 @@ impl McpDispatch<ServerMessages, ClientMessages, ServerMessage, ClientMessage>
 
                 if let Some(rx) = rx_response {
                     // Wait for the response with timeout
-                    match await_timeout(rx, request_timeout.unwrap_or(self.request_timeout)).await {
+                    match self
+                        .await_response(message.request_id(), rx, request_timeout)
+                        .await
+                    {
                         Ok(response) => Ok(Some(ServerMessages::Single(response))),
                         Err(error) => match error {
                             TransportError::ChannelClosed(_) => {
@@ -212,9 +259,13 @@ impl McpDispatch<ServerMessages, ClientMessages, ServerMessage, ClientMessage>
                     return Ok(None);
                 }
 
-                let timeout_wrapped_futures = tasks.into_iter().filter_map(|rx| {
-                    rx.map(|rx| await_timeout(rx, request_timeout.unwrap_or(self.request_timeout)))
-                });
+                let timeout_wrapped_futures =
+                    tasks
+                        .into_iter()
+                        .zip(request_ids.iter())
+                        .filter_map(|(rx, request_id)| {
+                            rx.map(|rx| self.await_response(*request_id, rx, request_timeout))
+                        });
 
                 let results: Vec<_> = join_all(timeout_wrapped_futures)
                     .await
@@ -320,7 +371,10 @@ impl McpDispatch<ClientMessages, ServerMessages, ClientMessage, ServerMessage>
                 self.write_str(message_payload.as_str(), false).await?;
 
                 if let Some(rx) = rx_response {
-                    match await_timeout(rx, request_timeout.unwrap_or(self.request_timeout)).await {
+                    match self
+                        .await_response(message.request_id(), rx, request_timeout)
+                        .await
+                    {
                         Ok(response) => Ok(Some(ClientMessages::Single(response))),
                         Err(error) => Err(error),
                     }
@@ -354,9 +408,13 @@ impl McpDispatch<ClientMessages, ServerMessages, ClientMessage, ServerMessage>
 
                 let tasks = join_all(pending_tasks).await;
 
-                let timeout_wrapped_futures = tasks.into_iter().filter_map(|rx| {
-                    rx.map(|rx| await_timeout(rx, request_timeout.unwrap_or(self.request_timeout)))
-                });
+                let timeout_wrapped_futures =
+                    tasks
+                        .into_iter()
+                        .zip(request_ids.iter())
+                        .filter_map(|(rx, request_id)| {
+                            rx.map(|rx| self.await_response(*request_id, rx, request_timeout))
+                        });
 
                 let results: Vec<_> = join_all(timeout_wrapped_futures)
                     .await
@@ -406,13 +464,16 @@ impl McpDispatch<ClientMessages, ServerMessages, ClientMessage, ServerMessage>
     async fn write_str(&self, payload: &str, skip_store: bool) -> TransportResult<()> {
         let mut event_id = None;
 
-        if !skip_store && !payload.trim().is_empty() {
+        if !skip_store
+            && !payload.trim().is_empty()
+            && !self.resumability_lost.load(Ordering::Relaxed)
+        {
             if let (Some(session_id), Some(stream_id), Some(event_store)) = (
                 self.session_id.as_ref(),
                 self.stream_id.as_ref(),
                 self.event_store.as_ref(),
             ) {
-                event_id = event_store
+                match event_store
                     .store_event(
                         session_id.clone(),
                         stream_id.clone(),
@@ -420,11 +481,26 @@ impl McpDispatch<ClientMessages, ServerMessages, ClientMessage, ServerMessage>
                         payload.to_owned(),
                     )
                     .await
-                    .map(Some)
-                    .unwrap_or_else(|err| {
-                        tracing::error!("{err}");
-                        None
-                    });
+                {
+                    Ok(id) => event_id = Some(id),
+                    Err(err) => match self.event_store_error_policy {
+                        EventStoreErrorPolicy::FailSend => {
+                            tracing::error!(
+                                "Failed to store event for session '{session_id}', stream '{stream_id}': {err}"
+                            );
+                            return Err(TransportError::SendFailure(format!(
+                                "failed to store event: {err}"
+                            )));
+                        }
+                        EventStoreErrorPolicy::ContinueWithoutResumability => {
+                            self.resumability_lost.store(true, Ordering::Relaxed);
+                            tracing::error!(
+                                "Failed to store event for session '{session_id}', stream '{stream_id}': {err}. \
+                                Resumability is disabled for this stream, clients won't be able to resume it."
+                            );
+                        }
+                    },
+                }
             };
         }
 
@@ -1,4 +1,4 @@
-use crate::event_store::EventStore;
+use crate::event_store::{EventStore, EventStoreErrorPolicy};
 use crate::schema::schema_utils::{
     ClientMessage, ClientMessages, MessageFromServer, SdkError, ServerMessage, ServerMessages,
 };
@@ -38,6 +38,7 @@ where
     session_id: Option<SessionId>,
     stream_id: Option<StreamId>,
     event_store: Option<Arc<dyn EventStore>>,
+    event_store_error_policy: EventStoreErrorPolicy,
 }
 
 /// Server-Sent Events (SSE) transport implementation
@@ -75,6 +76,7 @@ where
             session_id: None,
             stream_id: None,
             event_store: None,
+            event_store_error_policy: EventStoreErrorPolicy::default(),
         })
     }
 
@@ -102,10 +104,28 @@ where
         session_id: SessionId,
         stream_id: StreamId,
         event_store: Arc<dyn EventStore>,
+    ) {
+        self.make_resumable_with_policy(
+            session_id,
+            stream_id,
+            event_store,
+            EventStoreErrorPolicy::default(),
+        );
+    }
+
+    /// Same as [`make_resumable`](Self::make_resumable), where `event_store_error_policy` decides
+    /// what happens to a message the event store fails to store.
+    pub fn make_resumable_with_policy(
+        &mut self,
+        session_id: SessionId,
+        stream_id: StreamId,
+        event_store: Arc<dyn EventStore>,
+        event_store_error_policy: EventStoreErrorPolicy,
     ) {
         self.session_id = Some(session_id);
         self.stream_id = Some(stream_id);
         self.event_store = Some(event_store);
+        self.event_store_error_policy = event_store_error_policy;
     }
 }
 
@@ -198,10 +218,11 @@ impl Transport<ClientMessages, MessageFromServer, ClientMessage, ServerMessages,
             self.stream_id.as_ref(),
             self.event_store.as_ref(),
         ) {
-            sender.make_resumable(
+            sender.make_resumable_with_policy(
                 session_id.to_owned(),
                 stream_id.to_owned(),
                 event_store.clone(),
+                self.event_store_error_policy,
             );
         }
 
@@ -16,7 +16,7 @@ use tokio::task::JoinHandle;
 use crate::error::{TransportError, TransportResult};
 use crate::mcp_stream::MCPStream;
 use crate::message_dispatcher::MessageDispatcher;
-use crate::transport::Transport;
+use crate::transport::{Transport, TransportKind};
 use crate::utils::CancellationTokenSource;
 use crate::{IoStream, McpDispatch, TransportDispatcher, TransportOptions};
 
@@ -260,6 +260,10 @@ where
         *result
     }
 
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::Stdio
+    }
+
     fn message_sender(&self) -> Arc<tokio::sync::RwLock<Option<MessageDispatcher<M>>>> {
         self.message_sender.clone() as _
     }
@@ -29,6 +29,23 @@ pub enum IoStream {
 /// large tool results or responses.
 pub const DEFAULT_MAX_LINE_LENGTH: usize = 16 * 1024 * 1024;
 
+/// The kind of transport an MCP session runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportKind {
+    /// Newline-delimited JSON-RPC over the standard input and output of a process.
+    Stdio,
+    /// The deprecated HTTP+SSE transport (protocol version 2024-11-05).
+    Sse,
+    /// The Streamable HTTP transport (protocol version 2025-03-26 and later).
+    StreamableHttp,
+    /// JSON-RPC messages exchanged as WebSocket text frames.
+    WebSocket,
+    /// Newline-delimited JSON-RPC over a Unix domain socket connection.
+    UnixSocket,
+    /// Any other transport, e.g. channels connecting a client and a server within the same process.
+    InProcess,
+}
+
 /// Configuration for the transport layer
 #[derive(Debug, Clone)]
 pub struct TransportOptions {
@@ -146,6 +163,10 @@ where
     async fn session_id(&self) -> Option<SessionId> {
         None
     }
+    /// Returns the kind of this transport, [`TransportKind::InProcess`] unless overridden.
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::InProcess
+    }
 }
 
 /// A composite trait that combines both transport and dispatch capabilities for the MCP protocol.
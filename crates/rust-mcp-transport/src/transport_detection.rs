@@ -0,0 +1,117 @@
+use crate::error::{TransportError, TransportResult};
+use crate::schema::{
+    schema_utils::{ClientJsonrpcRequest, RequestFromClient},
+    InitializeRequestParams, RequestId,
+};
+use crate::utils::{http_delete, http_get, http_post};
+use crate::{ClientSseTransportOptions, RequestOptions, MCP_SESSION_ID_HEADER};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+
+/// Transport protocol spoken by an MCP server, as detected by [`detect_transport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectedTransport {
+    /// The server implements the Streamable HTTP transport (protocol version 2025-03-26 and later).
+    StreamableHttp,
+    /// The server implements the deprecated HTTP+SSE transport (protocol version 2024-11-05).
+    Sse,
+}
+
+/// Probes `server_url` to find out which HTTP transport the server implements.
+///
+/// Follows the backwards compatibility procedure of the MCP specification:
+/// an `initialize` request built from `client_details` is POSTed to the URL, a successful response
+/// means the server speaks Streamable HTTP. The session opened by the probe is terminated right away.
+/// If the POST fails with a 4xx status code (other than `401` and `403`), a GET request is sent to the
+/// same URL, and a `text/event-stream` response means the server speaks the deprecated HTTP+SSE transport.
+///
+/// Any other outcome is returned as an error, without falling back.
+pub async fn detect_transport(
+    server_url: &str,
+    client_details: &InitializeRequestParams,
+    request_options: &RequestOptions,
+) -> TransportResult<DetectedTransport> {
+    let client = request_options.http_client()?;
+    let headers = request_options.request_headers()?;
+
+    let initialize_request = ClientJsonrpcRequest::new(
+        RequestId::Integer(0),
+        RequestFromClient::InitializeRequest(client_details.clone()),
+    );
+    let payload = serde_json::to_string(&initialize_request)
+        .map_err(|err| TransportError::Internal(err.to_string()))?;
+
+    match http_post(&client, server_url, payload, None, headers.as_ref()).await {
+        Ok(response) => {
+            let session_id = response
+                .headers()
+                .get(MCP_SESSION_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+            drop(response);
+            if let Some(session_id) = session_id {
+                if let Err(error) =
+                    http_delete(&client, server_url, Some(&session_id), headers.as_ref()).await
+                {
+                    tracing::debug!("Failed to terminate the transport detection session: {error}");
+                }
+            }
+            Ok(DetectedTransport::StreamableHttp)
+        }
+        Err(TransportError::Http(status)) if falls_back_to_sse(status) => {
+            tracing::debug!("POST to {server_url} failed with {status}, trying the SSE transport");
+            let response = http_get(&client, server_url, None, headers.as_ref()).await?;
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("<empty>")
+                .to_ascii_lowercase();
+            if content_type.starts_with("text/event-stream") {
+                Ok(DetectedTransport::Sse)
+            } else {
+                Err(TransportError::UnexpectedContentType(content_type))
+            }
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn falls_back_to_sse(status: StatusCode) -> bool {
+    status.is_client_error()
+        && status != StatusCode::UNAUTHORIZED
+        && status != StatusCode::FORBIDDEN
+}
+
+/// Carries the settings shared by both transports over to the SSE transport,
+/// for servers detected as [`DetectedTransport::Sse`].
+impl From<RequestOptions> for ClientSseTransportOptions {
+    fn from(options: RequestOptions) -> Self {
+        Self {
+            request_timeout: options.request_timeout,
+            max_line_length: options.max_line_length,
+            channel_capacity: options.channel_capacity,
+            retry_delay: options.retry_delay,
+            max_retries: options.max_retries,
+            reconnect: None,
+            custom_headers: options.custom_headers,
+            proxy: options.proxy,
+            origin: options.origin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_on_client_errors_except_auth_failures() {
+        assert!(falls_back_to_sse(StatusCode::NOT_FOUND));
+        assert!(falls_back_to_sse(StatusCode::METHOD_NOT_ALLOWED));
+        assert!(falls_back_to_sse(StatusCode::BAD_REQUEST));
+        assert!(!falls_back_to_sse(StatusCode::UNAUTHORIZED));
+        assert!(!falls_back_to_sse(StatusCode::FORBIDDEN));
+        assert!(!falls_back_to_sse(StatusCode::INTERNAL_SERVER_ERROR));
+    }
+}
@@ -0,0 +1,472 @@
+use crate::schema::schema_utils::{
+    ClientMessage, ClientMessages, MessageFromClient, MessageFromServer, SdkError, ServerMessage,
+    ServerMessages,
+};
+use crate::schema::RequestId;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::net::UnixStream;
+use tokio::sync::oneshot::Sender;
+use tokio::sync::{oneshot, watch, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::error::{TransportError, TransportResult};
+use crate::mcp_stream::MCPStream;
+use crate::message_dispatcher::MessageDispatcher;
+use crate::transport::{Transport, TransportKind};
+use crate::utils::CancellationTokenSource;
+use crate::{IoStream, McpDispatch, TransportDispatcher, TransportOptions};
+
+/// Implements a Unix domain socket transport for MCP communication between local processes.
+///
+/// Messages are exchanged as newline-delimited JSON-RPC, exactly like [`StdioTransport`](crate::StdioTransport),
+/// but a server can accept any number of clients on the same socket, each connection being its own session.
+///
+/// A client creates the transport with [`UnixSocketTransport::new`] and connects to the socket path
+/// when the transport starts. A server wraps each accepted connection with [`UnixSocketTransport::from_stream`].
+pub struct UnixSocketTransport<R>
+where
+    R: Clone + Send + Sync + DeserializeOwned + 'static,
+{
+    /// Path of the socket to connect to, for client transports
+    socket_path: Option<PathBuf>,
+    /// The accepted connection, for server transports, taken when the transport starts
+    stream: Mutex<Option<UnixStream>>,
+    options: TransportOptions,
+    /// Set to `true` once the peer closes the connection
+    closed: Arc<watch::Sender<bool>>,
+    shutdown_source: tokio::sync::RwLock<Option<CancellationTokenSource>>,
+    is_shut_down: Mutex<bool>,
+    message_sender: Arc<tokio::sync::RwLock<Option<MessageDispatcher<R>>>>,
+    error_stream: tokio::sync::RwLock<Option<IoStream>>,
+    pending_requests: Arc<Mutex<HashMap<RequestId, tokio::sync::oneshot::Sender<R>>>>,
+}
+
+impl<R> UnixSocketTransport<R>
+where
+    R: Clone + Send + Sync + DeserializeOwned + 'static,
+{
+    /// Creates a new `UnixSocketTransport` instance for MCP Client.
+    ///
+    /// # Arguments
+    /// * `socket_path` - Path of the socket the MCP server listens on, connected to on `start`.
+    /// * `options` - Configuration options for the transport, including timeout settings.
+    pub fn new(socket_path: impl AsRef<Path>, options: TransportOptions) -> TransportResult<Self> {
+        Ok(Self::create(
+            Some(socket_path.as_ref().to_path_buf()),
+            None,
+            options,
+        ))
+    }
+
+    /// Creates a new `UnixSocketTransport` instance for MCP Server, from a connection accepted on its socket.
+    ///
+    /// # Arguments
+    /// * `stream` - The connection accepted from the client.
+    /// * `options` - Configuration options for the transport, including timeout settings.
+    pub fn from_stream(stream: UnixStream, options: TransportOptions) -> TransportResult<Self> {
+        Ok(Self::create(None, Some(stream), options))
+    }
+
+    fn create(
+        socket_path: Option<PathBuf>,
+        stream: Option<UnixStream>,
+        options: TransportOptions,
+    ) -> Self {
+        let (closed, _) = watch::channel(false);
+        Self {
+            socket_path,
+            stream: Mutex::new(stream),
+            options,
+            closed: Arc::new(closed),
+            shutdown_source: tokio::sync::RwLock::new(None),
+            is_shut_down: Mutex::new(false),
+            message_sender: Arc::new(tokio::sync::RwLock::new(None)),
+            error_stream: tokio::sync::RwLock::new(None),
+            pending_requests: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the connection to exchange messages on, connecting to the socket path for client transports.
+    async fn connection(&self) -> TransportResult<UnixStream> {
+        if let Some(stream) = self.stream.lock().await.take() {
+            return Ok(stream);
+        }
+        match self.socket_path.as_ref() {
+            Some(socket_path) => UnixStream::connect(socket_path).await.map_err(|err| {
+                TransportError::Io(std::io::Error::new(
+                    err.kind(),
+                    format!("Failed to connect to {}: {err}", socket_path.display()),
+                ))
+            }),
+            None => Err(TransportError::Internal(
+                "UnixSocketTransport has already been started.".to_string(),
+            )),
+        }
+    }
+
+    pub(crate) async fn set_message_sender(&self, sender: MessageDispatcher<R>) {
+        let mut lock = self.message_sender.write().await;
+        *lock = Some(sender);
+    }
+
+    pub(crate) async fn set_error_stream(&self, error_stream: IoStream) {
+        let mut lock = self.error_stream.write().await;
+        *lock = Some(error_stream);
+    }
+}
+
+/// Wraps the read half of the connection, signaling when the peer closes it.
+struct ClosingReader<T> {
+    inner: T,
+    closed: Arc<watch::Sender<bool>>,
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for ClosingReader<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(read) = &result {
+            // a read filling nothing into a non-empty buffer means the peer closed the connection
+            if read.is_err() || (buf.filled().len() == filled && buf.remaining() > 0) {
+                self.closed.send_replace(true);
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<R, S, M, OR, OM> Transport<R, S, M, OR, OM> for UnixSocketTransport<M>
+where
+    R: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+    S: Clone + Send + Sync + serde::Serialize + 'static,
+    M: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+    OR: Clone + Send + Sync + serde::Serialize + 'static,
+    OM: Clone + Send + Sync + serde::de::DeserializeOwned + 'static,
+{
+    /// Starts the transport, initializing streams and the message dispatcher.
+    ///
+    /// Client transports connect to the socket path first, server transports use the accepted connection.
+    ///
+    /// # Errors
+    /// Returns a `TransportError` if the connection to the socket fails, or if the transport was already started.
+    async fn start(&self) -> TransportResult<tokio_stream::wrappers::ReceiverStream<R>>
+    where
+        MessageDispatcher<M>: McpDispatch<R, OR, M, OM>,
+    {
+        let connection = self.connection().await?;
+        let (read_half, write_half) = connection.into_split();
+
+        // Create CancellationTokenSource and token
+        let (cancellation_source, cancellation_token) = CancellationTokenSource::new();
+        let mut lock = self.shutdown_source.write().await;
+        *lock = Some(cancellation_source);
+
+        let readable = ClosingReader {
+            inner: read_half,
+            closed: self.closed.clone(),
+        };
+
+        let (stream, sender, error_stream) = MCPStream::create(
+            Box::pin(readable),
+            Mutex::new(Box::pin(write_half)),
+            IoStream::Writable(Box::pin(tokio::io::stderr())),
+            self.pending_requests.clone(),
+            self.options.timeout,
+            self.options.max_line_length,
+            cancellation_token,
+            self.options.channel_capacity,
+        );
+
+        self.set_message_sender(sender).await;
+        self.set_error_stream(error_stream).await;
+        Ok(stream)
+    }
+
+    async fn pending_request_tx(&self, request_id: &RequestId) -> Option<Sender<M>> {
+        let mut pending_requests = self.pending_requests.lock().await;
+        pending_requests.remove(request_id)
+    }
+
+    /// Checks if the transport has been shut down.
+    async fn is_shut_down(&self) -> bool {
+        let result = self.is_shut_down.lock().await;
+        *result
+    }
+
+    fn transport_kind(&self) -> TransportKind {
+        TransportKind::UnixSocket
+    }
+
+    fn message_sender(&self) -> Arc<tokio::sync::RwLock<Option<MessageDispatcher<M>>>> {
+        self.message_sender.clone() as _
+    }
+
+    fn error_stream(&self) -> &tokio::sync::RwLock<Option<IoStream>> {
+        &self.error_stream as _
+    }
+
+    async fn consume_string_payload(&self, _payload: &str) -> TransportResult<()> {
+        Err(TransportError::Internal(
+            "Invalid invocation of consume_string_payload() function in UnixSocketTransport"
+                .to_string(),
+        ))
+    }
+
+    /// Signals `disconnect_tx` once the peer closes the connection.
+    ///
+    /// No ping is needed to detect a closed Unix socket, so `interval` is not used.
+    async fn keep_alive(
+        &self,
+        _interval: Duration,
+        disconnect_tx: oneshot::Sender<()>,
+    ) -> TransportResult<JoinHandle<()>> {
+        let mut closed = self.closed.subscribe();
+        let handle = tokio::spawn(async move {
+            if closed.wait_for(|closed| *closed).await.is_ok() {
+                let _ = disconnect_tx.send(());
+            }
+        });
+        Ok(handle)
+    }
+
+    /// Shuts down the transport, closing the connection and signaling closure.
+    ///
+    /// # Errors
+    /// Returns a `TransportError` if the shutdown signal fails.
+    async fn shut_down(&self) -> TransportResult<()> {
+        // Trigger cancellation
+        let mut cancellation_lock = self.shutdown_source.write().await;
+        if let Some(source) = cancellation_lock.as_ref() {
+            source.cancel()?;
+        }
+        *cancellation_lock = None; // Clear cancellation_source
+
+        // Dropping the dispatcher drops the write half, so the peer sees the connection closed
+        self.message_sender.write().await.take();
+
+        // Mark as shut down
+        let mut is_shut_down_lock = self.is_shut_down.lock().await;
+        *is_shut_down_lock = true;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl McpDispatch<ClientMessages, ServerMessages, ClientMessage, ServerMessage>
+    for UnixSocketTransport<ClientMessage>
+{
+    async fn send_message(
+        &self,
+        message: ServerMessages,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<Option<ClientMessages>> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.send_message(message, request_timeout).await
+    }
+
+    async fn send(
+        &self,
+        message: ServerMessage,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<Option<ClientMessage>> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.send(message, request_timeout).await
+    }
+
+    async fn send_batch(
+        &self,
+        message: Vec<ServerMessage>,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<Option<Vec<ClientMessage>>> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.send_batch(message, request_timeout).await
+    }
+
+    async fn write_str(&self, payload: &str, skip_store: bool) -> TransportResult<()> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.write_str(payload, skip_store).await
+    }
+}
+
+impl
+    TransportDispatcher<
+        ClientMessages,
+        MessageFromServer,
+        ClientMessage,
+        ServerMessages,
+        ServerMessage,
+    > for UnixSocketTransport<ClientMessage>
+{
+}
+
+#[async_trait]
+impl McpDispatch<ServerMessages, ClientMessages, ServerMessage, ClientMessage>
+    for UnixSocketTransport<ServerMessage>
+{
+    async fn send_message(
+        &self,
+        message: ClientMessages,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<Option<ServerMessages>> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.send_message(message, request_timeout).await
+    }
+
+    async fn send(
+        &self,
+        message: ClientMessage,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<Option<ServerMessage>> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.send(message, request_timeout).await
+    }
+
+    async fn send_batch(
+        &self,
+        message: Vec<ClientMessage>,
+        request_timeout: Option<Duration>,
+    ) -> TransportResult<Option<Vec<ServerMessage>>> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.send_batch(message, request_timeout).await
+    }
+
+    async fn write_str(&self, payload: &str, skip_store: bool) -> TransportResult<()> {
+        let sender = self.message_sender.read().await;
+        let sender = sender.as_ref().ok_or(SdkError::connection_closed())?;
+        sender.write_str(payload, skip_store).await
+    }
+}
+
+impl
+    TransportDispatcher<
+        ServerMessages,
+        MessageFromClient,
+        ServerMessage,
+        ClientMessages,
+        ClientMessage,
+    > for UnixSocketTransport<ServerMessage>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixListener;
+
+    #[tokio::test]
+    async fn messages_are_exchanged_as_lines() {
+        let socket_path =
+            std::env::temp_dir().join(format!("rust-mcp-transport-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read_half, mut write_half) = stream.into_split();
+            let mut lines = BufReader::new(read_half).lines();
+            let line = lines.next_line().await.unwrap().unwrap();
+            write_half
+                .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}\n")
+                .await
+                .unwrap();
+            // the client closes the connection when it shuts down
+            let closed = lines.next_line().await.unwrap();
+            (line, closed)
+        });
+
+        let transport =
+            UnixSocketTransport::<ServerMessage>::new(&socket_path, TransportOptions::default())
+                .unwrap();
+        let mut stream = Transport::<
+            ServerMessages,
+            MessageFromClient,
+            ServerMessage,
+            ClientMessages,
+            ClientMessage,
+        >::start(&transport)
+        .await
+        .unwrap();
+
+        transport
+            .write_str(
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+                true,
+            )
+            .await
+            .unwrap();
+
+        let message = stream.next().await.unwrap();
+        assert!(matches!(
+            message,
+            ServerMessages::Single(ServerMessage::Request(_))
+        ));
+
+        Transport::<ServerMessages, MessageFromClient, ServerMessage, ClientMessages, ClientMessage>::shut_down(&transport)
+            .await
+            .unwrap();
+
+        let (line, closed) = server.await.unwrap();
+        assert_eq!(
+            line,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#
+        );
+        assert_eq!(closed, None);
+        let _ = std::fs::remove_file(&socket_path);
+    }
+
+    #[tokio::test]
+    async fn keep_alive_reports_the_peer_closing_the_connection() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let transport =
+            UnixSocketTransport::<ClientMessage>::from_stream(server, TransportOptions::default())
+                .unwrap();
+        let _stream = Transport::<
+            ClientMessages,
+            MessageFromServer,
+            ClientMessage,
+            ServerMessages,
+            ServerMessage,
+        >::start(&transport)
+        .await
+        .unwrap();
+
+        let (disconnect_tx, disconnect_rx) = oneshot::channel();
+        let _keep_alive = Transport::<
+            ClientMessages,
+            MessageFromServer,
+            ClientMessage,
+            ServerMessages,
+            ServerMessage,
+        >::keep_alive(&transport, Duration::from_secs(60), disconnect_tx)
+        .await
+        .unwrap();
+
+        drop(client);
+        tokio::time::timeout(Duration::from_secs(5), disconnect_rx)
+            .await
+            .expect("the closed connection should be reported")
+            .unwrap();
+    }
+}
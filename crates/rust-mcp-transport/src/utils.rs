@@ -1,9 +1,12 @@
 mod cancellation_token;
 
+#[cfg(any(feature = "sse", feature = "websocket"))]
+mod client_transport;
+
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
 mod http_utils;
 
-#[cfg(any(feature = "sse", feature = "streamable-http"))]
+#[cfg(any(feature = "sse", feature = "streamable-http", feature = "websocket"))]
 mod readable_channel;
 
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
@@ -20,27 +23,33 @@ mod streamable_http_stream;
 
 mod time_utils;
 
-#[cfg(any(feature = "sse", feature = "streamable-http"))]
+#[cfg(feature = "websocket")]
+mod websocket_stream;
+
+#[cfg(any(feature = "sse", feature = "streamable-http", feature = "websocket"))]
 mod writable_channel;
 
 use crate::error::{TransportError, TransportResult};
 use crate::schema::schema_utils::SdkError;
 pub(crate) use cancellation_token::*;
 
+#[cfg(any(feature = "sse", feature = "websocket"))]
+pub(crate) use client_transport::*;
+
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
 use crate::SessionId;
 
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
 pub(crate) use http_utils::*;
 
-#[cfg(any(feature = "sse", feature = "streamable-http"))]
+#[cfg(any(feature = "sse", feature = "streamable-http", feature = "websocket"))]
 pub(crate) use readable_channel::*;
 
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
 pub use sse_event::*;
 
 #[cfg(any(feature = "sse", feature = "streamable-http"))]
-pub(crate) use sse_parser::*;
+pub use sse_parser::SseParser;
 
 #[cfg(feature = "sse")]
 pub(crate) use sse_stream::*;
@@ -50,7 +59,9 @@ pub(crate) use streamable_http_stream::*;
 
 pub use time_utils::*;
 use tokio::time::{timeout, Duration};
-#[cfg(any(feature = "sse", feature = "streamable-http"))]
+#[cfg(feature = "websocket")]
+pub(crate) use websocket_stream::*;
+#[cfg(any(feature = "sse", feature = "streamable-http", feature = "websocket"))]
 pub(crate) use writable_channel::*;
 
 pub async fn await_timeout<F, T, E>(operation: F, timeout_duration: Duration) -> TransportResult<T>
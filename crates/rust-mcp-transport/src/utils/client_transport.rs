@@ -0,0 +1,159 @@
+use super::CancellationTokenSource;
+use crate::error::{TransportError, TransportResult};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+
+/// Maximum time a client transport waits for its background tasks when shutting down
+const SHUTDOWN_TIMEOUT_SECONDS: u64 = 5;
+
+/// Validates and converts a HashMap of headers into a HeaderMap
+///
+/// # Arguments
+/// * `headers` - The HashMap of header names and values
+///
+/// # Returns
+/// * `TransportResult<HeaderMap>` - The validated HeaderMap or an error
+pub(crate) fn validate_headers(headers: &HashMap<String, String>) -> TransportResult<HeaderMap> {
+    let mut header_map = HeaderMap::new();
+
+    for (key, value) in headers {
+        let header_name = key
+            .parse::<HeaderName>()
+            .map_err(|e| TransportError::Configuration {
+                message: format!("Invalid header name: {e}"),
+            })?;
+        let header_value =
+            HeaderValue::from_str(value).map_err(|e| TransportError::Configuration {
+                message: format!("Invalid header value: {e}"),
+            })?;
+        header_map.insert(header_name, header_value);
+    }
+
+    Ok(header_map)
+}
+
+/// Shuts down a client transport: cancels it, marks it as shut down and waits for its
+/// background tasks to finish, in order.
+///
+/// # Errors
+/// Returns a `TransportError` if the shutdown signal fails or the tasks do not finish in time.
+pub(crate) async fn shut_down_client(
+    shutdown_source: &RwLock<Option<CancellationTokenSource>>,
+    is_shut_down: &Mutex<bool>,
+    tasks: Vec<Option<JoinHandle<()>>>,
+) -> TransportResult<()> {
+    // Trigger cancellation
+    let mut cancellation_lock = shutdown_source.write().await;
+    if let Some(source) = cancellation_lock.as_ref() {
+        source.cancel()?;
+    }
+    *cancellation_lock = None; // Clear cancellation_source
+
+    // Mark as shut down
+    let mut is_shut_down_lock = is_shut_down.lock().await;
+    *is_shut_down_lock = true;
+
+    // Wait for tasks to complete with a timeout
+    let timeout = Duration::from_secs(SHUTDOWN_TIMEOUT_SECONDS);
+    let shutdown_future = async {
+        for handle in tasks.into_iter().flatten() {
+            let _ = handle.await;
+        }
+        Ok::<(), TransportError>(())
+    };
+
+    tokio::select! {
+        result = shutdown_future => {
+            result // result of task completion
+        }
+        _ = tokio::time::sleep(timeout) => {
+            tracing::warn!("Shutdown timed out after {:?}", timeout);
+            Err(TransportError::ShutdownTimeout)
+        }
+    }
+}
+
+/// Implements `McpDispatch` and `TransportDispatcher` for a client transport, forwarding
+/// every message to the `MessageDispatcher` stored in its `message_sender` field.
+macro_rules! impl_client_dispatch {
+    ($transport:ident) => {
+        #[async_trait::async_trait]
+        impl
+            $crate::McpDispatch<
+                $crate::schema::schema_utils::ServerMessages,
+                $crate::schema::schema_utils::ClientMessages,
+                $crate::schema::schema_utils::ServerMessage,
+                $crate::schema::schema_utils::ClientMessage,
+            > for $transport<$crate::schema::schema_utils::ServerMessage>
+        {
+            async fn send_message(
+                &self,
+                message: $crate::schema::schema_utils::ClientMessages,
+                request_timeout: Option<std::time::Duration>,
+            ) -> $crate::error::TransportResult<
+                Option<$crate::schema::schema_utils::ServerMessages>,
+            > {
+                let sender = self.message_sender.read().await;
+                let sender = sender
+                    .as_ref()
+                    .ok_or($crate::schema::schema_utils::SdkError::connection_closed())?;
+                sender.send_message(message, request_timeout).await
+            }
+
+            async fn send(
+                &self,
+                message: $crate::schema::schema_utils::ClientMessage,
+                request_timeout: Option<std::time::Duration>,
+            ) -> $crate::error::TransportResult<Option<$crate::schema::schema_utils::ServerMessage>>
+            {
+                let sender = self.message_sender.read().await;
+                let sender = sender
+                    .as_ref()
+                    .ok_or($crate::schema::schema_utils::SdkError::connection_closed())?;
+                sender.send(message, request_timeout).await
+            }
+
+            async fn send_batch(
+                &self,
+                message: Vec<$crate::schema::schema_utils::ClientMessage>,
+                request_timeout: Option<std::time::Duration>,
+            ) -> $crate::error::TransportResult<
+                Option<Vec<$crate::schema::schema_utils::ServerMessage>>,
+            > {
+                let sender = self.message_sender.read().await;
+                let sender = sender
+                    .as_ref()
+                    .ok_or($crate::schema::schema_utils::SdkError::connection_closed())?;
+                sender.send_batch(message, request_timeout).await
+            }
+
+            async fn write_str(
+                &self,
+                payload: &str,
+                skip_store: bool,
+            ) -> $crate::error::TransportResult<()> {
+                let sender = self.message_sender.read().await;
+                let sender = sender
+                    .as_ref()
+                    .ok_or($crate::schema::schema_utils::SdkError::connection_closed())?;
+                sender.write_str(payload, skip_store).await
+            }
+        }
+
+        impl
+            $crate::TransportDispatcher<
+                $crate::schema::schema_utils::ServerMessages,
+                $crate::schema::schema_utils::MessageFromClient,
+                $crate::schema::schema_utils::ServerMessage,
+                $crate::schema::schema_utils::ClientMessages,
+                $crate::schema::schema_utils::ClientMessage,
+            > for $transport<$crate::schema::schema_utils::ServerMessage>
+        {
+        }
+    };
+}
+
+pub(crate) use impl_client_dispatch;
@@ -1,8 +1,8 @@
 use crate::error::{TransportError, TransportResult};
 use crate::{SessionId, MCP_SESSION_ID_HEADER};
 
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
-use reqwest::{Client, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE, ORIGIN};
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy, Response};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResponseType {
@@ -31,6 +31,38 @@ pub async fn validate_response_type(response: &Response) -> TransportResult<Resp
     }
 }
 
+/// Routes all requests of the client through `proxy`, if provided.
+///
+/// Hosts listed in the `NO_PROXY` environment variable bypass the explicit proxy as well.
+/// Without an explicit proxy reqwest already honors `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY`.
+pub fn with_proxy(builder: ClientBuilder, proxy: Option<&str>) -> TransportResult<ClientBuilder> {
+    let Some(proxy_url) = proxy else {
+        return Ok(builder);
+    };
+    let proxy = Proxy::all(proxy_url)
+        .map_err(|err| TransportError::Configuration {
+            message: format!("Invalid proxy url '{proxy_url}': {err}"),
+        })?
+        .no_proxy(NoProxy::from_env());
+    Ok(builder.proxy(proxy))
+}
+
+/// Adds the `Origin` header to `headers`, if an origin is provided.
+pub fn with_origin(
+    headers: Option<HeaderMap>,
+    origin: Option<&str>,
+) -> TransportResult<Option<HeaderMap>> {
+    let Some(origin) = origin else {
+        return Ok(headers);
+    };
+    let value = HeaderValue::from_str(origin).map_err(|err| TransportError::Configuration {
+        message: format!("Invalid origin '{origin}': {err}"),
+    })?;
+    let mut headers = headers.unwrap_or_default();
+    headers.insert(ORIGIN, value);
+    Ok(Some(headers))
+}
+
 /// Sends an HTTP POST request with the given body and headers
 ///
 /// # Arguments
@@ -1,4 +1,4 @@
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use tokio::io::{AsyncRead, ErrorKind};
@@ -58,6 +58,20 @@ impl AsyncRead for ReadableChannel {
     }
 }
 
+/// Frames a message received as a whole (the data of an SSE event or a WebSocket frame) as a
+/// single newline-terminated line, as read by the transport's line-delimited message stream.
+/// Line breaks within the message (e.g. a pretty printed JSON-RPC message) are whitespace in
+/// JSON, so they are replaced by spaces.
+pub(crate) fn message_line(data: &[u8]) -> Bytes {
+    let mut line = BytesMut::with_capacity(data.len() + 1);
+    line.extend(
+        data.iter()
+            .map(|&b| if matches!(b, b'\n' | b'\r') { b' ' } else { b }),
+    );
+    line.extend_from_slice(b"\n");
+    line.freeze()
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -159,4 +173,13 @@ mod tests {
         // If the channel has no data and is still open, read should timeout
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_message_line() {
+        assert_eq!(
+            super::message_line(b"{\"a\":\r\n1}"),
+            Bytes::from("{\"a\":  1}\n")
+        );
+        assert_eq!(super::message_line(b""), Bytes::from("\n"));
+    }
 }
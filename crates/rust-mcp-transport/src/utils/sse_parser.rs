@@ -1,18 +1,37 @@
 use bytes::{Bytes, BytesMut};
-use std::collections::HashMap;
 
 use super::SseEvent;
 const BUFFER_CAPACITY: usize = 1024;
 
-/// A parser for Server-Sent Events (SSE) that processes incoming byte chunks into `SseEvent`s.
-/// This Parser is specifically designed for MCP messages and with no multi-line data support
+/// An incremental parser for Server-Sent Events (SSE) that processes incoming byte chunks into `SseEvent`s.
 ///
-/// This struct maintains a buffer to accumulate incoming data and parses it into SSE events
-/// based on the SSE protocol. It handles fields like `event`, `data`, and `id` as defined
-/// in the SSE specification.
+/// Events are parsed as described in the
+/// [SSE specification](https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation):
+/// - lines may end with `\n`, `\r\n` or `\r`, and a blank line dispatches the event
+/// - `field:value` and `field: value` are equivalent, a line without a colon is a field with an empty value
+/// - lines starting with `:` are comments and are ignored
+/// - multiple `data` lines are joined with `\n`
+/// - `id` values containing a NUL character and non-numeric `retry` values are ignored
+/// - unknown fields are ignored
+///
+/// Unlike a browser `EventSource`, an event carrying only `id`, `event` or `retry` fields is still
+/// returned, so that priming events (SEP-1699) are visible, and `id` is reported per event rather
+/// than carried over to later events.
 #[derive(Debug)]
 pub struct SseParser {
-    pub buffer: BytesMut,
+    /// Bytes received after the last complete line.
+    buffer: BytesMut,
+    event: Option<String>,
+    /// Each `data` line followed by `\n`, or `None` if no `data` line was seen.
+    data: Option<BytesMut>,
+    id: Option<String>,
+    retry: Option<u64>,
+}
+
+impl Default for SseParser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SseParser {
@@ -26,143 +45,117 @@ impl SseParser {
     pub fn new() -> Self {
         Self {
             buffer: BytesMut::with_capacity(BUFFER_CAPACITY),
+            event: None,
+            data: None,
+            id: None,
+            retry: None,
         }
     }
 
     /// Processes a new chunk of bytes and parses it into a vector of `SseEvent`s.
     ///
-    /// This method appends the incoming `bytes` to the internal buffer, splits it into
-    /// complete lines (delimited by `\n`), and parses each line according to the SSE
-    /// protocol. It supports `event`, `id`, and `data` fields, as well as comments
-    /// (lines starting with `:`). Empty lines are skipped, and incomplete lines remain
-    /// in the buffer for future processing.
+    /// This method appends the incoming `bytes` to the internal buffer and processes every
+    /// complete line in it. Fields of an event that is not complete yet, and bytes of an
+    /// incomplete line, are kept for the next chunk, so the parsed events do not depend on
+    /// how the stream is split into chunks.
     ///
     /// # Parameters
     /// - `bytes`: The incoming chunk of bytes to parse.
     ///
     /// # Returns
-    /// A vector of `SseEvent`s parsed from the complete lines in the buffer. If no
+    /// A vector of the `SseEvent`s completed by this chunk. If no
     /// complete events are found, an empty vector is returned.
     pub fn process_new_chunk(&mut self, bytes: Bytes) -> Vec<SseEvent> {
         self.buffer.extend_from_slice(&bytes);
 
-        // Collect complete lines (ending in \n)-keep ALL lines, including empty ones for \n\n detection
-        let mut lines = Vec::new();
-        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
-            let line = self.buffer.split_to(pos + 1).freeze();
-            lines.push(line);
-        }
-
         let mut events = Vec::new();
-        let mut current_message_lines: Vec<Bytes> = Vec::new();
-
-        for line in lines {
-            current_message_lines.push(line);
-
-            // Check if we've hit a double newline (end of message)
-            if current_message_lines.len() >= 2
-                && current_message_lines
-                    .last()
-                    .is_some_and(|b| b.as_ref() == b"\n")
-            {
-                // Process the complete message (exclude the last empty lines for parsing)
-                let message_lines: Vec<_> = current_message_lines
-                    .drain(..current_message_lines.len() - 1)
-                    .filter(|l| l.as_ref() != b"\n") // Filter internal empties
-                    .collect();
-
-                if let Some(event) = self.parse_sse_message(&message_lines) {
+        while let Some(line) = self.next_line() {
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
                     events.push(event);
                 }
+            } else {
+                self.process_line(&line);
             }
         }
-
-        // Put back any incomplete message, preserving partial bytes
-        // that were after the last \n (self.buffer after the while loop).
-        // this prevents data loss when data is received in multiple chunks (like from Cloudflare workers (Issue: #199))
-        if !current_message_lines.is_empty() {
-            let mut new_buf = BytesMut::new();
-            for line in current_message_lines.into_iter() {
-                new_buf.extend_from_slice(&line);
-            }
-            new_buf.extend_from_slice(&self.buffer);
-            self.buffer = new_buf;
-        }
-
         events
     }
 
-    fn parse_sse_message(&self, lines: &[Bytes]) -> Option<SseEvent> {
-        let mut fields: HashMap<String, String> = HashMap::new();
-        let mut data_parts: Vec<String> = Vec::new();
-        // Track whether we saw a `data:` line, even with an empty value, so we
-        // can distinguish "no data field at all" from "data: " (empty data).
-        let mut saw_data_line = false;
+    /// Removes the next complete line from the buffer, without its line terminator.
+    fn next_line(&mut self) -> Option<BytesMut> {
+        let pos = self.buffer.iter().position(|&b| b == b'\n' || b == b'\r')?;
+        let terminator_len = if self.buffer[pos] == b'\r' {
+            match self.buffer.get(pos + 1) {
+                Some(b'\n') => 2,
+                Some(_) => 1,
+                // a trailing `\r` may be the first half of a `\r\n` split across chunks
+                None => return None,
+            }
+        } else {
+            1
+        };
+        let mut line = self.buffer.split_to(pos + terminator_len);
+        line.truncate(pos);
+        Some(line)
+    }
 
-        for line_bytes in lines {
-            let line_str = String::from_utf8_lossy(line_bytes);
+    fn process_line(&mut self, line: &[u8]) {
+        // comment, e.g. a keep-alive
+        if line.starts_with(b":") {
+            return;
+        }
 
-            // Skip comments and empty lines
-            if line_str.is_empty() || line_str.starts_with(':') {
-                continue;
+        let (field, value) = match line.iter().position(|&b| b == b':') {
+            Some(pos) => {
+                let value = &line[pos + 1..];
+                (&line[..pos], value.strip_prefix(b" ").unwrap_or(value))
             }
+            None => (line, &[][..]),
+        };
 
-            // Per the SSE spec, "data: " and "data:" are both valid; the value
-            // may be empty. We accept either form. The trailing newline is
-            // retained for backwards compatibility with existing consumers.
-            let (key, value) = if let Some(value) = line_str.strip_prefix("data: ") {
-                ("data", value.to_string())
-            } else if line_str.trim_end_matches('\n') == "data:"
-                || line_str.trim_end_matches('\n').is_empty() && line_str.starts_with("data:")
-            {
-                ("data", String::new())
-            } else if let Some(value) = line_str.strip_prefix("event: ") {
-                ("event", value.trim().to_string())
-            } else if let Some(value) = line_str.strip_prefix("id: ") {
-                ("id", value.trim().to_string())
-            } else if let Some(value) = line_str.strip_prefix("retry: ") {
-                ("retry", value.trim().to_string())
-            } else {
-                // Invalid line; skip
-                continue;
-            };
-
-            if key == "data" {
-                saw_data_line = true;
-                // Skip empty data values. The legacy behavior preserves a
-                // trailing newline in the value (e.g. "hello\n" for the
-                // line "data: hello\n"), so a bare "\n" represents an
-                // empty data line and must not be pushed.
-                if !value.is_empty() && value != "\n" {
-                    data_parts.push(value);
+        match field {
+            b"event" => self.event = Some(String::from_utf8_lossy(value).into_owned()),
+            b"data" => {
+                let data = self.data.get_or_insert_with(BytesMut::new);
+                data.extend_from_slice(value);
+                data.extend_from_slice(b"\n");
+            }
+            b"id" if !value.contains(&0) => {
+                self.id = Some(String::from_utf8_lossy(value).into_owned());
+            }
+            b"retry" if !value.is_empty() && value.iter().all(u8::is_ascii_digit) => {
+                if let Some(retry) = std::str::from_utf8(value)
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                {
+                    self.retry = Some(retry);
                 }
-            } else {
-                fields.insert(key.to_string(), value);
             }
+            _ => {}
         }
+    }
 
-        // Build data (concat multi-line data with \n) , should not occur in MCP tho
-        let data = if data_parts.is_empty() {
-            None
-        } else {
-            let full_data = data_parts.join("\n");
-            Some(Bytes::copy_from_slice(full_data.as_bytes())) // Use copy_from_slice for efficiency
-        };
+    /// Builds the event from the fields collected since the previous blank line.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let data = self.data.take();
+        let event = self.event.take();
+        let id = self.id.take();
+        let retry = self.retry.take();
 
-        // Get event (default to None)
-        let event = fields.get("event").cloned();
-        let id = fields.get("id").cloned();
-        let retry = fields
-            .get("retry")
-            .and_then(|r| r.trim().parse::<u64>().ok());
-
-        // Emit a priming event (no data) if it carries an `id` or `retry`
-        // (SEP-1699 resumability checkpoint / reconnection timing). Skip pure
-        // junk messages with no fields at all.
-        if data.is_none() && id.is_none() && retry.is_none() && event.is_none() && !saw_data_line {
+        // Skip blocks with no fields at all, such as a lone comment.
+        if data.is_none() && event.is_none() && id.is_none() && retry.is_none() {
             return None;
         }
 
+        // Drop the `\n` following the last data line; an empty data value
+        // (e.g. the `data:` line of a priming event) carries no payload.
+        let data = data
+            .map(|mut data| {
+                data.truncate(data.len() - 1);
+                data.freeze()
+            })
+            .filter(|data| !data.is_empty());
+
         Some(SseEvent {
             event,
             data,
@@ -186,7 +179,7 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(
             events[0].data.as_deref(),
-            Some(Bytes::from("hello\n").as_ref())
+            Some(Bytes::from("hello").as_ref())
         );
         assert!(events[0].event.is_none());
         assert!(events[0].id.is_none());
@@ -203,7 +196,7 @@ mod tests {
         assert_eq!(events[0].id.as_deref(), Some("123"));
         assert_eq!(
             events[0].data.as_deref(),
-            Some(Bytes::from("hello\n").as_ref())
+            Some(Bytes::from("hello").as_ref())
         );
     }
 
@@ -218,7 +211,7 @@ mod tests {
         assert_eq!(events[0].id.as_deref(), Some("123"));
         assert_eq!(
             events[0].data.as_deref(),
-            Some(Bytes::from("hello\n").as_ref())
+            Some(Bytes::from("hello").as_ref())
         );
     }
 
@@ -271,7 +264,7 @@ mod tests {
         assert_eq!(events2.len(), 1);
         assert_eq!(
             events2[0].data.as_deref(),
-            Some(Bytes::from("hello world\n").as_ref())
+            Some(Bytes::from("hello world").as_ref())
         );
     }
 
@@ -290,7 +283,7 @@ mod tests {
         assert_eq!(events2[0].event.as_deref(), Some("message"));
         assert_eq!(
             events2[0].data.as_deref(),
-            Some(Bytes::from("hello\n").as_ref())
+            Some(Bytes::from("hello").as_ref())
         );
     }
 
@@ -313,7 +306,7 @@ mod tests {
         assert_eq!(events3[0].event.as_deref(), Some("message"));
         assert_eq!(
             events3[0].data.as_deref(),
-            Some(Bytes::from("alpha bravo charlie delta echo foxtrot\n").as_ref())
+            Some(Bytes::from("alpha bravo charlie delta echo foxtrot").as_ref())
         );
     }
 
@@ -328,14 +321,14 @@ mod tests {
         assert_eq!(events1.len(), 1);
         assert_eq!(
             events1[0].data.as_deref(),
-            Some(Bytes::from("one\n").as_ref())
+            Some(Bytes::from("one").as_ref())
         );
 
         let events2 = parser.process_new_chunk(part2);
         assert_eq!(events2.len(), 1);
         assert_eq!(
             events2[0].data.as_deref(),
-            Some(Bytes::from("two\n").as_ref())
+            Some(Bytes::from("two").as_ref())
         );
     }
 
@@ -347,10 +340,7 @@ mod tests {
         let events = parser.process_new_chunk(input);
 
         assert_eq!(events.len(), 1);
-        assert_eq!(
-            events[0].data.as_deref(),
-            Some(Bytes::from("ok\n").as_ref())
-        );
+        assert_eq!(events[0].data.as_deref(), Some(Bytes::from("ok").as_ref()));
     }
 
     #[test]
@@ -362,11 +352,11 @@ mod tests {
         assert_eq!(events.len(), 2);
         assert_eq!(
             events[0].data.as_deref(),
-            Some(Bytes::from("first\n").as_ref())
+            Some(Bytes::from("first").as_ref())
         );
         assert_eq!(
             events[1].data.as_deref(),
-            Some(Bytes::from("second\n").as_ref())
+            Some(Bytes::from("second").as_ref())
         );
     }
 
@@ -381,8 +371,110 @@ mod tests {
 
         let event = &events[0];
         assert_eq!(event.event.as_deref(), Some("message"));
-        assert_eq!(event.data.as_deref(), Some(Bytes::from("Hello\n").as_ref()));
+        assert_eq!(event.data.as_deref(), Some(Bytes::from("Hello").as_ref()));
         assert_eq!(event.id.as_deref(), Some("1"));
         assert_eq!(event.retry, Some(5000));
     }
+
+    #[test]
+    fn test_multi_line_data() {
+        let mut parser = SseParser::new();
+        let input = Bytes::from("data: {\"a\":\ndata:  1}\ndata:\ndata: end\n\n");
+        let events = parser.process_new_chunk(input);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].data.as_deref(),
+            Some(Bytes::from("{\"a\":\n 1}\n\nend").as_ref())
+        );
+    }
+
+    #[test]
+    fn test_fields_without_space_after_colon() {
+        let mut parser = SseParser::new();
+        let input = Bytes::from("event:message\nid:7\nretry:250\ndata:hello\n\n");
+        let events = parser.process_new_chunk(input);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event.as_deref(), Some("message"));
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+        assert_eq!(events[0].retry, Some(250));
+        assert_eq!(
+            events[0].data.as_deref(),
+            Some(Bytes::from("hello").as_ref())
+        );
+    }
+
+    #[test]
+    fn test_crlf_and_cr_line_endings() {
+        let mut parser = SseParser::new();
+        let input = Bytes::from("event: a\r\ndata: one\r\n\r\nevent: b\rdata: two\r\r");
+        let events = parser.process_new_chunk(input);
+
+        // the final `\r` may still be followed by `\n`, so the second event waits for more input
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event.as_deref(), Some("a"));
+        assert_eq!(events[0].data.as_deref(), Some(Bytes::from("one").as_ref()));
+
+        let events = parser.process_new_chunk(Bytes::from("\n"));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event.as_deref(), Some("b"));
+        assert_eq!(events[0].data.as_deref(), Some(Bytes::from("two").as_ref()));
+    }
+
+    #[test]
+    fn test_crlf_split_across_chunks() {
+        let mut parser = SseParser::new();
+
+        assert!(parser
+            .process_new_chunk(Bytes::from("data: hello\r"))
+            .is_empty());
+        assert!(parser.process_new_chunk(Bytes::from("\n\r")).is_empty());
+        let events = parser.process_new_chunk(Bytes::from("\n"));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].data.as_deref(),
+            Some(Bytes::from("hello").as_ref())
+        );
+    }
+
+    #[test]
+    fn test_comments_within_an_event() {
+        let mut parser = SseParser::new();
+        let input = Bytes::from("data: a\n: keep-alive\n:\ndata: b\n\n");
+        let events = parser.process_new_chunk(input);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].data.as_deref(),
+            Some(Bytes::from("a\nb").as_ref())
+        );
+    }
+
+    #[test]
+    fn test_invalid_retry_and_id_are_ignored() {
+        let mut parser = SseParser::new();
+        let input = Bytes::from("retry: 10s\nid: a\0b\ndata: x\n\nretry: -5\ndata: y\n\n");
+        let events = parser.process_new_chunk(input);
+
+        assert_eq!(events.len(), 2);
+        assert!(events[0].retry.is_none());
+        assert!(events[0].id.is_none());
+        assert!(events[1].retry.is_none());
+    }
+
+    #[test]
+    fn test_field_without_colon() {
+        let mut parser = SseParser::new();
+        // a `data` line without a colon is a data field with an empty value
+        let input = Bytes::from("data\ndata\ndata: x\n\n");
+        let events = parser.process_new_chunk(input);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].data.as_deref(),
+            Some(Bytes::from("\n\nx").as_ref())
+        );
+    }
 }
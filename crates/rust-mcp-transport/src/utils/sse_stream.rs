@@ -1,4 +1,4 @@
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
 use reqwest::Client;
 use std::time::Duration;
@@ -6,10 +6,11 @@ use tokio::sync::{mpsc, oneshot};
 use tokio::time;
 use tokio_stream::StreamExt;
 
-use super::CancellationToken;
+use super::{message_line, CancellationToken, SseParser};
+use crate::client_sse::ReconnectPolicy;
 
-const BUFFER_CAPACITY: usize = 1024;
 const ENDPOINT_SSE_EVENT: &str = "endpoint";
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
 
 /// Server-Sent Events (SSE) stream handler
 ///
@@ -21,8 +22,10 @@ pub(crate) struct SseStream {
     pub sse_url: String,
     /// Maximum number of retry attempts for failed connections
     pub max_retries: usize,
-    /// Delay between retry attempts
+    /// Delay between retry attempts, until the server sets one with the SSE `retry:` field
     pub retry_delay: Duration,
+    /// Exponential backoff replacing `max_retries` and `retry_delay` when set
+    pub reconnect: Option<ReconnectPolicy>,
     /// Sender for transmitting received data to the readable channel
     pub read_tx: mpsc::Sender<Bytes>,
 }
@@ -32,6 +35,7 @@ impl SseStream {
     ///
     /// Continuously attempts to connect to the SSE endpoint in case connection is lost, processes incoming data,
     /// and sends it to the read channel. Handles retries and cancellation.
+    /// Reconnections send the id of the last received event in the `Last-Event-ID` header.
     ///
     /// # Arguments
     /// * `endpoint_event_tx` - Optional one-shot sender for the messages endpoint
@@ -43,8 +47,13 @@ impl SseStream {
         custom_headers: &Option<HeaderMap>,
     ) {
         let mut retry_count = 0;
-        let mut buffer = BytesMut::with_capacity(BUFFER_CAPACITY);
-        let mut endpoint_event_received = false;
+        // the server may change the reconnection delay with the `retry:` field, and the
+        // value applies to every later reconnection, as per the SSE spec
+        let mut retry_delay = self
+            .reconnect
+            .as_ref()
+            .map_or(self.retry_delay, |policy| policy.initial_delay);
+        let mut last_event_id: Option<String> = None;
 
         let mut request_headers: HeaderMap = custom_headers.to_owned().unwrap_or_default();
         request_headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
@@ -57,6 +66,13 @@ impl SseStream {
                 return;
             }
 
+            if let Some(value) = last_event_id
+                .as_deref()
+                .and_then(|id| HeaderValue::from_str(id).ok())
+            {
+                request_headers.insert(LAST_EVENT_ID_HEADER, value);
+            }
+
             // Send GET request to the SSE endpoint
             let response = match self
                 .sse_client
@@ -68,21 +84,23 @@ impl SseStream {
                 Ok(resp) => resp,
                 Err(e) => {
                     tracing::error!("Failed to connect to SSE: {e}");
-                    if retry_count >= self.max_retries {
-                        tracing::error!("Max retries reached, giving up");
+                    retry_count += 1;
+                    if !self
+                        .wait_to_reconnect(retry_count, retry_delay, &cancellation_token)
+                        .await
+                    {
                         if let Some(tx) = endpoint_event_tx.take() {
                             let _ = tx.send(None);
                         }
                         return;
                     }
-                    retry_count += 1;
-                    time::sleep(self.retry_delay).await;
                     continue;
                 }
             };
 
             // Create a stream from the response bytes
             let mut stream = response.bytes_stream();
+            let mut parser = SseParser::new();
 
             // Inner loop for processing stream chunks
             loop {
@@ -92,15 +110,13 @@ impl SseStream {
                         match chunk {
                             Some(chunk) => chunk,
                             None => {
-                                if retry_count >= self.max_retries {
-                                    tracing::error!("Max retries ({}) reached, giving up",self.max_retries);
+                                retry_count += 1;
+                                if !self.wait_to_reconnect(retry_count, retry_delay, &cancellation_token).await {
                                     if let Some(tx) = endpoint_event_tx.take() {
                                         let _ = tx.send(None);
                                     }
                                     return;
                                 }
-                                retry_count += 1;
-                                time::sleep(self.retry_delay).await;
                                 break; // Stream ended, break from inner loop to reconnect
                             }
                         }
@@ -113,85 +129,91 @@ impl SseStream {
 
                 match next_chunk {
                     Ok(bytes) => {
-                        buffer.extend_from_slice(&bytes);
-
-                        let mut batch = Vec::new();
-                        // collect complete lines for processing
-                        while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
-                            let line = buffer.split_to(pos + 1).freeze();
-                            // Skip empty lines
-                            if line.len() > 1 {
-                                batch.push(line);
+                        for event in parser.process_new_chunk(bytes) {
+                            if let Some(retry_ms) = event.retry {
+                                tracing::debug!(
+                                    "SSE reconnection delay set by server to {retry_ms}ms"
+                                );
+                                retry_delay = Duration::from_millis(retry_ms);
                             }
-                        }
-
-                        let mut current_event: Option<String> = None;
 
-                        // Process complete lines
-                        for line in batch {
-                            // Parse line as UTF-8, keep the trailing newline
-                            let line_str = String::from_utf8_lossy(&line);
+                            if let Some(id) = event.id.filter(|id| !id.is_empty()) {
+                                last_event_id = Some(id);
+                            }
 
-                            if let Some(event_name) = line_str.strip_prefix("event: ") {
-                                current_event = Some(event_name.trim().to_string());
+                            if event.event.as_deref() == Some(ENDPOINT_SSE_EVENT) {
+                                // the messages endpoint is announced once, when the stream opens
+                                if let Some(tx) = endpoint_event_tx.take() {
+                                    let endpoint = event.data.map(|data| {
+                                        String::from_utf8_lossy(&data).trim().to_owned()
+                                    });
+                                    let _ = tx.send(endpoint);
+                                }
                                 continue;
                             }
 
-                            // Extract content after data: or :
-                            let content = if let Some(data) = line_str.strip_prefix("data: ") {
-                                let payload = data.trim_start();
-                                if !endpoint_event_received {
-                                    if let Some(ENDPOINT_SSE_EVENT) = current_event.as_deref() {
-                                        if let Some(tx) = endpoint_event_tx.take() {
-                                            endpoint_event_received = true;
-                                            let _ = tx.send(Some(payload.trim().to_owned()));
-                                            continue;
-                                        }
-                                    }
-                                }
-                                payload
-                            } else if let Some(comment) = line_str.strip_prefix(":") {
-                                comment.trim_start()
-                            } else {
+                            let Some(data) = event.data else {
                                 continue;
                             };
-
-                            if !content.is_empty() {
-                                let bytes = Bytes::copy_from_slice(content.as_bytes());
-                                if self.read_tx.send(bytes).await.is_err() {
-                                    tracing::error!(
-                                        "Readable stream closed, shutting down SSE task"
-                                    );
-                                    if !endpoint_event_received {
-                                        if let Some(tx) = endpoint_event_tx.take() {
-                                            let _ = tx.send(None);
-                                        }
-                                    }
-                                    return;
+                            if self.read_tx.send(message_line(&data)).await.is_err() {
+                                tracing::error!("Readable stream closed, shutting down SSE task");
+                                if let Some(tx) = endpoint_event_tx.take() {
+                                    let _ = tx.send(None);
                                 }
+                                return;
                             }
                         }
                         retry_count = 0; // Reset retry count on successful chunk
                     }
                     Err(e) => {
                         tracing::error!("SSE stream error: {}", e);
-                        if retry_count >= self.max_retries {
-                            tracing::error!("Max retries reached, giving up");
-                            if !endpoint_event_received {
-                                if let Some(tx) = endpoint_event_tx.take() {
-                                    let _ = tx.send(None);
-                                }
+                        retry_count += 1;
+                        if !self
+                            .wait_to_reconnect(retry_count, retry_delay, &cancellation_token)
+                            .await
+                        {
+                            if let Some(tx) = endpoint_event_tx.take() {
+                                let _ = tx.send(None);
                             }
                             return;
                         }
-                        retry_count += 1;
-                        time::sleep(self.retry_delay).await;
                         break; // Break inner loop to reconnect
                     }
                 }
             }
         }
     }
+
+    /// Waits before the reconnection `attempt`, returns `false` when all attempts are used up
+    /// or the stream is cancelled meanwhile
+    ///
+    /// # Arguments
+    /// * `attempt` - Number of the reconnection attempt, starting at 1
+    /// * `retry_delay` - The configured delay, or the one set by the server
+    /// * `cancellation_token` - Token for monitoring cancellation requests
+    async fn wait_to_reconnect(
+        &self,
+        attempt: usize,
+        retry_delay: Duration,
+        cancellation_token: &CancellationToken,
+    ) -> bool {
+        let (max_retries, delay) = match self.reconnect.as_ref() {
+            Some(policy) => (policy.max_retries, policy.delay(attempt, retry_delay)),
+            None => (self.max_retries, retry_delay),
+        };
+        if attempt > max_retries {
+            tracing::error!("Max retries ({max_retries}) reached, giving up");
+            return false;
+        }
+
+        tracing::warn!(
+            "SSE connection lost, reconnecting in {delay:?} (attempt {attempt}/{max_retries})"
+        );
+        tokio::select! {
+            _ = time::sleep(delay) => true,
+            _ = cancellation_token.cancelled() => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -234,6 +256,7 @@ mod tests {
             sse_url: format!("{}/sse", mock_server.uri()),
             max_retries: 0, // to receive one request only
             retry_delay: Duration::from_millis(100),
+            reconnect: None,
             read_tx,
         };
 
@@ -272,4 +295,111 @@ mod tests {
             _ => panic!("Did not receive expected endpoint event"),
         }
     }
+
+    #[tokio::test]
+    async fn test_sse_client_honors_server_retry_delay() {
+        let mock_server = MockServer::builder().start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/sse"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("retry: 50\nevent: endpoint\ndata: mock-endpoint\n\n")
+                    .append_header("Content-Type", "text/event-stream")
+                    .append_header("Connection", "close"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let (read_tx, _read_rx) = mpsc::channel::<Bytes>(64);
+        let sse = SseStream {
+            sse_client: reqwest::Client::new(),
+            sse_url: format!("{}/sse", mock_server.uri()),
+            max_retries: 1,
+            // far longer than the test waits, so a reconnect can only come from `retry: 50`
+            retry_delay: Duration::from_secs(30),
+            reconnect: None,
+            read_tx,
+        };
+
+        let (cancellation_source, cancellation_token) = CancellationTokenSource::new();
+        let sse_task = tokio::spawn(async move {
+            sse.run(None, cancellation_token, &None).await;
+        });
+
+        let reconnected = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let requests = mock_server.received_requests().await.unwrap_or_default();
+                if requests.len() >= 2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await;
+
+        let _ = cancellation_source.cancel();
+        sse_task.abort();
+
+        assert!(
+            reconnected.is_ok(),
+            "Expected the client to reconnect after the server provided retry delay"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sse_client_resumes_from_the_last_event_id() {
+        let mock_server = MockServer::builder().start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/sse"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(
+                        "event: endpoint\ndata: mock-endpoint\n\nid: e-7\ndata: {}\n\n",
+                    )
+                    .append_header("Content-Type", "text/event-stream")
+                    .append_header("Connection", "close"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let (read_tx, _read_rx) = mpsc::channel::<Bytes>(64);
+        let sse = SseStream {
+            sse_client: reqwest::Client::new(),
+            sse_url: format!("{}/sse", mock_server.uri()),
+            max_retries: 0,
+            retry_delay: Duration::from_secs(30),
+            reconnect: Some(ReconnectPolicy {
+                max_retries: 3,
+                initial_delay: Duration::from_millis(20),
+                max_delay: Duration::from_millis(100),
+                jitter: 0.0,
+            }),
+            read_tx,
+        };
+
+        let (cancellation_source, cancellation_token) = CancellationTokenSource::new();
+        let sse_task = tokio::spawn(async move {
+            sse.run(None, cancellation_token, &None).await;
+        });
+
+        let requests = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let requests = mock_server.received_requests().await.unwrap_or_default();
+                if requests.len() >= 2 {
+                    break requests;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("Expected the client to reconnect with the backoff policy");
+
+        let _ = cancellation_source.cancel();
+        sse_task.abort();
+
+        assert!(requests[0].headers.get("last-event-id").is_none());
+        assert_eq!(requests[1].headers.get("last-event-id").unwrap(), "e-7");
+    }
 }
@@ -1,7 +1,7 @@
 use super::CancellationToken;
 use crate::error::{TransportError, TransportResult};
-use crate::utils::SseParser;
 use crate::utils::{http_get, validate_response_type, ResponseType};
+use crate::utils::{message_line, SseParser};
 use crate::{utils::http_post, MCP_SESSION_ID_HEADER};
 use crate::{EventId, MCP_LAST_EVENT_ID_HEADER};
 use bytes::Bytes;
@@ -128,6 +128,7 @@ impl StreamableHttpStream {
         // retry delay.
         let mut has_priming_event = false;
         let mut received_response = false;
+        let mut received_message = false;
         let mut sse_retry_delay: Option<Duration> = None;
 
         // Inner loop for processing stream chunks
@@ -159,6 +160,9 @@ impl StreamableHttpStream {
                                 stream = reconnect_response.bytes_stream();
                                 continue;
                             }
+                            if received_message {
+                                return Ok(());
+                            }
                             return Err(TransportError::Internal("Stream has ended.".to_string()));
                         }
                     }
@@ -185,9 +189,11 @@ impl StreamableHttpStream {
                                 has_priming_event = true;
                             }
                             if let Some(bytes) = event.data {
-                                received_response = true;
+                                // requests and notifications related to the request may precede its response
+                                received_response |= is_response_payload(&bytes);
+                                received_message = true;
 
-                                if self.read_tx.send(bytes).await.is_err() {
+                                if self.read_tx.send(message_line(&bytes)).await.is_err() {
                                     tracing::error!(
                                         "Readable stream closed, shutting down MCP task"
                                     );
@@ -267,7 +273,8 @@ impl StreamableHttpStream {
 
                 Err(error) => {
                     match error {
-                        crate::error::TransportError::HttpConnection(_) => {
+                        crate::error::TransportError::HttpConnection(_)
+                        | crate::error::TransportError::ConnectTimeout(_) => {
                             // A reqwest::Error happened, we do not return ans instead retry the operation
                         }
                         crate::error::TransportError::Http(status_code) => match status_code {
@@ -395,7 +402,7 @@ impl StreamableHttpStream {
                                         _last_event_id = event.id.clone();
                                     }
 
-                                    if self.read_tx.send(bytes).await.is_err() {
+                                    if self.read_tx.send(message_line(&bytes)).await.is_err() {
                                         tracing::error!(
                                             "Readable stream closed, shutting down MCP task"
                                         );
@@ -430,3 +437,17 @@ impl StreamableHttpStream {
         }
     }
 }
+
+/// Returns true if an SSE data payload holds a JSON-RPC response or error,
+/// or a batch containing one.
+fn is_response_payload(data: &[u8]) -> bool {
+    let is_response = |message: &serde_json::Value| {
+        message.get("method").is_none()
+            && (message.get("result").is_some() || message.get("error").is_some())
+    };
+    match serde_json::from_slice::<serde_json::Value>(data) {
+        Ok(serde_json::Value::Array(messages)) => messages.iter().any(is_response),
+        Ok(message) => is_response(&message),
+        Err(_) => false,
+    }
+}
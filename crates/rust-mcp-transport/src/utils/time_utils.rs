@@ -1,8 +1,34 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+static LAST_TIMESTAMP: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the current time in nanoseconds since the Unix epoch, strictly greater than any
+/// previously returned value, so event ids created on the same clock tick remain ordered.
 pub fn current_timestamp() -> u128 {
-    SystemTime::now()
+    let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("Invalid time")
-        .as_nanos()
+        .as_nanos() as u64;
+
+    let mut last = LAST_TIMESTAMP.load(Ordering::Relaxed);
+    loop {
+        let next = now.max(last + 1);
+        match LAST_TIMESTAMP.compare_exchange_weak(last, next, Ordering::AcqRel, Ordering::Relaxed)
+        {
+            Ok(_) => return next as u128,
+            Err(actual) => last = actual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_strictly_increasing() {
+        let timestamps: Vec<u128> = (0..10_000).map(|_| current_timestamp()).collect();
+        assert!(timestamps.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 }
@@ -0,0 +1,235 @@
+use bytes::{Bytes, BytesMut};
+use futures::{SinkExt, StreamExt};
+use rustls::ClientConfig;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::time::{self, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderMap;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async_tls_with_config, Connector, MaybeTlsStream};
+
+use super::{message_line, CancellationToken};
+use crate::error::{TransportError, TransportResult};
+use crate::schema::RequestId;
+
+type PendingRequests<R> = Arc<Mutex<HashMap<RequestId, oneshot::Sender<R>>>>;
+
+pub(crate) type WebSocket = tokio_tungstenite::WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// WebSocket connection handler
+///
+/// Exchanges newline-delimited messages from the write channel and the read channel with
+/// text frames on the socket, one JSON-RPC message per frame, and reconnects when the socket closes.
+pub(crate) struct WebSocketConnection<R> {
+    /// URL of the WebSocket endpoint
+    pub url: String,
+    /// Headers added to the opening handshake
+    pub headers: HeaderMap,
+    /// TLS configuration used for `wss://` URLs
+    pub tls_config: Arc<ClientConfig>,
+    /// Interval between ping frames, none are sent when `None`
+    pub ping_interval: Option<Duration>,
+    /// Maximum number of reconnection attempts after the socket closes
+    pub max_retries: usize,
+    /// Delay between reconnection attempts
+    pub retry_delay: Duration,
+    /// Sender for transmitting received messages to the readable channel
+    pub read_tx: mpsc::Sender<Bytes>,
+    /// Requests awaiting a response, failed when their message can not be sent
+    pub pending_requests: PendingRequests<R>,
+}
+
+impl<R> WebSocketConnection<R> {
+    /// Opens the WebSocket, performing the handshake with the configured headers
+    pub(crate) async fn connect(&self) -> TransportResult<WebSocket> {
+        let mut request = self.url.as_str().into_client_request().map_err(|err| {
+            TransportError::Configuration {
+                message: format!("Invalid WebSocket URL: {err}"),
+            }
+        })?;
+        request.headers_mut().extend(self.headers.clone());
+
+        let (socket, _) = connect_async_tls_with_config(
+            request,
+            None,
+            true,
+            Some(Connector::Rustls(self.tls_config.clone())),
+        )
+        .await
+        .map_err(|err| {
+            std::io::Error::other(format!("Failed to connect to the WebSocket server: {err}"))
+        })?;
+        Ok(socket)
+    }
+
+    /// Runs the connection until it is cancelled, the write channel closes, or the socket
+    /// closes and can not be reopened within `max_retries` attempts
+    ///
+    /// # Arguments
+    /// * `socket` - The socket opened by [`Self::connect`]
+    /// * `write_rx` - Receiver for the newline-delimited messages to send
+    /// * `cancellation_token` - Token for monitoring cancellation requests
+    pub(crate) async fn run(
+        &self,
+        mut socket: WebSocket,
+        mut write_rx: mpsc::Receiver<Bytes>,
+        cancellation_token: CancellationToken,
+    ) {
+        let mut pending = BytesMut::new();
+        let mut ping_interval = self.ping_interval.map(|period| {
+            let mut interval = time::interval_at(time::Instant::now() + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    let _ = socket.close(None).await;
+                    return;
+                }
+
+                data = write_rx.recv() => {
+                    let Some(data) = data else {
+                        let _ = socket.close(None).await;
+                        return;
+                    };
+                    pending.extend_from_slice(&data);
+                    // messages are written newline-delimited, possibly split across several writes
+                    while let Some(position) = pending.iter().position(|b| *b == b'\n') {
+                        let line = pending.split_to(position + 1);
+                        let text = String::from_utf8_lossy(&line).trim().to_string();
+                        if text.is_empty() {
+                            continue;
+                        }
+                        if let Err(err) = socket.send(Message::text(text.as_str())).await {
+                            tracing::error!("Failed to send WebSocket message: {err}");
+                            fail_pending_requests(&self.pending_requests, &text).await;
+                        }
+                    }
+                }
+
+                frame = socket.next() => {
+                    let payload = match frame {
+                        Some(Ok(Message::Text(text))) => Bytes::from(text),
+                        Some(Ok(Message::Binary(data))) => data,
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                            match self.reconnect(&cancellation_token).await {
+                                Some(reopened) => {
+                                    socket = reopened;
+                                    continue;
+                                }
+                                None => return,
+                            }
+                        }
+                        // pings are answered by tungstenite, pongs need no handling
+                        Some(Ok(_)) => continue,
+                    };
+                    if self.read_tx.send(message_line(&payload)).await.is_err() {
+                        tracing::error!("Readable stream closed, shutting down WebSocket");
+                        let _ = socket.close(None).await;
+                        return;
+                    }
+                }
+
+                _ = next_ping(&mut ping_interval) => {
+                    if let Err(err) = socket.send(Message::Ping(Bytes::new())).await {
+                        tracing::warn!("Failed to send WebSocket ping: {err}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reopens the socket after it was closed, returns `None` once all attempts failed or
+    /// the connection is cancelled
+    async fn reconnect(&self, cancellation_token: &CancellationToken) -> Option<WebSocket> {
+        for attempt in 1..=self.max_retries {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => return None,
+                _ = time::sleep(self.retry_delay) => {}
+            }
+            match self.connect().await {
+                Ok(socket) => {
+                    tracing::info!("WebSocket reconnected after {attempt} attempt(s)");
+                    return Some(socket);
+                }
+                Err(err) => tracing::error!("{err}"),
+            }
+        }
+        tracing::error!("WebSocket closed, giving up");
+        None
+    }
+}
+
+/// Removes the pending requests of a JSON-RPC message or batch that could not be sent, so the
+/// callers awaiting their responses fail right away instead of timing out.
+async fn fail_pending_requests<R>(pending_requests: &PendingRequests<R>, message: &str) {
+    let messages = match serde_json::from_str::<Value>(message) {
+        Ok(Value::Array(messages)) => messages,
+        Ok(message) => vec![message],
+        Err(_) => return,
+    };
+    let mut pending_requests = pending_requests.lock().await;
+    for message in messages {
+        if message.get("method").is_none() {
+            continue; // responses have no pending request
+        }
+        if let Some(id) = message
+            .get("id")
+            .and_then(|id| serde_json::from_value::<RequestId>(id.clone()).ok())
+        {
+            pending_requests.remove(&id);
+        }
+    }
+}
+
+async fn next_ping(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unsent_requests_are_removed_from_the_pending_requests() {
+        let pending_requests: PendingRequests<()> = Arc::new(Mutex::new(HashMap::new()));
+        let mut receivers = Vec::new();
+        for id in [1, 2, 3] {
+            let (tx, rx) = oneshot::channel();
+            pending_requests
+                .lock()
+                .await
+                .insert(RequestId::Integer(id), tx);
+            receivers.push(rx);
+        }
+
+        // a batch with a request and a response, the response id has no pending request
+        fail_pending_requests(
+            &pending_requests,
+            r#"[{"jsonrpc":"2.0","id":1,"method":"ping"},{"jsonrpc":"2.0","id":2,"result":{}}]"#,
+        )
+        .await;
+        fail_pending_requests(
+            &pending_requests,
+            r#"{"jsonrpc":"2.0","id":3,"method":"ping"}"#,
+        )
+        .await;
+
+        let pending: Vec<_> = pending_requests.lock().await.keys().cloned().collect();
+        assert_eq!(pending, vec![RequestId::Integer(2)]);
+        let mut receivers = receivers.into_iter();
+        assert!(receivers.next().unwrap().await.is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use proptest::prelude::*;
+use rust_mcp_transport::event_store::{
+    BatchedEventStore, BatchedEventStoreOptions, EventStore, InMemoryEventStore,
+};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SESSIONS: &[&str] = &["session-a", "session-b"];
+const STREAMS: &[&str] = &["STANDALONE-STREAM", "stream-1", "stream-2"];
+
+/// A stored event as tracked by the reference model.
+#[derive(Debug, Clone)]
+struct StoredEvent {
+    session_id: String,
+    stream_id: String,
+    event_id: String,
+    message: String,
+}
+
+/// Reference model of the store: a bounded queue per session, shared by all streams of the session.
+struct Model {
+    capacity: usize,
+    sessions: Vec<(String, VecDeque<StoredEvent>)>,
+}
+
+impl Model {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            sessions: vec![],
+        }
+    }
+
+    fn push(&mut self, event: StoredEvent) {
+        let index = match self
+            .sessions
+            .iter()
+            .position(|(session_id, _)| *session_id == event.session_id)
+        {
+            Some(index) => index,
+            None => {
+                self.sessions
+                    .push((event.session_id.clone(), VecDeque::new()));
+                self.sessions.len() - 1
+            }
+        };
+        let queue = &mut self.sessions[index].1;
+        if queue.len() == self.capacity {
+            queue.pop_front();
+        }
+        queue.push_back(event);
+    }
+
+    fn retained(&self) -> impl Iterator<Item = &StoredEvent> {
+        self.sessions.iter().flat_map(|(_, queue)| queue.iter())
+    }
+
+    /// Messages stored after `event` on the same session and stream, in insertion order.
+    fn messages_after(&self, event: &StoredEvent) -> Vec<String> {
+        self.retained()
+            .filter(|e| e.session_id == event.session_id && e.stream_id == event.stream_id)
+            .skip_while(|e| e.event_id != event.event_id)
+            .skip(1)
+            .map(|e| e.message.clone())
+            .collect()
+    }
+}
+
+/// Generates a sequence of (session, stream) pairs, each becoming one stored event.
+fn event_sequence() -> impl Strategy<Value = Vec<(usize, usize)>> {
+    prop::collection::vec((0..SESSIONS.len(), 0..STREAMS.len()), 1..80)
+}
+
+fn run<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+proptest! {
+    #[test]
+    fn events_after_replays_exactly_the_later_events_of_the_same_stream(
+        sequence in event_sequence(),
+        capacity in 1usize..40,
+        start_time in 0u128..1_000_000,
+        gaps in prop::collection::vec(1u128..1_000, 80),
+    ) {
+        run(async {
+            let store = InMemoryEventStore::new(Some(capacity));
+            let mut model = Model::new(capacity);
+            let mut all_events = vec![];
+            let mut time_stamp = start_time;
+
+            for (index, (session, stream)) in sequence.iter().enumerate() {
+                time_stamp += gaps[index];
+                let session_id = SESSIONS[*session].to_string();
+                let stream_id = STREAMS[*stream].to_string();
+                let message = format!("{session_id}/{stream_id}/{index}");
+
+                let event_id = store
+                    .store_event(session_id.clone(), stream_id.clone(), time_stamp, message.clone())
+                    .await
+                    .unwrap();
+
+                let event = StoredEvent { session_id, stream_id, event_id, message };
+                model.push(event.clone());
+                all_events.push(event);
+            }
+
+            for event in model.retained() {
+                let entry = store
+                    .events_after(event.event_id.clone())
+                    .await
+                    .unwrap()
+                    .expect("session of a retained event must exist");
+
+                prop_assert_eq!(&entry.session_id, &event.session_id);
+                prop_assert_eq!(&entry.stream_id, &event.stream_id);
+
+                // same events, same order: no duplicates, no gaps, nothing from other streams or sessions
+                prop_assert_eq!(entry.messages, model.messages_after(event));
+            }
+
+            // evicted events can no longer be resumed from, but must never leak other streams
+            for event in &all_events {
+                if model.retained().any(|e| e.event_id == event.event_id) {
+                    continue;
+                }
+                if let Some(entry) = store.events_after(event.event_id.clone()).await.unwrap() {
+                    let prefix = format!("{}/{}/", event.session_id, event.stream_id);
+                    prop_assert!(entry.messages.iter().all(|m| m.starts_with(&prefix)));
+                }
+            }
+
+            Ok(())
+        })?;
+    }
+
+    #[test]
+    fn removing_a_stream_does_not_affect_other_streams(
+        sequence in event_sequence(),
+        removed in 0..STREAMS.len(),
+    ) {
+        run(async {
+            let store = InMemoryEventStore::new(Some(1024));
+            let mut model = Model::new(1024);
+
+            for (index, (session, stream)) in sequence.iter().enumerate() {
+                let session_id = SESSIONS[*session].to_string();
+                let stream_id = STREAMS[*stream].to_string();
+                let message = format!("{session_id}/{stream_id}/{index}");
+                let event_id = store
+                    .store_event(session_id.clone(), stream_id.clone(), index as u128, message.clone())
+                    .await
+                    .unwrap();
+                model.push(StoredEvent { session_id, stream_id, event_id, message });
+            }
+
+            let removed_stream = STREAMS[removed];
+            store
+                .remove_stream_in_session(SESSIONS[0].to_string(), removed_stream.to_string())
+                .await
+                .unwrap();
+
+            for event in model.retained() {
+                let result = store.events_after(event.event_id.clone()).await.unwrap();
+                if event.session_id == SESSIONS[0] && event.stream_id == removed_stream {
+                    // the event itself is gone, nothing can be replayed from it
+                    prop_assert!(result.map_or(true, |entry| entry.messages.is_empty()));
+                } else {
+                    let entry = result.expect("untouched sessions must still exist");
+                    prop_assert_eq!(entry.messages, model.messages_after(event));
+                }
+            }
+
+            Ok(())
+        })?;
+    }
+}
+
+#[tokio::test]
+async fn batched_store_replays_concurrent_writes() {
+    let store = Arc::new(BatchedEventStore::new(
+        Arc::new(InMemoryEventStore::new(Some(1000))),
+        BatchedEventStoreOptions {
+            max_batch_size: 16,
+            max_delay: Duration::from_millis(5),
+        },
+    ));
+
+    let writers = (0..100u128).map(|index| {
+        let store = store.clone();
+        tokio::spawn(async move {
+            store
+                .store_event(
+                    SESSIONS[0].to_string(),
+                    STREAMS[1].to_string(),
+                    index,
+                    format!("message-{index}"),
+                )
+                .await
+                .unwrap()
+        })
+    });
+    let event_ids: Vec<_> = futures::future::join_all(writers)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    let entry = store
+        .events_after(event_ids[0].clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let expected: Vec<_> = (1..100).map(|index| format!("message-{index}")).collect();
+    assert_eq!(entry.messages, expected);
+}
+
+#[tokio::test]
+async fn batched_store_flushes_pending_events_on_read() {
+    let inner = Arc::new(InMemoryEventStore::default());
+    let store = Arc::new(BatchedEventStore::new(
+        inner.clone(),
+        BatchedEventStoreOptions {
+            max_batch_size: 16,
+            max_delay: Duration::from_secs(60),
+        },
+    ));
+
+    let first_event_id = inner
+        .store_event(
+            SESSIONS[0].to_string(),
+            STREAMS[0].to_string(),
+            1,
+            "first".to_string(),
+        )
+        .await
+        .unwrap();
+
+    // the second event waits for more events to batch with, until a read flushes it
+    let writer = store.clone();
+    let pending = tokio::spawn(async move {
+        writer
+            .store_event(
+                SESSIONS[0].to_string(),
+                STREAMS[0].to_string(),
+                2,
+                "second".to_string(),
+            )
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!pending.is_finished());
+
+    let entry = tokio::time::timeout(Duration::from_secs(1), store.events_after(first_event_id))
+        .await
+        .expect("events_after should flush pending events")
+        .unwrap()
+        .unwrap();
+    assert_eq!(entry.messages, vec!["second".to_string()]);
+    assert!(pending.await.unwrap().is_ok());
+}
@@ -0,0 +1,46 @@
+//! Feeds arbitrary bytes into the deserialization of incoming client messages,
+//! the first thing a server does with a request body or a stdio line.
+//!
+//! Malformed input must be rejected with an error, never a panic or a hang.
+//! Inputs that parse successfully must survive a serialize / deserialize round trip,
+//! since the same messages are re-serialized when they are stored or forwarded.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_mcp_schema::schema_utils::{ClientMessage, ClientMessages, McpMessage, RpcMessage};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(messages) = serde_json::from_slice::<ClientMessages>(data) else {
+        return;
+    };
+
+    match &messages {
+        ClientMessages::Single(message) => inspect(message),
+        ClientMessages::Batch(messages) => messages.iter().for_each(inspect),
+    }
+
+    let serialized = serde_json::to_string(&messages).expect("parsed messages must serialize");
+    let reparsed = serde_json::from_str::<ClientMessages>(&serialized)
+        .expect("serialized messages must deserialize");
+
+    assert_eq!(message_count(&messages), message_count(&reparsed));
+});
+
+fn message_count(messages: &ClientMessages) -> Option<usize> {
+    match messages {
+        ClientMessages::Single(_) => None,
+        ClientMessages::Batch(messages) => Some(messages.len()),
+    }
+}
+
+/// Exercises the accessors the server runtime uses to route a message.
+fn inspect(message: &ClientMessage) {
+    let _ = message.message_type();
+    let _ = message.request_id();
+    let _ = message.method();
+    let _ = message.is_request();
+    let _ = message.is_response();
+    let _ = message.is_notification();
+    let _ = message.is_error();
+    let _ = message.is_initialize_request();
+}
@@ -0,0 +1,46 @@
+//! Feeds arbitrary bytes into the SSE framing used by the client transports,
+//! split into chunks of arbitrary sizes to mimic how bytes arrive from the network.
+//!
+//! The parser must never panic or loop forever, and the events it produces must not
+//! depend on where the chunk boundaries fall.
+#![no_main]
+
+use bytes::Bytes;
+use libfuzzer_sys::fuzz_target;
+use rust_mcp_transport::{SseEvent, SseParser};
+
+fuzz_target!(|data: &[u8]| {
+    // the first byte selects the chunk size, the rest is the stream content
+    let Some((&chunk_size, payload)) = data.split_first() else {
+        return;
+    };
+    let chunk_size = usize::from(chunk_size).max(1);
+
+    let mut whole_parser = SseParser::new();
+    let whole = whole_parser.process_new_chunk(Bytes::copy_from_slice(payload));
+
+    let mut chunked_parser = SseParser::new();
+    let chunked: Vec<SseEvent> = payload
+        .chunks(chunk_size)
+        .flat_map(|chunk| chunked_parser.process_new_chunk(Bytes::copy_from_slice(chunk)))
+        .collect();
+
+    assert_same_events(&whole, &chunked);
+
+    // whatever is left over is an incomplete event, both parsers must agree on it once it ends
+    let end = Bytes::from_static(b"\n\n");
+    assert_same_events(
+        &whole_parser.process_new_chunk(end.clone()),
+        &chunked_parser.process_new_chunk(end),
+    );
+});
+
+fn assert_same_events(whole: &[SseEvent], chunked: &[SseEvent]) {
+    assert_eq!(whole.len(), chunked.len(), "chunking changed the event count");
+    for (left, right) in whole.iter().zip(chunked.iter()) {
+        assert_eq!(left.event, right.event);
+        assert_eq!(left.data, right.data);
+        assert_eq!(left.id, right.id);
+        assert_eq!(left.retry, right.retry);
+    }
+}